@@ -81,7 +81,7 @@ use core::mem::{align_of_val, size_of_val};
 use core::intrinsics::drop_in_place;
 use core::mem;
 use core::nonzero::NonZero;
-use core::ops::{Deref, CoerceUnsized};
+use core::ops::{Deref, CoerceUnsized, Receiver};
 use core::marker::Unsize;
 use core::hash::{Hash, Hasher};
 use heap::deallocate;
@@ -346,6 +346,8 @@ impl<T: ?Sized> Deref for Arc<T> {
     }
 }
 
+impl<T: ?Sized> Receiver for Arc<T> {}
+
 impl<T: Clone> Arc<T> {
     /// Make a mutable reference from the given `Arc<T>`.
     ///
@@ -164,7 +164,7 @@ use core::intrinsics::{assume, drop_in_place};
 use core::marker::{self, Unsize};
 use core::mem::{self, align_of, size_of, align_of_val, size_of_val, forget};
 use core::nonzero::NonZero;
-use core::ops::{CoerceUnsized, Deref};
+use core::ops::{CoerceUnsized, Deref, Receiver};
 use core::ptr;
 
 use heap::deallocate;
@@ -452,6 +452,8 @@ impl<T: ?Sized> Deref for Rc<T> {
     }
 }
 
+impl<T: ?Sized> Receiver for Rc<T> {}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: ?Sized> Drop for Rc<T> {
     /// Drops the `Rc<T>`.
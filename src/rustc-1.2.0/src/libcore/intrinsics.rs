@@ -498,6 +498,9 @@ extern "rust-intrinsic" {
     pub fn ctpop32(x: u32) -> u32;
     /// Returns the number of bits set in a `u64`.
     pub fn ctpop64(x: u64) -> u64;
+    /// Returns the number of bits set in a `u128`.
+    #[cfg(not(stage0))]
+    pub fn ctpop128(x: u128) -> u128;
 
     /// Returns the number of leading bits unset in a `u8`.
     pub fn ctlz8(x: u8) -> u8;
@@ -507,6 +510,9 @@ extern "rust-intrinsic" {
     pub fn ctlz32(x: u32) -> u32;
     /// Returns the number of leading bits unset in a `u64`.
     pub fn ctlz64(x: u64) -> u64;
+    /// Returns the number of leading bits unset in a `u128`.
+    #[cfg(not(stage0))]
+    pub fn ctlz128(x: u128) -> u128;
 
     /// Returns the number of trailing bits unset in a `u8`.
     pub fn cttz8(x: u8) -> u8;
@@ -516,6 +522,9 @@ extern "rust-intrinsic" {
     pub fn cttz32(x: u32) -> u32;
     /// Returns the number of trailing bits unset in a `u64`.
     pub fn cttz64(x: u64) -> u64;
+    /// Returns the number of trailing bits unset in a `u128`.
+    #[cfg(not(stage0))]
+    pub fn cttz128(x: u128) -> u128;
 
     /// Reverses the bytes in a `u16`.
     pub fn bswap16(x: u16) -> u16;
@@ -523,6 +532,9 @@ extern "rust-intrinsic" {
     pub fn bswap32(x: u32) -> u32;
     /// Reverses the bytes in a `u64`.
     pub fn bswap64(x: u64) -> u64;
+    /// Reverses the bytes in a `u128`.
+    #[cfg(not(stage0))]
+    pub fn bswap128(x: u128) -> u128;
 
     /// Performs checked `i8` addition.
     pub fn i8_add_with_overflow(x: i8, y: i8) -> (i8, bool);
@@ -532,6 +544,9 @@ extern "rust-intrinsic" {
     pub fn i32_add_with_overflow(x: i32, y: i32) -> (i32, bool);
     /// Performs checked `i64` addition.
     pub fn i64_add_with_overflow(x: i64, y: i64) -> (i64, bool);
+    /// Performs checked `i128` addition.
+    #[cfg(not(stage0))]
+    pub fn i128_add_with_overflow(x: i128, y: i128) -> (i128, bool);
 
     /// Performs checked `u8` addition.
     pub fn u8_add_with_overflow(x: u8, y: u8) -> (u8, bool);
@@ -541,6 +556,9 @@ extern "rust-intrinsic" {
     pub fn u32_add_with_overflow(x: u32, y: u32) -> (u32, bool);
     /// Performs checked `u64` addition.
     pub fn u64_add_with_overflow(x: u64, y: u64) -> (u64, bool);
+    /// Performs checked `u128` addition.
+    #[cfg(not(stage0))]
+    pub fn u128_add_with_overflow(x: u128, y: u128) -> (u128, bool);
 
     /// Performs checked `i8` subtraction.
     pub fn i8_sub_with_overflow(x: i8, y: i8) -> (i8, bool);
@@ -550,6 +568,9 @@ extern "rust-intrinsic" {
     pub fn i32_sub_with_overflow(x: i32, y: i32) -> (i32, bool);
     /// Performs checked `i64` subtraction.
     pub fn i64_sub_with_overflow(x: i64, y: i64) -> (i64, bool);
+    /// Performs checked `i128` subtraction.
+    #[cfg(not(stage0))]
+    pub fn i128_sub_with_overflow(x: i128, y: i128) -> (i128, bool);
 
     /// Performs checked `u8` subtraction.
     pub fn u8_sub_with_overflow(x: u8, y: u8) -> (u8, bool);
@@ -559,6 +580,9 @@ extern "rust-intrinsic" {
     pub fn u32_sub_with_overflow(x: u32, y: u32) -> (u32, bool);
     /// Performs checked `u64` subtraction.
     pub fn u64_sub_with_overflow(x: u64, y: u64) -> (u64, bool);
+    /// Performs checked `u128` subtraction.
+    #[cfg(not(stage0))]
+    pub fn u128_sub_with_overflow(x: u128, y: u128) -> (u128, bool);
 
     /// Performs checked `i8` multiplication.
     pub fn i8_mul_with_overflow(x: i8, y: i8) -> (i8, bool);
@@ -568,6 +592,9 @@ extern "rust-intrinsic" {
     pub fn i32_mul_with_overflow(x: i32, y: i32) -> (i32, bool);
     /// Performs checked `i64` multiplication.
     pub fn i64_mul_with_overflow(x: i64, y: i64) -> (i64, bool);
+    /// Performs checked `i128` multiplication.
+    #[cfg(not(stage0))]
+    pub fn i128_mul_with_overflow(x: i128, y: i128) -> (i128, bool);
 
     /// Performs checked `u8` multiplication.
     pub fn u8_mul_with_overflow(x: u8, y: u8) -> (u8, bool);
@@ -577,6 +604,9 @@ extern "rust-intrinsic" {
     pub fn u32_mul_with_overflow(x: u32, y: u32) -> (u32, bool);
     /// Performs checked `u64` multiplication.
     pub fn u64_mul_with_overflow(x: u64, y: u64) -> (u64, bool);
+    /// Performs checked `u128` multiplication.
+    #[cfg(not(stage0))]
+    pub fn u128_mul_with_overflow(x: u128, y: u128) -> (u128, bool);
 
     /// Returns (a + b) mod 2^N, where N is the width of N in bits.
     pub fn overflowing_add<T>(a: T, b: T) -> T;
@@ -65,6 +65,7 @@
 #![allow(raw_pointer_derive)]
 #![deny(missing_docs)]
 
+#![feature(associated_consts)]
 #![feature(associated_type_defaults)]
 #![feature(intrinsics)]
 #![feature(lang_items)]
@@ -80,6 +81,7 @@
 #![feature(custom_attribute)]
 #![feature(const_fn)]
 #![feature(allow_internal_unstable)]
+#![cfg_attr(not(stage0), feature(i128_type))]
 
 #[macro_use]
 mod macros;
@@ -104,12 +106,16 @@ mod uint_macros;
 #[path = "num/i16.rs"]  pub mod i16;
 #[path = "num/i32.rs"]  pub mod i32;
 #[path = "num/i64.rs"]  pub mod i64;
+#[cfg(not(stage0))]
+#[path = "num/i128.rs"] pub mod i128;
 
 #[path = "num/usize.rs"] pub mod usize;
 #[path = "num/u8.rs"]   pub mod u8;
 #[path = "num/u16.rs"]  pub mod u16;
 #[path = "num/u32.rs"]  pub mod u32;
 #[path = "num/u64.rs"]  pub mod u64;
+#[cfg(not(stage0))]
+#[path = "num/u128.rs"] pub mod u128;
 
 #[path = "num/f32.rs"]   pub mod f32;
 #[path = "num/f64.rs"]   pub mod f64;
@@ -17,10 +17,12 @@ use self::wrapping::OverflowingOps;
 
 use char::CharExt;
 use cmp::{Eq, PartialOrd};
+use convert::From;
 use fmt;
 use intrinsics;
 use marker::Copy;
-use mem::size_of;
+use mem::{self, size_of};
+use nonzero::NonZero;
 use option::Option::{self, Some, None};
 use result::Result::{self, Ok, Err};
 use str::{FromStr, StrExt};
@@ -41,8 +43,91 @@ use str::{FromStr, StrExt};
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub struct Wrapping<T>(#[stable(feature = "rust1", since = "1.0.0")] pub T);
 
+/// Provides intentionally-saturated arithmetic on `T`.
+///
+/// All standard arithmetic operations on the underlying value clamp
+/// to the bounds of `T` instead of overflowing, so `Saturating(250u8) +
+/// Saturating(10)` is `Saturating(255)`.
+#[unstable(feature = "saturating", reason = "recently added")]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct Saturating<T>(#[unstable(feature = "saturating", reason = "recently added")] pub T);
+
 pub mod wrapping;
 pub mod flt2dec;
+mod saturating;
+
+macro_rules! nonzero_integers {
+    ($($Ty:ident($Int:ty);)+) => {
+        $(
+            /// An integer that is known not to equal zero.
+            ///
+            /// This enables some memory layout optimization.
+            /// For example, `Option<NonZeroU32>` is the same size as `u32`.
+            #[unstable(feature = "nonzero_int", reason = "recently added")]
+            #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+            pub struct $Ty(NonZero<$Int>);
+
+            impl $Ty {
+                /// Creates a non-zero without checking the value.
+                ///
+                /// # Safety
+                ///
+                /// The value must not be zero.
+                #[unstable(feature = "nonzero_int", reason = "recently added")]
+                #[inline]
+                pub unsafe fn new_unchecked(n: $Int) -> $Ty {
+                    $Ty(NonZero::new(n))
+                }
+
+                /// Creates a non-zero if the given value is not zero.
+                #[unstable(feature = "nonzero_int", reason = "recently added")]
+                #[inline]
+                pub fn new(n: $Int) -> Option<$Ty> {
+                    if n != 0 {
+                        Some($Ty(unsafe { NonZero::new(n) }))
+                    } else {
+                        None
+                    }
+                }
+
+                /// Returns the value as a primitive type.
+                #[unstable(feature = "nonzero_int", reason = "recently added")]
+                #[inline]
+                pub fn get(self) -> $Int {
+                    *self.0
+                }
+            }
+
+            #[unstable(feature = "nonzero_int", reason = "recently added")]
+            impl From<$Ty> for $Int {
+                fn from(nonzero: $Ty) -> $Int {
+                    nonzero.get()
+                }
+            }
+
+            #[unstable(feature = "nonzero_int", reason = "recently added")]
+            impl fmt::Debug for $Ty {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Debug::fmt(&self.get(), f)
+                }
+            }
+
+            #[unstable(feature = "nonzero_int", reason = "recently added")]
+            impl fmt::Display for $Ty {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Display::fmt(&self.get(), f)
+                }
+            }
+        )+
+    }
+}
+
+nonzero_integers! {
+    NonZeroU8(u8);
+    NonZeroU16(u16);
+    NonZeroU32(u32);
+    NonZeroU64(u64);
+}
 
 /// Types that have a "zero" value.
 ///
@@ -79,6 +164,8 @@ macro_rules! zero_one_impl {
     )*)
 }
 zero_one_impl! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+zero_one_impl! { u128 i128 }
 
 macro_rules! zero_one_impl_float {
     ($($t:ty)*) => ($(
@@ -111,6 +198,22 @@ macro_rules! int_impl {
      $add_with_overflow:path,
      $sub_with_overflow:path,
      $mul_with_overflow:path) => {
+        /// The size of this integer type in bits.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const BITS: usize = $BITS;
+
+        /// The size of this integer type in bytes.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const BYTES: usize = $BITS / 8;
+
+        /// The smallest value that can be represented by this integer type.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const MIN: Self = (-1 as Self) << ($BITS - 1);
+
+        /// The largest value that can be represented by this integer type.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const MAX: Self = !((-1 as Self) << ($BITS - 1));
+
         /// Returns the smallest value that can be represented by this integer type.
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
@@ -140,6 +243,136 @@ macro_rules! int_impl {
             from_str_radix(src, radix)
         }
 
+        /// Converts a string slice in a given base to an integer, skipping
+        /// `_` digit separators the way integer literals do.
+        ///
+        /// As in a literal, the first digit may not be a `_`, but any
+        /// number of separators may follow it, including at the end.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_parse_lenient)]
+        /// assert_eq!(i32::from_str_radix_lenient("-1_000_000", 10), Ok(-1000000));
+        /// assert_eq!(i32::from_str_radix_lenient("7f_ff", 16), Ok(0x7fff));
+        /// assert!(i32::from_str_radix_lenient("-_1", 10).is_err());
+        /// ```
+        #[unstable(feature = "int_parse_lenient", reason = "recently added")]
+        pub fn from_str_radix_lenient(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+            from_str_radix_separated(src, radix, true)
+        }
+
+        /// Converts an `i8` to this type, or returns an error if it is out of
+        /// range.
+        ///
+        /// The `try_from_*` family converts from each integer type without
+        /// the silent truncation of `as`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_try_from)]
+        /// assert_eq!(u8::try_from_i32(200), Ok(200));
+        /// assert!(u8::try_from_i32(300).is_err());
+        /// assert!(u8::try_from_i32(-1).is_err());
+        /// assert_eq!(i8::try_from_u64(127), Ok(127));
+        /// ```
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i8(n: i8) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i16` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i16(n: i16) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i32` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i32(n: i32) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i64` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i64(n: i64) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `isize` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_isize(n: isize) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u8` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u8(n: u8) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u16` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u16(n: u16) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u32` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u32(n: u32) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u64` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u64(n: u64) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `usize` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_usize(n: usize) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i128` to this type, or returns an error if it is out of
+        /// range.
+        #[cfg(not(stage0))]
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i128(n: i128) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u128` to this type, or returns an error if it is out of
+        /// range.
+        #[cfg(not(stage0))]
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u128(n: u128) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
         /// Returns the number of ones in the binary representation of `self`.
         ///
         /// # Examples
@@ -339,6 +572,86 @@ macro_rules! int_impl {
             if cfg!(target_endian = "little") { self } else { self.swap_bytes() }
         }
 
+        /// Returns the memory representation of this integer as a byte array
+        /// in big-endian (network) byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(0x12345678i32.to_be_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn to_be_bytes(self) -> [u8; $BITS / 8] {
+            self.to_be().to_ne_bytes()
+        }
+
+        /// Returns the memory representation of this integer as a byte array
+        /// in little-endian byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(0x12345678i32.to_le_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn to_le_bytes(self) -> [u8; $BITS / 8] {
+            self.to_le().to_ne_bytes()
+        }
+
+        /// Returns the memory representation of this integer as a byte array
+        /// in the target platform's native byte order.
+        ///
+        /// Portable code should prefer `to_be_bytes` or `to_le_bytes`.
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn to_ne_bytes(self) -> [u8; $BITS / 8] {
+            unsafe { mem::transmute(self) }
+        }
+
+        /// Creates an integer from its representation as a byte array in
+        /// big-endian (network) byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(i32::from_be_bytes([0x12, 0x34, 0x56, 0x78]), 0x12345678);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn from_be_bytes(bytes: [u8; $BITS / 8]) -> Self {
+            Self::from_be(Self::from_ne_bytes(bytes))
+        }
+
+        /// Creates an integer from its representation as a byte array in
+        /// little-endian byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(i32::from_le_bytes([0x78, 0x56, 0x34, 0x12]), 0x12345678);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn from_le_bytes(bytes: [u8; $BITS / 8]) -> Self {
+            Self::from_le(Self::from_ne_bytes(bytes))
+        }
+
+        /// Creates an integer from its representation as a byte array in the
+        /// target platform's native byte order.
+        ///
+        /// Portable code should prefer `from_be_bytes` or `from_le_bytes`.
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn from_ne_bytes(bytes: [u8; $BITS / 8]) -> Self {
+            unsafe { mem::transmute(bytes) }
+        }
+
         /// Checked integer addition. Computes `self + other`, returning `None`
         /// if overflow occurred.
         ///
@@ -405,6 +718,80 @@ macro_rules! int_impl {
             }
         }
 
+        /// Checked integer remainder. Computes `self % other`, returning
+        /// `None` if `other == 0` or the operation results in underflow or
+        /// overflow.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(5i32.checked_rem(2), Some(1));
+        /// assert_eq!(5i32.checked_rem(0), None);
+        /// assert_eq!((-128i8).checked_rem(-1), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_rem(self, v: Self) -> Option<Self> {
+            match v {
+                0   => None,
+               -1 if self == Self::min_value()
+                    => None,
+                v   => Some(self % v),
+            }
+        }
+
+        /// Checked negation. Computes `-self`, returning `None` if the result
+        /// cannot be represented.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(5i8.checked_neg(), Some(-5));
+        /// assert_eq!((-128i8).checked_neg(), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_neg(self) -> Option<Self> {
+            let (a, b) = self.overflowing_neg();
+            if b { None } else { Some(a) }
+        }
+
+        /// Checked shift left. Computes `self << rhs`, returning `None` if
+        /// `rhs` is larger than or equal to the number of bits in `self`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(0x10u32.checked_shl(4), Some(0x100));
+        /// assert_eq!(0x10u32.checked_shl(32), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_shl(self, rhs: u32) -> Option<Self> {
+            let (a, b) = self.overflowing_shl(rhs);
+            if b { None } else { Some(a) }
+        }
+
+        /// Checked shift right. Computes `self >> rhs`, returning `None` if
+        /// `rhs` is larger than or equal to the number of bits in `self`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(0x10u32.checked_shr(4), Some(0x1));
+        /// assert_eq!(0x10u32.checked_shr(33), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_shr(self, rhs: u32) -> Option<Self> {
+            let (a, b) = self.overflowing_shr(rhs);
+            if b { None } else { Some(a) }
+        }
+
         /// Saturating integer addition. Computes `self + other`, saturating at
         /// the numeric bounds instead of overflowing.
         #[stable(feature = "rust1", since = "1.0.0")]
@@ -429,6 +816,27 @@ macro_rules! int_impl {
             }
         }
 
+        /// Saturating integer multiplication. Computes `self * other`,
+        /// saturating at the numeric bounds instead of overflowing.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(100i8.saturating_mul(2), 127);
+        /// assert_eq!((-100i8).saturating_mul(2), -128);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn saturating_mul(self, other: Self) -> Self {
+            match self.checked_mul(other) {
+                Some(x) => x,
+                None if (self < Self::zero()) == (other < Self::zero())
+                        => Self::max_value(),
+                None => Self::min_value(),
+            }
+        }
+
         /// Wrapping (modular) addition. Computes `self + other`,
         /// wrapping around at the boundary of the type.
         #[stable(feature = "rust1", since = "1.0.0")]
@@ -519,6 +927,105 @@ macro_rules! int_impl {
             self.overflowing_shr(rhs).0
         }
 
+        /// Calculates `self + rhs`.
+        ///
+        /// Returns a tuple of the addition along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would
+        /// have occurred then the wrapped value is returned.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_add(self, rhs)
+        }
+
+        /// Calculates `self - rhs`.
+        ///
+        /// Returns a tuple of the subtraction along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would
+        /// have occurred then the wrapped value is returned.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_sub(self, rhs)
+        }
+
+        /// Calculates the multiplication of `self` and `rhs`.
+        ///
+        /// Returns a tuple of the multiplication along with a boolean
+        /// indicating whether an arithmetic overflow would occur. If an
+        /// overflow would have occurred then the wrapped value is returned.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_mul(self, rhs)
+        }
+
+        /// Calculates the divisor when `self` is divided by `rhs`.
+        ///
+        /// Returns a tuple of the divisor along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would
+        /// occur then `self` is returned.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `rhs` is 0.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_div(self, rhs)
+        }
+
+        /// Calculates the remainder when `self` is divided by `rhs`.
+        ///
+        /// Returns a tuple of the remainder after dividing along with a
+        /// boolean indicating whether an arithmetic overflow would occur. If
+        /// an overflow would occur then 0 is returned.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `rhs` is 0.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_rem(self, rhs)
+        }
+
+        /// Negates self, overflowing if this is equal to the minimum value.
+        ///
+        /// Returns a tuple of the negated version of self along with a
+        /// boolean indicating whether an overflow happened.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_neg(self) -> (Self, bool) {
+            OverflowingOps::overflowing_neg(self)
+        }
+
+        /// Shifts self left by `rhs` bits.
+        ///
+        /// Returns a tuple of the shifted version of self along with a
+        /// boolean indicating whether the shift value was larger than or
+        /// equal to the number of bits. If the shift value is too large,
+        /// then value is masked (N-1) where N is the number of bits, and
+        /// this value is then used to perform the shift.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_shl(self, rhs: u32) -> (Self, bool) {
+            OverflowingOps::overflowing_shl(self, rhs)
+        }
+
+        /// Shifts self right by `rhs` bits.
+        ///
+        /// Returns a tuple of the shifted version of self along with a
+        /// boolean indicating whether the shift value was larger than or
+        /// equal to the number of bits. If the shift value is too large,
+        /// then value is masked (N-1) where N is the number of bits, and
+        /// this value is then used to perform the shift.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_shr(self, rhs: u32) -> (Self, bool) {
+            OverflowingOps::overflowing_shr(self, rhs)
+        }
+
         /// Raises self to the power of `exp`, using exponentiation by squaring.
         ///
         /// # Examples
@@ -556,6 +1063,234 @@ macro_rules! int_impl {
             acc
         }
 
+        /// Checked exponentiation. Computes `self.pow(exp)`, returning `None`
+        /// if overflow occurred.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(8i32.checked_pow(2), Some(64));
+        /// assert_eq!(2i8.checked_pow(7), None);
+        /// assert_eq!((-2i8).checked_pow(7), Some(-128));
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_pow(self, mut exp: u32) -> Option<Self> {
+            let mut base = self;
+            let mut acc = Self::one();
+
+            // The base is only squared while bits of `exp` remain, so a
+            // square that would overflow but is never used does not
+            // count as an overflow.
+            while exp > 1 {
+                if (exp & 1) == 1 {
+                    acc = match acc.checked_mul(base) { Some(x) => x, None => return None };
+                }
+                exp /= 2;
+                base = match base.checked_mul(base) { Some(x) => x, None => return None };
+            }
+            if exp == 1 {
+                acc = match acc.checked_mul(base) { Some(x) => x, None => return None };
+            }
+            Some(acc)
+        }
+
+        /// Saturating exponentiation. Computes `self.pow(exp)`, saturating at
+        /// the numeric bounds instead of overflowing.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!((-4i32).saturating_pow(2), 16);
+        /// assert_eq!(3i8.saturating_pow(5), 127);
+        /// assert_eq!((-3i8).saturating_pow(5), -128);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn saturating_pow(self, exp: u32) -> Self {
+            match self.checked_pow(exp) {
+                Some(x) => x,
+                None if self < 0 && exp % 2 == 1 => Self::min_value(),
+                None => Self::max_value(),
+            }
+        }
+
+        /// Raises self to the power of `exp`, using exponentiation by squaring.
+        ///
+        /// Returns a tuple of the exponentiation along with a boolean
+        /// indicating whether an arithmetic overflow would occur. If an
+        /// overflow would have occurred then the wrapped value is returned.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_overflowing)]
+        /// assert_eq!(3i8.overflowing_pow(4), (81, false));
+        /// assert_eq!(3i8.overflowing_pow(5), (-13, true));
+        /// ```
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline]
+        pub fn overflowing_pow(self, mut exp: u32) -> (Self, bool) {
+            let mut base = self;
+            let mut acc = Self::one();
+            let mut overflown = false;
+            let mut r;
+
+            while exp > 1 {
+                if (exp & 1) == 1 {
+                    r = acc.overflowing_mul(base);
+                    acc = r.0;
+                    overflown |= r.1;
+                }
+                exp /= 2;
+                r = base.overflowing_mul(base);
+                base = r.0;
+                overflown |= r.1;
+            }
+            if exp == 1 {
+                r = acc.overflowing_mul(base);
+                acc = r.0;
+                overflown |= r.1;
+            }
+            (acc, overflown)
+        }
+
+        /// Calculates the quotient of Euclidean division of `self` by `rhs`.
+        ///
+        /// This computes the integer `q` such that `self = q * rhs + r`,
+        /// with `r = self.rem_euclid(rhs)` and `0 <= r < abs(rhs)`. Unlike
+        /// `/`, which rounds towards zero, this rounds towards negative
+        /// infinity for positive `rhs`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `rhs` is 0 or if `self` is `min_value()` and `rhs` is
+        /// -1. See `checked_div_euclid` for a variant that doesn't.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_euclid)]
+        /// assert_eq!(7i32.div_euclid(4), 1);
+        /// assert_eq!((-7i32).div_euclid(4), -2);
+        /// assert_eq!(7i32.div_euclid(-4), -1);
+        /// assert_eq!((-7i32).div_euclid(-4), 2);
+        /// ```
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn div_euclid(self, rhs: Self) -> Self {
+            let q = self / rhs;
+            if self % rhs < 0 {
+                if rhs > 0 { q - 1 } else { q + 1 }
+            } else {
+                q
+            }
+        }
+
+        /// Calculates the least nonnegative remainder of `self (mod rhs)`.
+        ///
+        /// The result is always in `0..abs(rhs)`, whatever the signs of
+        /// the operands, which is usually what is wanted from `%` when
+        /// `self` may be negative.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `rhs` is 0. Unlike `%`, `min_value().rem_euclid(-1)`
+        /// is 0 rather than an overflow.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_euclid)]
+        /// assert_eq!(7i32.rem_euclid(4), 3);
+        /// assert_eq!((-7i32).rem_euclid(4), 1);
+        /// assert_eq!(7i32.rem_euclid(-4), 3);
+        /// assert_eq!((-7i32).rem_euclid(-4), 1);
+        /// ```
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn rem_euclid(self, rhs: Self) -> Self {
+            let r = self.wrapping_rem(rhs);
+            if r < 0 {
+                // `min_value() < r < 0`, so adding `abs(rhs)` cannot
+                // overflow, even when `rhs` is `min_value()`.
+                if rhs < 0 { r - rhs } else { r + rhs }
+            } else {
+                r
+            }
+        }
+
+        /// Checked Euclidean division. Computes `self.div_euclid(rhs)`,
+        /// returning `None` if `rhs == 0` or the division results in
+        /// overflow.
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn checked_div_euclid(self, rhs: Self) -> Option<Self> {
+            if rhs == 0 || (self == Self::min_value() && rhs == -1) {
+                None
+            } else {
+                Some(self.div_euclid(rhs))
+            }
+        }
+
+        /// Checked Euclidean remainder. Computes `self.rem_euclid(rhs)`,
+        /// returning `None` if `rhs == 0` or, as with `checked_rem`, the
+        /// corresponding division results in overflow.
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+            if rhs == 0 || (self == Self::min_value() && rhs == -1) {
+                None
+            } else {
+                Some(self.rem_euclid(rhs))
+            }
+        }
+
+        /// Calculates the middle point of `self` and `rhs`, rounding
+        /// towards zero. This never overflows, unlike `(self + rhs) / 2`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_midpoint)]
+        /// assert_eq!(0i8.midpoint(5), 2);
+        /// assert_eq!((-1i8).midpoint(-4), -2);
+        /// assert_eq!(127i8.midpoint(125), 126);
+        /// assert_eq!((-128i8).midpoint(127), 0);
+        /// ```
+        #[unstable(feature = "num_midpoint", reason = "recently added")]
+        #[inline]
+        pub fn midpoint(self, rhs: Self) -> Self {
+            // The floor of the average, from the bits the operands share
+            // plus half of the bits where they differ.
+            let floor = (self & rhs) + ((self ^ rhs) >> 1);
+            if floor < 0 && (self ^ rhs) & 1 != 0 { floor + 1 } else { floor }
+        }
+
+        /// Computes the absolute difference between `self` and `rhs`.
+        ///
+        /// The result is unsigned, so this never overflows, unlike
+        /// `(self - rhs).abs()`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_midpoint)]
+        /// assert_eq!(100i8.abs_diff(-100), 200u8);
+        /// assert_eq!((-128i8).abs_diff(127), 255u8);
+        /// ```
+        #[unstable(feature = "num_midpoint", reason = "recently added")]
+        #[inline]
+        pub fn abs_diff(self, rhs: Self) -> $UnsignedT {
+            if self < rhs {
+                (rhs as $UnsignedT).wrapping_sub(self as $UnsignedT)
+            } else {
+                (self as $UnsignedT).wrapping_sub(rhs as $UnsignedT)
+            }
+        }
+
         /// Computes the absolute value of `self`.
         ///
         /// # Overflow behavior
@@ -638,6 +1373,15 @@ impl i64 {
         intrinsics::i64_mul_with_overflow }
 }
 
+#[cfg(not(stage0))]
+#[lang = "i128"]
+impl i128 {
+    int_impl! { i128, u128, 128,
+        intrinsics::i128_add_with_overflow,
+        intrinsics::i128_sub_with_overflow,
+        intrinsics::i128_mul_with_overflow }
+}
+
 #[cfg(target_pointer_width = "32")]
 #[lang = "isize"]
 impl isize {
@@ -666,6 +1410,22 @@ macro_rules! uint_impl {
      $add_with_overflow:path,
      $sub_with_overflow:path,
      $mul_with_overflow:path) => {
+        /// The size of this integer type in bits.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const BITS: usize = $BITS;
+
+        /// The size of this integer type in bytes.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const BYTES: usize = $BITS / 8;
+
+        /// The smallest value that can be represented by this integer type.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const MIN: Self = 0;
+
+        /// The largest value that can be represented by this integer type.
+        #[unstable(feature = "num_assoc_consts", reason = "recently added")]
+        pub const MAX: Self = !0;
+
         /// Returns the smallest value that can be represented by this integer type.
         #[stable(feature = "rust1", since = "1.0.0")]
         pub fn min_value() -> Self { 0 }
@@ -693,6 +1453,136 @@ macro_rules! uint_impl {
             from_str_radix(src, radix)
         }
 
+        /// Converts a string slice in a given base to an integer, skipping
+        /// `_` digit separators the way integer literals do.
+        ///
+        /// As in a literal, the first digit may not be a `_`, but any
+        /// number of separators may follow it, including at the end.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_parse_lenient)]
+        /// assert_eq!(u32::from_str_radix_lenient("1_000_000", 10), Ok(1000000));
+        /// assert_eq!(u32::from_str_radix_lenient("ff_ff", 16), Ok(0xffff));
+        /// assert!(u32::from_str_radix_lenient("_1", 10).is_err());
+        /// ```
+        #[unstable(feature = "int_parse_lenient", reason = "recently added")]
+        pub fn from_str_radix_lenient(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+            from_str_radix_separated(src, radix, true)
+        }
+
+        /// Converts an `i8` to this type, or returns an error if it is out of
+        /// range.
+        ///
+        /// The `try_from_*` family converts from each integer type without
+        /// the silent truncation of `as`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_try_from)]
+        /// assert_eq!(u8::try_from_i32(200), Ok(200));
+        /// assert!(u8::try_from_i32(300).is_err());
+        /// assert!(u8::try_from_i32(-1).is_err());
+        /// assert_eq!(i8::try_from_u64(127), Ok(127));
+        /// ```
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i8(n: i8) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i16` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i16(n: i16) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i32` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i32(n: i32) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i64` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i64(n: i64) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `isize` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_isize(n: isize) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u8` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u8(n: u8) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u16` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u16(n: u16) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u32` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u32(n: u32) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u64` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u64(n: u64) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `usize` to this type, or returns an error if it is out of
+        /// range.
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_usize(n: usize) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts an `i128` to this type, or returns an error if it is out of
+        /// range.
+        #[cfg(not(stage0))]
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_i128(n: i128) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
+        /// Converts a `u128` to this type, or returns an error if it is out of
+        /// range.
+        #[cfg(not(stage0))]
+        #[unstable(feature = "int_try_from", reason = "recently added")]
+        #[inline]
+        pub fn try_from_u128(n: u128) -> Result<Self, TryFromIntError> {
+            try_from_int(n, stringify!($ActualT))
+        }
+
         /// Returns the number of ones in the binary representation of `self`.
         ///
         /// # Examples
@@ -911,6 +1801,86 @@ macro_rules! uint_impl {
             if cfg!(target_endian = "little") { self } else { self.swap_bytes() }
         }
 
+        /// Returns the memory representation of this integer as a byte array
+        /// in big-endian (network) byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(0x12345678u32.to_be_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn to_be_bytes(self) -> [u8; $BITS / 8] {
+            self.to_be().to_ne_bytes()
+        }
+
+        /// Returns the memory representation of this integer as a byte array
+        /// in little-endian byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(0x12345678u32.to_le_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn to_le_bytes(self) -> [u8; $BITS / 8] {
+            self.to_le().to_ne_bytes()
+        }
+
+        /// Returns the memory representation of this integer as a byte array
+        /// in the target platform's native byte order.
+        ///
+        /// Portable code should prefer `to_be_bytes` or `to_le_bytes`.
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn to_ne_bytes(self) -> [u8; $BITS / 8] {
+            unsafe { mem::transmute(self) }
+        }
+
+        /// Creates an integer from its representation as a byte array in
+        /// big-endian (network) byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(u32::from_be_bytes([0x12, 0x34, 0x56, 0x78]), 0x12345678);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn from_be_bytes(bytes: [u8; $BITS / 8]) -> Self {
+            Self::from_be(Self::from_ne_bytes(bytes))
+        }
+
+        /// Creates an integer from its representation as a byte array in
+        /// little-endian byte order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #![feature(int_to_from_bytes)]
+        /// assert_eq!(u32::from_le_bytes([0x78, 0x56, 0x34, 0x12]), 0x12345678);
+        /// ```
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn from_le_bytes(bytes: [u8; $BITS / 8]) -> Self {
+            Self::from_le(Self::from_ne_bytes(bytes))
+        }
+
+        /// Creates an integer from its representation as a byte array in the
+        /// target platform's native byte order.
+        ///
+        /// Portable code should prefer `from_be_bytes` or `from_le_bytes`.
+        #[unstable(feature = "int_to_from_bytes", reason = "recently added")]
+        #[inline]
+        pub fn from_ne_bytes(bytes: [u8; $BITS / 8]) -> Self {
+            unsafe { mem::transmute(bytes) }
+        }
+
         /// Checked integer addition. Computes `self + other`, returning `None`
         /// if overflow occurred.
         ///
@@ -975,6 +1945,76 @@ macro_rules! uint_impl {
             }
         }
 
+        /// Checked integer remainder. Computes `self % other`, returning
+        /// `None` if `other == 0`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(5u32.checked_rem(2), Some(1));
+        /// assert_eq!(5u32.checked_rem(0), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_rem(self, v: Self) -> Option<Self> {
+            match v {
+                0 => None,
+                v => Some(self % v),
+            }
+        }
+
+        /// Checked negation. Computes `-self`, returning `None` if the result
+        /// cannot be represented.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(0u8.checked_neg(), Some(0));
+        /// assert_eq!(1u8.checked_neg(), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_neg(self) -> Option<Self> {
+            let (a, b) = self.overflowing_neg();
+            if b { None } else { Some(a) }
+        }
+
+        /// Checked shift left. Computes `self << rhs`, returning `None` if
+        /// `rhs` is larger than or equal to the number of bits in `self`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(0x10u32.checked_shl(4), Some(0x100));
+        /// assert_eq!(0x10u32.checked_shl(32), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_shl(self, rhs: u32) -> Option<Self> {
+            let (a, b) = self.overflowing_shl(rhs);
+            if b { None } else { Some(a) }
+        }
+
+        /// Checked shift right. Computes `self >> rhs`, returning `None` if
+        /// `rhs` is larger than or equal to the number of bits in `self`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(0x10u32.checked_shr(4), Some(0x1));
+        /// assert_eq!(0x10u32.checked_shr(33), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_shr(self, rhs: u32) -> Option<Self> {
+            let (a, b) = self.overflowing_shr(rhs);
+            if b { None } else { Some(a) }
+        }
+
         /// Saturating integer addition. Computes `self + other`, saturating at
         /// the numeric bounds instead of overflowing.
         #[stable(feature = "rust1", since = "1.0.0")]
@@ -999,6 +2039,22 @@ macro_rules! uint_impl {
             }
         }
 
+        /// Saturating integer multiplication. Computes `self * other`,
+        /// saturating at the numeric bounds instead of overflowing.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(100u8.saturating_mul(2), 200);
+        /// assert_eq!(100u8.saturating_mul(3), 255);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn saturating_mul(self, other: Self) -> Self {
+            self.checked_mul(other).unwrap_or(Self::max_value())
+        }
+
         /// Wrapping (modular) addition. Computes `self + other`,
         /// wrapping around at the boundary of the type.
         #[stable(feature = "rust1", since = "1.0.0")]
@@ -1089,6 +2145,105 @@ macro_rules! uint_impl {
             self.overflowing_shr(rhs).0
         }
 
+        /// Calculates `self + rhs`.
+        ///
+        /// Returns a tuple of the addition along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would
+        /// have occurred then the wrapped value is returned.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_add(self, rhs)
+        }
+
+        /// Calculates `self - rhs`.
+        ///
+        /// Returns a tuple of the subtraction along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would
+        /// have occurred then the wrapped value is returned.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_sub(self, rhs)
+        }
+
+        /// Calculates the multiplication of `self` and `rhs`.
+        ///
+        /// Returns a tuple of the multiplication along with a boolean
+        /// indicating whether an arithmetic overflow would occur. If an
+        /// overflow would have occurred then the wrapped value is returned.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_mul(self, rhs)
+        }
+
+        /// Calculates the divisor when `self` is divided by `rhs`.
+        ///
+        /// Returns a tuple of the divisor along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would
+        /// occur then `self` is returned.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `rhs` is 0.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_div(self, rhs)
+        }
+
+        /// Calculates the remainder when `self` is divided by `rhs`.
+        ///
+        /// Returns a tuple of the remainder after dividing along with a
+        /// boolean indicating whether an arithmetic overflow would occur. If
+        /// an overflow would occur then 0 is returned.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `rhs` is 0.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+            OverflowingOps::overflowing_rem(self, rhs)
+        }
+
+        /// Negates self, overflowing unless `self` is zero.
+        ///
+        /// Returns a tuple of the negated version of self along with a
+        /// boolean indicating whether an overflow happened.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_neg(self) -> (Self, bool) {
+            OverflowingOps::overflowing_neg(self)
+        }
+
+        /// Shifts self left by `rhs` bits.
+        ///
+        /// Returns a tuple of the shifted version of self along with a
+        /// boolean indicating whether the shift value was larger than or
+        /// equal to the number of bits. If the shift value is too large,
+        /// then value is masked (N-1) where N is the number of bits, and
+        /// this value is then used to perform the shift.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_shl(self, rhs: u32) -> (Self, bool) {
+            OverflowingOps::overflowing_shl(self, rhs)
+        }
+
+        /// Shifts self right by `rhs` bits.
+        ///
+        /// Returns a tuple of the shifted version of self along with a
+        /// boolean indicating whether the shift value was larger than or
+        /// equal to the number of bits. If the shift value is too large,
+        /// then value is masked (N-1) where N is the number of bits, and
+        /// this value is then used to perform the shift.
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline(always)]
+        pub fn overflowing_shr(self, rhs: u32) -> (Self, bool) {
+            OverflowingOps::overflowing_shr(self, rhs)
+        }
+
         /// Raises self to the power of `exp`, using exponentiation by squaring.
         ///
         /// # Examples
@@ -1124,6 +2279,171 @@ macro_rules! uint_impl {
             acc
         }
 
+        /// Checked exponentiation. Computes `self.pow(exp)`, returning `None`
+        /// if overflow occurred.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(2u32.checked_pow(5), Some(32));
+        /// assert_eq!(2u8.checked_pow(8), None);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn checked_pow(self, mut exp: u32) -> Option<Self> {
+            let mut base = self;
+            let mut acc = Self::one();
+
+            // The base is only squared while bits of `exp` remain, so a
+            // square that would overflow but is never used does not
+            // count as an overflow.
+            while exp > 1 {
+                if (exp & 1) == 1 {
+                    acc = match acc.checked_mul(base) { Some(x) => x, None => return None };
+                }
+                exp /= 2;
+                base = match base.checked_mul(base) { Some(x) => x, None => return None };
+            }
+            if exp == 1 {
+                acc = match acc.checked_mul(base) { Some(x) => x, None => return None };
+            }
+            Some(acc)
+        }
+
+        /// Saturating exponentiation. Computes `self.pow(exp)`, saturating at
+        /// the numeric bounds instead of overflowing.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_checked_ops)]
+        /// assert_eq!(4u32.saturating_pow(3), 64);
+        /// assert_eq!(2u8.saturating_pow(8), 255);
+        /// ```
+        #[unstable(feature = "num_checked_ops", reason = "recently added")]
+        #[inline]
+        pub fn saturating_pow(self, exp: u32) -> Self {
+            match self.checked_pow(exp) {
+                Some(x) => x,
+                None => Self::max_value(),
+            }
+        }
+
+        /// Raises self to the power of `exp`, using exponentiation by squaring.
+        ///
+        /// Returns a tuple of the exponentiation along with a boolean
+        /// indicating whether an arithmetic overflow would occur. If an
+        /// overflow would have occurred then the wrapped value is returned.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_overflowing)]
+        /// assert_eq!(3u8.overflowing_pow(5), (243, false));
+        /// assert_eq!(3u8.overflowing_pow(6), (217, true));
+        /// ```
+        #[unstable(feature = "num_overflowing", reason = "recently added")]
+        #[inline]
+        pub fn overflowing_pow(self, mut exp: u32) -> (Self, bool) {
+            let mut base = self;
+            let mut acc = Self::one();
+            let mut overflown = false;
+            let mut r;
+
+            while exp > 1 {
+                if (exp & 1) == 1 {
+                    r = acc.overflowing_mul(base);
+                    acc = r.0;
+                    overflown |= r.1;
+                }
+                exp /= 2;
+                r = base.overflowing_mul(base);
+                base = r.0;
+                overflown |= r.1;
+            }
+            if exp == 1 {
+                r = acc.overflowing_mul(base);
+                acc = r.0;
+                overflown |= r.1;
+            }
+            (acc, overflown)
+        }
+
+        /// Performs Euclidean division.
+        ///
+        /// For unsigned types this is the same as `self / rhs`; it exists
+        /// so that code generic over signedness can use it.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `rhs` is 0.
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn div_euclid(self, rhs: Self) -> Self {
+            self / rhs
+        }
+
+        /// Calculates the least remainder of `self (mod rhs)`.
+        ///
+        /// For unsigned types this is the same as `self % rhs`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `rhs` is 0.
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn rem_euclid(self, rhs: Self) -> Self {
+            self % rhs
+        }
+
+        /// Checked Euclidean division. Computes `self.div_euclid(rhs)`,
+        /// returning `None` if `rhs == 0`.
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn checked_div_euclid(self, rhs: Self) -> Option<Self> {
+            self.checked_div(rhs)
+        }
+
+        /// Checked Euclidean remainder. Computes `self.rem_euclid(rhs)`,
+        /// returning `None` if `rhs == 0`.
+        #[unstable(feature = "num_euclid", reason = "recently added")]
+        #[inline]
+        pub fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+            self.checked_rem(rhs)
+        }
+
+        /// Calculates the middle point of `self` and `rhs`, rounding down.
+        /// This never overflows, unlike `(self + rhs) / 2`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_midpoint)]
+        /// assert_eq!(0u8.midpoint(5), 2);
+        /// assert_eq!(255u8.midpoint(253), 254);
+        /// ```
+        #[unstable(feature = "num_midpoint", reason = "recently added")]
+        #[inline]
+        pub fn midpoint(self, rhs: Self) -> Self {
+            (self & rhs) + ((self ^ rhs) >> 1)
+        }
+
+        /// Computes the absolute difference between `self` and `rhs`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # #![feature(num_midpoint)]
+        /// assert_eq!(3u8.abs_diff(10), 7);
+        /// assert_eq!(10u8.abs_diff(3), 7);
+        /// ```
+        #[unstable(feature = "num_midpoint", reason = "recently added")]
+        #[inline]
+        pub fn abs_diff(self, rhs: Self) -> Self {
+            if self < rhs { rhs - self } else { self - rhs }
+        }
+
         /// Returns `true` iff `self == 2^k` for some `k`.
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
@@ -1206,6 +2526,19 @@ impl u64 {
         intrinsics::u64_mul_with_overflow }
 }
 
+#[cfg(not(stage0))]
+#[lang = "u128"]
+impl u128 {
+    uint_impl! { u128, 128,
+        intrinsics::ctpop128,
+        intrinsics::ctlz128,
+        intrinsics::cttz128,
+        intrinsics::bswap128,
+        intrinsics::u128_add_with_overflow,
+        intrinsics::u128_sub_with_overflow,
+        intrinsics::u128_mul_with_overflow }
+}
+
 #[cfg(target_pointer_width = "32")]
 #[lang = "usize"]
 impl usize {
@@ -1415,6 +2748,8 @@ macro_rules! from_str_radix_int_impl {
     )*}
 }
 from_str_radix_int_impl! { isize i8 i16 i32 i64 usize u8 u16 u32 u64 }
+#[cfg(not(stage0))]
+from_str_radix_int_impl! { i128 u128 }
 
 #[doc(hidden)]
 trait FromStrRadixHelper: PartialOrd + Copy {
@@ -1441,9 +2776,18 @@ macro_rules! doit {
     })*)
 }
 doit! { i8 i16 i32 i64 isize u8 u16 u32 u64 usize }
+#[cfg(not(stage0))]
+doit! { i128 u128 }
 
 fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32)
                                          -> Result<T, ParseIntError> {
+    from_str_radix_separated(src, radix, false)
+}
+
+/// Parses an integer, skipping `_` after the first digit if `separators`
+/// is set.
+fn from_str_radix_separated<T: FromStrRadixHelper>(src: &str, radix: u32, separators: bool)
+                                                   -> Result<T, ParseIntError> {
     use self::IntErrorKind::*;
     use self::ParseIntError as PIE;
     assert!(radix >= 2 && radix <= 36,
@@ -1454,13 +2798,14 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32)
 
     match src.slice_shift_char() {
         Some(('-', "")) => Err(PIE { kind: Empty }),
-        Some(('-', src)) if is_signed_ty => {
+        Some(('-', digits)) if is_signed_ty => {
             // The number is negative
             let mut result = T::from_u32(0);
-            for c in src.chars() {
+            for (i, c) in digits.char_indices() {
+                if separators && c == '_' && i > 0 { continue }
                 let x = match c.to_digit(radix) {
                     Some(x) => x,
-                    None => return Err(PIE { kind: InvalidDigit }),
+                    None => return Err(PIE { kind: InvalidDigit { offset: i + 1 } }),
                 };
                 result = match result.checked_mul(radix) {
                     Some(result) => result,
@@ -1476,10 +2821,11 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32)
         Some((_, _)) => {
             // The number is signed
             let mut result = T::from_u32(0);
-            for c in src.chars() {
+            for (i, c) in src.char_indices() {
+                if separators && c == '_' && i > 0 { continue }
                 let x = match c.to_digit(radix) {
                     Some(x) => x,
-                    None => return Err(PIE { kind: InvalidDigit }),
+                    None => return Err(PIE { kind: InvalidDigit { offset: i } }),
                 };
                 result = match result.checked_mul(radix) {
                     Some(result) => result,
@@ -1496,20 +2842,89 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32)
     }
 }
 
+#[doc(hidden)]
+trait IntCast<T>: Copy {
+    fn cast(self) -> T;
+}
+
+macro_rules! int_cast_impl {
+    ($S:ty; [$($T:ty)*]) => ($(impl IntCast<$T> for $S {
+        #[inline]
+        fn cast(self) -> $T { self as $T }
+    })*)
+}
+
+macro_rules! int_cast_impl_all {
+    ([$($S:ty)*] => $Ts:tt) => ($(int_cast_impl! { $S; $Ts })*)
+}
+
+int_cast_impl_all! { [i8 i16 i32 i64 isize u8 u16 u32 u64 usize] =>
+                     [i8 i16 i32 i64 isize u8 u16 u32 u64 usize] }
+#[cfg(not(stage0))]
+int_cast_impl_all! { [i8 i16 i32 i64 isize u8 u16 u32 u64 usize] => [i128 u128] }
+#[cfg(not(stage0))]
+int_cast_impl_all! { [i128 u128] =>
+                     [i8 i16 i32 i64 isize u8 u16 u32 u64 usize i128 u128] }
+
+/// Converts `n` if it survives a round trip through `T` without changing
+/// sign, which is exactly when it is in range.
+fn try_from_int<S, T>(n: S, target: &'static str) -> Result<T, TryFromIntError>
+    where S: IntCast<T> + PartialOrd + Zero,
+          T: IntCast<S> + PartialOrd + Zero
+{
+    let t: T = n.cast();
+    let back: S = t.cast();
+    let negative = n < S::zero();
+    if back == n && negative == (t < T::zero()) {
+        Ok(t)
+    } else {
+        let kind = if negative { IntErrorKind::Underflow } else { IntErrorKind::Overflow };
+        Err(TryFromIntError { kind: kind, target: target })
+    }
+}
+
 /// An error which can be returned when parsing an integer.
 #[derive(Debug, Clone, PartialEq)]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct ParseIntError { kind: IntErrorKind }
 
-#[derive(Debug, Clone, PartialEq)]
-enum IntErrorKind {
+/// The reason parsing an integer failed, as reported by
+/// `ParseIntError::kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[unstable(feature = "int_error_kind",
+           reason = "recently added, the set of kinds may grow")]
+pub enum IntErrorKind {
+    /// The string was empty, or consisted only of a `-` sign.
     Empty,
-    InvalidDigit,
+    /// The string contained a character that is not a digit in the
+    /// requested radix. `offset` is the byte offset of that character.
+    InvalidDigit { offset: usize },
+    /// The value is larger than the maximum value of the target type.
     Overflow,
+    /// The value is smaller than the minimum value of the target type.
     Underflow,
 }
 
 impl ParseIntError {
+    /// Returns the reason parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(int_error_kind)]
+    /// use std::num::IntErrorKind;
+    ///
+    /// assert_eq!(u8::from_str_radix("256", 10).unwrap_err().kind(),
+    ///            &IntErrorKind::Overflow);
+    /// assert_eq!(u8::from_str_radix("12x", 10).unwrap_err().kind(),
+    ///            &IntErrorKind::InvalidDigit { offset: 2 });
+    /// ```
+    #[unstable(feature = "int_error_kind",
+               reason = "recently added, the set of kinds may grow")]
+    pub fn kind(&self) -> &IntErrorKind {
+        &self.kind
+    }
+
     #[unstable(feature = "int_error_internals",
                reason = "available through Error trait and this method should \
                          not be exposed publicly")]
@@ -1517,7 +2932,7 @@ impl ParseIntError {
     pub fn __description(&self) -> &str {
         match self.kind {
             IntErrorKind::Empty => "cannot parse integer from empty string",
-            IntErrorKind::InvalidDigit => "invalid digit found in string",
+            IntErrorKind::InvalidDigit { .. } => "invalid digit found in string",
             IntErrorKind::Overflow => "number too large to fit in target type",
             IntErrorKind::Underflow => "number too small to fit in target type",
         }
@@ -1531,6 +2946,45 @@ impl fmt::Display for ParseIntError {
     }
 }
 
+/// The error returned by the `try_from_*` integer conversions when the
+/// value does not fit in the target type.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[unstable(feature = "int_try_from", reason = "recently added")]
+pub struct TryFromIntError {
+    kind: IntErrorKind,
+    target: &'static str,
+}
+
+impl TryFromIntError {
+    /// Returns `Overflow` if the value was too large for the target type
+    /// and `Underflow` if it was too small.
+    #[unstable(feature = "int_try_from", reason = "recently added")]
+    pub fn kind(&self) -> &IntErrorKind {
+        &self.kind
+    }
+
+    #[unstable(feature = "int_error_internals",
+               reason = "available through Error trait and this method should \
+                         not be exposed publicly")]
+    #[doc(hidden)]
+    pub fn __description(&self) -> &str {
+        match self.kind {
+            IntErrorKind::Underflow => "number too small to fit in target type",
+            _ => "number too large to fit in target type",
+        }
+    }
+}
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = match self.kind {
+            IntErrorKind::Underflow => "small",
+            _ => "large",
+        };
+        write!(f, "number too {} to fit in `{}`", size, self.target)
+    }
+}
+
 /// An error which can be returned when parsing a float.
 #[derive(Debug, Clone, PartialEq)]
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -0,0 +1,203 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Operator impls for `Saturating<T>`.
+
+#![unstable(feature = "saturating", reason = "recently added")]
+
+use super::{Saturating, Wrapping};
+
+use convert::From;
+use fmt;
+use ops::*;
+
+macro_rules! saturating_impl {
+    ($($t:ty)*) => ($(
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Add for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn add(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0.saturating_add(other.0))
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Sub for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn sub(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0.saturating_sub(other.0))
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Mul for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn mul(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0.saturating_mul(other.0))
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Rem for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn rem(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0.wrapping_rem(other.0))
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Not for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn not(self) -> Saturating<$t> {
+                Saturating(!self.0)
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl BitXor for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn bitxor(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0 ^ other.0)
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl BitOr for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn bitor(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0 | other.0)
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl BitAnd for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn bitand(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0 & other.0)
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl From<$t> for Saturating<$t> {
+            #[inline(always)]
+            fn from(n: $t) -> Saturating<$t> {
+                Saturating(n)
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl From<Saturating<$t>> for $t {
+            #[inline(always)]
+            fn from(n: Saturating<$t>) -> $t {
+                n.0
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl From<Wrapping<$t>> for Saturating<$t> {
+            #[inline(always)]
+            fn from(n: Wrapping<$t>) -> Saturating<$t> {
+                Saturating(n.0)
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl From<Saturating<$t>> for Wrapping<$t> {
+            #[inline(always)]
+            fn from(n: Saturating<$t>) -> Wrapping<$t> {
+                Wrapping(n.0)
+            }
+        }
+    )*)
+}
+
+saturating_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+saturating_impl! { u128 i128 }
+
+// The only signed quotient that overflows is `MIN / -1`, and the only
+// signed negation that overflows is `-MIN`; both clamp to `MAX`.
+macro_rules! saturating_signed_impl {
+    ($($t:ident)*) => ($(
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Div for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn div(self, other: Saturating<$t>) -> Saturating<$t> {
+                match self.0.overflowing_div(other.0) {
+                    (_, true) => Saturating($t::max_value()),
+                    (n, false) => Saturating(n),
+                }
+            }
+        }
+
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Neg for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn neg(self) -> Saturating<$t> {
+                Saturating(self.0.checked_neg().unwrap_or($t::max_value()))
+            }
+        }
+    )*)
+}
+
+saturating_signed_impl! { isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+saturating_signed_impl! { i128 }
+
+macro_rules! saturating_unsigned_impl {
+    ($($t:ty)*) => ($(
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl Div for Saturating<$t> {
+            type Output = Saturating<$t>;
+
+            #[inline(always)]
+            fn div(self, other: Saturating<$t>) -> Saturating<$t> {
+                Saturating(self.0 / other.0)
+            }
+        }
+    )*)
+}
+
+saturating_unsigned_impl! { usize u8 u16 u32 u64 }
+#[cfg(not(stage0))]
+saturating_unsigned_impl! { u128 }
+
+macro_rules! saturating_fmt_impl {
+    ($($Trait:ident)*) => ($(
+        #[unstable(feature = "saturating", reason = "recently added")]
+        impl<T: fmt::$Trait> fmt::$Trait for Saturating<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    )*)
+}
+
+saturating_fmt_impl! { Display Binary Octal LowerHex UpperHex }
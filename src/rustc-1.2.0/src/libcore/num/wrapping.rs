@@ -14,6 +14,7 @@
 
 use super::Wrapping;
 
+use fmt;
 use ops::*;
 
 use intrinsics::{i8_add_with_overflow, u8_add_with_overflow};
@@ -28,8 +29,16 @@ use intrinsics::{i8_mul_with_overflow, u8_mul_with_overflow};
 use intrinsics::{i16_mul_with_overflow, u16_mul_with_overflow};
 use intrinsics::{i32_mul_with_overflow, u32_mul_with_overflow};
 use intrinsics::{i64_mul_with_overflow, u64_mul_with_overflow};
+#[cfg(not(stage0))]
+use intrinsics::{i128_add_with_overflow, u128_add_with_overflow};
+#[cfg(not(stage0))]
+use intrinsics::{i128_sub_with_overflow, u128_sub_with_overflow};
+#[cfg(not(stage0))]
+use intrinsics::{i128_mul_with_overflow, u128_mul_with_overflow};
 
 use ::{i8,i16,i32,i64};
+#[cfg(not(stage0))]
+use ::i128;
 
 pub trait OverflowingOps {
     fn overflowing_add(self, rhs: Self) -> (Self, bool);
@@ -52,7 +61,7 @@ macro_rules! sh_impl {
 
             #[inline(always)]
             fn shl(self, other: $f) -> Wrapping<$t> {
-                Wrapping(self.0 << other)
+                Wrapping(self.0.wrapping_shl(other as u32))
             }
         }
 
@@ -62,7 +71,7 @@ macro_rules! sh_impl {
 
             #[inline(always)]
             fn shr(self, other: $f) -> Wrapping<$t> {
-                Wrapping(self.0 >> other)
+                Wrapping(self.0.wrapping_shr(other as u32))
             }
         }
     )
@@ -86,6 +95,8 @@ macro_rules! sh_impl_all {
 }
 
 sh_impl_all! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+sh_impl_all! { u128 i128 }
 
 macro_rules! wrapping_impl {
     ($($t:ty)*) => ($(
@@ -119,6 +130,36 @@ macro_rules! wrapping_impl {
             }
         }
 
+        #[unstable(feature = "wrapping", reason = "recently added")]
+        impl Div for Wrapping<$t> {
+            type Output = Wrapping<$t>;
+
+            #[inline(always)]
+            fn div(self, other: Wrapping<$t>) -> Wrapping<$t> {
+                Wrapping(self.0.wrapping_div(other.0))
+            }
+        }
+
+        #[unstable(feature = "wrapping", reason = "recently added")]
+        impl Rem for Wrapping<$t> {
+            type Output = Wrapping<$t>;
+
+            #[inline(always)]
+            fn rem(self, other: Wrapping<$t>) -> Wrapping<$t> {
+                Wrapping(self.0.wrapping_rem(other.0))
+            }
+        }
+
+        #[unstable(feature = "wrapping", reason = "recently added")]
+        impl Neg for Wrapping<$t> {
+            type Output = Wrapping<$t>;
+
+            #[inline(always)]
+            fn neg(self) -> Wrapping<$t> {
+                Wrapping(self.0.wrapping_neg())
+            }
+        }
+
         #[stable(feature = "rust1", since = "1.0.0")]
         impl Not for Wrapping<$t> {
             type Output = Wrapping<$t>;
@@ -162,6 +203,21 @@ macro_rules! wrapping_impl {
 }
 
 wrapping_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+wrapping_impl! { u128 i128 }
+
+macro_rules! wrapping_fmt_impl {
+    ($($Trait:ident)*) => ($(
+        #[unstable(feature = "wrapping", reason = "recently added")]
+        impl<T: fmt::$Trait> fmt::$Trait for Wrapping<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    )*)
+}
+
+wrapping_fmt_impl! { Display Binary Octal LowerHex UpperHex }
 
 mod shift_max {
     #![allow(non_upper_case_globals)]
@@ -170,11 +226,13 @@ mod shift_max {
     pub const i16: u32 = (1 << 4) - 1;
     pub const i32: u32 = (1 << 5) - 1;
     pub const i64: u32 = (1 << 6) - 1;
+    pub const i128: u32 = (1 << 7) - 1;
 
     pub const  u8: u32 = i8;
     pub const u16: u32 = i16;
     pub const u32: u32 = i32;
     pub const u64: u32 = i64;
+    pub const u128: u32 = i128;
 }
 
 macro_rules! signed_overflowing_impl {
@@ -283,7 +341,7 @@ macro_rules! unsigned_overflowing_impl {
 
             #[inline(always)]
             fn overflowing_neg(self) -> ($t, bool) {
-                ((!self).wrapping_add(1), true)
+                ((!self).wrapping_add(1), self != 0)
             }
         }
     )*)
@@ -291,6 +349,10 @@ macro_rules! unsigned_overflowing_impl {
 
 signed_overflowing_impl! { i8 i16 i32 i64 }
 unsigned_overflowing_impl! { u8 u16 u32 u64 }
+#[cfg(not(stage0))]
+signed_overflowing_impl! { i128 }
+#[cfg(not(stage0))]
+unsigned_overflowing_impl! { u128 }
 
 #[cfg(target_pointer_width = "64")]
 impl OverflowingOps for usize {
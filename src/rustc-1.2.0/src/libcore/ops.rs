@@ -204,6 +204,8 @@ macro_rules! add_impl {
 }
 
 add_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 f32 f64 }
+#[cfg(not(stage0))]
+add_impl! { u128 i128 }
 
 /// The `Sub` trait is used to specify the functionality of `-`.
 ///
@@ -258,6 +260,8 @@ macro_rules! sub_impl {
 }
 
 sub_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 f32 f64 }
+#[cfg(not(stage0))]
+sub_impl! { u128 i128 }
 
 /// The `Mul` trait is used to specify the functionality of `*`.
 ///
@@ -312,6 +316,8 @@ macro_rules! mul_impl {
 }
 
 mul_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 f32 f64 }
+#[cfg(not(stage0))]
+mul_impl! { u128 i128 }
 
 /// The `Div` trait is used to specify the functionality of `/`.
 ///
@@ -366,6 +372,8 @@ macro_rules! div_impl {
 }
 
 div_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 f32 f64 }
+#[cfg(not(stage0))]
+div_impl! { u128 i128 }
 
 /// The `Rem` trait is used to specify the functionality of `%`.
 ///
@@ -437,6 +445,8 @@ macro_rules! rem_float_impl {
 }
 
 rem_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+rem_impl! { u128 i128 }
 rem_float_impl! { f32, fmodf }
 rem_float_impl! { f64, fmod }
 
@@ -510,6 +520,8 @@ macro_rules! neg_impl_unsigned {
 
 // neg_impl_unsigned! { usize u8 u16 u32 u64 }
 neg_impl_numeric! { isize i8 i16 i32 i64 f32 f64 }
+#[cfg(not(stage0))]
+neg_impl_numeric! { i128 }
 
 /// The `Not` trait is used to specify the functionality of unary `!`.
 ///
@@ -564,6 +576,8 @@ macro_rules! not_impl {
 }
 
 not_impl! { bool usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+not_impl! { u128 i128 }
 
 /// The `BitAnd` trait is used to specify the functionality of `&`.
 ///
@@ -618,6 +632,8 @@ macro_rules! bitand_impl {
 }
 
 bitand_impl! { bool usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+bitand_impl! { u128 i128 }
 
 /// The `BitOr` trait is used to specify the functionality of `|`.
 ///
@@ -672,6 +688,8 @@ macro_rules! bitor_impl {
 }
 
 bitor_impl! { bool usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+bitor_impl! { u128 i128 }
 
 /// The `BitXor` trait is used to specify the functionality of `^`.
 ///
@@ -726,6 +744,8 @@ macro_rules! bitxor_impl {
 }
 
 bitxor_impl! { bool usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+bitxor_impl! { u128 i128 }
 
 /// The `Shl` trait is used to specify the functionality of `<<`.
 ///
@@ -798,6 +818,8 @@ macro_rules! shl_impl_all {
 }
 
 shl_impl_all! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+shl_impl_all! { u128 i128 }
 
 /// The `Shr` trait is used to specify the functionality of `>>`.
 ///
@@ -869,6 +891,8 @@ macro_rules! shr_impl_all {
 }
 
 shr_impl_all! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+shr_impl_all! { u128 i128 }
 
 /// The `Index` trait is used to specify the functionality of indexing operations
 /// like `arr[idx]` when used in an immutable context.
@@ -954,6 +978,44 @@ pub trait IndexMut<Idx: ?Sized>: Index<Idx> {
     fn index_mut<'a>(&'a mut self, index: Idx) -> &'a mut Self::Output;
 }
 
+/// The `IndexAssign` trait is used to specify the functionality of
+/// assignments like `container[index] = value` for containers that store
+/// the value themselves rather than handing out a reference to a slot.
+///
+/// When the container implements `IndexAssign`, such an assignment calls
+/// `index_assign` instead of assigning through `IndexMut`. This requires
+/// the `index_assign` feature.
+///
+/// # Examples
+///
+/// A log of assignments, which has no slots to hand out:
+///
+/// ```
+/// #![feature(core, index_assign)]
+/// use std::ops::IndexAssign;
+///
+/// struct Log(Vec<(usize, &'static str)>);
+///
+/// impl IndexAssign<usize, &'static str> for Log {
+///     fn index_assign(&mut self, index: usize, value: &'static str) {
+///         self.0.push((index, value));
+///     }
+/// }
+///
+/// fn main() {
+///     let mut log = Log(Vec::new());
+///     log[3] = "three";
+///     assert_eq!(log.0, [(3, "three")]);
+/// }
+/// ```
+#[lang = "index_assign"]
+#[unstable(feature = "core",
+           reason = "recently added, may be merged into `IndexMut`")]
+pub trait IndexAssign<Idx, Rhs> {
+    /// The method for the assignment `Foo[Bar] = Baz`
+    fn index_assign(&mut self, index: Idx, value: Rhs);
+}
+
 /// An unbounded range.
 #[derive(Copy, Clone, PartialEq, Eq)]
 #[lang = "range_full"]
@@ -1123,6 +1185,19 @@ impl<'a, T: ?Sized> DerefMut for &'a mut T {
     fn deref_mut(&mut self) -> &mut T { *self }
 }
 
+/// Marks a smart pointer that may be used as the type of `self` in a
+/// method, as in `fn consume(self: Rc<Self>)`.
+///
+/// The type the pointer derefs to is taken to be its first type
+/// parameter, which must be `Self` (or `&Self`, `&mut Self` or
+/// `Box<Self>`) for the method to be accepted. Methods taking such a
+/// receiver cannot be called on trait objects.
+#[lang = "receiver"]
+#[unstable(feature = "core", reason = "recently added, subject to change")]
+pub trait Receiver: Deref {
+    // Empty.
+}
+
 /// A version of the call operator that takes an immutable receiver.
 #[lang = "fn"]
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -34,12 +34,15 @@
 #![feature(iter_unfold)]
 #![feature(libc)]
 #![feature(nonzero)]
+#![feature(nonzero_int)]
+#![feature(num_assoc_consts)]
 #![feature(num_bits_bytes)]
 #![feature(ptr_as_ref)]
 #![feature(rand)]
 #![feature(range_inclusive)]
 #![feature(raw)]
 #![feature(result_expect)]
+#![feature(saturating)]
 #![feature(slice_bytes)]
 #![feature(slice_patterns)]
 #![feature(step_by)]
@@ -47,6 +50,7 @@
 #![feature(unboxed_closures)]
 #![feature(unicode)]
 #![feature(unique)]
+#![feature(wrapping)]
 
 extern crate core;
 extern crate test;
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use core::nonzero::NonZero;
+use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64};
 use core::option::Option;
 use core::option::Option::{Some, None};
 use std::mem::size_of;
@@ -25,6 +26,39 @@ fn test_size_nonzero_in_option() {
     assert_eq!(size_of::<NonZero<u32>>(), size_of::<Option<NonZero<u32>>>());
 }
 
+#[test]
+fn test_size_nonzero_int_in_option() {
+    assert_eq!(size_of::<NonZeroU8>(), size_of::<Option<NonZeroU8>>());
+    assert_eq!(size_of::<NonZeroU16>(), size_of::<Option<NonZeroU16>>());
+    assert_eq!(size_of::<NonZeroU32>(), size_of::<Option<NonZeroU32>>());
+    assert_eq!(size_of::<NonZeroU64>(), size_of::<Option<NonZeroU64>>());
+    assert_eq!(size_of::<u32>(), size_of::<Option<NonZeroU32>>());
+}
+
+#[test]
+fn test_nonzero_int_constructors() {
+    assert!(NonZeroU8::new(0).is_none());
+    assert_eq!(NonZeroU8::new(7).map(|n| n.get()), Some(7));
+    assert_eq!(NonZeroU64::new(!0).unwrap().get(), !0);
+    let n = unsafe { NonZeroU16::new_unchecked(300) };
+    assert_eq!(n.get(), 300);
+    let raw: u16 = n.into();
+    assert_eq!(raw, 300);
+    assert_eq!(format!("{:?} {}", n, n), "300 300");
+}
+
+#[test]
+fn test_match_on_nonzero_int_option() {
+    match NonZeroU32::new(42) {
+        Some(n) => assert_eq!(n.get(), 42),
+        None => panic!("unexpected None while matching on NonZeroU32::new(42)")
+    }
+    match NonZeroU32::new(0) {
+        Some(_) => panic!("unexpected Some while matching on NonZeroU32::new(0)"),
+        None => {}
+    }
+}
+
 #[test]
 fn test_match_on_nonzero_option() {
     let a = Some(unsafe {
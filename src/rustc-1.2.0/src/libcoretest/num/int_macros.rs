@@ -23,6 +23,16 @@ mod tests {
         assert!(MIN + MAX + 1 == 0);
     }
 
+    #[test]
+    fn test_assoc_consts() {
+        assert_eq!($T::BITS, BITS);
+        assert_eq!($T::BYTES, BYTES);
+        assert_eq!($T::MIN, MIN);
+        assert_eq!($T::MAX, MAX);
+        assert_eq!($T::MIN, $T::min_value());
+        assert_eq!($T::MAX, $T::max_value());
+    }
+
     #[test]
     fn test_num() {
         num::test_num(10 as $T, 2 as $T);
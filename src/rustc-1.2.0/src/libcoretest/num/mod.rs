@@ -30,6 +30,8 @@ mod u32;
 mod u64;
 
 mod flt2dec;
+mod saturating;
+mod wrapping;
 
 /// Helper function for testing numeric operations
 pub fn test_num<T>(ten: T, two: T) where
@@ -0,0 +1,68 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::num::{Saturating, Wrapping};
+
+macro_rules! saturating_test {
+    ($T:ident, $test:ident) => {
+        #[test]
+        fn $test() {
+            let max = Saturating($T::max_value());
+            let min = Saturating($T::min_value());
+            let one = Saturating(1 as $T);
+            let two = Saturating(2 as $T);
+
+            assert_eq!(max + one, max);
+            assert_eq!(min - one, min);
+            assert_eq!(max * two, max);
+            assert_eq!(max / one, max);
+            assert_eq!(max % max, Saturating(0));
+            assert_eq!(!min, max);
+            assert_eq!(one + one, two);
+
+            let raw: $T = max.into();
+            assert_eq!(raw, $T::max_value());
+            let w: Wrapping<$T> = max.into();
+            assert_eq!(w + Wrapping(1), Wrapping($T::min_value()));
+            assert_eq!(Saturating::from(w) + one, max);
+        }
+    }
+}
+
+saturating_test! { i8, test_saturating_i8 }
+saturating_test! { i16, test_saturating_i16 }
+saturating_test! { i32, test_saturating_i32 }
+saturating_test! { i64, test_saturating_i64 }
+saturating_test! { isize, test_saturating_isize }
+saturating_test! { u8, test_saturating_u8 }
+saturating_test! { u16, test_saturating_u16 }
+saturating_test! { u32, test_saturating_u32 }
+saturating_test! { u64, test_saturating_u64 }
+saturating_test! { usize, test_saturating_usize }
+
+#[test]
+fn test_saturating_signed_edges() {
+    let min = Saturating(i32::min_value());
+    let max = Saturating(i32::max_value());
+    assert_eq!(-min, max);
+    assert_eq!(-max, Saturating(-i32::max_value()));
+    assert_eq!(min / Saturating(-1), max);
+    assert_eq!(min % Saturating(-1), Saturating(0));
+    assert_eq!(min * Saturating(-1), max);
+    assert_eq!(min * Saturating(2), min);
+}
+
+#[test]
+fn test_saturating_fmt() {
+    let s = Saturating(255u8);
+    assert_eq!(format!("{}", s), "255");
+    assert_eq!(format!("{:x} {:X}", s, s), "ff FF");
+    assert_eq!(format!("{:?}", s), "Saturating(255)");
+}
@@ -0,0 +1,64 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::num::Wrapping;
+
+macro_rules! wrapping_test {
+    ($T:ident, $test:ident) => {
+        #[test]
+        fn $test() {
+            use core::$T::BITS;
+
+            let max = Wrapping($T::max_value());
+            let min = Wrapping($T::min_value());
+            let one = Wrapping(1 as $T);
+            let zero = Wrapping(0 as $T);
+
+            assert_eq!(max + one, min);
+            assert_eq!(min - one, max);
+            assert_eq!(-zero, zero);
+            assert_eq!(-one, zero - one);
+            assert_eq!(!zero, Wrapping(!0));
+            assert_eq!(max / one, max);
+            assert_eq!(max % max, zero);
+            assert_eq!(one << BITS, one);
+            assert_eq!(one << (BITS + 1), Wrapping(2));
+            assert_eq!(max >> BITS, max);
+        }
+    }
+}
+
+wrapping_test! { i8, test_wrapping_i8 }
+wrapping_test! { i16, test_wrapping_i16 }
+wrapping_test! { i32, test_wrapping_i32 }
+wrapping_test! { i64, test_wrapping_i64 }
+wrapping_test! { isize, test_wrapping_isize }
+wrapping_test! { u8, test_wrapping_u8 }
+wrapping_test! { u16, test_wrapping_u16 }
+wrapping_test! { u32, test_wrapping_u32 }
+wrapping_test! { u64, test_wrapping_u64 }
+wrapping_test! { usize, test_wrapping_usize }
+
+#[test]
+fn test_wrapping_signed_edges() {
+    assert_eq!(-Wrapping(i32::min_value()), Wrapping(i32::min_value()));
+    assert_eq!(Wrapping(i32::min_value()) / Wrapping(-1), Wrapping(i32::min_value()));
+    assert_eq!(Wrapping(i32::min_value()) % Wrapping(-1), Wrapping(0));
+}
+
+#[test]
+fn test_wrapping_fmt() {
+    let w = Wrapping(255u8);
+    assert_eq!(format!("{}", w), "255");
+    assert_eq!(format!("{:b}", w), "11111111");
+    assert_eq!(format!("{:o}", w), "377");
+    assert_eq!(format!("{:x} {:X}", w, w), "ff FF");
+    assert_eq!(format!("{:?}", w), "Wrapping(255)");
+}
@@ -36,6 +36,7 @@
 #![feature(num_bits_bytes)]
 #![feature(staged_api)]
 #![feature(step_by)]
+#![cfg_attr(not(stage0), feature(i128_type))]
 
 #![cfg_attr(test, feature(test, rand, rustc_private, iter_order))]
 
@@ -1077,5 +1077,6 @@ register_diagnostics! {
     E0316, // nested quantification of lifetimes
     E0370, // discriminant overflow
     E0395, // pointer comparison in const-expr
-    E0396  // pointer dereference in const-expr
+    E0396, // pointer dereference in const-expr
+    E0406  // 128-bit enum discriminants are not supported
 }
@@ -166,6 +166,17 @@ impl LintStore {
         self.by_name.insert(old_name.to_string(), Renamed(new_name.to_string(), target));
     }
 
+    /// Whether the name in a lint attribute refers to `lint`, either
+    /// directly or as a group containing it.
+    fn names(&self, name: &str, lint: LintId) -> bool {
+        match self.by_name.get(name) {
+            Some(&Id(lint_id)) | Some(&Renamed(_, lint_id)) => lint_id == lint,
+            None => {
+                self.lint_groups.get(name).map_or(false, |&(ref lints, _)| lints.contains(&lint))
+            }
+        }
+    }
+
     #[allow(unused_variables)]
     fn find_lint(&self, lint_name: &str, sess: &Session, span: Option<Span>)
                  -> Option<LintId>
@@ -324,6 +335,45 @@ pub fn raw_emit_lint(sess: &Session, lint: &'static Lint,
     }
 }
 
+/// The level `lint` has at the node `id`, found as the lint pass would
+/// find it: from the innermost enclosing item with a lint attribute naming
+/// it, else from the command line or the lint's default. Lets a check that
+/// runs before the lint pass, and whose findings would be lost were
+/// compilation to stop first, emit its lint with `raw_emit_lint` at once.
+pub fn node_level_source(tcx: &ty::ctxt, lint: &'static Lint, id: ast::NodeId)
+                         -> LevelSource {
+    let lint_id = LintId::of(lint);
+    let store = tcx.sess.lint_store.borrow();
+    let mut lvlsrc = store.get_level_source(lint_id);
+    let mut node = id;
+    loop {
+        let attrs = if node == ast::CRATE_NODE_ID {
+            &tcx.map.krate().attrs[..]
+        } else {
+            tcx.map.attrs(node)
+        };
+        // Later attributes on an item override earlier ones.
+        let found = gather_attrs(attrs).into_iter().rev()
+                                       .filter_map(|attr| attr.ok())
+                                       .find(|&(ref name, _, _)| store.names(name, lint_id));
+        if let Some((_, level, span)) = found {
+            lvlsrc = (level, Node(span));
+            break;
+        }
+        let parent = tcx.map.get_parent(node);
+        if parent == node {
+            break;
+        }
+        node = parent;
+    }
+
+    // As in `Context::lookup_and_emit`, warnings take the level of `warnings`.
+    if lvlsrc.0 == Warn && lint_id != LintId::of(builtin::WARNINGS) {
+        lvlsrc.0 = node_level_source(tcx, builtin::WARNINGS, id).0;
+    }
+    lvlsrc
+}
+
 impl<'a, 'tcx> Context<'a, 'tcx> {
     fn new(tcx: &'a ty::ctxt<'tcx>,
            krate: &'a ast::Crate,
@@ -20,6 +20,7 @@ use metadata::cstore::{CStore, CrateSource, MetadataBlob};
 use metadata::decoder;
 use metadata::loader;
 use metadata::loader::CratePaths;
+use util::nodemap::NodeMap;
 
 use std::cell::RefCell;
 use std::path::PathBuf;
@@ -385,6 +386,7 @@ impl<'a> CrateReader<'a> {
             cnum: cnum,
             codemap_import_info: RefCell::new(vec![]),
             span: span,
+            item_doc_positions: RefCell::new(NodeMap()),
         });
 
         let source = cstore::CrateSource {
@@ -59,6 +59,9 @@ pub struct crate_metadata {
     pub cnum: ast::CrateNum,
     pub codemap_import_info: RefCell<Vec<ImportedFileMap>>,
     pub span: codemap::Span,
+    /// Start and end of the item docs looked up so far, by node id. Filled
+    /// lazily by `decoder::lookup_item_cached`.
+    pub item_doc_positions: RefCell<NodeMap<(usize, usize)>>,
 }
 
 #[derive(Copy, Debug, PartialEq, Clone)]
@@ -108,6 +108,20 @@ fn lookup_item<'a>(item_id: ast::NodeId, data: &'a [u8]) -> rbml::Doc<'a> {
     find_item(item_id, items)
 }
 
+// Like `lookup_item`, but remembers where the item doc is. Finding an item
+// means scanning for the items doc and then a bucket of the hashed index;
+// items such as impls and generic types are looked up over and over while
+// relating substs and confirming methods, so only pay for that once.
+fn lookup_item_cached<'a>(item_id: ast::NodeId, cdata: Cmd<'a>) -> rbml::Doc<'a> {
+    let data = cdata.data();
+    if let Some(&(start, end)) = cdata.item_doc_positions.borrow().get(&item_id) {
+        return rbml::Doc { data: data, start: start, end: end };
+    }
+    let doc = lookup_item(item_id, data);
+    cdata.item_doc_positions.borrow_mut().insert(item_id, (doc.start, doc.end));
+    doc
+}
+
 #[derive(PartialEq)]
 enum Family {
     ImmStatic,             // c
@@ -391,7 +405,7 @@ pub fn get_trait_def<'tcx>(cdata: Cmd,
                            item_id: ast::NodeId,
                            tcx: &ty::ctxt<'tcx>) -> ty::TraitDef<'tcx>
 {
-    let item_doc = lookup_item(item_id, cdata.data());
+    let item_doc = lookup_item_cached(item_id, cdata);
     let generics = doc_generics(item_doc, tcx, cdata, tag_item_generics);
     let unsafety = parse_unsafety(item_doc);
     let associated_type_names = parse_associated_type_names(item_doc);
@@ -414,7 +428,7 @@ pub fn get_predicates<'tcx>(cdata: Cmd,
                             tcx: &ty::ctxt<'tcx>)
                             -> ty::GenericPredicates<'tcx>
 {
-    let item_doc = lookup_item(item_id, cdata.data());
+    let item_doc = lookup_item_cached(item_id, cdata);
     doc_predicates(item_doc, tcx, cdata, tag_item_generics)
 }
 
@@ -423,14 +437,14 @@ pub fn get_super_predicates<'tcx>(cdata: Cmd,
                                   tcx: &ty::ctxt<'tcx>)
                                   -> ty::GenericPredicates<'tcx>
 {
-    let item_doc = lookup_item(item_id, cdata.data());
+    let item_doc = lookup_item_cached(item_id, cdata);
     doc_predicates(item_doc, tcx, cdata, tag_item_super_predicates)
 }
 
 pub fn get_type<'tcx>(cdata: Cmd, id: ast::NodeId, tcx: &ty::ctxt<'tcx>)
                       -> ty::TypeScheme<'tcx>
 {
-    let item_doc = lookup_item(id, cdata.data());
+    let item_doc = lookup_item_cached(id, cdata);
     let t = item_type(ast::DefId { krate: cdata.cnum, node: id }, item_doc, tcx,
                       cdata);
     let generics = doc_generics(item_doc, tcx, cdata, tag_item_generics);
@@ -819,12 +833,12 @@ pub fn get_impl_or_trait_item<'tcx>(intr: Rc<IdentInterner>,
                                     id: ast::NodeId,
                                     tcx: &ty::ctxt<'tcx>)
                                     -> ty::ImplOrTraitItem<'tcx> {
-    let item_doc = lookup_item(id, cdata.data());
+    let item_doc = lookup_item_cached(id, cdata);
 
     let def_id = item_def_id(item_doc, cdata);
 
     let container_id = item_require_parent_item(cdata, item_doc);
-    let container_doc = lookup_item(container_id.node, cdata.data());
+    let container_doc = lookup_item_cached(container_id.node, cdata);
     let container = match item_family(container_doc) {
         Trait => TraitContainer(container_id),
         _ => ImplContainer(container_id),
@@ -893,8 +907,7 @@ pub fn get_trait_item_def_ids(cdata: Cmd, id: ast::NodeId)
 }
 
 pub fn get_item_variances(cdata: Cmd, id: ast::NodeId) -> ty::ItemVariances {
-    let data = cdata.data();
-    let item_doc = lookup_item(id, data);
+    let item_doc = lookup_item_cached(id, cdata);
     let variance_doc = reader::get_doc(item_doc, tag_item_variances);
     let mut decoder = reader::Decoder::new(variance_doc);
     Decodable::decode(&mut decoder).unwrap()
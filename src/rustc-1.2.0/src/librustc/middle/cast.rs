@@ -57,6 +57,64 @@ pub enum CastKind {
     FnPtrAddrCast
 }
 
+impl CastKind {
+    /// Classifies a non-coercion cast between two scalars by their cast
+    /// types alone, or returns `None` if no such cast is allowed. Casts
+    /// between pointers further depend on the types pointed to; checking
+    /// those is left to the caller.
+    pub fn classify<'tcx>(t_from: CastTy<'tcx>, t_cast: CastTy<'tcx>) -> Option<CastKind> {
+        use self::CastTy::*;
+        use self::IntTy::*;
+
+        let kind = match (t_from, t_cast) {
+            // These types have invariants! can't cast into them.
+            (_, RPtr(_)) | (_, Int(CEnum)) | (_, FnPtr) | (_, Int(Bool)) => return None,
+
+            // * -> Char
+            (Int(U(ast::TyU8)), Int(Char)) => CastKind::U8CharCast, // u8-char-cast
+            (_, Int(Char)) => return None,
+
+            // prim -> float,ptr
+            (Int(Bool), Float) | (Int(CEnum), Float) | (Int(Char), Float) |
+            (Int(Bool), Ptr(_)) | (Int(CEnum), Ptr(_)) | (Int(Char), Ptr(_)) => return None,
+
+            // ptr -> *
+            (Ptr(_), Ptr(_)) => CastKind::PtrPtrCast, // ptr-ptr-cast
+            (Ptr(_), Int(_)) => CastKind::PtrAddrCast, // ptr-addr-cast
+            (Ptr(_), Float) | (FnPtr, Float) => return None,
+            (FnPtr, Int(_)) => CastKind::FnPtrAddrCast,
+            (RPtr(_), Int(_)) | (RPtr(_), Float) => return None,
+
+            // * -> ptr
+            (Int(_), Ptr(_)) => CastKind::AddrPtrCast, // addr-ptr-cast
+            (FnPtr, Ptr(_)) => CastKind::FnPtrPtrCast,
+            (Float, Ptr(_)) => return None,
+            (RPtr(_), Ptr(_)) => CastKind::ArrayPtrCast, // array-ptr-cast
+
+            // prim -> prim
+            (Int(CEnum), Int(_)) => CastKind::EnumCast,
+            (Int(Char), Int(_)) | (Int(Bool), Int(_)) => CastKind::PrimIntCast,
+
+            (Int(_), Int(_)) |
+            (Int(_), Float) |
+            (Float, Int(_)) |
+            (Float, Float) => CastKind::NumericCast,
+        };
+        Some(kind)
+    }
+
+    /// Classifies a cast that type checking has already accepted, given
+    /// its fully resolved source and target types. Returns `None` unless
+    /// both types are scalars; such casts can only be coercion-casts.
+    pub fn of_valid_cast<'tcx>(tcx: &ty::ctxt<'tcx>, t_from: Ty<'tcx>, t_cast: Ty<'tcx>)
+                               -> Option<CastKind> {
+        match (CastTy::from_ty(tcx, t_from), CastTy::from_ty(tcx, t_cast)) {
+            (Some(t_from), Some(t_cast)) => CastKind::classify(t_from, t_cast),
+            _ => None
+        }
+    }
+}
+
 impl<'tcx> CastTy<'tcx> {
     pub fn from_ty(tcx: &ty::ctxt<'tcx>, t: Ty<'tcx>)
                    -> Option<CastTy<'tcx>> {
@@ -39,8 +39,64 @@ use std::collections::hash_map::Entry::Vacant;
 use std::{i8, i16, i32, i64, u8, u16, u32, u64};
 use std::rc::Rc;
 
+/// The definition a path expression refers to.
+///
+/// Paths through a primitive type, like `u8::MAX`, stay partially
+/// resolved until type checking. Array lengths in signatures are
+/// evaluated before that, so such paths are looked up in the
+/// primitive's inherent impl here.
+fn path_def(tcx: &ty::ctxt, e: &Expr) -> Option<def::Def> {
+    let path_res = match tcx.def_map.borrow().get(&e.id) {
+        Some(&path_res) => path_res,
+        None => return None
+    };
+    if path_res.depth == 0 {
+        return Some(path_res.base_def);
+    }
+    let item_name = match e.node {
+        ast::ExprPath(None, ref path) => path.segments.last().unwrap().identifier.name,
+        _ => return None
+    };
+    match path_res.base_def {
+        def::DefPrimTy(prim_ty) if path_res.depth == 1 => {
+            lookup_primitive_assoc_const(tcx, prim_ty, item_name)
+        }
+        _ => None
+    }
+}
+
+fn lookup_primitive_assoc_const(tcx: &ty::ctxt,
+                                prim_ty: ast::PrimTy,
+                                name: ast::Name)
+                                -> Option<def::Def> {
+    let ty = match prim_ty {
+        ast::TyInt(t) => ty::mk_mach_int(tcx, t),
+        ast::TyUint(t) => ty::mk_mach_uint(tcx, t),
+        ast::TyFloat(t) => ty::mk_mach_float(tcx, t),
+        ast::TyBool => tcx.types.bool,
+        ast::TyChar => tcx.types.char,
+        ast::TyStr => ty::mk_str(tcx),
+    };
+    let impl_def_id = match ty::primitive_inherent_impl(tcx, ty) {
+        Some(impl_def_id) => impl_def_id,
+        None => return None
+    };
+
+    let impl_items = tcx.impl_items.borrow();
+    let items = match impl_items.get(&impl_def_id) {
+        Some(items) => items,
+        None => return None
+    };
+    items.iter().filter_map(|item| match *item {
+        ty::ConstTraitItemId(def_id) => Some(def_id),
+        _ => None
+    }).find(|&def_id| {
+        ty::impl_or_trait_item(tcx, def_id).name() == name
+    }).map(|def_id| def::DefAssociatedConst(def_id, def::FromImpl(impl_def_id)))
+}
+
 fn lookup_const<'a>(tcx: &'a ty::ctxt, e: &Expr) -> Option<&'a Expr> {
-    let opt_def = tcx.def_map.borrow().get(&e.id).map(|d| d.full_def());
+    let opt_def = path_def(tcx, e);
     match opt_def {
         Some(def::DefConst(def_id)) |
         Some(def::DefAssociatedConst(def_id, _)) => {
@@ -310,7 +366,7 @@ pub fn const_expr_to_pat(tcx: &ty::ctxt, expr: &Expr, span: Span) -> P<ast::Pat>
         }
 
         ast::ExprPath(_, ref path) => {
-            let opt_def = tcx.def_map.borrow().get(&expr.id).map(|d| d.full_def());
+            let opt_def = path_def(tcx, expr);
             match opt_def {
                 Some(def::DefStruct(..)) =>
                     ast::PatStruct(path.clone(), vec![], false),
@@ -363,24 +419,28 @@ pub enum ErrKind {
     NotOnStruct,
     NotOnTuple,
 
-    NegateWithOverflow(i64),
-    AddiWithOverflow(i64, i64),
-    SubiWithOverflow(i64, i64),
-    MuliWithOverflow(i64, i64),
-    AdduWithOverflow(u64, u64),
-    SubuWithOverflow(u64, u64),
-    MuluWithOverflow(u64, u64),
+    // The overflow errors carry the operands and, when it is known,
+    // the type whose range was exceeded.
+    NegateWithOverflow(i64, Option<IntTy>),
+    AddiWithOverflow(i64, i64, Option<IntTy>),
+    SubiWithOverflow(i64, i64, Option<IntTy>),
+    MuliWithOverflow(i64, i64, Option<IntTy>),
+    AdduWithOverflow(u64, u64, Option<UintTy>),
+    SubuWithOverflow(u64, u64, Option<UintTy>),
+    MuluWithOverflow(u64, u64, Option<UintTy>),
     DivideByZero,
-    DivideWithOverflow,
+    DivideWithOverflow(i64, i64, Option<IntTy>),
     ModuloByZero,
-    ModuloWithOverflow,
-    ShiftLeftWithOverflow,
-    ShiftRightWithOverflow,
+    ModuloWithOverflow(i64, i64, Option<IntTy>),
+    // The shift amount and the bit width of the shifted type.
+    ShiftLeftWithOverflow(ConstVal, u32),
+    ShiftRightWithOverflow(ConstVal, u32),
     MissingStructField,
     NonConstPath,
     ExpectedConstTuple,
     ExpectedConstStruct,
     TupleIndexOutOfBounds,
+    WideIntOutOfRange,
 
     MiscBinaryOp,
     MiscCatchAll,
@@ -408,24 +468,43 @@ impl ConstEvalErr {
             NotOnStruct => "not on struct".into_cow(),
             NotOnTuple => "not on tuple".into_cow(),
 
-            NegateWithOverflow(..) => "attempted to negate with overflow".into_cow(),
-            AddiWithOverflow(..) => "attempted to add with overflow".into_cow(),
-            SubiWithOverflow(..) => "attempted to sub with overflow".into_cow(),
-            MuliWithOverflow(..) => "attempted to mul with overflow".into_cow(),
-            AdduWithOverflow(..) => "attempted to add with overflow".into_cow(),
-            SubuWithOverflow(..) => "attempted to sub with overflow".into_cow(),
-            MuluWithOverflow(..) => "attempted to mul with overflow".into_cow(),
+            NegateWithOverflow(a, t) =>
+                overflow_msg("negate", format!("-{}", a), t.map(|t| t.name())),
+            AddiWithOverflow(a, b, t) =>
+                overflow_msg("add", format!("{} + {}", a, b), t.map(|t| t.name())),
+            SubiWithOverflow(a, b, t) =>
+                overflow_msg("sub", format!("{} - {}", a, b), t.map(|t| t.name())),
+            MuliWithOverflow(a, b, t) =>
+                overflow_msg("mul", format!("{} * {}", a, b), t.map(|t| t.name())),
+            AdduWithOverflow(a, b, t) =>
+                overflow_msg("add", format!("{} + {}", a, b), t.map(|t| t.name())),
+            SubuWithOverflow(a, b, t) =>
+                overflow_msg("sub", format!("{} - {}", a, b), t.map(|t| t.name())),
+            MuluWithOverflow(a, b, t) =>
+                overflow_msg("mul", format!("{} * {}", a, b), t.map(|t| t.name())),
             DivideByZero         => "attempted to divide by zero".into_cow(),
-            DivideWithOverflow   => "attempted to divide with overflow".into_cow(),
+            DivideWithOverflow(a, b, t) =>
+                overflow_msg("divide", format!("{} / {}", a, b), t.map(|t| t.name())),
             ModuloByZero         => "attempted remainder with a divisor of zero".into_cow(),
-            ModuloWithOverflow   => "attempted remainder with overflow".into_cow(),
-            ShiftLeftWithOverflow => "attempted left shift with overflow".into_cow(),
-            ShiftRightWithOverflow => "attempted right shift with overflow".into_cow(),
+            ModuloWithOverflow(a, b, t) =>
+                format!("attempted remainder with overflow: `{} % {}`{}", a, b,
+                        t.map_or(String::new(), |t| format!(" in `{}`", t.name())))
+                    .into_cow(),
+            ShiftLeftWithOverflow(ref amount, bits) =>
+                format!("attempted left shift with overflow: shift amount `{}` is not \
+                         less than the bit width {}",
+                        shift_amount_to_string(amount), bits).into_cow(),
+            ShiftRightWithOverflow(ref amount, bits) =>
+                format!("attempted right shift with overflow: shift amount `{}` is not \
+                         less than the bit width {}",
+                        shift_amount_to_string(amount), bits).into_cow(),
             MissingStructField  => "nonexistent struct field".into_cow(),
             NonConstPath        => "non-constant path in constant expr".into_cow(),
             ExpectedConstTuple => "expected constant tuple".into_cow(),
             ExpectedConstStruct => "expected constant struct".into_cow(),
             TupleIndexOutOfBounds => "tuple index out of bounds".into_cow(),
+            WideIntOutOfRange => "128-bit constant exceeds the 64-bit range supported \
+                                  by constant evaluation".into_cow(),
 
             MiscBinaryOp => "bad operands for binary".into_cow(),
             MiscCatchAll => "unsupported constant expr".into_cow(),
@@ -433,13 +512,29 @@ impl ConstEvalErr {
     }
 }
 
+fn overflow_msg<'a>(op: &str, expr: String, ty: Option<&str>) -> Cow<'a, str> {
+    match ty {
+        Some(ty) => format!("attempted to {} with overflow: `{}` does not fit in `{}`",
+                            op, expr, ty),
+        None => format!("attempted to {} with overflow: `{}`", op, expr),
+    }.into_cow()
+}
+
+fn shift_amount_to_string(amount: &ConstVal) -> String {
+    match *amount {
+        Int(i) => i.to_string(),
+        Uint(u) => u.to_string(),
+        _ => "?".to_string(),
+    }
+}
+
 pub type EvalResult = Result<ConstVal, ConstEvalErr>;
 pub type CastResult = Result<ConstVal, ErrKind>;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub enum IntTy { I8, I16, I32, I64 }
+pub enum IntTy { I8, I16, I32, I64, I128 }
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub enum UintTy { U8, U16, U32, U64 }
+pub enum UintTy { U8, U16, U32, U64, U128 }
 
 impl IntTy {
     pub fn from(tcx: &ty::ctxt, t: ast::IntTy) -> IntTy {
@@ -454,6 +549,32 @@ impl IntTy {
             ast::TyI16 => IntTy::I16,
             ast::TyI32 => IntTy::I32,
             ast::TyI64 => IntTy::I64,
+            ast::TyI128 => IntTy::I128,
+        }
+    }
+
+    /// Constants are evaluated with 64-bit arithmetic, so an overflow for
+    /// a 128-bit type means the value left the range we can represent
+    /// rather than the range of the type.
+    fn is_128_bit(&self) -> bool { *self == IntTy::I128 }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::I128 => "i128",
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match *self {
+            IntTy::I8 => 8,
+            IntTy::I16 => 16,
+            IntTy::I32 => 32,
+            IntTy::I64 => 64,
+            IntTy::I128 => 128,
         }
     }
 }
@@ -471,6 +592,29 @@ impl UintTy {
             ast::TyU16 => UintTy::U16,
             ast::TyU32 => UintTy::U32,
             ast::TyU64 => UintTy::U64,
+            ast::TyU128 => UintTy::U128,
+        }
+    }
+
+    fn is_128_bit(&self) -> bool { *self == UintTy::U128 }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            UintTy::U8 => "u8",
+            UintTy::U16 => "u16",
+            UintTy::U32 => "u32",
+            UintTy::U64 => "u64",
+            UintTy::U128 => "u128",
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match *self {
+            UintTy::U8 => 8,
+            UintTy::U16 => 16,
+            UintTy::U32 => 32,
+            UintTy::U64 => 64,
+            UintTy::U128 => 128,
         }
     }
 }
@@ -489,6 +633,9 @@ macro_rules! signal {
 // overall expression (`e`) and, if available, whole expression's
 // concrete type (`opt_ety`).
 //
+// Overflow in a constant expression is always an error, whether or not
+// overflow checks are enabled for the code generated at runtime.
+//
 // If the whole expression's concrete type is None, then this is a
 // constant evaluation happening before type check (e.g. in the check
 // to confirm that a pattern range's left-side is not greater than its
@@ -507,12 +654,12 @@ pub fn const_int_checked_neg<'a>(
         Some(IntTy::I8) =>  (-(i8::MAX as i64), -(i8::MIN as i64)),
         Some(IntTy::I16) => (-(i16::MAX as i64), -(i16::MIN as i64)),
         Some(IntTy::I32) => (-(i32::MAX as i64), -(i32::MIN as i64)),
-        None | Some(IntTy::I64) => (-i64::MAX, -(i64::MIN+1)),
+        None | Some(IntTy::I64) | Some(IntTy::I128) => (-i64::MAX, -(i64::MIN+1)),
     };
 
     let oflo = a < min || a > max;
     if oflo {
-        signal!(e, NegateWithOverflow(a));
+        signal!(e, NegateWithOverflow(a, opt_ety));
     } else {
         Ok(Int(-a))
     }
@@ -524,21 +671,23 @@ pub fn const_uint_checked_neg<'a>(
     Ok(Uint((!a).wrapping_add(1)))
 }
 
-fn const_uint_not(a: u64, opt_ety: Option<UintTy>) -> ConstVal {
+fn const_uint_not<'a>(a: u64, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
     let mask = match opt_ety {
         Some(UintTy::U8) => u8::MAX as u64,
         Some(UintTy::U16) => u16::MAX as u64,
         Some(UintTy::U32) => u32::MAX as u64,
         None | Some(UintTy::U64) => u64::MAX,
+        // The high 64 bits of the result are all set.
+        Some(UintTy::U128) => signal!(e, WideIntOutOfRange),
     };
-    Uint(!a & mask)
+    Ok(Uint(!a & mask))
 }
 
 macro_rules! overflow_checking_body {
     ($a:ident, $b:ident, $ety:ident, $overflowing_op:ident,
      lhs: $to_8_lhs:ident $to_16_lhs:ident $to_32_lhs:ident,
      rhs: $to_8_rhs:ident $to_16_rhs:ident $to_32_rhs:ident $to_64_rhs:ident,
-     $EnumTy:ident $T8: ident $T16: ident $T32: ident $T64: ident,
+     $EnumTy:ident $T8: ident $T16: ident $T32: ident $T64: ident $T128: ident,
      $result_type: ident) => { {
         let (a,b,opt_ety) = ($a,$b,$ety);
         match opt_ety {
@@ -563,7 +712,7 @@ macro_rules! overflow_checking_body {
                 }
                 (None, _) | (_, None) => (0, true)
             },
-            None | Some($EnumTy::$T64) => match b.$to_64_rhs() {
+            None | Some($EnumTy::$T64) | Some($EnumTy::$T128) => match b.$to_64_rhs() {
                 Some(b) => a.$overflowing_op(b),
                 None => (0, true),
             }
@@ -576,7 +725,7 @@ macro_rules! int_arith_body {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
             lhs: to_i8 to_i16 to_i32,
-            rhs: to_i8 to_i16 to_i32 to_i64, IntTy I8 I16 I32 I64, i64)
+            rhs: to_i8 to_i16 to_i32 to_i64, IntTy I8 I16 I32 I64 I128, i64)
     }
 }
 
@@ -585,7 +734,7 @@ macro_rules! uint_arith_body {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
             lhs: to_u8 to_u16 to_u32,
-            rhs: to_u8 to_u16 to_u32 to_u64, UintTy U8 U16 U32 U64, u64)
+            rhs: to_u8 to_u16 to_u32 to_u64, UintTy U8 U16 U32 U64 U128, u64)
     }
 }
 
@@ -594,7 +743,7 @@ macro_rules! int_shift_body {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
             lhs: to_i8 to_i16 to_i32,
-            rhs: to_u32 to_u32 to_u32 to_u32, IntTy I8 I16 I32 I64, i64)
+            rhs: to_u32 to_u32 to_u32 to_u32, IntTy I8 I16 I32 I64 I128, i64)
     }
 }
 
@@ -603,107 +752,128 @@ macro_rules! uint_shift_body {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
             lhs: to_u8 to_u16 to_u32,
-            rhs: to_u32 to_u32 to_u32 to_u32, UintTy U8 U16 U32 U64, u64)
+            rhs: to_u32 to_u32 to_u32 to_u32, UintTy U8 U16 U32 U64 U128, u64)
     }
 }
 
+macro_rules! check_wide {
+    ($e:expr, $oflo:expr, $opt_ety:expr) => {
+        if $oflo && $opt_ety.map_or(false, |t| t.is_128_bit()) {
+            signal!($e, WideIntOutOfRange)
+        }
+    }
+}
+
+fn int_bits(opt_ety: Option<IntTy>) -> u32 {
+    opt_ety.map_or(64, |t| t.bits())
+}
+
+fn uint_bits(opt_ety: Option<UintTy>) -> u32 {
+    opt_ety.map_or(64, |t| t.bits())
+}
+
 macro_rules! pub_fn_checked_op {
-    {$fn_name:ident ($a:ident : $a_ty:ty, $b:ident : $b_ty:ty,.. $WhichTy:ident) {
+    {$fn_name:ident ($a:ident : $a_ty:ty, $b:ident : $b_ty:ty,
+                     $ety:ident : Option<$WhichTy:ident>) {
         $ret_oflo_body:ident $overflowing_op:ident
             $const_ty:ident $signal_exn:expr
     }} => {
         pub fn $fn_name<'a>($a: $a_ty,
                             $b: $b_ty,
                             e: &'a Expr,
-                            opt_ety: Option<$WhichTy>) -> EvalResult {
-            let (ret, oflo) = $ret_oflo_body!($a, $b, opt_ety, $overflowing_op);
+                            $ety: Option<$WhichTy>) -> EvalResult {
+            let (ret, oflo) = $ret_oflo_body!($a, $b, $ety, $overflowing_op);
+            check_wide!(e, oflo, $ety);
             if !oflo { Ok($const_ty(ret)) } else { signal!(e, $signal_exn) }
         }
     }
 }
 
-pub_fn_checked_op!{ const_int_checked_add(a: i64, b: i64,.. IntTy) {
-           int_arith_body overflowing_add Int AddiWithOverflow(a, b)
+pub_fn_checked_op!{ const_int_checked_add(a: i64, b: i64, ety: Option<IntTy>) {
+           int_arith_body overflowing_add Int AddiWithOverflow(a, b, ety)
 }}
 
-pub_fn_checked_op!{ const_int_checked_sub(a: i64, b: i64,.. IntTy) {
-           int_arith_body overflowing_sub Int SubiWithOverflow(a, b)
+pub_fn_checked_op!{ const_int_checked_sub(a: i64, b: i64, ety: Option<IntTy>) {
+           int_arith_body overflowing_sub Int SubiWithOverflow(a, b, ety)
 }}
 
-pub_fn_checked_op!{ const_int_checked_mul(a: i64, b: i64,.. IntTy) {
-           int_arith_body overflowing_mul Int MuliWithOverflow(a, b)
+pub_fn_checked_op!{ const_int_checked_mul(a: i64, b: i64, ety: Option<IntTy>) {
+           int_arith_body overflowing_mul Int MuliWithOverflow(a, b, ety)
 }}
 
 pub fn const_int_checked_div<'a>(
     a: i64, b: i64, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
     if b == 0 { signal!(e, DivideByZero); }
     let (ret, oflo) = int_arith_body!(a, b, opt_ety, overflowing_div);
-    if !oflo { Ok(Int(ret)) } else { signal!(e, DivideWithOverflow) }
+    check_wide!(e, oflo, opt_ety);
+    if !oflo { Ok(Int(ret)) } else { signal!(e, DivideWithOverflow(a, b, opt_ety)) }
 }
 
 pub fn const_int_checked_rem<'a>(
     a: i64, b: i64, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
     if b == 0 { signal!(e, ModuloByZero); }
     let (ret, oflo) = int_arith_body!(a, b, opt_ety, overflowing_rem);
-    if !oflo { Ok(Int(ret)) } else { signal!(e, ModuloWithOverflow) }
+    check_wide!(e, oflo, opt_ety);
+    if !oflo { Ok(Int(ret)) } else { signal!(e, ModuloWithOverflow(a, b, opt_ety)) }
 }
 
-pub_fn_checked_op!{ const_int_checked_shl(a: i64, b: i64,.. IntTy) {
-           int_shift_body overflowing_shl Int ShiftLeftWithOverflow
+pub_fn_checked_op!{ const_int_checked_shl(a: i64, b: i64, ety: Option<IntTy>) {
+           int_shift_body overflowing_shl Int ShiftLeftWithOverflow(Int(b), int_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_int_checked_shl_via_uint(a: i64, b: u64,.. IntTy) {
-           int_shift_body overflowing_shl Int ShiftLeftWithOverflow
+pub_fn_checked_op!{ const_int_checked_shl_via_uint(a: i64, b: u64, ety: Option<IntTy>) {
+           int_shift_body overflowing_shl Int ShiftLeftWithOverflow(Uint(b), int_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_int_checked_shr(a: i64, b: i64,.. IntTy) {
-           int_shift_body overflowing_shr Int ShiftRightWithOverflow
+pub_fn_checked_op!{ const_int_checked_shr(a: i64, b: i64, ety: Option<IntTy>) {
+           int_shift_body overflowing_shr Int ShiftRightWithOverflow(Int(b), int_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_int_checked_shr_via_uint(a: i64, b: u64,.. IntTy) {
-           int_shift_body overflowing_shr Int ShiftRightWithOverflow
+pub_fn_checked_op!{ const_int_checked_shr_via_uint(a: i64, b: u64, ety: Option<IntTy>) {
+           int_shift_body overflowing_shr Int ShiftRightWithOverflow(Uint(b), int_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_uint_checked_add(a: u64, b: u64,.. UintTy) {
-           uint_arith_body overflowing_add Uint AdduWithOverflow(a, b)
+pub_fn_checked_op!{ const_uint_checked_add(a: u64, b: u64, ety: Option<UintTy>) {
+           uint_arith_body overflowing_add Uint AdduWithOverflow(a, b, ety)
 }}
 
-pub_fn_checked_op!{ const_uint_checked_sub(a: u64, b: u64,.. UintTy) {
-           uint_arith_body overflowing_sub Uint SubuWithOverflow(a, b)
+pub_fn_checked_op!{ const_uint_checked_sub(a: u64, b: u64, ety: Option<UintTy>) {
+           uint_arith_body overflowing_sub Uint SubuWithOverflow(a, b, ety)
 }}
 
-pub_fn_checked_op!{ const_uint_checked_mul(a: u64, b: u64,.. UintTy) {
-           uint_arith_body overflowing_mul Uint MuluWithOverflow(a, b)
+pub_fn_checked_op!{ const_uint_checked_mul(a: u64, b: u64, ety: Option<UintTy>) {
+           uint_arith_body overflowing_mul Uint MuluWithOverflow(a, b, ety)
 }}
 
 pub fn const_uint_checked_div<'a>(
     a: u64, b: u64, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
     if b == 0 { signal!(e, DivideByZero); }
-    let (ret, oflo) = uint_arith_body!(a, b, opt_ety, overflowing_div);
-    if !oflo { Ok(Uint(ret)) } else { signal!(e, DivideWithOverflow) }
+    // Unsigned division cannot overflow.
+    let (ret, _) = uint_arith_body!(a, b, opt_ety, overflowing_div);
+    Ok(Uint(ret))
 }
 
 pub fn const_uint_checked_rem<'a>(
     a: u64, b: u64, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
     if b == 0 { signal!(e, ModuloByZero); }
-    let (ret, oflo) = uint_arith_body!(a, b, opt_ety, overflowing_rem);
-    if !oflo { Ok(Uint(ret)) } else { signal!(e, ModuloWithOverflow) }
+    let (ret, _) = uint_arith_body!(a, b, opt_ety, overflowing_rem);
+    Ok(Uint(ret))
 }
 
-pub_fn_checked_op!{ const_uint_checked_shl(a: u64, b: u64,.. UintTy) {
-           uint_shift_body overflowing_shl Uint ShiftLeftWithOverflow
+pub_fn_checked_op!{ const_uint_checked_shl(a: u64, b: u64, ety: Option<UintTy>) {
+           uint_shift_body overflowing_shl Uint ShiftLeftWithOverflow(Uint(b), uint_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_uint_checked_shl_via_int(a: u64, b: i64,.. UintTy) {
-           uint_shift_body overflowing_shl Uint ShiftLeftWithOverflow
+pub_fn_checked_op!{ const_uint_checked_shl_via_int(a: u64, b: i64, ety: Option<UintTy>) {
+           uint_shift_body overflowing_shl Uint ShiftLeftWithOverflow(Int(b), uint_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_uint_checked_shr(a: u64, b: u64,.. UintTy) {
-           uint_shift_body overflowing_shr Uint ShiftRightWithOverflow
+pub_fn_checked_op!{ const_uint_checked_shr(a: u64, b: u64, ety: Option<UintTy>) {
+           uint_shift_body overflowing_shr Uint ShiftRightWithOverflow(Uint(b), uint_bits(ety))
 }}
 
-pub_fn_checked_op!{ const_uint_checked_shr_via_int(a: u64, b: i64,.. UintTy) {
-           uint_shift_body overflowing_shr Uint ShiftRightWithOverflow
+pub_fn_checked_op!{ const_uint_checked_shr_via_int(a: u64, b: i64, ety: Option<UintTy>) {
+           uint_shift_body overflowing_shr Uint ShiftRightWithOverflow(Int(b), uint_bits(ety))
 }}
 
 // After type checking, `eval_const_expr_partial` should always suffice. The
@@ -764,7 +934,7 @@ pub fn eval_const_expr_with_substs<'tcx, S>(tcx: &ty::ctxt<'tcx>,
       ast::ExprUnary(ast::UnNot, ref inner) => {
         match try!(eval_const_expr_partial(tcx, &**inner, ety)) {
           Int(i) => Int(!i),
-          Uint(i) => const_uint_not(i, expr_uint_type),
+          Uint(i) => try!(const_uint_not(i, e, expr_uint_type)),
           Bool(b) => Bool(!b),
           Str(_) => signal!(e, NotOnString),
           Float(_) => signal!(e, NotOnFloat),
@@ -879,10 +1049,14 @@ pub fn eval_const_expr_with_substs<'tcx, S>(tcx: &ty::ctxt<'tcx>,
 
         // Prefer known type to noop, but always have a type hint.
         //
-        // FIXME (#23833): the type-hint can cause problems,
-        // e.g. `(i8::MAX + 1_i8) as u32` feeds in `u32` as result
-        // type to the sum, and thus no overflow is signaled.
-        let base_hint = ty::expr_ty_opt(tcx, &**base).unwrap_or(ety);
+        // Before type checking (e.g. for array lengths) the base has no
+        // type yet, so look for one in its literal suffixes and paths
+        // before falling back to the target type; otherwise
+        // `(i8::MAX + 1_i8) as u32` would feed in `u32` as the type of
+        // the sum, and no overflow would be signaled (#23833).
+        let base_hint = ty::expr_ty_opt(tcx, &**base)
+            .or_else(|| syntactic_int_ty(tcx, &**base))
+            .unwrap_or(ety);
         let val = try!(eval_const_expr_partial(tcx, &**base, Some(base_hint)));
         match cast_const(tcx, val, ety) {
             Ok(val) => val,
@@ -890,7 +1064,7 @@ pub fn eval_const_expr_with_substs<'tcx, S>(tcx: &ty::ctxt<'tcx>,
         }
       }
       ast::ExprPath(..) => {
-          let opt_def = tcx.def_map.borrow().get(&e.id).map(|d| d.full_def());
+          let opt_def = path_def(tcx, e);
           let (const_expr, const_ty) = match opt_def {
               Some(def::DefConst(def_id)) => {
                   if ast_util::is_local(def_id) {
@@ -1107,11 +1281,13 @@ fn cast_const<'tcx>(tcx: &ty::ctxt<'tcx>, val: ConstVal, ty: Ty) -> CastResult {
         ty::TyInt(ast::TyI16) => convert_val!(i16, Int, i64),
         ty::TyInt(ast::TyI32) => convert_val!(i32, Int, i64),
         ty::TyInt(ast::TyI64) => convert_val!(i64, Int, i64),
+        ty::TyInt(ast::TyI128) => convert_val!(i64, Int, i64),
 
         ty::TyUint(ast::TyU8) => convert_val!(u8, Uint, u64),
         ty::TyUint(ast::TyU16) => convert_val!(u16, Uint, u64),
         ty::TyUint(ast::TyU32) => convert_val!(u32, Uint, u64),
         ty::TyUint(ast::TyU64) => convert_val!(u64, Uint, u64),
+        ty::TyUint(ast::TyU128) => convert_val!(u64, Uint, u64),
 
         ty::TyFloat(ast::TyF32) => convert_val!(f32, Float, f64),
         ty::TyFloat(ast::TyF64) => convert_val!(f64, Float, f64),
@@ -1119,6 +1295,63 @@ fn cast_const<'tcx>(tcx: &ty::ctxt<'tcx>, val: ConstVal, ty: Ty) -> CastResult {
     }
 }
 
+/// The integer type of `e` as far as it can be told without type
+/// checking: from literal suffixes, casts and the declared types of
+/// constants.
+fn syntactic_int_ty<'tcx>(tcx: &ty::ctxt<'tcx>, e: &Expr) -> Option<Ty<'tcx>> {
+    match e.node {
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitInt(_, ast::SignedIntLit(t, _)) => Some(ty::mk_mach_int(tcx, t)),
+            ast::LitInt(_, ast::UnsignedIntLit(t)) => Some(ty::mk_mach_uint(tcx, t)),
+            _ => None,
+        },
+        ast::ExprParen(ref inner) |
+        ast::ExprUnary(ast::UnNeg, ref inner) |
+        ast::ExprUnary(ast::UnNot, ref inner) => syntactic_int_ty(tcx, &**inner),
+        ast::ExprCast(_, ref target_ty) => ast_ty_to_prim_ty(tcx, &**target_ty),
+        ast::ExprBinary(op, ref a, ref b) => match op.node {
+            ast::BiShl | ast::BiShr => syntactic_int_ty(tcx, &**a),
+            ast::BiAdd | ast::BiSub | ast::BiMul | ast::BiDiv | ast::BiRem |
+            ast::BiBitAnd | ast::BiBitOr | ast::BiBitXor => {
+                syntactic_int_ty(tcx, &**a).or_else(|| syntactic_int_ty(tcx, &**b))
+            }
+            _ => None,
+        },
+        ast::ExprPath(..) => {
+            let opt_def = path_def(tcx, e);
+            match opt_def {
+                Some(def::DefConst(def_id)) if ast_util::is_local(def_id) => {
+                    match tcx.map.find(def_id.node) {
+                        Some(ast_map::NodeItem(it)) => match it.node {
+                            ast::ItemConst(ref ty, _) => ast_ty_to_prim_ty(tcx, &**ty),
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                }
+                Some(def::DefConst(def_id)) => {
+                    let ty = ty::lookup_item_type(tcx, def_id).ty;
+                    match ty.sty {
+                        ty::TyInt(_) | ty::TyUint(_) => Some(ty),
+                        _ => None,
+                    }
+                }
+                Some(def::DefAssociatedConst(def_id, def::FromImpl(_))) => {
+                    match ty::impl_or_trait_item(tcx, def_id) {
+                        ty::ConstTraitItem(ref ac) => match ac.ty.sty {
+                            ty::TyInt(_) | ty::TyUint(_) => Some(ac.ty),
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 fn lit_to_const(lit: &ast::Lit, ty_hint: Option<Ty>) -> ConstVal {
     match lit.node {
         ast::LitStr(ref s, _) => Str((*s).clone()),
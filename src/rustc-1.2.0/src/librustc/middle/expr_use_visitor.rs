@@ -458,7 +458,7 @@ impl<'d,'t,'tcx,TYPER:mc::Typer<'tcx>> ExprUseVisitor<'d,'t,'tcx,TYPER> {
                 self.consume_exprs(args);
             }
 
-            ast::ExprMethodCall(_, _, ref args) => { // callee.m(args)
+            ast::ExprMethodCall(_, _, _, ref args) => { // callee.m(args)
                 self.consume_exprs(args);
             }
 
@@ -575,8 +575,20 @@ impl<'d,'t,'tcx,TYPER:mc::Typer<'tcx>> ExprUseVisitor<'d,'t,'tcx,TYPER> {
             }
 
             ast::ExprAssign(ref lhs, ref rhs) => {
-                self.mutate_expr(expr, &**lhs, JustWrite);
-                self.consume_expr(&**rhs);
+                match lhs.node {
+                    // `IndexAssign::index_assign` takes the (autoref'd)
+                    // container, the index and the value, so nothing is
+                    // written in place.
+                    ast::ExprIndex(ref base, ref idx) if self.is_index_assign(&**lhs) => {
+                        self.walk_overloaded_operator(&**lhs, &**base,
+                                                      vec![&**idx, &**rhs],
+                                                      PassArgs::ByValue);
+                    }
+                    _ => {
+                        self.mutate_expr(expr, &**lhs, JustWrite);
+                        self.consume_expr(&**rhs);
+                    }
+                }
             }
 
             ast::ExprCast(ref base, _) => {
@@ -974,6 +986,15 @@ impl<'d,'t,'tcx,TYPER:mc::Typer<'tcx>> ExprUseVisitor<'d,'t,'tcx,TYPER> {
         return true;
     }
 
+    // True if `expr`, the left-hand side of an assignment, was resolved to
+    // an `IndexAssign` call rather than to an lvalue.
+    fn is_index_assign(&self, expr: &ast::Expr) -> bool {
+        match self.typer.node_method_origin(MethodCall::expr(expr.id)) {
+            Some(origin) => ty::is_index_assign_method(self.tcx(), &origin),
+            None => false
+        }
+    }
+
     fn arm_move_mode(&mut self, discr_cmt: mc::cmt<'tcx>, arm: &ast::Arm) -> TrackMatchMode {
         let mut mode = Unknown;
         for pat in &arm.pats {
@@ -49,6 +49,8 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Bivariate<'a, 'tcx> {
 
     fn a_is_expected(&self) -> bool { self.fields.a_is_expected }
 
+    combine_fields_hooks!();
+
     fn will_change(&mut self, _: bool, _: bool) -> bool {
         // since we are not comparing regions, we don't care
         false
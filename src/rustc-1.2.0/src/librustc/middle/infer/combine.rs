@@ -46,17 +46,92 @@ use middle::ty::{IntType, UintType};
 use middle::ty::{self, Ty};
 use middle::ty_fold;
 use middle::ty_fold::{TypeFolder, TypeFoldable};
-use middle::ty_relate::{self, Relate, RelateResult, TypeRelation};
+use middle::ty_relate::{self, Relate, RelateObserver, RelateResult, RelationCache};
+use middle::ty_relate::TypeRelation;
 
 use syntax::ast;
 use syntax::codemap::Span;
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Implements the `TypeRelation` hooks that every combiner answers from
+/// its `CombineFields`, which the combiner must keep in a field named
+/// `fields`.
+macro_rules! combine_fields_hooks {
+    () => {
+        fn on_error(&mut self, err: ::middle::ty::type_err<'tcx>)
+                    -> ::middle::ty_relate::RelateResult<'tcx, ()> {
+            self.fields.on_error(err)
+        }
+
+        fn probe<F>(&mut self, f: F) -> bool
+            where F: FnOnce(&mut Self) -> bool
+        {
+            // Component errors met while probing are not the relation's own.
+            let infcx = self.fields.infcx;
+            let errors = self.fields.errors.take();
+            let r = infcx.probe(|_| f(self));
+            self.fields.errors = errors;
+            r
+        }
+
+        fn relation_cache(&self) -> Option<&::middle::ty_relate::RelationCache<'tcx>> {
+            self.fields.relation_cache()
+        }
+
+        fn relation_stack(&self) -> Option<&::middle::ty_relate::RelationStack<'tcx>> {
+            Some(&self.fields.infcx.relation_stack)
+        }
+
+        fn with_cause<F,R>(&mut self, cause: ::middle::ty_relate::Cause, f: F) -> R
+            where F: FnOnce(&mut Self) -> R
+        {
+            debug!("{} with_cause={:?}", self.tag(), cause);
+            self.fields.causes.push(cause);
+            let r = f(self);
+            self.fields.causes.pop();
+            r
+        }
+
+        fn causes(&self) -> &[::middle::ty_relate::Cause] {
+            &self.fields.causes
+        }
+
+        fn observer(&mut self) -> Option<&mut ::middle::ty_relate::RelateObserver<'tcx>> {
+            self.fields.observer()
+        }
+
+        fn expected_span(&self) -> Option<::syntax::codemap::Span> {
+            self.fields.expected_span()
+        }
+
+        fn expected_def_id(&self) -> Option<::syntax::ast::DefId> {
+            self.fields.expected_def_id
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct CombineFields<'a, 'tcx: 'a> {
     pub infcx: &'a InferCtxt<'a, 'tcx>,
     pub a_is_expected: bool,
     pub trace: TypeTrace<'tcx>,
-    pub cause: Option<ty_relate::Cause>,
+
+    /// The causes of the positions being related, outermost first; see
+    /// `TypeRelation::with_cause`.
+    pub causes: Vec<ty_relate::Cause>,
+
+    /// When set, component mismatches are collected here instead of
+    /// ending the relation; see `InferCtxt::sub_types_all_errors`.
+    pub errors: Option<Rc<RefCell<Vec<ty::type_err<'tcx>>>>>,
+
+    /// The item the expected side comes from, if the caller knows; see
+    /// `InferCtxt::sub_types_expected_from`.
+    pub expected_def_id: Option<ast::DefId>,
+
+    /// Prints the relation tree under `-Z trace-relations`.
+    pub tracer: Option<ty_relate::RelationTracer>,
 }
 
 pub fn super_combine_tys<'a,'tcx:'a,R>(infcx: &InferCtxt<'a, 'tcx>,
@@ -165,6 +240,44 @@ impl<'a, 'tcx> CombineFields<'a, 'tcx> {
         Equate::new(self.clone())
     }
 
+    /// Shared `TypeRelation::expected_span` for the combiners: the span
+    /// of the expected item, if it is local.
+    pub fn expected_span(&self) -> Option<Span> {
+        match self.expected_def_id {
+            Some(def_id) if def_id.krate == ast::LOCAL_CRATE => {
+                self.tcx().map.opt_span(def_id.node)
+            }
+            _ => None,
+        }
+    }
+
+    /// Shared `TypeRelation::observer` for the combiners.
+    pub fn observer(&mut self) -> Option<&mut RelateObserver<'tcx>> {
+        self.tracer.as_mut().map(|tracer| tracer as &mut RelateObserver<'tcx>)
+    }
+
+    /// Shared `TypeRelation::on_error` for the combiners.
+    pub fn on_error(&self, err: ty::type_err<'tcx>) -> RelateResult<'tcx, ()> {
+        match self.errors {
+            Some(ref errors) => {
+                errors.borrow_mut().push(err);
+                Ok(())
+            }
+            None => Err(err),
+        }
+    }
+
+    /// Shared `TypeRelation::relation_cache` for the combiners. A cached
+    /// success would hide the component errors that relating the pair
+    /// again ought to collect, so there is no cache while collecting.
+    pub fn relation_cache(&self) -> Option<&'a RelationCache<'tcx>> {
+        if self.errors.is_some() {
+            None
+        } else {
+            self.infcx.relation_cache.as_ref()
+        }
+    }
+
     pub fn bivariate(&self) -> Bivariate<'a, 'tcx> {
         Bivariate::new(self.clone())
     }
@@ -10,12 +10,12 @@
 
 use super::combine::{self, CombineFields};
 use super::higher_ranked::HigherRankedRelations;
-use super::{Subtype};
+use super::{Invariant, Subtype};
 use super::type_variable::{EqTo};
 
 use middle::ty::{self, Ty};
 use middle::ty::TyVar;
-use middle::ty_relate::{Relate, RelateResult, TypeRelation};
+use middle::ty_relate::{self, Relate, RelateResult, TypeRelation};
 
 pub struct Equate<'a, 'tcx: 'a> {
     fields: CombineFields<'a, 'tcx>
@@ -34,6 +34,8 @@ impl<'a, 'tcx> TypeRelation<'a,'tcx> for Equate<'a, 'tcx> {
 
     fn a_is_expected(&self) -> bool { self.fields.a_is_expected }
 
+    combine_fields_hooks!();
+
     fn will_change(&mut self, a: bool, b: bool) -> bool {
         // if either side changed from what it was, that could cause equality to fail
         a || b
@@ -83,7 +85,12 @@ impl<'a, 'tcx> TypeRelation<'a,'tcx> for Equate<'a, 'tcx> {
                self.tag(),
                a,
                b);
-        let origin = Subtype(self.fields.trace.clone());
+        let reasons = ty_relate::invariance_reasons(self.causes());
+        let origin = if reasons.is_empty() {
+            Subtype(self.fields.trace.clone())
+        } else {
+            Invariant(self.fields.trace.clone(), reasons)
+        };
         self.fields.infcx.region_vars.make_eqregion(origin, a, b);
         Ok(a)
     }
@@ -79,6 +79,7 @@ use middle::region;
 use middle::subst;
 use middle::ty::{self, Ty};
 use middle::ty::{Region, ReFree};
+use middle::ty_relate::InvarianceReason;
 
 use std::cell::{Cell, RefCell};
 use std::char::from_u32;
@@ -271,6 +272,15 @@ trait ErrorReportingHelpers<'tcx> {
     fn note_region_origin(&self,
                           origin: &SubregionOrigin<'tcx>);
 
+    fn note_invariance(&self,
+                       span: Span,
+                       reasons: &[InvarianceReason],
+                       regions: Option<(Region, Region)>);
+
+    fn note_fn_type_err(&self,
+                        trace: &TypeTrace<'tcx>,
+                        terr: &ty::type_err<'tcx>);
+
     fn give_expl_lifetime_param(&self,
                                 decl: &ast::FnDecl,
                                 unsafety: ast::Unsafety,
@@ -490,8 +500,9 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
                                      trace: TypeTrace<'tcx>,
                                      terr: &ty::type_err<'tcx>) {
         let span = trace.origin.span();
-        self.report_type_error(trace, terr);
+        self.report_type_error(trace.clone(), terr);
         ty::note_and_explain_type_err(self.tcx, terr, span);
+        self.note_fn_type_err(&trace, terr);
     }
 
     /// Returns a string of the form "expected `{}`, found `{}`", or None if this is a derived
@@ -597,6 +608,12 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
                 let terr = ty::terr_regions_does_not_outlive(sup, sub);
                 self.report_and_explain_type_error(trace, &terr);
             }
+            infer::Invariant(trace, reasons) => {
+                let span = trace.origin.span();
+                let terr = ty::terr_regions_does_not_outlive(sup, sub);
+                self.report_and_explain_type_error(trace, &terr);
+                self.note_invariance(span, &reasons, Some((sub, sup)));
+            }
             infer::Reborrow(span) => {
                 span_err!(self.tcx.sess, span, E0312,
                     "lifetime of reference outlines \
@@ -1606,7 +1623,8 @@ impl<'a, 'tcx> ErrorReportingHelpers<'tcx> for InferCtxt<'a, 'tcx> {
     fn note_region_origin(&self, origin: &SubregionOrigin<'tcx>) {
         match *origin {
             infer::Subtype(ref trace) |
-            infer::DefaultExistentialBound(ref trace) => {
+            infer::DefaultExistentialBound(ref trace) |
+            infer::Invariant(ref trace, _) => {
                 let desc = match trace.origin {
                     infer::Misc(_) => {
                         "types are compatible"
@@ -1661,6 +1679,10 @@ impl<'a, 'tcx> ErrorReportingHelpers<'tcx> for InferCtxt<'a, 'tcx> {
                             &format!("...so that {}", desc));
                     }
                 }
+
+                if let infer::Invariant(_, ref reasons) = *origin {
+                    self.note_invariance(trace.origin.span(), reasons, None);
+                }
             }
             infer::Reborrow(span) => {
                 self.tcx.sess.span_note(
@@ -1791,6 +1813,106 @@ impl<'a, 'tcx> ErrorReportingHelpers<'tcx> for InferCtxt<'a, 'tcx> {
             }
         }
     }
+
+    /// Adds suggestions for mismatches between fn types that need the
+    /// actual types being compared, which `ty::note_and_explain_type_err`
+    /// does not have.
+    fn note_fn_type_err(&self,
+                        trace: &TypeTrace<'tcx>,
+                        terr: &ty::type_err<'tcx>) {
+        let (expected, found) = match trace.values {
+            infer::Types(ref exp_found) => {
+                (exp_found.expected.resolve(self), exp_found.found.resolve(self))
+            }
+            _ => return,
+        };
+        if expected.contains_error() || found.contains_error() {
+            return;
+        }
+        let span = trace.origin.span();
+
+        match (*terr, &expected.sty, &found.sty) {
+            (ty::terr_unsafety_mismatch(values), &ty::TyBareFn(..), &ty::TyBareFn(..))
+                if values.expected == ast::Unsafety::Unsafe => {
+                self.tcx.sess.span_help(
+                    span,
+                    &format!("add a cast to convert the safe fn to the expected type: \
+                              `... as {}`",
+                             self.ty_to_string(expected)));
+            }
+            (ty::terr_abi_mismatch(values), _, &ty::TyBareFn(Some(def_id), _)) => {
+                let msg = format!("`{}` is declared with the {} ABI; declaring it as \
+                                   `extern {} fn` would make this valid",
+                                  ty::item_path_str(self.tcx, def_id),
+                                  values.found,
+                                  values.expected);
+                if def_id.krate == ast::LOCAL_CRATE {
+                    self.tcx.sess.span_note(self.tcx.map.span(def_id.node), &msg);
+                } else {
+                    self.tcx.sess.note(&msg);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn note_invariance(&self,
+                       span: Span,
+                       reasons: &[InvarianceReason],
+                       regions: Option<(Region, Region)>) {
+        let generics_of = |def_id: ast::DefId| {
+            // Trait refs are related using the trait's own generics,
+            // which are always loaded by the time we get here.
+            match self.tcx.trait_defs.borrow().get(&def_id) {
+                Some(trait_def) => trait_def.generics.clone(),
+                None => ty::lookup_item_type(self.tcx, def_id).generics,
+            }
+        };
+
+        let position = |reason: InvarianceReason| match reason {
+            InvarianceReason::MutRef => {
+                "types inside `&mut` are invariant".to_string()
+            }
+            InvarianceReason::MutRawPtr => {
+                "types inside `*mut` are invariant".to_string()
+            }
+            InvarianceReason::TypeParam(def_id, space, index) => {
+                let generics = generics_of(def_id);
+                format!("the type parameter `{}` of `{}` is invariant",
+                        token::get_name(generics.types.get(space, index).name),
+                        ty::item_path_str(self.tcx, def_id))
+            }
+            InvarianceReason::RegionParam(def_id, space, index) => {
+                let generics = generics_of(def_id);
+                format!("the lifetime parameter `{}` of `{}` is invariant",
+                        token::get_name(generics.regions.get(space, index).name),
+                        ty::item_path_str(self.tcx, def_id))
+            }
+        };
+        // Explain each enclosing invariant position, so that the user can
+        // see how the innermost one came to be invariant.
+        let positions = reasons.iter()
+                               .map(|&reason| position(reason))
+                               .collect::<Vec<_>>()
+                               .connect(", and ");
+
+        // Only name the regions if both of them have a name the user
+        // would recognize; anonymous scopes and variables print as "".
+        let named = |r: Region| {
+            let s = r.to_string();
+            if s.starts_with("'") && s != "'<empty>" { Some(s) } else { None }
+        };
+        let consequence = match regions.map(|(a, b)| (named(a), named(b))) {
+            Some((Some(a), Some(b))) => {
+                format!("`{}` must exactly equal `{}` here", a, b)
+            }
+            _ => {
+                "the lifetimes involved must be exactly equal here".to_string()
+            }
+        };
+
+        self.tcx.sess.span_note(span, &format!("{}, so {}", positions, consequence));
+    }
 }
 
 pub trait Resolvable<'tcx> {
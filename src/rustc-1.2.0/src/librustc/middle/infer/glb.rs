@@ -15,7 +15,7 @@ use super::lattice::{self, LatticeDir};
 use super::Subtype;
 
 use middle::ty::{self, Ty};
-use middle::ty_relate::{Relate, RelateResult, TypeRelation};
+use middle::ty_relate::{self, Relate, RelateResult, TypeRelation};
 
 /// "Greatest lower bound" (common subtype)
 pub struct Glb<'a, 'tcx: 'a> {
@@ -35,6 +35,8 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Glb<'a, 'tcx> {
 
     fn a_is_expected(&self) -> bool { self.fields.a_is_expected }
 
+    combine_fields_hooks!();
+
     fn will_change(&mut self, a: bool, b: bool) -> bool {
         // Hmm, so the result of GLB will still be a LB if one or both
         // sides change to 'static, but it may no longer be the GLB.
@@ -45,6 +47,11 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Glb<'a, 'tcx> {
         a || b
     }
 
+    fn builtin_bounds(&mut self, a: ty::BuiltinBounds, b: ty::BuiltinBounds)
+                      -> RelateResult<'tcx, ty::BuiltinBounds> {
+        Ok(ty_relate::glb_builtin_bounds(a, b))
+    }
+
     fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
                                                variance: ty::Variance,
                                                a: &T,
@@ -15,7 +15,7 @@ use super::lattice::{self, LatticeDir};
 use super::Subtype;
 
 use middle::ty::{self, Ty};
-use middle::ty_relate::{Relate, RelateResult, TypeRelation};
+use middle::ty_relate::{self, Relate, RelateResult, TypeRelation};
 
 /// "Least upper bound" (common supertype)
 pub struct Lub<'a, 'tcx: 'a> {
@@ -35,11 +35,18 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Lub<'a, 'tcx> {
 
     fn a_is_expected(&self) -> bool { self.fields.a_is_expected }
 
+    combine_fields_hooks!();
+
     fn will_change(&mut self, a: bool, b: bool) -> bool {
         // result will be 'static if a || b
         a || b
     }
 
+    fn builtin_bounds(&mut self, a: ty::BuiltinBounds, b: ty::BuiltinBounds)
+                      -> RelateResult<'tcx, ty::BuiltinBounds> {
+        Ok(ty_relate::lub_builtin_bounds(a, b))
+    }
+
     fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
                                                variance: ty::Variance,
                                                a: &T,
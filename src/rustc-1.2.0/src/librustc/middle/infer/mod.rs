@@ -16,6 +16,7 @@ pub use self::LateBoundRegionConversionTime::*;
 pub use self::RegionVariableOrigin::*;
 pub use self::SubregionOrigin::*;
 pub use self::TypeOrigin::*;
+pub use self::TypeVariableOrigin::*;
 pub use self::ValuePairs::*;
 pub use self::fixup_err::*;
 pub use middle::ty::IntVarValue;
@@ -25,13 +26,16 @@ pub use self::region_inference::GenericKind;
 use middle::free_region::FreeRegionMap;
 use middle::subst;
 use middle::subst::Substs;
+use middle::traits::{FulfillmentContext, FulfillmentSnapshot};
 use middle::ty::{TyVid, IntVid, FloatVid, RegionVid, UnconstrainedNumeric};
 use middle::ty::{self, Ty};
 use middle::ty_fold::{self, TypeFolder, TypeFoldable};
-use middle::ty_relate::{Relate, RelateResult, TypeRelation};
+use middle::ty_relate::{InvarianceReason, Relate, RelateResult, RelationCache, RelationStack};
+use middle::ty_relate::{self, TypeRelation};
 use rustc_data_structures::unify::{self, UnificationTable};
-use std::cell::{RefCell};
+use std::cell::RefCell;
 use std::fmt;
+use std::rc::Rc;
 use syntax::ast;
 use syntax::codemap;
 use syntax::codemap::Span;
@@ -42,8 +46,9 @@ use self::region_inference::{RegionVarBindings, RegionSnapshot};
 use self::error_reporting::ErrorReporting;
 use self::unify_key::ToType;
 
-pub mod bivariate;
+#[macro_use]
 pub mod combine;
+pub mod bivariate;
 pub mod equate;
 pub mod error_reporting;
 pub mod glb;
@@ -77,6 +82,31 @@ pub struct InferCtxt<'a, 'tcx: 'a> {
 
     // For region variables.
     region_vars: RegionVarBindings<'a, 'tcx>,
+
+    // Why each type variable created with `next_ty_var_with_origin` was
+    // created, so that a variable left unconstrained can be traced back
+    // to the code that introduced it.
+    ty_var_origins: RefCell<FnvHashMap<TyVid, TypeVariableOrigin>>,
+
+    // Results of relating pairs of types, when enabled with
+    // `-Z cache-relations`. Cleared whenever a snapshot is rolled back.
+    relation_cache: Option<RelationCache<'tcx>>,
+
+    // The pairs of types currently being related, shared by all the
+    // combiners so that nesting is limited across them.
+    relation_stack: RelationStack<'tcx>,
+
+    // The labels of the checkpoints still open, innermost last.
+    checkpoints: RefCell<Vec<&'static str>>,
+
+    // The integer and float variables defaulted by
+    // `apply_literal_fallback`, in the order they were defaulted.
+    defaulted_literals: RefCell<Vec<DefaultedLiteral<'tcx>>>,
+
+    // Types that `fully_resolve` managed to resolve completely, and what
+    // they resolved to. Bindings are only ever added outside of a
+    // rollback, so these stay valid until a snapshot is rolled back.
+    resolved_tys: RefCell<FnvHashMap<Ty<'tcx>, Ty<'tcx>>>,
 }
 
 /// A map returned by `skolemize_late_bound_regions()` indicating the skolemized
@@ -165,6 +195,18 @@ pub struct TypeTrace<'tcx> {
     values: ValuePairs<'tcx>,
 }
 
+/// Why `mk_subty`, `mk_eqty` or `mk_sub_poly_trait_refs` failed: the
+/// error the relation ran into, together with the trace of the two
+/// values that were being related. Callers can recover from the error,
+/// or hand both to `report_type_trace_error` for the usual diagnostic.
+#[derive(Clone, Debug)]
+pub struct TypeTraceError<'tcx> {
+    pub err: ty::type_err<'tcx>,
+    pub trace: TypeTrace<'tcx>,
+}
+
+pub type TraceResult<'tcx> = Result<(), TypeTraceError<'tcx>>;
+
 /// The origin of a `r1 <= r2` constraint.
 ///
 /// See `error_reporting.rs` for more details
@@ -176,6 +218,12 @@ pub enum SubregionOrigin<'tcx> {
     // Arose from a subtyping relation
     DefaultExistentialBound(TypeTrace<'tcx>),
 
+    // Arose from equating the two sides of an invariant position
+    // (e.g., the referent of a `&mut`) during a subtyping relation;
+    // the reasons are those of the enclosing invariant positions,
+    // outermost first
+    Invariant(TypeTrace<'tcx>, Vec<InvarianceReason>),
+
     // Stack-allocated closures cannot outlive innermost loop
     // or function so as to ensure we only require finite stack
     InfStackClosure(Span),
@@ -291,7 +339,50 @@ pub enum RegionVariableOrigin {
     BoundRegionInCoherence(ast::Name),
 }
 
+/// Reasons to create a type inference variable, recorded for the ones that
+/// stand for something the user could have written out explicitly.
+#[derive(Copy, Clone, Debug)]
+pub enum TypeVariableOrigin {
+    // The type parameter with the given name of the item referred to by
+    // the path at the span
+    TypeParameterVar(Span, ast::Name),
+
+    // The type parameter with the given name of the method with the
+    // given name, called at the span
+    MethodTypeParameterVar(Span, ast::Name, ast::Name),
+
+    // A `_` written in a type
+    TypeInferenceVar(Span),
+
+    // The element type of an array expression
+    ArrayElementVar(Span),
+}
+
+/// The types that integer and float variables which nothing constrained
+/// fall back to at the end of type checking.
+#[derive(Copy, Clone, Debug)]
+pub struct LiteralFallback<'tcx> {
+    pub int_ty: Ty<'tcx>,
+    pub float_ty: Ty<'tcx>,
+}
+
+impl<'tcx> LiteralFallback<'tcx> {
+    /// The usual fallback, to `i32` and `f64`.
+    pub fn new(tcx: &ty::ctxt<'tcx>) -> LiteralFallback<'tcx> {
+        LiteralFallback { int_ty: tcx.types.i32, float_ty: tcx.types.f64 }
+    }
+}
+
+/// An integer or float variable defaulted by `apply_literal_fallback`,
+/// with the node whose type it was found in and the type it was given.
 #[derive(Copy, Clone, Debug)]
+pub struct DefaultedLiteral<'tcx> {
+    pub var: ty::InferTy,
+    pub node_id: ast::NodeId,
+    pub ty: Ty<'tcx>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum fixup_err {
     unresolved_int_ty(IntVid),
     unresolved_float_ty(FloatVid),
@@ -320,6 +411,16 @@ pub fn new_infer_ctxt<'a, 'tcx>(tcx: &'a ty::ctxt<'tcx>)
         int_unification_table: RefCell::new(UnificationTable::new()),
         float_unification_table: RefCell::new(UnificationTable::new()),
         region_vars: RegionVarBindings::new(tcx),
+        ty_var_origins: RefCell::new(FnvHashMap()),
+        relation_cache: if tcx.sess.opts.debugging_opts.cache_relations {
+            Some(RelationCache::new())
+        } else {
+            None
+        },
+        relation_stack: RelationStack::new(),
+        checkpoints: RefCell::new(Vec::new()),
+        defaulted_literals: RefCell::new(Vec::new()),
+        resolved_tys: RefCell::new(FnvHashMap()),
     }
 }
 
@@ -355,10 +456,12 @@ pub fn mk_subty<'a, 'tcx>(cx: &InferCtxt<'a, 'tcx>,
                           origin: TypeOrigin,
                           a: Ty<'tcx>,
                           b: Ty<'tcx>)
-                          -> UnitResult<'tcx>
+                          -> TraceResult<'tcx>
 {
     debug!("mk_subty({:?} <: {:?})", a, b);
+    let trace = TypeTrace::types(origin, a_is_expected, a, b);
     cx.sub_types(a_is_expected, origin, a, b)
+        .map_err(|err| TypeTraceError { err: err, trace: trace })
 }
 
 pub fn can_mk_subty<'a, 'tcx>(cx: &InferCtxt<'a, 'tcx>,
@@ -396,10 +499,12 @@ pub fn mk_eqty<'a, 'tcx>(cx: &InferCtxt<'a, 'tcx>,
                          origin: TypeOrigin,
                          a: Ty<'tcx>,
                          b: Ty<'tcx>)
-                         -> UnitResult<'tcx>
+                         -> TraceResult<'tcx>
 {
     debug!("mk_eqty({:?} <: {:?})", a, b);
+    let trace = TypeTrace::types(origin, a_is_expected, a, b);
     cx.commit_if_ok(|_| cx.eq_types(a_is_expected, origin, a, b))
+        .map_err(|err| TypeTraceError { err: err, trace: trace })
 }
 
 pub fn mk_sub_poly_trait_refs<'a, 'tcx>(cx: &InferCtxt<'a, 'tcx>,
@@ -407,11 +512,16 @@ pub fn mk_sub_poly_trait_refs<'a, 'tcx>(cx: &InferCtxt<'a, 'tcx>,
                                    origin: TypeOrigin,
                                    a: ty::PolyTraitRef<'tcx>,
                                    b: ty::PolyTraitRef<'tcx>)
-                                   -> UnitResult<'tcx>
+                                   -> TraceResult<'tcx>
 {
     debug!("mk_sub_trait_refs({:?} <: {:?})",
            a, b);
+    let trace = TypeTrace {
+        origin: origin,
+        values: PolyTraitRefs(expected_found(a_is_expected, a.clone(), b.clone()))
+    };
     cx.commit_if_ok(|_| cx.sub_poly_trait_refs(a_is_expected, origin, a.clone(), b.clone()))
+        .map_err(|err| TypeTraceError { err: err, trace: trace })
 }
 
 fn expected_found<T>(a_is_expected: bool,
@@ -420,9 +530,9 @@ fn expected_found<T>(a_is_expected: bool,
                      -> ty::expected_found<T>
 {
     if a_is_expected {
-        ty::expected_found {expected: a, found: b}
+        ty::expected_found {expected: a, found: b, origin: None}
     } else {
-        ty::expected_found {expected: b, found: a}
+        ty::expected_found {expected: b, found: a, origin: None}
     }
 }
 
@@ -434,6 +544,23 @@ pub struct CombinedSnapshot {
     region_vars_snapshot: RegionSnapshot,
 }
 
+/// A labeled point that inference can be rolled back to, taken with
+/// `InferCtxt::checkpoint`. Checkpoints must be released in the reverse
+/// of the order they were taken, with either `rollback_to` or
+/// `commit_checkpoint`.
+#[must_use = "a checkpoint must be either rolled back to or committed"]
+pub struct Checkpoint<'b, 'tcx: 'b> {
+    label: &'static str,
+    depth: usize,
+    snapshot: CombinedSnapshot,
+    obligations: Option<SavedObligations<'b, 'tcx>>,
+}
+
+// The fulfillment context whose obligations a checkpoint restores, with
+// the obligations it had when the checkpoint was taken.
+type SavedObligations<'b, 'tcx> = (&'b RefCell<FulfillmentContext<'tcx>>,
+                                   FulfillmentSnapshot<'tcx>);
+
 impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
     pub fn freshen<T:TypeFoldable<'tcx>>(&self, t: T) -> T {
         t.fold_with(&mut self.freshener())
@@ -476,7 +603,14 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         CombineFields {infcx: self,
                        a_is_expected: a_is_expected,
                        trace: trace,
-                       cause: None}
+                       causes: Vec::new(),
+                       errors: None,
+                       expected_def_id: None,
+                       tracer: if self.tcx.sess.trace_relations() {
+                           Some(ty_relate::RelationTracer::new())
+                       } else {
+                           None
+                       }}
     }
 
     // public so that it can be used from the rustc_driver unit tests
@@ -516,7 +650,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         }
     }
 
-    fn rollback_to(&self, snapshot: CombinedSnapshot) {
+    fn rollback_to_snapshot(&self, snapshot: CombinedSnapshot) {
         debug!("rollback!");
         let CombinedSnapshot { type_snapshot,
                                int_snapshot,
@@ -534,6 +668,22 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
             .rollback_to(float_snapshot);
         self.region_vars
             .rollback_to(region_vars_snapshot);
+        self.clear_relation_cache();
+    }
+
+    /// Whether a snapshot is open, in which case anything inferred now may
+    /// yet be rolled back.
+    pub fn in_snapshot(&self) -> bool {
+        self.region_vars.in_snapshot()
+    }
+
+    /// Forgets any cached relation and resolution results, as they may
+    /// depend on variable bindings that have just been undone.
+    fn clear_relation_cache(&self) {
+        if let Some(ref cache) = self.relation_cache {
+            cache.clear();
+        }
+        self.resolved_tys.borrow_mut().clear();
     }
 
     fn commit_from(&self, snapshot: CombinedSnapshot) {
@@ -577,7 +727,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         debug!("commit_if_ok() -- r.is_ok() = {}", r.is_ok());
         match r {
             Ok(_) => { self.commit_from(snapshot); }
-            Err(_) => { self.rollback_to(snapshot); }
+            Err(_) => { self.rollback_to_snapshot(snapshot); }
         }
         r
     }
@@ -607,6 +757,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         self.float_unification_table
             .borrow_mut()
             .rollback_to(float_snapshot);
+        self.clear_relation_cache();
 
         // Commit region vars that may escape through resolved types.
         self.region_vars
@@ -622,10 +773,73 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         debug!("probe()");
         let snapshot = self.start_snapshot();
         let r = f(&snapshot);
-        self.rollback_to(snapshot);
+        self.rollback_to_snapshot(snapshot);
         r
     }
 
+    /// Takes a checkpoint of the type, integer, float and region
+    /// variables. `label` names it in debug output and in the assertions
+    /// that checkpoints are released in order.
+    pub fn checkpoint(&self, label: &'static str) -> Checkpoint<'a, 'tcx> {
+        self.start_checkpoint(label, None)
+    }
+
+    /// Like `checkpoint`, but also saves the obligations pending in
+    /// `fulfillment_cx`, so that rolling back undoes the obligations
+    /// registered in the meantime along with the variable bindings.
+    pub fn checkpoint_with_obligations<'b>(&self,
+                                           label: &'static str,
+                                           fulfillment_cx: &'b RefCell<FulfillmentContext<'tcx>>)
+                                           -> Checkpoint<'b, 'tcx>
+    {
+        let obligations = fulfillment_cx.borrow().snapshot();
+        self.start_checkpoint(label, Some((fulfillment_cx, obligations)))
+    }
+
+    fn start_checkpoint<'b>(&self,
+                            label: &'static str,
+                            obligations: Option<SavedObligations<'b, 'tcx>>)
+                            -> Checkpoint<'b, 'tcx>
+    {
+        debug!("checkpoint({})", label);
+        let mut checkpoints = self.checkpoints.borrow_mut();
+        checkpoints.push(label);
+        Checkpoint {
+            label: label,
+            depth: checkpoints.len() - 1,
+            snapshot: self.start_snapshot(),
+            obligations: obligations,
+        }
+    }
+
+    /// Undoes everything done since `checkpoint` was taken.
+    pub fn rollback_to<'b>(&self, checkpoint: Checkpoint<'b, 'tcx>) {
+        debug!("rollback_to(checkpoint={})", checkpoint.label);
+        self.release_checkpoint(&checkpoint);
+        let Checkpoint { snapshot, obligations, .. } = checkpoint;
+        self.rollback_to_snapshot(snapshot);
+        if let Some((fulfillment_cx, obligations)) = obligations {
+            fulfillment_cx.borrow_mut().rollback_to(obligations);
+        }
+    }
+
+    /// Keeps everything done since `checkpoint` was taken.
+    pub fn commit_checkpoint<'b>(&self, checkpoint: Checkpoint<'b, 'tcx>) {
+        debug!("commit_checkpoint({})", checkpoint.label);
+        self.release_checkpoint(&checkpoint);
+        self.commit_from(checkpoint.snapshot);
+    }
+
+    // Closes `checkpoint`, which must be the innermost one still open.
+    fn release_checkpoint(&self, checkpoint: &Checkpoint) {
+        let mut checkpoints = self.checkpoints.borrow_mut();
+        debug_assert!(checkpoints.len() == checkpoint.depth + 1,
+                      "checkpoint `{}` released while `{}` is still open",
+                      checkpoint.label,
+                      checkpoints.last().cloned().unwrap_or("<none>"));
+        checkpoints.truncate(checkpoint.depth);
+    }
+
     pub fn add_given(&self,
                      sub: ty::FreeRegion,
                      sup: ty::RegionVid)
@@ -647,6 +861,51 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         })
     }
 
+    /// Like `sub_types`, but the expected side is known to come from the
+    /// item `expected_def_id`, which errors then record as their origin.
+    pub fn sub_types_expected_from(&self,
+                                   a_is_expected: bool,
+                                   origin: TypeOrigin,
+                                   a: Ty<'tcx>,
+                                   b: Ty<'tcx>,
+                                   expected_def_id: ast::DefId)
+                                   -> UnitResult<'tcx>
+    {
+        debug!("sub_types_expected_from({:?} <: {:?}, {:?})", a, b, expected_def_id);
+        self.commit_if_ok(|_| {
+            let trace = TypeTrace::types(origin, a_is_expected, a, b);
+            let mut fields = self.combine_fields(a_is_expected, trace);
+            fields.expected_def_id = Some(expected_def_id);
+            fields.sub().relate(&a, &b).map(|_| ())
+        })
+    }
+
+    /// Like `sub_types`, but a mismatch in one component (a tuple
+    /// element, fn argument or type parameter) does not stop the others
+    /// from being related, so every such mismatch between `a` and `b` is
+    /// returned. As with `sub_types`, nothing is committed on failure.
+    pub fn sub_types_all_errors(&self,
+                                a_is_expected: bool,
+                                origin: TypeOrigin,
+                                a: Ty<'tcx>,
+                                b: Ty<'tcx>)
+                                -> Result<(), Vec<ty::type_err<'tcx>>>
+    {
+        debug!("sub_types_all_errors({:?} <: {:?})", a, b);
+        self.commit_if_ok(|_| {
+            let errors = Rc::new(RefCell::new(Vec::new()));
+            let trace = TypeTrace::types(origin, a_is_expected, a, b);
+            let mut fields = self.combine_fields(a_is_expected, trace);
+            fields.errors = Some(errors.clone());
+            let result = fields.sub().relate(&a, &b);
+            let mut errors = errors.borrow().clone();
+            if let Err(err) = result {
+                errors.push(err);
+            }
+            if errors.is_empty() { Ok(()) } else { Err(errors) }
+        })
+    }
+
     pub fn eq_types(&self,
                     a_is_expected: bool,
                     origin: TypeOrigin,
@@ -751,7 +1010,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
             let (ty::EquatePredicate(a, b), skol_map) =
                 self.skolemize_late_bound_regions(predicate, snapshot);
             let origin = EquatePredicate(span);
-            let () = try!(mk_eqty(self, false, origin, a, b));
+            let () = try!(mk_eqty(self, false, origin, a, b).map_err(|e| e.err));
             self.leak_check(&skol_map, snapshot)
         })
     }
@@ -770,15 +1029,62 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
     }
 
     pub fn next_ty_var_id(&self, diverging: bool) -> TyVid {
-        self.type_variables
-            .borrow_mut()
-            .new_var(diverging)
+        let vid = self.type_variables
+                      .borrow_mut()
+                      .new_var(diverging);
+        // The id may have belonged to a variable that was rolled back.
+        self.ty_var_origins.borrow_mut().remove(&vid);
+        vid
     }
 
     pub fn next_ty_var(&self) -> Ty<'tcx> {
         ty::mk_var(self.tcx, self.next_ty_var_id(false))
     }
 
+    pub fn next_ty_var_with_origin(&self, origin: TypeVariableOrigin) -> Ty<'tcx> {
+        let vid = self.next_ty_var_id(false);
+        self.ty_var_origins.borrow_mut().insert(vid, origin);
+        ty::mk_var(self.tcx, vid)
+    }
+
+    /// Why the type variable `vid` was created, if that was recorded.
+    pub fn ty_var_origin(&self, vid: TyVid) -> Option<TypeVariableOrigin> {
+        self.ty_var_origins.borrow().get(&vid).cloned()
+    }
+
+    /// Unifies each integer and float variable among `tys` that nothing
+    /// constrained with the type it falls back to, and records it with
+    /// the node whose type it was found in. A variable that appears in
+    /// several of the types is defaulted, and recorded, only once.
+    pub fn apply_literal_fallback<I>(&self, fallback: &LiteralFallback<'tcx>, tys: I)
+        where I: IntoIterator<Item=(ast::NodeId, Ty<'tcx>)>
+    {
+        for (node_id, ty) in tys {
+            let (var, default) = match self.shallow_resolve(ty).sty {
+                ty::TyInfer(var @ ty::IntVar(_)) => (var, fallback.int_ty),
+                ty::TyInfer(var @ ty::FloatVar(_)) => (var, fallback.float_ty),
+                _ => continue,
+            };
+
+            debug!("apply_literal_fallback: defaulting {:?} of node {} to {:?}",
+                   var, node_id, default);
+            if let Err(e) = mk_eqty(self, false, Misc(codemap::DUMMY_SP), ty, default) {
+                self.tcx.sess.bug(
+                    &format!("cannot default {:?} to {:?}: {}", var, default, e));
+            }
+            self.defaulted_literals.borrow_mut().push(DefaultedLiteral {
+                var: var,
+                node_id: node_id,
+                ty: default,
+            });
+        }
+    }
+
+    /// The variables defaulted by `apply_literal_fallback` so far.
+    pub fn defaulted_literals(&self) -> Vec<DefaultedLiteral<'tcx>> {
+        self.defaulted_literals.borrow().clone()
+    }
+
     pub fn next_diverging_ty_var(&self) -> Ty<'tcx> {
         ty::mk_var(self.tcx, self.next_ty_var_id(true))
     }
@@ -944,6 +1250,14 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         resolve::fully_resolve(self, value)
     }
 
+    /// Like `fully_resolve`, but on failure still returns `value` with its
+    /// structure intact and only the unresolved variables replaced by
+    /// `TyError`, along with the first failure.
+    pub fn fully_resolve_with_fallback<T:TypeFoldable<'tcx>>(&self, value: &T)
+                                                             -> (T, Option<fixup_err>) {
+        resolve::fully_resolve_with_fallback(self, value)
+    }
+
     // [Note-Type-error-reporting]
     // An invariant is that anytime the expected or actual type is TyError (the special
     // error type, meaning that an error occurred when typechecking this expression),
@@ -1023,12 +1337,19 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
             origin: Misc(span),
             values: Types(ty::expected_found {
                 expected: expected,
-                found: actual
+                found: actual,
+                origin: None,
             })
         };
         self.report_and_explain_type_error(trace, err);
     }
 
+    /// Reports the failure of one of the `mk_*` entry points against the
+    /// values recorded in its trace.
+    pub fn report_type_trace_error(&self, err: &TypeTraceError<'tcx>) {
+        self.report_and_explain_type_error(err.trace.clone(), &err.err);
+    }
+
     pub fn replace_late_bound_regions_with_fresh_var<T>(
         &self,
         span: Span,
@@ -1079,6 +1400,14 @@ impl<'tcx> TypeTrace<'tcx> {
         self.origin.span()
     }
 
+    pub fn origin(&self) -> TypeOrigin {
+        self.origin
+    }
+
+    pub fn values(&self) -> &ValuePairs<'tcx> {
+        &self.values
+    }
+
     pub fn types(origin: TypeOrigin,
                  a_is_expected: bool,
                  a: Ty<'tcx>,
@@ -1096,11 +1425,18 @@ impl<'tcx> TypeTrace<'tcx> {
             values: Types(ty::expected_found {
                 expected: tcx.types.err,
                 found: tcx.types.err,
+                origin: None,
             })
         }
     }
 }
 
+impl<'tcx> fmt::Display for TypeTraceError<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.err, f)
+    }
+}
+
 impl<'tcx> fmt::Debug for TypeTrace<'tcx> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "TypeTrace({:?})", self.origin)
@@ -1130,6 +1466,7 @@ impl<'tcx> SubregionOrigin<'tcx> {
         match *self {
             Subtype(ref a) => a.span(),
             DefaultExistentialBound(ref a) => a.span(),
+            Invariant(ref a, _) => a.span(),
             InfStackClosure(a) => a,
             InvokeClosure(a) => a,
             DerefPointer(a) => a,
@@ -13,7 +13,8 @@
 //! rendering of the graph represented by the list of `Constraint`
 //! instances (which make up the edges of the graph), as well as the
 //! origin for each constraint (which are attached to the labels on
-//! each edge).
+//! each edge). Once the regions are resolved, the graph can also be
+//! dumped along with the value of each variable, as graphviz and JSON.
 
 /// For clarity, rename the graphviz crate locally to dot.
 use graphviz as dot;
@@ -22,15 +23,17 @@ use middle::ty;
 use middle::region::CodeExtent;
 use super::Constraint;
 use middle::infer::SubregionOrigin;
-use middle::infer::region_inference::RegionVarBindings;
+use middle::infer::region_inference::{RegionVarBindings, VarValue};
 use util::nodemap::{FnvHashMap, FnvHashSet};
 
+use rustc_serialize::json;
 use std::borrow::Cow;
 use std::collections::hash_map::Entry::Vacant;
 use std::env;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use syntax::ast;
 
@@ -121,6 +124,8 @@ struct ConstraintGraph<'a, 'tcx: 'a> {
     graph_name: String,
     map: &'a FnvHashMap<Constraint, SubregionOrigin<'tcx>>,
     node_ids: FnvHashMap<Node, usize>,
+    // The value of each variable, once the regions are resolved.
+    values: Option<&'a [VarValue]>,
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug, Copy)]
@@ -139,7 +144,8 @@ enum Edge {
 impl<'a, 'tcx> ConstraintGraph<'a, 'tcx> {
     fn new(tcx: &'a ty::ctxt<'tcx>,
            name: String,
-           map: &'a ConstraintMap<'tcx>) -> ConstraintGraph<'a, 'tcx> {
+           map: &'a ConstraintMap<'tcx>,
+           values: Option<&'a [VarValue]>) -> ConstraintGraph<'a, 'tcx> {
         let mut i = 0;
         let mut node_ids = FnvHashMap();
         {
@@ -164,7 +170,8 @@ impl<'a, 'tcx> ConstraintGraph<'a, 'tcx> {
         ConstraintGraph { tcx: tcx,
                           graph_name: name,
                           map: map,
-                          node_ids: node_ids }
+                          node_ids: node_ids,
+                          values: values }
     }
 }
 
@@ -187,8 +194,11 @@ impl<'a, 'tcx> dot::Labeller<'a, Node, Edge> for ConstraintGraph<'a, 'tcx> {
     }
     fn node_label(&self, n: &Node) -> dot::LabelText {
         match *n {
-            Node::RegionVid(n_vid) =>
-                dot::LabelText::label(format!("{:?}", n_vid)),
+            Node::RegionVid(n_vid) => match self.values {
+                Some(values) => dot::LabelText::label(
+                    format!("{:?} = {:?}", n_vid, values[n_vid.index as usize])),
+                None => dot::LabelText::label(format!("{:?}", n_vid)),
+            },
             Node::Region(n_rgn) =>
                 dot::LabelText::label(format!("{:?}", n_rgn)),
         }
@@ -259,9 +269,93 @@ fn dump_region_constraints_to<'a, 'tcx:'a >(tcx: &'a ty::ctxt<'tcx>,
                                             map: &ConstraintMap<'tcx>,
                                             path: &str) -> io::Result<()> {
     debug!("dump_region_constraints map (len: {}) path: {}", map.len(), path);
-    let g = ConstraintGraph::new(tcx, format!("region_constraints"), map);
+    let g = ConstraintGraph::new(tcx, format!("region_constraints"), map, None);
     debug!("dump_region_constraints calling render");
     let mut v = Vec::new();
     dot::render(&g, &mut v).unwrap();
     File::create(path).and_then(|mut f| f.write_all(&v))
 }
+
+fn node_to_string(node: Node) -> String {
+    match node {
+        Node::RegionVid(vid) => format!("{:?}", vid),
+        Node::Region(r) => format!("{:?}", r),
+    }
+}
+
+#[derive(RustcEncodable)]
+struct RegionGraphReport {
+    subject: String,
+    variables: Vec<VariableReport>,
+    constraints: Vec<ConstraintReport>,
+}
+
+#[derive(RustcEncodable)]
+struct VariableReport {
+    var: String,
+    origin: String,
+    span: String,
+    value: String,
+}
+
+#[derive(RustcEncodable)]
+struct ConstraintReport {
+    sub: String,
+    sup: String,
+    origin: String,
+    span: String,
+}
+
+impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
+    /// Writes the constraint graph of `subject_node` to `dir`, both as
+    /// graphviz (`region_graph.nodeN.dot`) and as JSON
+    /// (`region_graph.nodeN.json`), with the origin of every variable and
+    /// constraint and the value each variable resolved to. Only valid
+    /// once `resolve_regions` has computed the values.
+    pub fn dump_region_graph(&self, subject_node: ast::NodeId, dir: &Path) -> io::Result<()> {
+        let tcx = self.tcx;
+        let values = self.values.borrow();
+        let values = match *values {
+            Some(ref values) => &values[..],
+            None => tcx.sess.bug("region graph dumped before the regions were resolved"),
+        };
+        let constraints = self.constraints.borrow();
+        let codemap = tcx.sess.codemap();
+
+        let g = ConstraintGraph::new(tcx, format!("region_constraints"), &*constraints,
+                                     Some(values));
+        let mut dot_output = Vec::new();
+        try!(dot::render(&g, &mut dot_output));
+        let dot_path = dir.join(format!("region_graph.node{}.dot", subject_node));
+        try!(File::create(dot_path).and_then(|mut f| f.write_all(&dot_output)));
+
+        let variables = self.var_origins.borrow().iter().enumerate().map(|(i, origin)| {
+            VariableReport {
+                var: format!("{:?}", ty::RegionVid { index: i as u32 }),
+                origin: format!("{:?}", origin),
+                span: codemap.span_to_string(origin.span()),
+                value: format!("{:?}", values[i]),
+            }
+        }).collect();
+        let mut constraints: Vec<_> = constraints.iter().map(|(c, origin)| {
+            let (sub, sup) = constraint_to_nodes(c);
+            ConstraintReport {
+                sub: node_to_string(sub),
+                sup: node_to_string(sup),
+                origin: format!("{:?}", origin),
+                span: codemap.span_to_string(origin.span()),
+            }
+        }).collect();
+        constraints.sort_by(|a, b| (&a.sub, &a.sup).cmp(&(&b.sub, &b.sup)));
+
+        let report = RegionGraphReport {
+            subject: tcx.map.node_to_string(subject_node),
+            variables: variables,
+            constraints: constraints,
+        };
+        let json_path = dir.join(format!("region_graph.node{}.json", subject_node));
+        File::create(json_path).and_then(|mut f| {
+            write!(f, "{}", json::as_pretty_json(&report))
+        })
+    }
+}
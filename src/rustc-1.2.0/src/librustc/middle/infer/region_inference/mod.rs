@@ -35,6 +35,7 @@ use std::cell::{Cell, RefCell};
 use std::cmp::Ordering::{self, Less, Greater, Equal};
 use std::fmt;
 use std::iter::repeat;
+use std::path::Path;
 use std::u32;
 use syntax::ast;
 
@@ -245,7 +246,7 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
         }
     }
 
-    fn in_snapshot(&self) -> bool {
+    pub fn in_snapshot(&self) -> bool {
         !self.undo_log.borrow().is_empty()
     }
 
@@ -721,29 +722,19 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
         let mut errors = vec!();
         let v = self.infer_variable_values(free_regions, &mut errors, subject_node);
         *self.values.borrow_mut() = Some(v);
+
+        if let Some(dir) = self.tcx.sess.dump_region_graph() {
+            if let Err(e) = self.dump_region_graph(subject_node, Path::new(dir)) {
+                self.tcx.sess.err(&format!("could not dump the region graph of node {} \
+                                            to `{}`: {}",
+                                           subject_node, dir, e));
+            }
+        }
         errors
     }
 
     fn lub_concrete_regions(&self, free_regions: &FreeRegionMap, a: Region, b: Region) -> Region {
         match (a, b) {
-          (ReLateBound(..), _) |
-          (_, ReLateBound(..)) |
-          (ReEarlyBound(..), _) |
-          (_, ReEarlyBound(..)) => {
-            self.tcx.sess.bug(
-                &format!("cannot relate bound region: LUB({:?}, {:?})",
-                        a,
-                        b));
-          }
-
-          (ReStatic, _) | (_, ReStatic) => {
-            ReStatic // nothing lives longer than static
-          }
-
-          (ReEmpty, r) | (r, ReEmpty) => {
-            r // everything lives longer than empty
-          }
-
           (ReInfer(ReVar(v_id)), _) | (_, ReInfer(ReVar(v_id))) => {
             self.tcx.sess.span_bug(
                 (*self.var_origins.borrow())[v_id.index as usize].span(),
@@ -753,73 +744,7 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
                         b));
           }
 
-          (ReFree(ref fr), ReScope(s_id)) |
-          (ReScope(s_id), ReFree(ref fr)) => {
-            let f = ReFree(*fr);
-            // A "free" region can be interpreted as "some region
-            // at least as big as the block fr.scope_id".  So, we can
-            // reasonably compare free regions and scopes:
-            let fr_scope = fr.scope.to_code_extent();
-            let r_id = self.tcx.region_maps.nearest_common_ancestor(fr_scope, s_id);
-
-            if r_id == fr_scope {
-              // if the free region's scope `fr.scope_id` is bigger than
-              // the scope region `s_id`, then the LUB is the free
-              // region itself:
-              f
-            } else {
-              // otherwise, we don't know what the free region is,
-              // so we must conservatively say the LUB is static:
-              ReStatic
-            }
-          }
-
-          (ReScope(a_id), ReScope(b_id)) => {
-            // The region corresponding to an outer block is a
-            // subtype of the region corresponding to an inner
-            // block.
-            ReScope(self.tcx.region_maps.nearest_common_ancestor(a_id, b_id))
-          }
-
-          (ReFree(ref a_fr), ReFree(ref b_fr)) => {
-             self.lub_free_regions(free_regions, a_fr, b_fr)
-          }
-
-          // For these types, we cannot define any additional
-          // relationship:
-          (ReInfer(ReSkolemized(..)), _) |
-          (_, ReInfer(ReSkolemized(..))) => {
-            if a == b {a} else {ReStatic}
-          }
-        }
-    }
-
-    /// Computes a region that encloses both free region arguments. Guarantee that if the same two
-    /// regions are given as argument, in any order, a consistent result is returned.
-    fn lub_free_regions(&self,
-                        free_regions: &FreeRegionMap,
-                        a: &FreeRegion,
-                        b: &FreeRegion)
-                        -> ty::Region
-    {
-        return match a.cmp(b) {
-            Less => helper(self, free_regions, a, b),
-            Greater => helper(self, free_regions, b, a),
-            Equal => ty::ReFree(*a)
-        };
-
-        fn helper(_this: &RegionVarBindings,
-                  free_regions: &FreeRegionMap,
-                  a: &FreeRegion,
-                  b: &FreeRegion) -> ty::Region
-        {
-            if free_regions.sub_free_region(*a, *b) {
-                ty::ReFree(*b)
-            } else if free_regions.sub_free_region(*b, *a) {
-                ty::ReFree(*a)
-            } else {
-                ty::ReStatic
-            }
+          _ => lub_concrete_regions(self.tcx, free_regions, a, b)
         }
     }
 
@@ -829,28 +754,7 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
                             b: Region)
                             -> RelateResult<'tcx, Region>
     {
-        debug!("glb_concrete_regions({:?}, {:?})", a, b);
         match (a, b) {
-            (ReLateBound(..), _) |
-            (_, ReLateBound(..)) |
-            (ReEarlyBound(..), _) |
-            (_, ReEarlyBound(..)) => {
-              self.tcx.sess.bug(
-                  &format!("cannot relate bound region: GLB({:?}, {:?})",
-                          a,
-                          b));
-            }
-
-            (ReStatic, r) | (r, ReStatic) => {
-                // static lives longer than everything else
-                Ok(r)
-            }
-
-            (ReEmpty, _) | (_, ReEmpty) => {
-                // nothing lives shorter than everything else
-                Ok(ReEmpty)
-            }
-
             (ReInfer(ReVar(v_id)), _) |
             (_, ReInfer(ReVar(v_id))) => {
                 self.tcx.sess.span_bug(
@@ -861,99 +765,241 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
                             b));
             }
 
-            (ReFree(ref fr), ReScope(s_id)) |
-            (ReScope(s_id), ReFree(ref fr)) => {
-                let s = ReScope(s_id);
-                // Free region is something "at least as big as
-                // `fr.scope_id`."  If we find that the scope `fr.scope_id` is bigger
-                // than the scope `s_id`, then we can say that the GLB
-                // is the scope `s_id`.  Otherwise, as we do not know
-                // big the free region is precisely, the GLB is undefined.
-                let fr_scope = fr.scope.to_code_extent();
-                if self.tcx.region_maps.nearest_common_ancestor(fr_scope, s_id) == fr_scope {
-                    Ok(s)
-                } else {
-                    Err(ty::terr_regions_no_overlap(b, a))
-                }
-            }
+            _ => glb_concrete_regions(self.tcx, free_regions, a, b)
+        }
+    }
+}
 
-            (ReScope(a_id), ReScope(b_id)) => {
-                self.intersect_scopes(a, b, a_id, b_id)
-            }
+/// Computes the smallest region enclosing both of the concrete regions `a`
+/// and `b`. Shared with the inference-free relations in `ty_relate::lattice`.
+pub fn lub_concrete_regions(tcx: &ty::ctxt, free_regions: &FreeRegionMap, a: Region, b: Region)
+                            -> Region {
+    match (a, b) {
+      (ReLateBound(..), _) |
+      (_, ReLateBound(..)) |
+      (ReEarlyBound(..), _) |
+      (_, ReEarlyBound(..)) => {
+        tcx.sess.bug(
+            &format!("cannot relate bound region: LUB({:?}, {:?})",
+                    a,
+                    b));
+      }
+
+      (ReStatic, _) | (_, ReStatic) => {
+        ReStatic // nothing lives longer than static
+      }
+
+      (ReEmpty, r) | (r, ReEmpty) => {
+        r // everything lives longer than empty
+      }
+
+      (ReInfer(ReVar(_)), _) | (_, ReInfer(ReVar(_))) => {
+        tcx.sess.bug(
+            &format!("lub_concrete_regions invoked with \
+                     non-concrete regions: {:?}, {:?}",
+                    a,
+                    b));
+      }
+
+      (ReFree(ref fr), ReScope(s_id)) |
+      (ReScope(s_id), ReFree(ref fr)) => {
+        let f = ReFree(*fr);
+        // A "free" region can be interpreted as "some region
+        // at least as big as the block fr.scope_id".  So, we can
+        // reasonably compare free regions and scopes:
+        let fr_scope = fr.scope.to_code_extent();
+        let r_id = tcx.region_maps.nearest_common_ancestor(fr_scope, s_id);
+
+        if r_id == fr_scope {
+          // if the free region's scope `fr.scope_id` is bigger than
+          // the scope region `s_id`, then the LUB is the free
+          // region itself:
+          f
+        } else {
+          // otherwise, we don't know what the free region is,
+          // so we must conservatively say the LUB is static:
+          ReStatic
+        }
+      }
+
+      (ReScope(a_id), ReScope(b_id)) => {
+        // The region corresponding to an outer block is a
+        // subtype of the region corresponding to an inner
+        // block.
+        ReScope(tcx.region_maps.nearest_common_ancestor(a_id, b_id))
+      }
+
+      (ReFree(ref a_fr), ReFree(ref b_fr)) => {
+         lub_free_regions(free_regions, a_fr, b_fr)
+      }
+
+      // For these types, we cannot define any additional
+      // relationship:
+      (ReInfer(ReSkolemized(..)), _) |
+      (_, ReInfer(ReSkolemized(..))) => {
+        if a == b {a} else {ReStatic}
+      }
+    }
+}
 
-            (ReFree(ref a_fr), ReFree(ref b_fr)) => {
-                self.glb_free_regions(free_regions, a_fr, b_fr)
-            }
+/// Computes a region that encloses both free region arguments. Guarantee that if the same two
+/// regions are given as argument, in any order, a consistent result is returned.
+fn lub_free_regions(free_regions: &FreeRegionMap,
+                    a: &FreeRegion,
+                    b: &FreeRegion)
+                    -> ty::Region
+{
+    return match a.cmp(b) {
+        Less => helper(free_regions, a, b),
+        Greater => helper(free_regions, b, a),
+        Equal => ty::ReFree(*a)
+    };
+
+    fn helper(free_regions: &FreeRegionMap,
+              a: &FreeRegion,
+              b: &FreeRegion) -> ty::Region
+    {
+        if free_regions.sub_free_region(*a, *b) {
+            ty::ReFree(*b)
+        } else if free_regions.sub_free_region(*b, *a) {
+            ty::ReFree(*a)
+        } else {
+            ty::ReStatic
+        }
+    }
+}
 
-            // For these types, we cannot define any additional
-            // relationship:
-            (ReInfer(ReSkolemized(..)), _) |
-            (_, ReInfer(ReSkolemized(..))) => {
-                if a == b {
-                    Ok(a)
-                } else {
-                    Err(ty::terr_regions_no_overlap(b, a))
-                }
+/// Computes the largest region enclosed by both of the concrete regions `a`
+/// and `b`, if there is one. Shared with `ty_relate::lattice`.
+pub fn glb_concrete_regions<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                  free_regions: &FreeRegionMap,
+                                  a: Region,
+                                  b: Region)
+                                  -> RelateResult<'tcx, Region>
+{
+    debug!("glb_concrete_regions({:?}, {:?})", a, b);
+    match (a, b) {
+        (ReLateBound(..), _) |
+        (_, ReLateBound(..)) |
+        (ReEarlyBound(..), _) |
+        (_, ReEarlyBound(..)) => {
+          tcx.sess.bug(
+              &format!("cannot relate bound region: GLB({:?}, {:?})",
+                      a,
+                      b));
+        }
+
+        (ReStatic, r) | (r, ReStatic) => {
+            // static lives longer than everything else
+            Ok(r)
+        }
+
+        (ReEmpty, _) | (_, ReEmpty) => {
+            // nothing lives shorter than everything else
+            Ok(ReEmpty)
+        }
+
+        (ReInfer(ReVar(_)), _) |
+        (_, ReInfer(ReVar(_))) => {
+            tcx.sess.bug(
+                &format!("glb_concrete_regions invoked with \
+                         non-concrete regions: {:?}, {:?}",
+                        a,
+                        b));
+        }
+
+        (ReFree(ref fr), ReScope(s_id)) |
+        (ReScope(s_id), ReFree(ref fr)) => {
+            let s = ReScope(s_id);
+            // Free region is something "at least as big as
+            // `fr.scope_id`."  If we find that the scope `fr.scope_id` is bigger
+            // than the scope `s_id`, then we can say that the GLB
+            // is the scope `s_id`.  Otherwise, as we do not know
+            // big the free region is precisely, the GLB is undefined.
+            let fr_scope = fr.scope.to_code_extent();
+            if tcx.region_maps.nearest_common_ancestor(fr_scope, s_id) == fr_scope {
+                Ok(s)
+            } else {
+                Err(ty::terr_regions_no_overlap(b, a))
             }
         }
-    }
 
-    /// Computes a region that is enclosed by both free region arguments, if any. Guarantees that
-    /// if the same two regions are given as argument, in any order, a consistent result is
-    /// returned.
-    fn glb_free_regions(&self,
-                        free_regions: &FreeRegionMap,
-                        a: &FreeRegion,
-                        b: &FreeRegion)
-                        -> RelateResult<'tcx, ty::Region>
-    {
-        return match a.cmp(b) {
-            Less => helper(self, free_regions, a, b),
-            Greater => helper(self, free_regions, b, a),
-            Equal => Ok(ty::ReFree(*a))
-        };
+        (ReScope(a_id), ReScope(b_id)) => {
+            intersect_scopes(tcx, a, b, a_id, b_id)
+        }
 
-        fn helper<'a, 'tcx>(this: &RegionVarBindings<'a, 'tcx>,
-                            free_regions: &FreeRegionMap,
-                            a: &FreeRegion,
-                            b: &FreeRegion) -> RelateResult<'tcx, ty::Region>
-        {
-            if free_regions.sub_free_region(*a, *b) {
-                Ok(ty::ReFree(*a))
-            } else if free_regions.sub_free_region(*b, *a) {
-                Ok(ty::ReFree(*b))
+        (ReFree(ref a_fr), ReFree(ref b_fr)) => {
+            glb_free_regions(tcx, free_regions, a_fr, b_fr)
+        }
+
+        // For these types, we cannot define any additional
+        // relationship:
+        (ReInfer(ReSkolemized(..)), _) |
+        (_, ReInfer(ReSkolemized(..))) => {
+            if a == b {
+                Ok(a)
             } else {
-                this.intersect_scopes(ty::ReFree(*a), ty::ReFree(*b),
-                                      a.scope.to_code_extent(),
-                                      b.scope.to_code_extent())
+                Err(ty::terr_regions_no_overlap(b, a))
             }
         }
     }
+}
 
-    fn intersect_scopes(&self,
-                        region_a: ty::Region,
-                        region_b: ty::Region,
-                        scope_a: region::CodeExtent,
-                        scope_b: region::CodeExtent)
-                        -> RelateResult<'tcx, Region>
+/// Computes a region that is enclosed by both free region arguments, if any. Guarantees that
+/// if the same two regions are given as argument, in any order, a consistent result is
+/// returned.
+fn glb_free_regions<'tcx>(tcx: &ty::ctxt<'tcx>,
+                          free_regions: &FreeRegionMap,
+                          a: &FreeRegion,
+                          b: &FreeRegion)
+                          -> RelateResult<'tcx, ty::Region>
+{
+    return match a.cmp(b) {
+        Less => helper(tcx, free_regions, a, b),
+        Greater => helper(tcx, free_regions, b, a),
+        Equal => Ok(ty::ReFree(*a))
+    };
+
+    fn helper<'tcx>(tcx: &ty::ctxt<'tcx>,
+                    free_regions: &FreeRegionMap,
+                    a: &FreeRegion,
+                    b: &FreeRegion) -> RelateResult<'tcx, ty::Region>
     {
-        // We want to generate the intersection of two
-        // scopes or two free regions.  So, if one of
-        // these scopes is a subscope of the other, return
-        // it. Otherwise fail.
-        debug!("intersect_scopes(scope_a={:?}, scope_b={:?}, region_a={:?}, region_b={:?})",
-               scope_a, scope_b, region_a, region_b);
-        let r_id = self.tcx.region_maps.nearest_common_ancestor(scope_a, scope_b);
-        if r_id == scope_a {
-            Ok(ReScope(scope_b))
-        } else if r_id == scope_b {
-            Ok(ReScope(scope_a))
+        if free_regions.sub_free_region(*a, *b) {
+            Ok(ty::ReFree(*a))
+        } else if free_regions.sub_free_region(*b, *a) {
+            Ok(ty::ReFree(*b))
         } else {
-            Err(ty::terr_regions_no_overlap(region_a, region_b))
+            intersect_scopes(tcx, ty::ReFree(*a), ty::ReFree(*b),
+                             a.scope.to_code_extent(),
+                             b.scope.to_code_extent())
         }
     }
 }
 
+fn intersect_scopes<'tcx>(tcx: &ty::ctxt<'tcx>,
+                          region_a: ty::Region,
+                          region_b: ty::Region,
+                          scope_a: region::CodeExtent,
+                          scope_b: region::CodeExtent)
+                          -> RelateResult<'tcx, Region>
+{
+    // We want to generate the intersection of two
+    // scopes or two free regions.  So, if one of
+    // these scopes is a subscope of the other, return
+    // it. Otherwise fail.
+    debug!("intersect_scopes(scope_a={:?}, scope_b={:?}, region_a={:?}, region_b={:?})",
+           scope_a, scope_b, region_a, region_b);
+    let r_id = tcx.region_maps.nearest_common_ancestor(scope_a, scope_b);
+    if r_id == scope_a {
+        Ok(ReScope(scope_b))
+    } else if r_id == scope_b {
+        Ok(ReScope(scope_a))
+    } else {
+        Err(ty::terr_regions_no_overlap(region_a, region_b))
+    }
+}
+
 // ______________________________________________________________________
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -53,13 +53,25 @@ impl<'a, 'tcx> ty_fold::TypeFolder<'tcx> for OpportunisticTypeResolver<'a, 'tcx>
 /// then an `Err` result is returned.
 pub fn fully_resolve<'a, 'tcx, T>(infcx: &InferCtxt<'a,'tcx>, value: &T) -> fres<T>
     where T : TypeFoldable<'tcx>
+{
+    match fully_resolve_with_fallback(infcx, value) {
+        (result, None) => Ok(result),
+        (_, Some(e)) => Err(e),
+    }
+}
+
+/// Like `fully_resolve`, but always returns the resolved value, with
+/// every variable that could not be resolved replaced by `TyError`. The
+/// rest of the value keeps its shape, so `Vec<_>` becomes `Vec<[error]>`
+/// rather than `[error]`. The first failure, if any, is returned too.
+pub fn fully_resolve_with_fallback<'a, 'tcx, T>(infcx: &InferCtxt<'a,'tcx>,
+                                                value: &T)
+                                                -> (T, Option<fixup_err>)
+    where T : TypeFoldable<'tcx>
 {
     let mut full_resolver = FullTypeResolver { infcx: infcx, err: None };
     let result = value.fold_with(&mut full_resolver);
-    match full_resolver.err {
-        None => Ok(result),
-        Some(e) => Err(e),
-    }
+    (result, full_resolver.err)
 }
 
 // N.B. This type is not public because the protocol around checking the
@@ -76,32 +88,24 @@ impl<'a, 'tcx> ty_fold::TypeFolder<'tcx> for FullTypeResolver<'a, 'tcx> {
 
     fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
         if !ty::type_needs_infer(t) {
-            t // micro-optimize -- if there is nothing in this type that this fold affects...
-        } else {
-            let t = self.infcx.shallow_resolve(t);
-            match t.sty {
-                ty::TyInfer(ty::TyVar(vid)) => {
-                    self.err = Some(unresolved_ty(vid));
-                    self.tcx().types.err
-                }
-                ty::TyInfer(ty::IntVar(vid)) => {
-                    self.err = Some(unresolved_int_ty(vid));
-                    self.tcx().types.err
-                }
-                ty::TyInfer(ty::FloatVar(vid)) => {
-                    self.err = Some(unresolved_float_ty(vid));
-                    self.tcx().types.err
-                }
-                ty::TyInfer(_) => {
-                    self.infcx.tcx.sess.bug(
-                        &format!("Unexpected type in full type resolver: {:?}",
-                                t));
-                }
-                _ => {
-                    ty_fold::super_fold_ty(self, t)
-                }
-            }
+            return t; // micro-optimize -- nothing in this type that this fold affects
+        }
+
+        // Writeback resolves the same types over and over, once per node
+        // that has them, so types that resolved completely are memoized.
+        // Ones that did not are not, as later bindings may still resolve
+        // them.
+        if let Some(&resolved) = self.infcx.resolved_tys.borrow().get(&t) {
+            return resolved;
         }
+
+        let outer_err = self.err.take();
+        let resolved = self.resolve_ty(t);
+        if self.err.is_none() {
+            self.infcx.resolved_tys.borrow_mut().insert(t, resolved);
+        }
+        self.err = outer_err.or(self.err);
+        resolved
     }
 
     fn fold_region(&mut self, r: ty::Region) -> ty::Region {
@@ -111,3 +115,31 @@ impl<'a, 'tcx> ty_fold::TypeFolder<'tcx> for FullTypeResolver<'a, 'tcx> {
         }
     }
 }
+
+impl<'a, 'tcx> FullTypeResolver<'a, 'tcx> {
+    fn resolve_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
+        let t = self.infcx.shallow_resolve(t);
+        match t.sty {
+            ty::TyInfer(ty::TyVar(vid)) => {
+                self.err = self.err.or(Some(unresolved_ty(vid)));
+                self.infcx.tcx.types.err
+            }
+            ty::TyInfer(ty::IntVar(vid)) => {
+                self.err = self.err.or(Some(unresolved_int_ty(vid)));
+                self.infcx.tcx.types.err
+            }
+            ty::TyInfer(ty::FloatVar(vid)) => {
+                self.err = self.err.or(Some(unresolved_float_ty(vid)));
+                self.infcx.tcx.types.err
+            }
+            ty::TyInfer(_) => {
+                self.infcx.tcx.sess.bug(
+                    &format!("Unexpected type in full type resolver: {:?}",
+                            t));
+            }
+            _ => {
+                ty_fold::super_fold_ty(self, t)
+            }
+        }
+    }
+}
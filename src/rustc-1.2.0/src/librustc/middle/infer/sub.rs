@@ -15,8 +15,7 @@ use super::type_variable::{SubtypeOf, SupertypeOf};
 
 use middle::ty::{self, Ty};
 use middle::ty::TyVar;
-use middle::ty_relate::{Cause, Relate, RelateResult, TypeRelation};
-use std::mem;
+use middle::ty_relate::{self, Cause, Relate, RelateResult, TypeRelation};
 
 /// "Greatest lower bound" (common subtype)
 pub struct Sub<'a, 'tcx: 'a> {
@@ -34,16 +33,7 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Sub<'a, 'tcx> {
     fn tcx(&self) -> &'a ty::ctxt<'tcx> { self.fields.infcx.tcx }
     fn a_is_expected(&self) -> bool { self.fields.a_is_expected }
 
-    fn with_cause<F,R>(&mut self, cause: Cause, f: F) -> R
-        where F: FnOnce(&mut Self) -> R
-    {
-        debug!("sub with_cause={:?}", cause);
-        let old_cause = mem::replace(&mut self.fields.cause, Some(cause));
-        let r = f(self);
-        debug!("sub old_cause={:?}", old_cause);
-        self.fields.cause = old_cause;
-        r
-    }
+    combine_fields_hooks!();
 
     fn will_change(&mut self, a: bool, b: bool) -> bool {
         // if we have (Foo+'a) <: (Foo+'b), this requires that 'a:'b.
@@ -53,6 +43,11 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Sub<'a, 'tcx> {
         !a && b
     }
 
+    fn builtin_bounds(&mut self, a: ty::BuiltinBounds, b: ty::BuiltinBounds)
+                      -> RelateResult<'tcx, ty::BuiltinBounds> {
+        ty_relate::sub_builtin_bounds(self, a, b)
+    }
+
     fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
                                                variance: ty::Variance,
                                                a: &T,
@@ -104,11 +99,14 @@ impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Sub<'a, 'tcx> {
     }
 
     fn regions(&mut self, a: ty::Region, b: ty::Region) -> RelateResult<'tcx, ty::Region> {
-        debug!("{}.regions({:?}, {:?}) self.cause={:?}",
-               self.tag(), a, b, self.fields.cause);
-        let origin = match self.fields.cause {
-            Some(Cause::ExistentialRegionBound(true)) =>
+        debug!("{}.regions({:?}, {:?}) self.causes={:?}",
+               self.tag(), a, b, self.fields.causes);
+        let reasons = ty_relate::invariance_reasons(self.causes());
+        let origin = match self.causes().last() {
+            Some(&Cause::ExistentialRegionBound(true)) =>
                 SubregionOrigin::DefaultExistentialBound(self.fields.trace.clone()),
+            _ if !reasons.is_empty() =>
+                SubregionOrigin::Invariant(self.fields.trace.clone(), reasons),
             _ =>
                 SubregionOrigin::Subtype(self.fields.trace.clone()),
         };
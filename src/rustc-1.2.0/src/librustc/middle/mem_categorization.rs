@@ -511,6 +511,13 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
             let method_call = ty::MethodCall::expr(expr.id());
             let context = InteriorOffsetKind::Index;
             match self.typer.node_method_ty(method_call) {
+                Some(_) if self.typer.node_method_origin(method_call).map_or(false, |origin| {
+                    ty::is_index_assign_method(self.tcx(), &origin)
+                }) => {
+                    // The left-hand side of an overloaded index assignment
+                    // names no place; the value is handed to the method.
+                    Ok(self.cat_rvalue_node(expr.id(), expr.span(), expr_ty))
+                }
                 Some(method_ty) => {
                     // If this is an index implemented by a method call, then it
                     // will include an implicit deref of the result.
@@ -27,7 +27,7 @@ use syntax::ast_util::{is_local, local_def};
 use syntax::attr::{Stability, AttrMetaMethods};
 use syntax::visit::{FnKind, Visitor};
 use syntax::feature_gate::emit_feature_err;
-use util::nodemap::{DefIdMap, FnvHashSet, FnvHashMap};
+use util::nodemap::{DefIdMap, FnvHashSet, FnvHashMap, NodeSet};
 
 use std::mem::replace;
 
@@ -38,7 +38,15 @@ pub struct Index<'tcx> {
     map: DefIdMap<Option<&'tcx Stability>>,
 
     /// Maps for each crate whether it is part of the staged API.
-    staged_api: FnvHashMap<ast::CrateNum, bool>
+    staged_api: FnvHashMap<ast::CrateNum, bool>,
+
+    /// Method calls whose use of an unstable method was already reported
+    /// by typeck when the method was picked.
+    reported_method_calls: NodeSet,
+
+    /// Likewise for method calls of deprecated methods, which the lint pass
+    /// then skips.
+    reported_deprecated_calls: NodeSet,
 }
 
 // A private tree-walker for producing an Index.
@@ -200,8 +208,16 @@ impl<'tcx> Index<'tcx> {
         Index {
             staged_api: staged_api,
             map: DefIdMap(),
+            reported_method_calls: NodeSet(),
+            reported_deprecated_calls: NodeSet(),
         }
     }
+
+    /// Whether typeck already reported the method call `id` as a use of a
+    /// deprecated method.
+    pub fn deprecation_reported(&self, id: NodeId) -> bool {
+        self.reported_deprecated_calls.contains(&id)
+    }
 }
 
 /// Cross-references the feature names of unstable APIs with enabled
@@ -244,14 +260,7 @@ impl<'a, 'tcx> Checker<'a, 'tcx> {
                 self.used_features.insert(feature.clone(), attr::Unstable);
 
                 if !self.active_features.contains(feature) {
-                    let msg = match *reason {
-                        Some(ref r) => format!("use of unstable library feature '{}': {}",
-                                               &feature, &r),
-                        None => format!("use of unstable library feature '{}'", &feature)
-                    };
-
-                    emit_feature_err(&self.tcx.sess.parse_sess.span_diagnostic,
-                                      &feature, span, &msg);
+                    emit_unstable_err(self.tcx, span, feature, reason);
                 }
             }
             Some(&Stability { level, ref feature, .. }) => {
@@ -294,8 +303,10 @@ impl<'a, 'v, 'tcx> Visitor<'v> for Checker<'a, 'tcx> {
     }
 
     fn visit_expr(&mut self, ex: &ast::Expr) {
-        check_expr(self.tcx, ex,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+        if !self.tcx.stability.borrow().reported_method_calls.contains(&ex.id) {
+            check_expr(self.tcx, ex,
+                       &mut |id, sp, stab| self.check(id, sp, stab));
+        }
         visit::walk_expr(self, ex);
     }
 
@@ -349,12 +360,57 @@ pub fn check_item(tcx: &ty::ctxt, item: &ast::Item, warn_about_defns: bool,
     }
 }
 
+fn emit_unstable_err(tcx: &ty::ctxt, span: Span, feature: &InternedString,
+                     reason: &Option<InternedString>) {
+    let msg = match *reason {
+        Some(ref r) => format!("use of unstable library feature '{}': {}",
+                               &feature, &r),
+        None => format!("use of unstable library feature '{}'", &feature)
+    };
+
+    emit_feature_err(&tcx.sess.parse_sess.span_diagnostic,
+                     &feature, span, &msg);
+}
+
+/// Checks the use of an unstable or deprecated method by the method call
+/// `call_id`, as soon as typeck has picked the method. Reports the feature
+/// gate with the method's declared feature name at `span`, and the
+/// `deprecated` lint at the level it has at the call. The call is then
+/// skipped by the crate-wide stability check and the lint pass.
+pub fn check_method_call(tcx: &ty::ctxt, call_id: ast::NodeId, span: Span, stab: &Stability) {
+    if stab.deprecated_since.is_some() {
+        let msg = match stab.reason {
+            Some(ref r) => format!("use of deprecated item: {}", r),
+            None => "use of deprecated item".to_string(),
+        };
+        let lvlsrc = lint::node_level_source(tcx, lint::builtin::DEPRECATED, call_id);
+        lint::raw_emit_lint(&tcx.sess, lint::builtin::DEPRECATED, lvlsrc, Some(span), &msg);
+        tcx.stability.borrow_mut().reported_deprecated_calls.insert(call_id);
+    }
+
+    let (feature, reason) = match *stab {
+        Stability { level: attr::Unstable, ref feature, ref reason, .. } => (feature, reason),
+        _ => return
+    };
+    if is_internal(tcx, span) {
+        return
+    }
+    let active = tcx.sess.features.borrow().declared_lib_features.iter()
+                    .any(|&(ref f, _)| f == feature);
+    if active {
+        return
+    }
+
+    emit_unstable_err(tcx, span, feature, reason);
+    tcx.stability.borrow_mut().reported_method_calls.insert(call_id);
+}
+
 /// Helper for discovering nodes to check for stability
 pub fn check_expr(tcx: &ty::ctxt, e: &ast::Expr,
                   cb: &mut FnMut(ast::DefId, Span, &Option<&Stability>)) {
     let span;
     let id = match e.node {
-        ast::ExprMethodCall(i, _, _) => {
+        ast::ExprMethodCall(i, _, _, _) => {
             span = i.span;
             let method_call = ty::MethodCall::expr(e.id);
             match tcx.method_map.borrow().get(&method_call) {
@@ -537,6 +593,15 @@ fn is_staged_api(tcx: &ty::ctxt, id: DefId) -> bool {
     }
 }
 
+/// Lookup the stability of an item from another crate that is part of the
+/// staged API, for a use of it in the current crate.
+pub fn lookup_cross_crate<'tcx>(tcx: &ty::ctxt<'tcx>, id: DefId) -> Option<&'tcx Stability> {
+    if is_local(id) || !is_staged_api(tcx, id) {
+        return None;
+    }
+    lookup(tcx, id)
+}
+
 /// Lookup the stability for a node, loading external crate
 /// metadata as necessary.
 pub fn lookup<'tcx>(tcx: &ty::ctxt<'tcx>, id: DefId) -> Option<&'tcx Stability> {
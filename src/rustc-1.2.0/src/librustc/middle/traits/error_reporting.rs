@@ -482,6 +482,65 @@ fn note_obligation_cause_code<'a, 'tcx, T>(infcx: &InferCtxt<'a, 'tcx>,
                       but not on the corresponding trait method",
                       predicate);
         }
+        ObligationCauseCode::MethodImplObligation(impl_def_id, method_name) => {
+            let impl_trait_ref = ty::impl_trait_ref(tcx, impl_def_id).unwrap();
+            let sized_trait = tcx.lang_items.sized_trait();
+            let where_clauses: Vec<_> =
+                ty::lookup_predicates(tcx, impl_def_id).predicates.iter()
+                    .filter(|predicate| match **predicate {
+                        ty::Predicate::Trait(ref data) => Some(data.def_id()) != sized_trait,
+                        _ => true
+                    })
+                    .map(|predicate| format!("`{}`", predicate))
+                    .collect();
+            let requires = if where_clauses.is_empty() {
+                String::new()
+            } else {
+                format!(", which requires {}", where_clauses.connect(", "))
+            };
+            span_note!(tcx.sess, cause_span,
+                       "the method `{}` was resolved via the impl of `{}` for `{}`{}",
+                       method_name,
+                       impl_trait_ref,
+                       impl_trait_ref.self_ty(),
+                       requires);
+        }
+        ObligationCauseCode::MethodObligation(ref data) => {
+            let item = ty::impl_or_trait_item(tcx, data.method_def_id);
+            let declared_on = match item.container() {
+                ty::ImplContainer(impl_def_id) => match ty::impl_trait_ref(tcx, impl_def_id) {
+                    Some(trait_ref) => {
+                        format!("the impl of `{}` for `{}`", trait_ref, trait_ref.self_ty())
+                    }
+                    None => {
+                        format!("the inherent impl for `{}`",
+                                ty::lookup_item_type(tcx, impl_def_id).ty)
+                    }
+                },
+                ty::TraitContainer(trait_def_id) => {
+                    format!("the trait `{}`", ty::item_path_str(tcx, trait_def_id))
+                }
+            };
+            let substs = infcx.resolve_type_vars_if_possible(&data.substs);
+            let params: Vec<_> = match item.as_opt_method() {
+                Some(method) => method.generics.types.iter().filter_map(|def| {
+                    substs.types.get_slice(def.space).get(def.index as usize)
+                        .map(|ty| format!("`{} = {}`", def.name, ty))
+                }).collect(),
+                None => vec![],
+            };
+            let instantiated = if params.is_empty() {
+                String::new()
+            } else {
+                format!(", instantiated with {}", params.connect(", "))
+            };
+            span_note!(tcx.sess, cause_span,
+                       "required by the method `{}`, declared on {}{}",
+                       item.name(),
+                       declared_on,
+                       instantiated);
+            note_obligation_cause_code(infcx, predicate, cause_span, &*data.parent_code);
+        }
     }
 }
 
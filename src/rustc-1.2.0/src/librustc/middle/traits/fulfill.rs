@@ -28,6 +28,7 @@ use super::select::SelectionContext;
 use super::Unimplemented;
 use super::util::predicate_for_builtin_bound;
 
+#[derive(Clone)]
 pub struct FulfilledPredicates<'tcx> {
     set: HashSet<ty::Predicate<'tcx>>
 }
@@ -95,6 +96,15 @@ pub struct RegionObligation<'tcx> {
     pub cause: ObligationCause<'tcx>,
 }
 
+/// The obligations of a `FulfillmentContext` as they were when
+/// `snapshot` was called, to be restored by `rollback_to`.
+pub struct FulfillmentSnapshot<'tcx> {
+    duplicate_set: FulfilledPredicates<'tcx>,
+    predicates: Vec<PredicateObligation<'tcx>>,
+    attempted_mark: usize,
+    region_obligations: NodeMap<Vec<RegionObligation<'tcx>>>,
+}
+
 impl<'tcx> FulfillmentContext<'tcx> {
     /// Creates a new fulfillment context.
     ///
@@ -250,6 +260,28 @@ impl<'tcx> FulfillmentContext<'tcx> {
         self.select(&mut selcx, false)
     }
 
+    /// Saves the obligations registered so far, so that those registered
+    /// or selected afterwards can be undone with `rollback_to`. This copies
+    /// every pending obligation, so it is meant for dry runs and not for
+    /// the common path. Results already stored in the selection caches are
+    /// kept, which is fine as they do not depend on the obligations.
+    pub fn snapshot(&self) -> FulfillmentSnapshot<'tcx> {
+        FulfillmentSnapshot {
+            duplicate_set: self.duplicate_set.clone(),
+            predicates: self.predicates.clone(),
+            attempted_mark: self.attempted_mark,
+            region_obligations: self.region_obligations.clone(),
+        }
+    }
+
+    pub fn rollback_to(&mut self, snapshot: FulfillmentSnapshot<'tcx>) {
+        debug!("rollback_to: {} pending obligations restored", snapshot.predicates.len());
+        self.duplicate_set = snapshot.duplicate_set;
+        self.predicates = snapshot.predicates;
+        self.attempted_mark = snapshot.attempted_mark;
+        self.region_obligations = snapshot.region_obligations;
+    }
+
     pub fn pending_obligations(&self) -> &[PredicateObligation<'tcx>] {
         &self.predicates
     }
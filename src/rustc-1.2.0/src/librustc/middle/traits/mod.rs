@@ -31,7 +31,8 @@ pub use self::error_reporting::suggest_new_overflow_limit;
 pub use self::coherence::orphan_check;
 pub use self::coherence::overlapping_impls;
 pub use self::coherence::OrphanCheckErr;
-pub use self::fulfill::{FulfillmentContext, FulfilledPredicates, RegionObligation};
+pub use self::fulfill::{FulfillmentContext, FulfillmentSnapshot, FulfilledPredicates};
+pub use self::fulfill::RegionObligation;
 pub use self::project::MismatchedProjectionTypes;
 pub use self::project::normalize;
 pub use self::project::Normalized;
@@ -54,6 +55,7 @@ pub use self::util::supertrait_def_ids;
 pub use self::util::SupertraitDefIds;
 pub use self::util::transitive_bounds;
 pub use self::util::upcast;
+pub use self::util::upcast_modulo_regions;
 
 mod coherence;
 mod error_reporting;
@@ -130,6 +132,13 @@ pub enum ObligationCauseCode<'tcx> {
     ImplDerivedObligation(DerivedObligationCause<'tcx>),
 
     CompareImplMethodObligation,
+
+    // The bounds of a method call that was resolved to the given method of
+    // the given trait impl.
+    MethodImplObligation(/* impl */ ast::DefId, /* method */ ast::Name),
+
+    // The bounds of a call to a method, as instantiated for the call.
+    MethodObligation(MethodObligationCause<'tcx>),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -144,6 +153,19 @@ pub struct DerivedObligationCause<'tcx> {
     parent_code: Rc<ObligationCauseCode<'tcx>>
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct MethodObligationCause<'tcx> {
+    /// The method that was called.
+    pub method_def_id: ast::DefId,
+
+    /// The substitutions the method was instantiated with for the call,
+    /// covering the type parameters of its impl or trait too.
+    pub substs: subst::Substs<'tcx>,
+
+    /// Any more specific reason for the bounds, noted after the method.
+    pub parent_code: Rc<ObligationCauseCode<'tcx>>
+}
+
 pub type Obligations<'tcx, O> = Vec<Obligation<'tcx, O>>;
 pub type PredicateObligations<'tcx> = Vec<PredicateObligation<'tcx>>;
 pub type TraitObligations<'tcx> = Vec<TraitObligation<'tcx>>;
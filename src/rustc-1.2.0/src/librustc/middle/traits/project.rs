@@ -134,7 +134,7 @@ fn project_and_unify_type<'cx,'tcx>(
     let origin = infer::RelateOutputImplTypes(obligation.cause.span);
     match infer::mk_eqty(infcx, true, origin, normalized_ty, obligation.predicate.ty) {
         Ok(()) => Ok(Some(obligations)),
-        Err(err) => Err(MismatchedProjectionTypes { err: err }),
+        Err(e) => Err(MismatchedProjectionTypes { err: e.err }),
     }
 }
 
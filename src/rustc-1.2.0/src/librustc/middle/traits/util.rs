@@ -11,6 +11,7 @@
 use middle::subst::Substs;
 use middle::infer::InferCtxt;
 use middle::ty::{self, Ty, AsPredicate, ToPolyTraitRef};
+use middle::ty_fold;
 use std::fmt;
 use syntax::ast;
 use syntax::codemap::Span;
@@ -396,6 +397,26 @@ pub fn upcast<'tcx>(tcx: &ty::ctxt<'tcx>,
         .collect()
 }
 
+/// Like `upcast`, but keeps only the first of any supertraits that differ
+/// from each other only in their regions, as these lead to the same
+/// methods. More than one result means the upcast is ambiguous.
+pub fn upcast_modulo_regions<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                   source_trait_ref: ty::PolyTraitRef<'tcx>,
+                                   target_trait_def_id: ast::DefId)
+                                   -> Vec<ty::PolyTraitRef<'tcx>>
+{
+    let mut seen = Vec::new();
+    let mut upcast_trait_refs = upcast(tcx, source_trait_ref, target_trait_def_id);
+    upcast_trait_refs.retain(|trait_ref| {
+        let erased = ty::anonymize_late_bound_regions(
+            tcx, &ty_fold::erase_regions(tcx, trait_ref.clone()));
+        let first = !seen.contains(&erased);
+        seen.push(erased);
+        first
+    });
+    upcast_trait_refs
+}
+
 /// Given an object of type `object_trait_ref`, returns the index of
 /// the method `n_method` found in the trait `trait_def_id` (which
 /// should be a supertrait of `object_trait_ref`) within the vtable
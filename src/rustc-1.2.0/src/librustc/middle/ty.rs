@@ -254,6 +254,15 @@ impl<'tcx> Method<'tcx> {
             ImplContainer(id) => id,
         }
     }
+
+    /// Whether this trait method takes a plain `self` (or `self: Self`),
+    /// as opposed to `&self`, `Box<Self>` or a smart pointer receiver
+    /// such as `self: Rc<Self>`, all of which can be passed through an
+    /// object as they are.
+    pub fn takes_self_by_value(&self) -> bool {
+        self.explicit_self == ByValueExplicitSelfCategory &&
+            type_is_self(self.fty.sig.0.inputs[0])
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -466,6 +475,90 @@ pub struct MethodCallee<'tcx> {
     pub substs: subst::Substs<'tcx>
 }
 
+/// How a method call expression was resolved, kept for tools such as
+/// IDEs that need it after type checking. See
+/// `ctxt::method_call_resolutions`.
+#[derive(Clone)]
+pub struct ResolvedMethod<'tcx> {
+    /// The method selected. For a call resolved to a trait impl, this is
+    /// the impl's method, or the trait's when the impl uses its default;
+    /// for a call whose impl is not known statically, it is the method
+    /// in the trait.
+    pub def_id: ast::DefId,
+
+    /// Substitutions for all the type and region parameters of the
+    /// method `def_id` and the impl or trait it belongs to.
+    pub substs: subst::Substs<'tcx>,
+
+    /// The autoderefs, autoref and unsizing applied to the receiver.
+    pub adjustment: AutoDerefRef<'tcx>,
+}
+
+/// Everything typeck computed for one body (a fn, or a constant
+/// expression), as written back from its inference tables. Closures are
+/// part of the body that contains them.
+///
+/// Writeback builds one of these per body and hands it to
+/// `ctxt::install_typeck_tables`, which moves the entries into the
+/// crate-wide tables. Those are the ones later passes read.
+pub struct TypeckTables<'tcx> {
+    pub node_types: NodeMap<Ty<'tcx>>,
+    pub item_substs: NodeMap<ItemSubsts<'tcx>>,
+    pub adjustments: NodeMap<AutoAdjustment<'tcx>>,
+    pub method_map: FnvHashMap<MethodCall, MethodCallee<'tcx>>,
+    pub method_call_resolutions: NodeMap<ResolvedMethod<'tcx>>,
+    pub autoderef_chains: NodeMap<Vec<AutoderefStep<'tcx>>>,
+    pub upvar_capture_map: UpvarCaptureMap,
+    pub upvar_capture_spans: UpvarCaptureSpanMap,
+    pub closure_tys: DefIdMap<ClosureTy<'tcx>>,
+    pub closure_kinds: DefIdMap<ClosureKind>,
+    pub inline_asm_operand_tys: NodeMap<InlineAsmOperandTys<'tcx>>,
+    pub cast_kinds: NodeMap<cast::CastKind>,
+}
+
+/// The resolved types of the operands of an inline assembly expression,
+/// in the order they are written. See `ctxt::inline_asm_operand_tys`.
+#[derive(Clone, Debug)]
+pub struct InlineAsmOperandTys<'tcx> {
+    pub outputs: Vec<Ty<'tcx>>,
+    pub inputs: Vec<Ty<'tcx>>,
+}
+
+impl<'tcx> TypeckTables<'tcx> {
+    pub fn new() -> TypeckTables<'tcx> {
+        TypeckTables {
+            node_types: NodeMap(),
+            item_substs: NodeMap(),
+            adjustments: NodeMap(),
+            method_map: FnvHashMap(),
+            method_call_resolutions: NodeMap(),
+            autoderef_chains: NodeMap(),
+            upvar_capture_map: FnvHashMap(),
+            upvar_capture_spans: FnvHashMap(),
+            closure_tys: DefIdMap(),
+            closure_kinds: DefIdMap(),
+            inline_asm_operand_tys: NodeMap(),
+            cast_kinds: NodeMap(),
+        }
+    }
+}
+
+/// One step of the autoderef chain of a method call receiver. See
+/// `ctxt::autoderef_chains`.
+#[derive(Clone, Debug)]
+pub struct AutoderefStep<'tcx> {
+    /// The type of the receiver after this many derefs.
+    pub ty: Ty<'tcx>,
+
+    /// Whether this step was reached through an overloaded deref.
+    pub overloaded: bool,
+
+    /// The `Deref` or `DerefMut` impl used for an overloaded deref. This
+    /// is `None` when the impl is not known statically, e.g. when it
+    /// comes from a bound on a type parameter.
+    pub deref_impl: Option<ast::DefId>,
+}
+
 /// With method calls, we store some extra information in
 /// side tables (i.e method_map). We use
 /// MethodCall as a key to index into these tables instead of
@@ -513,6 +606,21 @@ pub struct creader_cache_key {
     pub len: usize
 }
 
+/// An object type written without a lifetime bound, whose default bound
+/// will change to `'static` in Rust 1.3. Astconv pushes one of these into
+/// `future_compat_region_defaults` for each such type, so that they can
+/// all be reported by the `object_lifetime_default_change` lint; see also
+/// `ExistentialBounds::region_bound_will_change`.
+#[derive(Copy, Clone, Debug)]
+pub struct FutureCompatRegionDefault {
+    /// The object type as written.
+    pub span: Span,
+
+    /// The bound it gets today. This is the lifetime of the reference or
+    /// other binder the default is currently taken from.
+    pub region_bound: Region,
+}
+
 /// A restriction that certain types must be the same size. The use of
 /// `transmute` gives rise to these restrictions. These generally
 /// cannot be checked until trans; therefore, each call to `transmute`
@@ -555,6 +663,8 @@ pub struct CtxtArenas<'tcx> {
 
     // references
     trait_defs: TypedArena<TraitDef<'tcx>>,
+    type_diffs: TypedArena<TypeDiff<'tcx>>,
+    tuple_size_diffs: TypedArena<TupleSizeDiff>,
 }
 
 impl<'tcx> CtxtArenas<'tcx> {
@@ -566,7 +676,9 @@ impl<'tcx> CtxtArenas<'tcx> {
             region: TypedArena::new(),
             stability: TypedArena::new(),
 
-            trait_defs: TypedArena::new()
+            trait_defs: TypedArena::new(),
+            type_diffs: TypedArena::new(),
+            tuple_size_diffs: TypedArena::new()
         }
     }
 }
@@ -579,11 +691,13 @@ pub struct CommonTypes<'tcx> {
     pub i16: Ty<'tcx>,
     pub i32: Ty<'tcx>,
     pub i64: Ty<'tcx>,
+    pub i128: Ty<'tcx>,
     pub usize: Ty<'tcx>,
     pub u8: Ty<'tcx>,
     pub u16: Ty<'tcx>,
     pub u32: Ty<'tcx>,
     pub u64: Ty<'tcx>,
+    pub u128: Ty<'tcx>,
     pub f32: Ty<'tcx>,
     pub f64: Ty<'tcx>,
     pub err: Ty<'tcx>,
@@ -721,6 +835,15 @@ pub struct ctxt<'tcx> {
     /// Borrows
     pub upvar_capture_map: RefCell<UpvarCaptureMap>,
 
+    /// The uses that forced the capture kinds in `upvar_capture_map`.
+    pub upvar_capture_spans: RefCell<UpvarCaptureSpanMap>,
+
+    /// With `-Z writeback-fingerprints`, a hash of the inference tables of
+    /// each fn body, keyed by the fn's id, taken just before writeback.
+    /// They are printed once type checking is done, so that two runs can
+    /// be compared to find the bodies whose tables changed.
+    pub writeback_fingerprints: RefCell<NodeMap<u64>>,
+
     /// These caches are used by const_eval when decoding external constants.
     pub extern_const_statics: RefCell<DefIdMap<ast::NodeId>>,
     pub extern_const_variants: RefCell<DefIdMap<ast::NodeId>>,
@@ -728,6 +851,14 @@ pub struct ctxt<'tcx> {
 
     pub method_map: MethodMap<'tcx>,
 
+    /// The resolution of each method call expression, keyed by the id
+    /// of the call.
+    pub method_call_resolutions: RefCell<NodeMap<ResolvedMethod<'tcx>>>,
+
+    /// The types each method call receiver was autoderef'd through,
+    /// starting with its own type, keyed by the id of the receiver.
+    pub autoderef_chains: RefCell<NodeMap<Vec<AutoderefStep<'tcx>>>>,
+
     pub dependency_formats: RefCell<dependency_format::Dependencies>,
 
     /// Records the type of each closure. The def ID is the ID of the
@@ -738,6 +869,10 @@ pub struct ctxt<'tcx> {
     /// expression defining the closure.
     pub closure_tys: RefCell<DefIdMap<ClosureTy<'tcx>>>,
 
+    /// The operand types of each inline assembly expression, as checked
+    /// against their constraints once inference was done.
+    pub inline_asm_operand_tys: RefCell<NodeMap<InlineAsmOperandTys<'tcx>>>,
+
     pub node_lint_levels: RefCell<FnvHashMap<(ast::NodeId, lint::LintId),
                                               lint::LevelSource>>,
 
@@ -769,8 +904,14 @@ pub struct ctxt<'tcx> {
     pub custom_coerce_unsized_kinds: RefCell<DefIdMap<CustomCoerceUnsized>>,
 
     /// Maps a cast expression to its kind. This is keyed on the
-    /// *from* expression of the cast, not the cast itself.
+    /// *from* expression of the cast, not the cast itself. Filled in by
+    /// writeback from the fully resolved types of the cast.
     pub cast_kinds: RefCell<NodeMap<cast::CastKind>>,
+
+    /// The object types in this crate whose meaning will change with
+    /// the switch to `'static` object lifetime defaults. A type that is
+    /// converted more than once is recorded each time.
+    pub future_compat_region_defaults: RefCell<Vec<FutureCompatRegionDefault>>,
 }
 
 impl<'tcx> ctxt<'tcx> {
@@ -779,6 +920,28 @@ impl<'tcx> ctxt<'tcx> {
         self.node_types.borrow_mut().insert(id, ty);
     }
 
+    /// Moves the tables written back for a body into the crate-wide
+    /// tables.
+    pub fn install_typeck_tables(&self, tables: TypeckTables<'tcx>) {
+        let TypeckTables {
+            node_types, item_substs, adjustments, method_map, method_call_resolutions,
+            autoderef_chains, upvar_capture_map, upvar_capture_spans, closure_tys,
+            closure_kinds, inline_asm_operand_tys, cast_kinds
+        } = tables;
+        self.node_types.borrow_mut().extend(node_types);
+        self.item_substs.borrow_mut().extend(item_substs);
+        self.adjustments.borrow_mut().extend(adjustments);
+        self.method_map.borrow_mut().extend(method_map);
+        self.method_call_resolutions.borrow_mut().extend(method_call_resolutions);
+        self.autoderef_chains.borrow_mut().extend(autoderef_chains);
+        self.upvar_capture_map.borrow_mut().extend(upvar_capture_map);
+        self.upvar_capture_spans.borrow_mut().extend(upvar_capture_spans);
+        self.closure_tys.borrow_mut().extend(closure_tys);
+        self.closure_kinds.borrow_mut().extend(closure_kinds);
+        self.inline_asm_operand_tys.borrow_mut().extend(inline_asm_operand_tys);
+        self.cast_kinds.borrow_mut().extend(cast_kinds);
+    }
+
     pub fn intern_trait_def(&self, def: TraitDef<'tcx>) -> &'tcx TraitDef<'tcx> {
         let did = def.trait_ref.def_id;
         let interned = self.arenas.trait_defs.alloc(def);
@@ -786,6 +949,14 @@ impl<'tcx> ctxt<'tcx> {
         interned
     }
 
+    pub fn mk_type_diff(&self, diff: TypeDiff<'tcx>) -> &'tcx TypeDiff<'tcx> {
+        self.arenas.type_diffs.alloc(diff)
+    }
+
+    pub fn mk_tuple_size_diff(&self, diff: TupleSizeDiff) -> &'tcx TupleSizeDiff {
+        self.arenas.tuple_size_diffs.alloc(diff)
+    }
+
     pub fn intern_stability(&self, stab: attr::Stability) -> &'tcx attr::Stability {
         if let Some(st) = self.stability_interner.borrow().get(&stab) {
             return st;
@@ -1418,7 +1589,7 @@ pub struct EarlyBoundRegion {
 /// Upvars do not get their own node-id. Instead, we use the pair of
 /// the original var id (that is, the root variable that is referenced
 /// by the upvar) and the id of the closure expression.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UpvarId {
     pub var_id: ast::NodeId,
     pub closure_expr_id: ast::NodeId,
@@ -1496,6 +1667,11 @@ pub struct UpvarBorrow {
 
 pub type UpvarCaptureMap = FnvHashMap<UpvarId, UpvarCapture>;
 
+/// For each upvar whose capture was escalated past an immutable borrow
+/// (to a unique or mutable borrow, or to a by-value capture), the span of
+/// the use in the closure body that forced the final escalation.
+pub type UpvarCaptureSpanMap = FnvHashMap<UpvarId, Span>;
+
 impl Region {
     pub fn is_global(&self) -> bool {
         // does this represent a region that can be named in a global
@@ -1811,7 +1987,27 @@ pub enum terr_vstore_kind {
 #[derive(Clone, Copy, Debug)]
 pub struct expected_found<T> {
     pub expected: T,
-    pub found: T
+    pub found: T,
+
+    /// Where the values came from, if they were produced by a type
+    /// relation.
+    pub origin: Option<ExpectedOrigin>,
+}
+
+/// The provenance of an `expected_found`, filled in by
+/// `ty_relate::expected_found`. The inference relations know the span and
+/// item only when their caller supplied the item, as
+/// `InferCtxt::sub_types_expected_from` does.
+#[derive(Clone, Copy, Debug)]
+pub struct ExpectedOrigin {
+    /// The tag of the relation that compared the values, e.g. `"Sub"`.
+    pub tag: &'static str,
+
+    /// Where the expected value was written, if the relation knows.
+    pub span: Option<Span>,
+
+    /// The item the expected value comes from, if the relation knows.
+    pub def_id: Option<ast::DefId>,
 }
 
 // Data structures used in type unification
@@ -1826,6 +2022,7 @@ pub enum type_err<'tcx> {
     terr_ref_mutability,
     terr_vec_mutability,
     terr_tuple_size(expected_found<usize>),
+    terr_tuple_size_detail(&'tcx TupleSizeDiff),
     terr_fixed_array_size(expected_found<usize>),
     terr_ty_param_size(expected_found<usize>),
     terr_arg_count,
@@ -1835,6 +2032,7 @@ pub enum type_err<'tcx> {
     terr_regions_insufficiently_polymorphic(BoundRegion, Region),
     terr_regions_overly_polymorphic(BoundRegion, Region),
     terr_sorts(expected_found<Ty<'tcx>>),
+    terr_sorts_at(&'tcx TypeDiff<'tcx>),
     terr_integer_as_char,
     terr_int_mismatch(expected_found<IntVarValue>),
     terr_float_mismatch(expected_found<ast::FloatTy>),
@@ -1842,9 +2040,64 @@ pub enum type_err<'tcx> {
     terr_builtin_bounds(expected_found<BuiltinBounds>),
     terr_variadic_mismatch(expected_found<bool>),
     terr_cyclic_ty,
-    terr_convergence_mismatch(expected_found<bool>),
+    terr_convergence_mismatch(expected_found<FnOutput<'tcx>>),
     terr_projection_name_mismatched(expected_found<ast::Name>),
-    terr_projection_bounds_length(expected_found<usize>),
+    terr_projection_bound_mismatch(expected_found<Option<ast::Name>>),
+    terr_relation_overflow(expected_found<Ty<'tcx>>),
+    terr_length_mismatch(expected_found<usize>),
+    terr_presence_mismatch(expected_found<bool>),
+    terr_type_param_default(expected_found<Ty<'tcx>>),
+}
+
+/// A `terr_sorts` mismatch found somewhere inside two composite types,
+/// together with the path leading to it from the outermost types.
+#[derive(Debug)]
+pub struct TypeDiff<'tcx> {
+    /// The innermost types that could not be related.
+    pub values: expected_found<Ty<'tcx>>,
+
+    /// The components walked through to reach `values`, outermost first.
+    pub path: Vec<TypeDiffStep>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TypeDiffStep {
+    /// The type argument with the given index in the given space of a
+    /// struct, enum, closure, trait reference or projection.
+    TypeArg(ParamSpace, usize),
+
+    /// The element at the given position of a tuple.
+    TupleElem(usize),
+
+    /// The argument at the given position of a fn type.
+    FnArg(usize),
+
+    /// The return type of a fn type.
+    FnOutput,
+}
+
+impl fmt::Display for TypeDiffStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TypeDiffStep::TypeArg(subst::SelfSpace, _) => write!(f, "`Self` type"),
+            TypeDiffStep::TypeArg(subst::FnSpace, i) => write!(f, "method type argument {}", i),
+            TypeDiffStep::TypeArg(subst::TypeSpace, i) => write!(f, "type argument {}", i),
+            TypeDiffStep::TupleElem(i) => write!(f, "tuple element {}", i),
+            TypeDiffStep::FnArg(i) => write!(f, "argument {}", i),
+            TypeDiffStep::FnOutput => write!(f, "return type"),
+        }
+    }
+}
+
+/// A `terr_tuple_size` mismatch, together with which of the elements
+/// present in both tuples could be related.
+#[derive(Debug)]
+pub struct TupleSizeDiff {
+    pub sizes: expected_found<usize>,
+
+    /// Whether the elements at each position the two tuples have in
+    /// common were related successfully.
+    pub prefix_matches: Vec<bool>,
 }
 
 /// Bounds suitable for an existentially quantified type parameter
@@ -3017,11 +3270,13 @@ impl<'tcx> CommonTypes<'tcx> {
             i16: intern_ty(arena, interner, TyInt(ast::TyI16)),
             i32: intern_ty(arena, interner, TyInt(ast::TyI32)),
             i64: intern_ty(arena, interner, TyInt(ast::TyI64)),
+            i128: intern_ty(arena, interner, TyInt(ast::TyI128)),
             usize: intern_ty(arena, interner, TyUint(ast::TyUs)),
             u8: intern_ty(arena, interner, TyUint(ast::TyU8)),
             u16: intern_ty(arena, interner, TyUint(ast::TyU16)),
             u32: intern_ty(arena, interner, TyUint(ast::TyU32)),
             u64: intern_ty(arena, interner, TyUint(ast::TyU64)),
+            u128: intern_ty(arena, interner, TyUint(ast::TyU128)),
             f32: intern_ty(arena, interner, TyFloat(ast::TyF32)),
             f64: intern_ty(arena, interner, TyFloat(ast::TyF64)),
         }
@@ -3094,13 +3349,18 @@ pub fn with_ctxt<'tcx, F, R>(s: Session,
         populated_external_types: RefCell::new(DefIdSet()),
         populated_external_primitive_impls: RefCell::new(DefIdSet()),
         upvar_capture_map: RefCell::new(FnvHashMap()),
+        upvar_capture_spans: RefCell::new(FnvHashMap()),
+        writeback_fingerprints: RefCell::new(NodeMap()),
         extern_const_statics: RefCell::new(DefIdMap()),
         extern_const_variants: RefCell::new(DefIdMap()),
         extern_const_fns: RefCell::new(DefIdMap()),
         method_map: RefCell::new(FnvHashMap()),
+        method_call_resolutions: RefCell::new(NodeMap()),
+        autoderef_chains: RefCell::new(NodeMap()),
         dependency_formats: RefCell::new(FnvHashMap()),
         closure_kinds: RefCell::new(DefIdMap()),
         closure_tys: RefCell::new(DefIdMap()),
+        inline_asm_operand_tys: RefCell::new(NodeMap()),
         node_lint_levels: RefCell::new(FnvHashMap()),
         transmute_restrictions: RefCell::new(Vec::new()),
         stability: RefCell::new(stability),
@@ -3109,6 +3369,7 @@ pub fn with_ctxt<'tcx, F, R>(s: Session,
         const_qualif_map: RefCell::new(NodeMap()),
         custom_coerce_unsized_kinds: RefCell::new(DefIdMap()),
         cast_kinds: RefCell::new(NodeMap()),
+        future_compat_region_defaults: RefCell::new(Vec::new()),
    }, f)
 }
 
@@ -3415,6 +3676,7 @@ pub fn mk_mach_int<'tcx>(tcx: &ctxt<'tcx>, tm: ast::IntTy) -> Ty<'tcx> {
         ast::TyI16  => tcx.types.i16,
         ast::TyI32  => tcx.types.i32,
         ast::TyI64  => tcx.types.i64,
+        ast::TyI128 => tcx.types.i128,
     }
 }
 
@@ -3425,6 +3687,7 @@ pub fn mk_mach_uint<'tcx>(tcx: &ctxt<'tcx>, tm: ast::UintTy) -> Ty<'tcx> {
         ast::TyU16  => tcx.types.u16,
         ast::TyU32  => tcx.types.u32,
         ast::TyU64  => tcx.types.u64,
+        ast::TyU128 => tcx.types.u128,
     }
 }
 
@@ -4730,6 +4993,39 @@ pub fn deref<'tcx>(ty: Ty<'tcx>, explicit: bool) -> Option<mt<'tcx>> {
     }
 }
 
+// If `ty` is a smart pointer implementing the `Receiver` lang item,
+// returns the type it points to, which is its first type parameter.
+//
+// Impls of `Receiver` in the local crate are only recorded during
+// coherence, so before then only upstream smart pointers are found.
+pub fn receiver_target<'tcx>(cx: &ctxt<'tcx>, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    let (did, substs) = match ty.sty {
+        TyStruct(did, substs) | TyEnum(did, substs) => (did, substs),
+        _ => return None
+    };
+    let trait_id = match cx.lang_items.receiver_trait() {
+        Some(trait_id) => trait_id,
+        None => return None
+    };
+
+    let mut is_receiver = false;
+    lookup_trait_def(cx, trait_id).for_each_relevant_impl(cx, ty, |impl_def_id| {
+        let impl_self_ty = impl_trait_ref(cx, impl_def_id).unwrap().self_ty();
+        match impl_self_ty.sty {
+            TyStruct(impl_did, _) | TyEnum(impl_did, _) if impl_did == did => {
+                is_receiver = true;
+            }
+            _ => {}
+        }
+    });
+
+    if is_receiver {
+        substs.types.get_slice(subst::TypeSpace).first().cloned()
+    } else {
+        None
+    }
+}
+
 pub fn type_content<'tcx>(ty: Ty<'tcx>) -> Ty<'tcx> {
     match ty.sty {
         TyBox(ty) => ty,
@@ -5240,6 +5536,48 @@ pub fn ty_sort_string(cx: &ctxt, ty: Ty) -> String {
     }
 }
 
+impl<'tcx> type_err<'tcx> {
+    /// The provenance of the expected value, if this error carries one.
+    pub fn expected_origin(&self) -> Option<ExpectedOrigin> {
+        match *self {
+            terr_unsafety_mismatch(values) => values.origin,
+            terr_abi_mismatch(values) => values.origin,
+            terr_tuple_size(values) |
+            terr_fixed_array_size(values) |
+            terr_ty_param_size(values) => values.origin,
+            terr_tuple_size_detail(diff) => diff.sizes.origin,
+            terr_sorts(values) => values.origin,
+            terr_sorts_at(diff) => diff.values.origin,
+            terr_int_mismatch(values) => values.origin,
+            terr_float_mismatch(values) => values.origin,
+            terr_traits(values) => values.origin,
+            terr_builtin_bounds(values) => values.origin,
+            terr_variadic_mismatch(values) => values.origin,
+            terr_convergence_mismatch(values) => values.origin,
+            terr_projection_name_mismatched(values) => values.origin,
+            terr_projection_bound_mismatch(values) => values.origin,
+            terr_relation_overflow(values) => values.origin,
+            terr_length_mismatch(values) => values.origin,
+            terr_presence_mismatch(values) => values.origin,
+            terr_type_param_default(values) => values.origin,
+            terr_mismatch |
+            terr_mutability |
+            terr_box_mutability |
+            terr_ptr_mutability |
+            terr_ref_mutability |
+            terr_vec_mutability |
+            terr_arg_count |
+            terr_regions_does_not_outlive(..) |
+            terr_regions_not_same(..) |
+            terr_regions_no_overlap(..) |
+            terr_regions_insufficiently_polymorphic(..) |
+            terr_regions_overly_polymorphic(..) |
+            terr_integer_as_char |
+            terr_cyclic_ty => None,
+        }
+    }
+}
+
 /// Explains the source of a type err in a short, human readable way. This is meant to be placed
 /// in parentheses after some larger message. You should also invoke `note_and_explain_type_err()`
 /// afterwards to present additional details, particularly when it comes to lifetime-related
@@ -5255,7 +5593,7 @@ impl<'tcx> fmt::Display for type_err<'tcx> {
                        values.found)
             }
             terr_abi_mismatch(values) => {
-                write!(f, "expected {} fn, found {} fn",
+                write!(f, "expected `extern {}` fn, found `extern {}` fn",
                        values.expected,
                        values.found)
             }
@@ -5284,6 +5622,22 @@ impl<'tcx> fmt::Display for type_err<'tcx> {
                        values.expected,
                        values.found)
             }
+            terr_tuple_size_detail(diff) => {
+                try!(write!(f, "{}", terr_tuple_size(diff.sizes)));
+                let matching: Vec<_> = diff.prefix_matches.iter().enumerate()
+                                           .filter(|&(_, &m)| m)
+                                           .map(|(i, _)| i.to_string())
+                                           .collect();
+                if matching.is_empty() {
+                    Ok(())
+                } else if matching.len() == diff.prefix_matches.len() {
+                    write!(f, "; the first {} elements match", matching.len())
+                } else {
+                    write!(f, "; of the first {} elements, those at {} match",
+                           diff.prefix_matches.len(),
+                           matching.connect(", "))
+                }
+            }
             terr_arg_count => {
                 write!(f, "incorrect number of function parameters")
             }
@@ -5315,6 +5669,16 @@ impl<'tcx> fmt::Display for type_err<'tcx> {
                     write!(f, "expected {}, found {}", expected_str, found_str)
                 }
             }),
+            terr_sorts_at(diff) => {
+                try!(write!(f, "{} at ", terr_sorts(diff.values)));
+                for (i, step) in diff.path.iter().enumerate() {
+                    if i > 0 {
+                        try!(write!(f, ", "));
+                    }
+                    try!(write!(f, "{}", step));
+                }
+                Ok(())
+            }
             terr_traits(values) => tls::with(|tcx| {
                 write!(f, "expected trait `{}`, found trait `{}`",
                        item_path_str(tcx, values.expected),
@@ -5352,17 +5716,60 @@ impl<'tcx> fmt::Display for type_err<'tcx> {
                        if values.found { "variadic" } else { "non-variadic" })
             }
             terr_convergence_mismatch(ref values) => {
-                write!(f, "expected {} fn, found {} function",
-                       if values.expected { "converging" } else { "diverging" },
-                       if values.found { "converging" } else { "diverging" })
+                match (values.expected, values.found) {
+                    (FnConverging(t), FnDiverging) => {
+                        write!(f, "expected a function that returns `{}`, \
+                                   found one that never returns (`!`)", t)
+                    }
+                    (FnDiverging, FnConverging(t)) => {
+                        write!(f, "expected a function that never returns (`!`), \
+                                   found one that returns `{}`", t)
+                    }
+                    _ => write!(f, "functions differ in whether they return"),
+                }
             }
             terr_projection_name_mismatched(ref values) => {
                 write!(f, "expected {}, found {}",
                        values.expected,
                        values.found)
             }
-            terr_projection_bounds_length(ref values) => {
-                write!(f, "expected {} associated type bindings, found {}",
+            terr_projection_bound_mismatch(ref values) => {
+                match (values.expected, values.found) {
+                    (Some(expected), Some(found)) => {
+                        write!(f, "expected a binding for associated type `{}`, \
+                                   found one for `{}`", expected, found)
+                    }
+                    (Some(expected), None) => {
+                        write!(f, "expected a binding for associated type `{}`, \
+                                   found none", expected)
+                    }
+                    (None, Some(found)) => {
+                        write!(f, "found an unexpected binding for associated type `{}`",
+                               found)
+                    }
+                    (None, None) => write!(f, "associated type bindings differ"),
+                }
+            }
+            terr_relation_overflow(ref values) => {
+                write!(f, "overflow while relating `{}` and `{}`",
+                       values.expected,
+                       values.found)
+            }
+            terr_length_mismatch(ref values) => {
+                write!(f, "expected {} elements, found {}",
+                       values.expected,
+                       values.found)
+            }
+            terr_presence_mismatch(ref values) => {
+                if values.expected {
+                    write!(f, "expected a value, found none")
+                } else {
+                    write!(f, "expected no value, found one")
+                }
+            }
+            terr_type_param_default(ref values) => {
+                write!(f, "expected type parameter `{}`, found `{}`, \
+                           where one of them is a default",
                        values.expected,
                        values.found)
             }
@@ -5371,6 +5778,12 @@ impl<'tcx> fmt::Display for type_err<'tcx> {
 }
 
 pub fn note_and_explain_type_err<'tcx>(cx: &ctxt<'tcx>, err: &type_err<'tcx>, sp: Span) {
+    if let Some(ExpectedOrigin { span: Some(origin_sp), .. }) = err.expected_origin() {
+        if origin_sp != sp {
+            cx.sess.span_note(origin_sp, "the expected type comes from here");
+        }
+    }
+
     match *err {
         terr_regions_does_not_outlive(subregion, superregion) => {
             note_and_explain_region(cx, "", subregion, "...");
@@ -5401,6 +5814,45 @@ pub fn note_and_explain_type_err<'tcx>(cx: &ctxt<'tcx>, err: &type_err<'tcx>, sp
                                     "expected concrete lifetime is ",
                                     conc_region, "");
         }
+        terr_unsafety_mismatch(values) => {
+            match (values.expected, values.found) {
+                (ast::Unsafety::Normal, ast::Unsafety::Unsafe) => {
+                    cx.sess.span_help(sp, "an `unsafe fn` cannot be used where a safe fn is \
+                                           expected; consider calling it from an `unsafe {}` \
+                                           block inside a safe wrapper function, or changing \
+                                           the expected type to an `unsafe fn`");
+                }
+                (ast::Unsafety::Unsafe, ast::Unsafety::Normal) => {
+                    cx.sess.span_note(sp, "safe fns can be used where an `unsafe fn` is \
+                                           expected, but are only converted automatically \
+                                           at coercion sites, not when nested inside other \
+                                           types");
+                }
+                _ => {}
+            }
+        }
+        terr_abi_mismatch(values) => {
+            cx.sess.span_help(sp, &format!("only functions declared with `extern {}` \
+                                            can be used here",
+                                           values.expected));
+        }
+        terr_convergence_mismatch(_) => {
+            cx.sess.span_note(sp, "although a call to a diverging function can be used \
+                                   wherever a value is expected, the function itself is not \
+                                   interchangeable with one that returns a value");
+            cx.sess.span_help(sp, "consider wrapping the function in one with the expected \
+                                   signature");
+        }
+        terr_sorts_at(diff) => {
+            cx.sess.span_note(sp, &format!("the mismatched component is `{}` here, \
+                                            but `{}` was expected",
+                                           diff.values.found,
+                                           diff.values.expected));
+            // the origin, if any, has been noted already
+            let mut values = diff.values;
+            values.origin = None;
+            note_and_explain_type_err(cx, &terr_sorts(values), sp);
+        }
         terr_sorts(values) => {
             let expected_str = ty_sort_string(cx, values.expected);
             let found_str = ty_sort_string(cx, values.found);
@@ -5830,11 +6282,13 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI16)     => cx.types.i16,
             SignedInt(ast::TyI32)     => cx.types.i32,
             SignedInt(ast::TyI64)     => cx.types.i64,
+            SignedInt(ast::TyI128)    => cx.types.i128,
             SignedInt(ast::TyIs)   => cx.types.isize,
             UnsignedInt(ast::TyU8)    => cx.types.u8,
             UnsignedInt(ast::TyU16)   => cx.types.u16,
             UnsignedInt(ast::TyU32)   => cx.types.u32,
             UnsignedInt(ast::TyU64)   => cx.types.u64,
+            UnsignedInt(ast::TyU128)  => cx.types.u128,
             UnsignedInt(ast::TyUs) => cx.types.usize,
         }
     }
@@ -5844,11 +6298,13 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI8)    => val.to_i8()  .map(|v| v as Disr),
             SignedInt(ast::TyI16)   => val.to_i16() .map(|v| v as Disr),
             SignedInt(ast::TyI32)   => val.to_i32() .map(|v| v as Disr),
-            SignedInt(ast::TyI64)   => val.to_i64() .map(|v| v as Disr),
+            SignedInt(ast::TyI64) |
+            SignedInt(ast::TyI128)  => val.to_i64() .map(|v| v as Disr),
             UnsignedInt(ast::TyU8)  => val.to_u8()  .map(|v| v as Disr),
             UnsignedInt(ast::TyU16) => val.to_u16() .map(|v| v as Disr),
             UnsignedInt(ast::TyU32) => val.to_u32() .map(|v| v as Disr),
-            UnsignedInt(ast::TyU64) => val.to_u64() .map(|v| v as Disr),
+            UnsignedInt(ast::TyU64) |
+            UnsignedInt(ast::TyU128) => val.to_u64() .map(|v| v as Disr),
 
             UnsignedInt(ast::TyUs) |
             SignedInt(ast::TyIs) => unreachable!(),
@@ -5860,11 +6316,13 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI8)    => val.to_i8()  .map(|v| v as Disr),
             SignedInt(ast::TyI16)   => val.to_i16() .map(|v| v as Disr),
             SignedInt(ast::TyI32)   => val.to_i32() .map(|v| v as Disr),
-            SignedInt(ast::TyI64)   => val.to_i64() .map(|v| v as Disr),
+            SignedInt(ast::TyI64) |
+            SignedInt(ast::TyI128)  => val.to_i64() .map(|v| v as Disr),
             UnsignedInt(ast::TyU8)  => val.to_u8()  .map(|v| v as Disr),
             UnsignedInt(ast::TyU16) => val.to_u16() .map(|v| v as Disr),
             UnsignedInt(ast::TyU32) => val.to_u32() .map(|v| v as Disr),
-            UnsignedInt(ast::TyU64) => val.to_u64() .map(|v| v as Disr),
+            UnsignedInt(ast::TyU64) |
+            UnsignedInt(ast::TyU128) => val.to_u64() .map(|v| v as Disr),
 
             UnsignedInt(ast::TyUs) |
             SignedInt(ast::TyIs) => unreachable!(),
@@ -5882,12 +6340,14 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI8)    => add1!((val as i64).to_i8()),
             SignedInt(ast::TyI16)   => add1!((val as i64).to_i16()),
             SignedInt(ast::TyI32)   => add1!((val as i64).to_i32()),
-            SignedInt(ast::TyI64)   => add1!(Some(val as i64)),
+            SignedInt(ast::TyI64) |
+            SignedInt(ast::TyI128)  => add1!(Some(val as i64)),
 
             UnsignedInt(ast::TyU8)  => add1!(val.to_u8()),
             UnsignedInt(ast::TyU16) => add1!(val.to_u16()),
             UnsignedInt(ast::TyU32) => add1!(val.to_u32()),
-            UnsignedInt(ast::TyU64) => add1!(Some(val)),
+            UnsignedInt(ast::TyU64) |
+            UnsignedInt(ast::TyU128) => add1!(Some(val)),
 
             UnsignedInt(ast::TyUs) |
             SignedInt(ast::TyIs) => unreachable!(),
@@ -5902,11 +6362,13 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI8)    => format!("{}", val as i8 ),
             SignedInt(ast::TyI16)   => format!("{}", val as i16),
             SignedInt(ast::TyI32)   => format!("{}", val as i32),
-            SignedInt(ast::TyI64)   => format!("{}", val as i64),
+            SignedInt(ast::TyI64) |
+            SignedInt(ast::TyI128)  => format!("{}", val as i64),
             UnsignedInt(ast::TyU8)  => format!("{}", val as u8 ),
             UnsignedInt(ast::TyU16) => format!("{}", val as u16),
             UnsignedInt(ast::TyU32) => format!("{}", val as u32),
-            UnsignedInt(ast::TyU64) => format!("{}", val as u64),
+            UnsignedInt(ast::TyU64) |
+            UnsignedInt(ast::TyU128) => format!("{}", val as u64),
 
             UnsignedInt(ast::TyUs) |
             SignedInt(ast::TyIs) => unreachable!(),
@@ -5922,11 +6384,13 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI8)    => add1!(val as i8 ),
             SignedInt(ast::TyI16)   => add1!(val as i16),
             SignedInt(ast::TyI32)   => add1!(val as i32),
-            SignedInt(ast::TyI64)   => add1!(val as i64),
+            SignedInt(ast::TyI64) |
+            SignedInt(ast::TyI128)  => add1!(val as i64),
             UnsignedInt(ast::TyU8)  => add1!(val as u8 ),
             UnsignedInt(ast::TyU16) => add1!(val as u16),
             UnsignedInt(ast::TyU32) => add1!(val as u32),
-            UnsignedInt(ast::TyU64) => add1!(val as u64),
+            UnsignedInt(ast::TyU64) |
+            UnsignedInt(ast::TyU128) => add1!(val as u64),
 
             UnsignedInt(ast::TyUs) |
             SignedInt(ast::TyIs) => unreachable!(),
@@ -5991,6 +6455,21 @@ fn compute_enum_variants<'tcx>(cx: &ctxt<'tcx>,
 
     let (repr_type, repr_type_ty) = ty::enum_repr_type(cx, opt_hint);
 
+    // `Disr` is only 64 bits wide, so the discriminants of a 128-bit enum
+    // can neither be computed nor range-checked; number its variants in
+    // order instead, to avoid spurious errors downstream.
+    let unsupported_repr = match opt_hint {
+        Some(&attr::ReprInt(sp, int_t @ SignedInt(ast::TyI128))) |
+        Some(&attr::ReprInt(sp, int_t @ UnsignedInt(ast::TyU128))) => {
+            span_err!(cx.sess, sp, E0406,
+                      "`{}` enum discriminants are not supported; only discriminants \
+                       of at most 64 bits are",
+                      int_t.to_ty(cx));
+            true
+        }
+        _ => false,
+    };
+
     for v in vs {
         // If the discriminant value is specified explicitly in the
         // enum, check whether the initialization expression is valid,
@@ -6005,7 +6484,7 @@ fn compute_enum_variants<'tcx>(cx: &ctxt<'tcx>,
         };
 
         match v.node.disr_expr {
-            Some(ref e) => {
+            Some(ref e) if !unsupported_repr => {
                 debug!("disr expr, checking {}", pprust::expr_to_string(&**e));
 
                 // check_expr (from check_const pass) doesn't guarantee
@@ -6039,7 +6518,7 @@ fn compute_enum_variants<'tcx>(cx: &ctxt<'tcx>,
                     }
                 }
             },
-            None => {
+            _ => {
                 current_disr_val = match prev_disr_val {
                     Some(prev_disr_val) => {
                         if let Some(v) = repr_type.disr_incr(prev_disr_val) {
@@ -6511,6 +6990,41 @@ pub fn populate_implementations_for_primitive_if_necessary(tcx: &ctxt,
     tcx.populated_external_primitive_impls.borrow_mut().insert(primitive_def_id);
 }
 
+/// Returns the inherent impl that the lang items attach to the primitive type
+/// `ty` (e.g. `#[lang = "u8"] impl u8 { ... }` in libcore), if there is one.
+/// If that impl lives in another crate, its items are loaded from the
+/// metadata first, so callers can treat it like any other inherent impl.
+pub fn primitive_inherent_impl<'tcx>(tcx: &ctxt<'tcx>, ty: Ty<'tcx>) -> Option<ast::DefId> {
+    let lang_items = &tcx.lang_items;
+    let impl_def_id = match ty.sty {
+        TyChar => lang_items.char_impl(),
+        TyStr => lang_items.str_impl(),
+        TySlice(_) => lang_items.slice_impl(),
+        TyRawPtr(mt { ty: _, mutbl: ast::MutImmutable }) => lang_items.const_ptr_impl(),
+        TyRawPtr(mt { ty: _, mutbl: ast::MutMutable }) => lang_items.mut_ptr_impl(),
+        TyInt(ast::TyI8) => lang_items.i8_impl(),
+        TyInt(ast::TyI16) => lang_items.i16_impl(),
+        TyInt(ast::TyI32) => lang_items.i32_impl(),
+        TyInt(ast::TyI64) => lang_items.i64_impl(),
+        TyInt(ast::TyI128) => lang_items.i128_impl(),
+        TyInt(ast::TyIs) => lang_items.isize_impl(),
+        TyUint(ast::TyU8) => lang_items.u8_impl(),
+        TyUint(ast::TyU16) => lang_items.u16_impl(),
+        TyUint(ast::TyU32) => lang_items.u32_impl(),
+        TyUint(ast::TyU64) => lang_items.u64_impl(),
+        TyUint(ast::TyU128) => lang_items.u128_impl(),
+        TyUint(ast::TyUs) => lang_items.usize_impl(),
+        TyFloat(ast::TyF32) => lang_items.f32_impl(),
+        TyFloat(ast::TyF64) => lang_items.f64_impl(),
+        _ => None
+    };
+
+    if let Some(impl_def_id) = impl_def_id {
+        populate_implementations_for_primitive_if_necessary(tcx, impl_def_id);
+    }
+    impl_def_id
+}
+
 /// Populates the type context with all the inherent implementations for
 /// the given type if necessary.
 pub fn populate_inherent_implementations_for_type_if_necessary(tcx: &ctxt,
@@ -6634,6 +7148,19 @@ pub fn trait_of_item(tcx: &ctxt, def_id: ast::DefId) -> Option<ast::DefId> {
     }
 }
 
+/// Returns true if `origin` is a call to `IndexAssign::index_assign`. Such
+/// a call is recorded for the index expression on the left-hand side of
+/// `container[index] = value`, which then does not denote an lvalue.
+pub fn is_index_assign_method(tcx: &ctxt, origin: &MethodOrigin) -> bool {
+    let trait_def_id = match *origin {
+        MethodStatic(def_id) => trait_of_item(tcx, def_id),
+        MethodTypeParam(ref param) => Some(param.trait_ref.def_id),
+        MethodTraitObject(ref object) => Some(object.trait_ref.def_id),
+        MethodStaticClosure(_) => None,
+    };
+    trait_def_id.is_some() && trait_def_id == tcx.lang_items.index_assign_trait()
+}
+
 /// If the given def ID describes an item belonging to a trait, (either a
 /// default method or an implementation of a trait method), return the ID of
 /// the method inside trait definition (this means that if the given def ID
@@ -7143,6 +7670,73 @@ impl<'tcx> AutoDerefRef<'tcx> {
     }
 }
 
+/// Builds an `AutoDerefRef` one step at a time, in the order in which the
+/// steps are applied, checking that each step fits with the ones before it.
+/// Adjustments should be constructed through this rather than by hand, so
+/// that new kinds of steps only need their invariants enforced here.
+#[derive(Copy, Clone)]
+pub struct AdjustmentBuilder<'tcx> {
+    adjustment: AutoDerefRef<'tcx>,
+}
+
+impl<'tcx> AdjustmentBuilder<'tcx> {
+    /// Starts an adjustment that dereferences its operand `autoderefs` times.
+    pub fn autoderef(autoderefs: usize) -> AdjustmentBuilder<'tcx> {
+        AdjustmentBuilder {
+            adjustment: AutoDerefRef {
+                autoderefs: autoderefs,
+                autoref: None,
+                unsize: None
+            }
+        }
+    }
+
+    /// Takes a reference or raw pointer to the dereferenced lvalue.
+    pub fn autoref(mut self, autoref: AutoRef<'tcx>) -> AdjustmentBuilder<'tcx> {
+        assert!(self.adjustment.autoref.is_none(), "AdjustmentBuilder: autoref applied twice");
+        assert!(self.adjustment.unsize.is_none(), "AdjustmentBuilder: autoref after unsizing");
+        self.adjustment.autoref = Some(autoref);
+        self
+    }
+
+    pub fn opt_autoref(self, autoref: Option<AutoRef<'tcx>>) -> AdjustmentBuilder<'tcx> {
+        match autoref {
+            Some(autoref) => self.autoref(autoref),
+            None => self
+        }
+    }
+
+    /// Unsizes the value to the pointer type `target`. After an autoref,
+    /// `target` must be the same kind of pointer, with the same mutability.
+    pub fn unsize(mut self, target: Ty<'tcx>) -> AdjustmentBuilder<'tcx> {
+        assert!(self.adjustment.unsize.is_none(), "AdjustmentBuilder: unsized twice");
+        if let Some(autoref) = self.adjustment.autoref {
+            let compatible = match (autoref, &target.sty) {
+                (AutoPtr(_, mutbl), &TyRef(_, mt)) |
+                (AutoUnsafe(mutbl), &TyRawPtr(mt)) => mutbl == mt.mutbl,
+                _ => false
+            };
+            assert!(compatible,
+                    "AdjustmentBuilder: unsize target `{:?}` does not match autoref `{:?}`",
+                    target, autoref);
+        }
+        self.adjustment.unsize = Some(target);
+        self
+    }
+
+    /// Unsizes a method receiver to `target`. Method dispatch only unsizes
+    /// behind an autoref, e.g. `[T; N]` to `&[T]`.
+    pub fn unsize_receiver(self, target: Ty<'tcx>) -> AdjustmentBuilder<'tcx> {
+        assert!(self.adjustment.autoref.is_some(),
+                "AdjustmentBuilder: method receiver unsized without an autoref");
+        self.unsize(target)
+    }
+
+    pub fn build(self) -> AutoDerefRef<'tcx> {
+        self.adjustment
+    }
+}
+
 /// Replace any late-bound regions bound in `value` with free variants attached to scope-id
 /// `scope_id`.
 pub fn liberate_late_bound_regions<'tcx, T>(
@@ -0,0 +1,123 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Equality of values up to renaming of late-bound regions.
+//!
+//! `for<'a> fn(&'a u8)` and `for<'b> fn(&'b u8)` are the same type, but
+//! as the bound regions carry different names they do not compare equal
+//! with `==`. `AlphaEq` pairs up the regions bound by each binder as it
+//! walks the two values, and requires that the pairing be one-to-one.
+//! Everything else, including free regions, must be identical. No
+//! inference context is involved.
+
+use middle::ty::{self, Ty};
+use middle::ty_relate::{self, Relate, RelateResult, TypeRelation};
+
+pub struct AlphaEq<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+
+    /// For each binder entered, innermost last, the pairs of bound
+    /// regions found to correspond so far.
+    binders: Vec<Vec<(ty::BoundRegion, ty::BoundRegion)>>,
+}
+
+impl<'a, 'tcx> AlphaEq<'a, 'tcx> {
+    pub fn new(tcx: &'a ty::ctxt<'tcx>) -> AlphaEq<'a, 'tcx> {
+        AlphaEq { tcx: tcx, binders: Vec::new() }
+    }
+
+    /// Records that `a` and `b` are bound by the binder `depth` levels
+    /// out, failing if either has already been paired with another region.
+    fn pair_bound_regions(&mut self,
+                          depth: ty::DebruijnIndex,
+                          a: ty::BoundRegion,
+                          b: ty::BoundRegion)
+                          -> bool {
+        let index = match self.binders.len().checked_sub(depth.depth as usize) {
+            Some(index) => index,
+            None => {
+                // a region bound outside of the values being related;
+                // only identical ones correspond
+                return a == b;
+            }
+        };
+        let pairs = &mut self.binders[index];
+        let existing = pairs.iter().find(|&&(pa, pb)| pa == a || pb == b).cloned();
+        match existing {
+            Some((pa, pb)) => pa == a && pb == b,
+            None => {
+                pairs.push((a, b));
+                true
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx> TypeRelation<'a, 'tcx> for AlphaEq<'a, 'tcx> {
+    fn tag(&self) -> &'static str { "AlphaEq" }
+
+    fn tcx(&self) -> &'a ty::ctxt<'tcx> { self.tcx }
+
+    fn a_is_expected(&self) -> bool { true }
+
+    fn will_change(&mut self, a: bool, b: bool) -> bool {
+        a || b
+    }
+
+    fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
+                                               _: ty::Variance,
+                                               a: &T,
+                                               b: &T)
+                                               -> RelateResult<'tcx, T>
+    {
+        self.relate(a, b)
+    }
+
+    fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        // Identical types are only equivalent if no region in them is
+        // bound by a binder we have already paired differently.
+        if a == b && !ty::type_has_escaping_regions(a) {
+            return Ok(a);
+        }
+        ty_relate::super_relate_tys(self, a, b)
+    }
+
+    fn regions(&mut self, a: ty::Region, b: ty::Region) -> RelateResult<'tcx, ty::Region> {
+        debug!("{}.regions({:?}, {:?})", self.tag(), a, b);
+        let same = match (a, b) {
+            (ty::ReLateBound(a_depth, a_br), ty::ReLateBound(b_depth, b_br)) => {
+                a_depth == b_depth && self.pair_bound_regions(a_depth, a_br, b_br)
+            }
+            _ => a == b,
+        };
+        if same {
+            Ok(a)
+        } else {
+            Err(ty::terr_regions_not_same(a, b))
+        }
+    }
+
+    fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
+                  -> RelateResult<'tcx, ty::Binder<T>>
+        where T: Relate<'a, 'tcx>
+    {
+        self.binders.push(Vec::new());
+        let result = self.relate(a.skip_binder(), b.skip_binder());
+        self.binders.pop();
+        Ok(ty::Binder(try!(result)))
+    }
+}
+
+/// Whether `a` and `b` are equal up to renaming of late-bound regions.
+pub fn alpha_eq<'a, 'tcx, T>(tcx: &'a ty::ctxt<'tcx>, a: &T, b: &T) -> bool
+    where T: Relate<'a, 'tcx>
+{
+    AlphaEq::new(tcx).relate(a, b).is_ok()
+}
@@ -0,0 +1,278 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Least upper bounds and greatest lower bounds of fully resolved types.
+//!
+//! The combiners in `infer` compute LUBs and GLBs by creating fresh
+//! type and region variables and leaving the real work to region
+//! inference. The relations here need no inference context: they only
+//! accept types without inference variables, and combine concrete
+//! regions directly with the region inference lattice operations.
+//!
+//! Higher-ranked types are handled conservatively: the bound regions of
+//! two binders must match exactly, rather than being generalized as in
+//! `infer::higher_ranked`.
+
+use middle::free_region::FreeRegionMap;
+use middle::infer::region_inference;
+use middle::ty::{self, Region, Ty};
+use middle::ty::{ReEarlyBound, ReLateBound};
+use middle::ty_relate::{self, Relate, RelateResult, TypeRelation};
+
+/// The direction-specific parts of a lattice relation.
+pub trait LatticeDir<'a,'tcx> : TypeRelation<'a,'tcx> {
+    /// Combines two concrete regions in the direction of this relation.
+    fn combine_regions(&self, a: Region, b: Region) -> RelateResult<'tcx, Region>;
+
+    /// Relates `a` and `b` in the opposite direction, as needed for
+    /// contravariant positions.
+    fn relate_opposite<T:Relate<'a,'tcx>>(&mut self, a: &T, b: &T) -> RelateResult<'tcx, T>;
+}
+
+/// Handles `relate_with_variance` for any lattice relation. Invariant
+/// positions must be equal; bivariant ones are treated as covariant.
+pub fn super_lattice_relate_with_variance<'a,'tcx,L,T>(this: &mut L,
+                                                      variance: ty::Variance,
+                                                      a: &T,
+                                                      b: &T)
+                                                      -> RelateResult<'tcx, T>
+    where 'tcx: 'a, L: LatticeDir<'a,'tcx>, T: Relate<'a,'tcx>
+{
+    match variance {
+        ty::Invariant => Equal { tcx: this.tcx(), a_is_expected: this.a_is_expected() }
+                             .relate(a, b),
+        ty::Covariant | ty::Bivariant => this.relate(a, b),
+        ty::Contravariant => this.relate_opposite(a, b),
+    }
+}
+
+/// "Least upper bound" (common supertype) of two resolved types.
+pub struct Lub<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    free_regions: &'a FreeRegionMap,
+    a_is_expected: bool,
+}
+
+impl<'a, 'tcx> Lub<'a, 'tcx> {
+    pub fn new(tcx: &'a ty::ctxt<'tcx>, free_regions: &'a FreeRegionMap) -> Lub<'a, 'tcx> {
+        Lub { tcx: tcx, free_regions: free_regions, a_is_expected: true }
+    }
+}
+
+impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Lub<'a, 'tcx> {
+    fn tag(&self) -> &'static str { "lattice::Lub" }
+
+    fn tcx(&self) -> &'a ty::ctxt<'tcx> { self.tcx }
+
+    fn a_is_expected(&self) -> bool { self.a_is_expected }
+
+    fn will_change(&mut self, a: bool, b: bool) -> bool {
+        // result will be 'static if a || b
+        a || b
+    }
+
+    fn builtin_bounds(&mut self, a: ty::BuiltinBounds, b: ty::BuiltinBounds)
+                      -> RelateResult<'tcx, ty::BuiltinBounds> {
+        Ok(ty_relate::lub_builtin_bounds(a, b))
+    }
+
+    fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
+                                               variance: ty::Variance,
+                                               a: &T,
+                                               b: &T)
+                                               -> RelateResult<'tcx, T>
+    {
+        super_lattice_relate_with_variance(self, variance, a, b)
+    }
+
+    fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        if a == b { return Ok(a); }
+        ty_relate::super_relate_tys(self, a, b)
+    }
+
+    fn regions(&mut self, a: Region, b: Region) -> RelateResult<'tcx, Region> {
+        debug!("{}.regions({:?}, {:?})", self.tag(), a, b);
+        self.combine_regions(a, b)
+    }
+
+    fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
+                  -> RelateResult<'tcx, ty::Binder<T>>
+        where T: Relate<'a, 'tcx>
+    {
+        Ok(ty::Binder(try!(self.relate(a.skip_binder(), b.skip_binder()))))
+    }
+}
+
+impl<'a, 'tcx> LatticeDir<'a, 'tcx> for Lub<'a, 'tcx> {
+    fn combine_regions(&self, a: Region, b: Region) -> RelateResult<'tcx, Region> {
+        lub_regions(self.tcx, self.free_regions, a, b)
+    }
+
+    fn relate_opposite<T:Relate<'a,'tcx>>(&mut self, a: &T, b: &T) -> RelateResult<'tcx, T> {
+        Glb { tcx: self.tcx, free_regions: self.free_regions, a_is_expected: self.a_is_expected }
+            .relate(a, b)
+    }
+}
+
+/// "Greatest lower bound" (common subtype) of two resolved types.
+pub struct Glb<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    free_regions: &'a FreeRegionMap,
+    a_is_expected: bool,
+}
+
+impl<'a, 'tcx> Glb<'a, 'tcx> {
+    pub fn new(tcx: &'a ty::ctxt<'tcx>, free_regions: &'a FreeRegionMap) -> Glb<'a, 'tcx> {
+        Glb { tcx: tcx, free_regions: free_regions, a_is_expected: true }
+    }
+}
+
+impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Glb<'a, 'tcx> {
+    fn tag(&self) -> &'static str { "lattice::Glb" }
+
+    fn tcx(&self) -> &'a ty::ctxt<'tcx> { self.tcx }
+
+    fn a_is_expected(&self) -> bool { self.a_is_expected }
+
+    fn will_change(&mut self, a: bool, b: bool) -> bool {
+        // as in `infer::glb`, conservatively assume the result may be
+        // affected if either side changes
+        a || b
+    }
+
+    fn builtin_bounds(&mut self, a: ty::BuiltinBounds, b: ty::BuiltinBounds)
+                      -> RelateResult<'tcx, ty::BuiltinBounds> {
+        Ok(ty_relate::glb_builtin_bounds(a, b))
+    }
+
+    fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
+                                               variance: ty::Variance,
+                                               a: &T,
+                                               b: &T)
+                                               -> RelateResult<'tcx, T>
+    {
+        super_lattice_relate_with_variance(self, variance, a, b)
+    }
+
+    fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        if a == b { return Ok(a); }
+        ty_relate::super_relate_tys(self, a, b)
+    }
+
+    fn regions(&mut self, a: Region, b: Region) -> RelateResult<'tcx, Region> {
+        debug!("{}.regions({:?}, {:?})", self.tag(), a, b);
+        self.combine_regions(a, b)
+    }
+
+    fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
+                  -> RelateResult<'tcx, ty::Binder<T>>
+        where T: Relate<'a, 'tcx>
+    {
+        Ok(ty::Binder(try!(self.relate(a.skip_binder(), b.skip_binder()))))
+    }
+}
+
+impl<'a, 'tcx> LatticeDir<'a, 'tcx> for Glb<'a, 'tcx> {
+    fn combine_regions(&self, a: Region, b: Region) -> RelateResult<'tcx, Region> {
+        glb_regions(self.tcx, self.free_regions, a, b)
+    }
+
+    fn relate_opposite<T:Relate<'a,'tcx>>(&mut self, a: &T, b: &T) -> RelateResult<'tcx, T> {
+        Lub { tcx: self.tcx, free_regions: self.free_regions, a_is_expected: self.a_is_expected }
+            .relate(a, b)
+    }
+}
+
+/// Used for invariant positions: the two sides must be identical.
+struct Equal<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    a_is_expected: bool,
+}
+
+impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Equal<'a, 'tcx> {
+    fn tag(&self) -> &'static str { "lattice::Equal" }
+
+    fn tcx(&self) -> &'a ty::ctxt<'tcx> { self.tcx }
+
+    fn a_is_expected(&self) -> bool { self.a_is_expected }
+
+    fn will_change(&mut self, a: bool, b: bool) -> bool {
+        a || b
+    }
+
+    fn relate_with_variance<T:Relate<'a,'tcx>>(&mut self,
+                                               _: ty::Variance,
+                                               a: &T,
+                                               b: &T)
+                                               -> RelateResult<'tcx, T>
+    {
+        self.relate(a, b)
+    }
+
+    fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        if a == b { return Ok(a); }
+        ty_relate::super_relate_tys(self, a, b)
+    }
+
+    fn regions(&mut self, a: Region, b: Region) -> RelateResult<'tcx, Region> {
+        if a == b {
+            Ok(a)
+        } else {
+            Err(ty::terr_regions_not_same(a, b))
+        }
+    }
+
+    fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
+                  -> RelateResult<'tcx, ty::Binder<T>>
+        where T: Relate<'a, 'tcx>
+    {
+        Ok(ty::Binder(try!(self.relate(a.skip_binder(), b.skip_binder()))))
+    }
+}
+
+/// The smallest region enclosing both `a` and `b`. Concrete regions are
+/// combined as in region inference; bound regions are only related to
+/// themselves.
+pub fn lub_regions<'tcx>(tcx: &ty::ctxt<'tcx>,
+                         free_regions: &FreeRegionMap,
+                         a: Region,
+                         b: Region)
+                         -> RelateResult<'tcx, Region> {
+    match (a, b) {
+        _ if a == b => Ok(a),
+
+        (ReLateBound(..), _) | (_, ReLateBound(..)) |
+        (ReEarlyBound(..), _) | (_, ReEarlyBound(..)) => {
+            Err(ty::terr_regions_not_same(a, b))
+        }
+
+        _ => Ok(region_inference::lub_concrete_regions(tcx, free_regions, a, b))
+    }
+}
+
+/// The largest region enclosed by both `a` and `b`, if there is one.
+/// Concrete regions are combined as in region inference; bound regions
+/// are only related to themselves.
+pub fn glb_regions<'tcx>(tcx: &ty::ctxt<'tcx>,
+                         free_regions: &FreeRegionMap,
+                         a: Region,
+                         b: Region)
+                         -> RelateResult<'tcx, Region> {
+    match (a, b) {
+        _ if a == b => Ok(a),
+
+        (ReLateBound(..), _) | (_, ReLateBound(..)) |
+        (ReEarlyBound(..), _) | (_, ReEarlyBound(..)) => {
+            Err(ty::terr_regions_not_same(a, b))
+        }
+
+        _ => region_inference::glb_concrete_regions(tcx, free_regions, a, b)
+    }
+}
@@ -13,20 +13,183 @@
 //! can be other things. Examples of type relations are subtyping,
 //! type equality, etc.
 
-use middle::subst::{ErasedRegions, NonerasedRegions, ParamSpace, Substs};
+use middle::subst::{ErasedRegions, NonerasedRegions, ParamSpace, Subst, Substs};
 use middle::ty::{self, Ty};
 use middle::ty_fold::TypeFoldable;
+use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::iter::repeat;
 use std::rc::Rc;
 use syntax::abi;
 use syntax::ast;
+use syntax::codemap::Span;
+use util::nodemap::FnvHashMap;
+
+pub mod alpha_eq;
+pub mod lattice;
 
 pub type RelateResult<'tcx, T> = Result<T, ty::type_err<'tcx>>;
 
 #[derive(Clone, Debug)]
 pub enum Cause {
     ExistentialRegionBound(bool), // if true, this is a default, else explicit
+    InvariantPosition(InvarianceReason), // relating an invariant position
+}
+
+/// Records why a position is being related invariantly. When a region
+/// error arises from the equality constraints generated for such a
+/// position, this lets error reporting explain where the invariance
+/// came from rather than just saying that two lifetimes differ.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InvarianceReason {
+    /// The referent of a `&mut T`.
+    MutRef,
+
+    /// The referent of a `*mut T`.
+    MutRawPtr,
+
+    /// The type parameter with the given index in the given space of
+    /// the item, e.g. the `T` in `Cell<T>`.
+    TypeParam(ast::DefId, ParamSpace, usize),
+
+    /// The region parameter with the given index in the given space
+    /// of the item.
+    RegionParam(ast::DefId, ParamSpace, usize),
+}
+
+/// The reasons for the invariant positions among `causes`, outermost
+/// first, i.e. the chain of positions that made the innermost one
+/// invariant.
+pub fn invariance_reasons(causes: &[Cause]) -> Vec<InvarianceReason> {
+    causes.iter().filter_map(|cause| {
+        match *cause {
+            Cause::InvariantPosition(reason) => Some(reason),
+            Cause::ExistentialRegionBound(_) => None,
+        }
+    }).collect()
+}
+
+/// Receives callbacks as a relation walks two types, so that the full
+/// tree of sub-relations leading to a failure can be recorded. A
+/// relation hands one out from `TypeRelation::observer`.
+pub trait RelateObserver<'tcx> {
+    /// Called by `super_relate_tys` before it relates `a` and `b`.
+    fn on_enter_tys(&mut self, _a: Ty<'tcx>, _b: Ty<'tcx>) {}
+
+    /// Called by `super_relate_tys` once `a` and `b` have been related.
+    fn on_exit_tys(&mut self,
+                   _a: Ty<'tcx>,
+                   _b: Ty<'tcx>,
+                   _result: &RelateResult<'tcx, Ty<'tcx>>) {}
+
+    /// Called by `relate_substs` before relating a parameter with the
+    /// given variance.
+    fn on_variance_switch(&mut self, _variance: ty::Variance) {}
+}
+
+/// An observer that prints the tree of types being related, one line per
+/// pair indented by its depth, along with any failures. The combiners in
+/// `infer` use one under `-Z trace-relations`.
+#[derive(Clone)]
+pub struct RelationTracer {
+    depth: usize,
+}
+
+impl RelationTracer {
+    pub fn new() -> RelationTracer {
+        RelationTracer { depth: 0 }
+    }
+
+    fn indent(&self) -> String {
+        repeat("  ").take(self.depth).collect()
+    }
+}
+
+impl<'tcx> RelateObserver<'tcx> for RelationTracer {
+    fn on_enter_tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) {
+        println!("{}relate `{}` and `{}`", self.indent(), a, b);
+        self.depth += 1;
+    }
+
+    fn on_exit_tys(&mut self,
+                   a: Ty<'tcx>,
+                   b: Ty<'tcx>,
+                   result: &RelateResult<'tcx, Ty<'tcx>>) {
+        self.depth -= 1;
+        if let Err(ref err) = *result {
+            println!("{}failed to relate `{}` and `{}`: {}", self.indent(), a, b, err);
+        }
+    }
+}
+
+/// Remembers the results of relating pairs of types, so that a type
+/// which occurs many times within the values being related is only
+/// walked once. Entries are keyed by the relation's tag, which also
+/// fixes the variance it relates under. Only successful results are
+/// stored; the owner must `clear` the cache whenever the state the
+/// results depend on (e.g. inference variables) is rolled back.
+pub struct RelationCache<'tcx> {
+    map: RefCell<FnvHashMap<(&'static str, Ty<'tcx>, Ty<'tcx>), Ty<'tcx>>>,
+}
+
+impl<'tcx> RelationCache<'tcx> {
+    pub fn new() -> RelationCache<'tcx> {
+        RelationCache { map: RefCell::new(FnvHashMap()) }
+    }
+
+    pub fn get(&self, tag: &'static str, a: Ty<'tcx>, b: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        self.map.borrow().get(&(tag, a, b)).cloned()
+    }
+
+    pub fn insert(&self, tag: &'static str, a: Ty<'tcx>, b: Ty<'tcx>, result: Ty<'tcx>) {
+        self.map.borrow_mut().insert((tag, a, b), result);
+    }
+
+    pub fn clear(&self) {
+        self.map.borrow_mut().clear();
+    }
+}
+
+/// The pairs of types a relation is in the middle of relating, outermost
+/// first. Lets `super_relate_tys` give up with `terr_relation_overflow`
+/// once the relation nests too deeply, rather than overflowing the stack.
+pub struct RelationStack<'tcx> {
+    pairs: RefCell<Vec<(&'static str, Ty<'tcx>, Ty<'tcx>)>>,
+}
+
+impl<'tcx> RelationStack<'tcx> {
+    pub fn new() -> RelationStack<'tcx> {
+        RelationStack { pairs: RefCell::new(Vec::new()) }
+    }
+
+    /// Records that relating `a` and `b` has begun. Returns false, without
+    /// recording anything, if `limit` pairs are already being related.
+    pub fn enter(&self,
+                 limit: usize,
+                 tag: &'static str,
+                 a: Ty<'tcx>,
+                 b: Ty<'tcx>)
+                 -> bool {
+        let mut pairs = self.pairs.borrow_mut();
+        if pairs.len() >= limit {
+            return false;
+        }
+        pairs.push((tag, a, b));
+        true
+    }
+
+    /// Records that relating the innermost pair has finished.
+    pub fn exit(&self) {
+        self.pairs.borrow_mut().pop();
+    }
 }
 
+/// How many levels of type nesting the relations allow per unit of the
+/// crate's `recursion_limit`. Every type constructor is a level, so types
+/// built from long chains of adaptors nest far deeper than the recursive
+/// operations the limit was chosen for.
+pub const RELATION_DEPTH_PER_RECURSION_LIMIT: usize = 16;
+
 pub trait TypeRelation<'a,'tcx> : Sized {
     fn tcx(&self) -> &'a ty::ctxt<'tcx>;
 
@@ -37,12 +200,41 @@ pub trait TypeRelation<'a,'tcx> : Sized {
     /// relation. Just affects error messages.
     fn a_is_expected(&self) -> bool;
 
+    /// Runs `f` with `cause` pushed onto the relation's cause stack,
+    /// popping it again afterwards.
     fn with_cause<F,R>(&mut self, _cause: Cause, f: F) -> R
         where F: FnOnce(&mut Self) -> R
     {
         f(self)
     }
 
+    /// Runs `f`, then undoes whatever it did to the state the relation
+    /// works on, so a relation can find out whether two values could be
+    /// related without committing to it. Relations that keep no such
+    /// state have nothing to undo.
+    fn probe<F>(&mut self, f: F) -> bool
+        where F: FnOnce(&mut Self) -> bool
+    {
+        f(self)
+    }
+
+    /// The causes of the position currently being related, outermost
+    /// first. Relations that do not track causes report none.
+    fn causes(&self) -> &[Cause] {
+        &[]
+    }
+
+    /// Where the expected side of this relation was written, if known.
+    /// Recorded in the `expected_found` of any error the relation reports.
+    fn expected_span(&self) -> Option<Span> {
+        None
+    }
+
+    /// The item the expected side of this relation comes from, if known.
+    fn expected_def_id(&self) -> Option<ast::DefId> {
+        None
+    }
+
     /// Hack for deciding whether the lifetime bound defaults change
     /// will be a breaking change or not. The bools indicate whether
     /// `a`/`b` have a default that will change to `'static`; the
@@ -50,6 +242,119 @@ pub trait TypeRelation<'a,'tcx> : Sized {
     /// relating `a` and `b`.
     fn will_change(&mut self, a: bool, b: bool) -> bool;
 
+    /// Called when relating one component of a composite value (a tuple
+    /// element, fn argument or type parameter) fails. Returning `Err`
+    /// stops the whole relation, which is the default. A relation that
+    /// wants to report every mismatch at once can record `err` and
+    /// return `Ok(())`, in which case relating continues with the
+    /// remaining components.
+    fn on_error(&mut self, err: ty::type_err<'tcx>) -> RelateResult<'tcx, ()> {
+        Err(err)
+    }
+
+    /// Consulted by `super_relate_tys` before it compares two integral or
+    /// floating-point types, so that a relation can accept pairs of
+    /// distinct numeric types (e.g. lossless widening). Returning `None`
+    /// falls back to requiring the two types to be the same.
+    fn relate_numeric(&mut self, _a: Ty<'tcx>, _b: Ty<'tcx>)
+                      -> Option<RelateResult<'tcx, Ty<'tcx>>> {
+        None
+    }
+
+    /// Relates the builtin bounds of two object types. By default they
+    /// must be identical, which is also what invariant positions need;
+    /// subtyping and lattice relations override this to accept a superset
+    /// or to take the union or intersection of the two sets.
+    fn builtin_bounds(&mut self, a: ty::BuiltinBounds, b: ty::BuiltinBounds)
+                      -> RelateResult<'tcx, ty::BuiltinBounds> {
+        if a != b {
+            Err(ty::terr_builtin_bounds(expected_found(self, &a, &b)))
+        } else {
+            Ok(a)
+        }
+    }
+
+    /// Whether functions with the ABIs `a` and `b` may be related; the
+    /// related function keeps the ABI of `a`. By default they must be the
+    /// same ABI.
+    fn abi_compat(&self, a: abi::Abi, b: abi::Abi) -> bool {
+        a == b
+    }
+
+    /// Whether a non-variadic fn signature `a` may be related to a
+    /// variadic signature `b` whose fixed parameters relate to a prefix of
+    /// the parameters of `a`, the rest of which `b` would accept as
+    /// variadic arguments. Only sound for relations in which `a` is the
+    /// subtype, e.g. when checking a wrapper against a C signature.
+    fn variadic_subtyping(&self) -> bool {
+        false
+    }
+
+    /// Whether an array type `[T; N]` may be related to a slice type `[U]`
+    /// by `unsize`, as an unsizing coercion would. A struct whose last
+    /// type parameter is unsized this way, e.g. `Rc<[T; N]>` and
+    /// `Rc<[T]>`, then relates through its substitutions.
+    fn relates_unsized(&self) -> bool {
+        false
+    }
+
+    /// Relates the array type `a` to the slice type `b` when
+    /// `relates_unsized` is true. By default the element types are
+    /// related and the result is the slice type.
+    fn unsize(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        match (&a.sty, &b.sty) {
+            (&ty::TyArray(a_t, _), &ty::TySlice(b_t)) => {
+                let t = try!(self.relate(&a_t, &b_t));
+                Ok(ty::mk_vec(self.tcx(), t, None))
+            }
+            _ => Err(ty::terr_sorts(expected_found(self, &a, &b))),
+        }
+    }
+
+    /// Whether closure types with different def-ids may be related by
+    /// comparing their kinds, signatures and upvar types, rather than
+    /// always being considered distinct.
+    fn relate_closures_structurally(&self) -> bool {
+        false
+    }
+
+    /// Where to look up closure kinds, signatures and upvars when
+    /// `relate_closures_structurally` is true. The default reads the
+    /// tables in the `ty::ctxt`, which are only filled in once type
+    /// checking of the enclosing function is done, and treats closures
+    /// missing from them as distinct; relations used during type checking
+    /// should supply the function's own tables.
+    fn closure_typer(&self) -> Option<&ty::ClosureTyper<'tcx>> {
+        None
+    }
+
+    /// The observer to notify as types and substitutions are related,
+    /// if any.
+    fn observer(&mut self) -> Option<&mut RelateObserver<'tcx>> {
+        None
+    }
+
+    /// The cache consulted by `relate` before relating two types, if
+    /// any. A relation should only supply one while relating a pair of
+    /// types always has the same outcome and side effects as the first
+    /// time round.
+    fn relation_cache(&self) -> Option<&RelationCache<'tcx>> {
+        None
+    }
+
+    /// How many pairs of types may be related inside one another before
+    /// `super_relate_tys` gives up. Only enforced for relations that
+    /// supply a `relation_stack`.
+    fn recursion_limit(&self) -> usize {
+        self.tcx().sess.recursion_limit.get() * RELATION_DEPTH_PER_RECURSION_LIMIT
+    }
+
+    /// Where `super_relate_tys` records the pairs of types being related,
+    /// if anywhere.
+    fn relation_stack(&self) -> Option<&RelationStack<'tcx>> {
+        None
+    }
+
     /// Generic relation routine suitable for most anything.
     fn relate<T:Relate<'a,'tcx>>(&mut self, a: &T, b: &T) -> RelateResult<'tcx, T> {
         Relate::relate(self, a, b)
@@ -74,6 +379,17 @@ pub trait TypeRelation<'a,'tcx> : Sized {
     fn regions(&mut self, a: ty::Region, b: ty::Region)
                -> RelateResult<'tcx, ty::Region>;
 
+    /// Relates two constant values, such as the lengths of two array
+    /// types. Constants have no variance, so unless a relation
+    /// overrides this they must be equal.
+    fn consts(&mut self, a: usize, b: usize) -> RelateResult<'tcx, usize> {
+        if a == b {
+            Ok(a)
+        } else {
+            Err(ty::terr_fixed_array_size(expected_found(self, &a, &b)))
+        }
+    }
+
     fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
                   -> RelateResult<'tcx, ty::Binder<T>>
         where T: Relate<'a,'tcx>;
@@ -96,29 +412,99 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::mt<'tcx> {
                  -> RelateResult<'tcx, ty::mt<'tcx>>
         where R: TypeRelation<'a,'tcx>
     {
-        debug!("{}.mts({:?}, {:?})",
-               relation.tag(),
-               a,
-               b);
-        if a.mutbl != b.mutbl {
-            Err(ty::terr_mutability)
-        } else {
-            let mutbl = a.mutbl;
-            let variance = match mutbl {
-                ast::MutImmutable => ty::Covariant,
-                ast::MutMutable => ty::Invariant,
-            };
-            let ty = try!(relation.relate_with_variance(variance, &a.ty, &b.ty));
-            Ok(ty::mt {ty: ty, mutbl: mutbl})
+        relate_mt(relation, InvarianceReason::MutRef, a, b)
+    }
+}
+
+fn relate_mt<'a,'tcx:'a,R>(relation: &mut R,
+                           reason: InvarianceReason,
+                           a: &ty::mt<'tcx>,
+                           b: &ty::mt<'tcx>)
+                           -> RelateResult<'tcx, ty::mt<'tcx>>
+    where R: TypeRelation<'a,'tcx>
+{
+    debug!("{}.mts({:?}, {:?})",
+           relation.tag(),
+           a,
+           b);
+    if a.mutbl != b.mutbl {
+        Err(ty::terr_mutability)
+    } else {
+        let mutbl = a.mutbl;
+        let variance = match mutbl {
+            ast::MutImmutable => ty::Covariant,
+            ast::MutMutable => ty::Invariant,
+        };
+        let ty = try!(relate_with_variance_reason(relation, variance, reason, &a.ty, &b.ty));
+        Ok(ty::mt {ty: ty, mutbl: mutbl})
+    }
+}
+
+/// Like `relation.relate_with_variance()`, but if `variance` is
+/// invariant, records `reason` as the cause while relating `a` and `b`.
+fn relate_with_variance_reason<'a,'tcx:'a,R,T>(relation: &mut R,
+                                               variance: ty::Variance,
+                                               reason: InvarianceReason,
+                                               a: &T,
+                                               b: &T)
+                                               -> RelateResult<'tcx, T>
+    where R: TypeRelation<'a,'tcx>, T: Relate<'a,'tcx>
+{
+    if variance == ty::Invariant {
+        relation.with_cause(Cause::InvariantPosition(reason),
+                            |relation| relation.relate_with_variance(variance, a, b))
+    } else {
+        relation.relate_with_variance(variance, a, b)
+    }
+}
+
+/// Relates `a` with `b` as one component of a composite value, giving
+/// `relation.on_error` the chance to carry on past a mismatch; `a`
+/// stands in for the result of a component that failed. `step` says
+/// which component this is, and is recorded in any `terr_sorts` error.
+fn relate_component<'a,'tcx:'a,R,F,T>(relation: &mut R,
+                                      step: ty::TypeDiffStep,
+                                      a: &T,
+                                      f: F)
+                                      -> RelateResult<'tcx, T>
+    where R: TypeRelation<'a,'tcx>, F: FnOnce(&mut R) -> RelateResult<'tcx, T>, T: Clone
+{
+    match f(relation) {
+        Ok(v) => Ok(v),
+        Err(err) => {
+            let err = push_diff_step(relation.tcx(), step, err);
+            try!(relation.on_error(err));
+            Ok(a.clone())
         }
     }
 }
 
+/// Prepends `step` to the path of a sort mismatch, turning a plain
+/// `terr_sorts` into a `terr_sorts_at`. Other errors pass through.
+fn push_diff_step<'tcx>(tcx: &ty::ctxt<'tcx>,
+                        step: ty::TypeDiffStep,
+                        err: ty::type_err<'tcx>)
+                        -> ty::type_err<'tcx> {
+    let (values, rest) = match err {
+        ty::terr_sorts(values) => (values, &[][..]),
+        ty::terr_sorts_at(diff) => (diff.values, &diff.path[..]),
+        err => return err,
+    };
+    let mut path = Vec::with_capacity(rest.len() + 1);
+    path.push(step);
+    path.extend(rest.iter().cloned());
+    ty::terr_sorts_at(tcx.mk_type_diff(ty::TypeDiff { values: values, path: path }))
+}
+
 // substitutions are not themselves relatable without more context,
 // but they is an important subroutine for things that ARE relatable,
 // like traits etc.
+//
+// If `generics` is given, type parameters that one side leaves out are
+// filled in from their defaults before relating.
 fn relate_item_substs<'a,'tcx:'a,R>(relation: &mut R,
                                     item_def_id: ast::DefId,
+                                    generics: Option<&ty::Generics<'tcx>>,
                                     a_subst: &Substs<'tcx>,
                                     b_subst: &Substs<'tcx>)
                                     -> RelateResult<'tcx, Substs<'tcx>>
@@ -129,6 +515,25 @@ fn relate_item_substs<'a,'tcx:'a,R>(relation: &mut R,
            a_subst,
            b_subst);
 
+    if let (false, Some(generics)) = (same_type_param_counts(a_subst, b_subst), generics) {
+        let tcx = relation.tcx();
+        let mut a_filled = a_subst.clone();
+        let mut b_filled = b_subst.clone();
+        let defaulted = fill_type_param_defaults(tcx, generics, &mut a_filled, &mut b_filled);
+        return relate_item_substs(relation, item_def_id, None, &a_filled, &b_filled)
+            .map_err(|err| match err {
+                ty::terr_sorts_at(diff) => match diff.path[0] {
+                    ty::TypeDiffStep::TypeArg(space, i) if defaulted.contains(&(space, i)) => {
+                        let a_ty = *a_filled.types.get(space, i);
+                        let b_ty = *b_filled.types.get(space, i);
+                        ty::terr_type_param_default(expected_found(relation, &a_ty, &b_ty))
+                    }
+                    _ => err,
+                },
+                err => err,
+            });
+    }
+
     let variances;
     let opt_variances = if relation.tcx().variance_computed.get() {
         variances = ty::item_variances(relation.tcx(), item_def_id);
@@ -136,10 +541,57 @@ fn relate_item_substs<'a,'tcx:'a,R>(relation: &mut R,
     } else {
         None
     };
-    relate_substs(relation, opt_variances, a_subst, b_subst)
+    relate_substs(relation, Some(item_def_id), opt_variances, a_subst, b_subst)
+}
+
+/// Whether `a` and `b` have as many type parameters as each other in every
+/// space, so that relating them needs no defaults filled in.
+fn same_type_param_counts<'tcx>(a: &Substs<'tcx>, b: &Substs<'tcx>) -> bool {
+    ParamSpace::all().iter().all(|&space| a.types.len(space) == b.types.len(space))
+}
+
+/// Pushes the defaults of the trailing type parameters that the shorter of
+/// `a` and `b` leaves out in each space, stopping at the first parameter
+/// without a default. Returns the positions that were filled in.
+fn fill_type_param_defaults<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                  generics: &ty::Generics<'tcx>,
+                                  a: &mut Substs<'tcx>,
+                                  b: &mut Substs<'tcx>)
+                                  -> Vec<(ParamSpace, usize)> {
+    let mut defaulted = vec![];
+    for &space in &ParamSpace::all() {
+        let (a_len, b_len) = (a.types.len(space), b.types.len(space));
+        let (short, long_len) = if a_len < b_len { (&mut *a, b_len) } else { (&mut *b, a_len) };
+        let defs = generics.types.get_slice(space);
+        for i in short.types.len(space)..cmp::min(long_len, defs.len()) {
+            match defs[i].default {
+                Some(default) => {
+                    let ty = default.subst(tcx, short);
+                    short.types.push(space, ty);
+                    defaulted.push((space, i));
+                }
+                None => break,
+            }
+        }
+    }
+    defaulted
+}
+
+/// Relates two substitutions using the given variances for each type
+/// and region parameter, rather than those computed for an item.
+/// `variances` must have an entry for every parameter in each space.
+pub fn relate_substs_with_variances<'a,'tcx:'a,R>(relation: &mut R,
+                                                  variances: &ty::ItemVariances,
+                                                  a_subst: &Substs<'tcx>,
+                                                  b_subst: &Substs<'tcx>)
+                                                  -> RelateResult<'tcx, Substs<'tcx>>
+    where R: TypeRelation<'a,'tcx>
+{
+    relate_substs(relation, None, Some(variances), a_subst, b_subst)
 }
 
 fn relate_substs<'a,'tcx:'a,R>(relation: &mut R,
+                               item_def_id: Option<ast::DefId>,
                                variances: Option<&ty::ItemVariances>,
                                a_subst: &Substs<'tcx>,
                                b_subst: &Substs<'tcx>)
@@ -152,7 +604,12 @@ fn relate_substs<'a,'tcx:'a,R>(relation: &mut R,
         let a_tps = a_subst.types.get_slice(space);
         let b_tps = b_subst.types.get_slice(space);
         let t_variances = variances.map(|v| v.types.get_slice(space));
-        let tps = try!(relate_type_params(relation, t_variances, a_tps, b_tps));
+        let tps = try!(relate_type_params(relation,
+                                          item_def_id,
+                                          space,
+                                          t_variances,
+                                          a_tps,
+                                          b_tps));
         substs.types.replace(space, tps);
     }
 
@@ -167,6 +624,8 @@ fn relate_substs<'a,'tcx:'a,R>(relation: &mut R,
                 let b_regions = b.get_slice(space);
                 let r_variances = variances.map(|v| v.regions.get_slice(space));
                 let regions = try!(relate_region_params(relation,
+                                                        item_def_id,
+                                                        space,
                                                         r_variances,
                                                         a_regions,
                                                         b_regions));
@@ -179,6 +638,8 @@ fn relate_substs<'a,'tcx:'a,R>(relation: &mut R,
 }
 
 fn relate_type_params<'a,'tcx:'a,R>(relation: &mut R,
+                                    item_def_id: Option<ast::DefId>,
+                                    space: ParamSpace,
                                     variances: Option<&[ty::Variance]>,
                                     a_tys: &[Ty<'tcx>],
                                     b_tys: &[Ty<'tcx>])
@@ -196,12 +657,25 @@ fn relate_type_params<'a,'tcx:'a,R>(relation: &mut R,
             let a_ty = a_tys[i];
             let b_ty = b_tys[i];
             let v = variances.map_or(ty::Invariant, |v| v[i]);
-            relation.relate_with_variance(v, &a_ty, &b_ty)
+            if let Some(observer) = relation.observer() {
+                observer.on_variance_switch(v);
+            }
+            relate_component(relation, ty::TypeDiffStep::TypeArg(space, i), &a_ty, |relation| {
+                match item_def_id {
+                    Some(def_id) => {
+                        let reason = InvarianceReason::TypeParam(def_id, space, i);
+                        relate_with_variance_reason(relation, v, reason, &a_ty, &b_ty)
+                    }
+                    None => relation.relate_with_variance(v, &a_ty, &b_ty),
+                }
+            })
         })
         .collect()
 }
 
 fn relate_region_params<'a,'tcx:'a,R>(relation: &mut R,
+                                      item_def_id: Option<ast::DefId>,
+                                      space: ParamSpace,
                                       variances: Option<&[ty::Variance]>,
                                       a_rs: &[ty::Region],
                                       b_rs: &[ty::Region])
@@ -227,7 +701,16 @@ fn relate_region_params<'a,'tcx:'a,R>(relation: &mut R,
             let a_r = a_rs[i];
             let b_r = b_rs[i];
             let variance = variances.map_or(ty::Invariant, |v| v[i]);
-            relation.relate_with_variance(variance, &a_r, &b_r)
+            if let Some(observer) = relation.observer() {
+                observer.on_variance_switch(variance);
+            }
+            match item_def_id {
+                Some(def_id) => {
+                    let reason = InvarianceReason::RegionParam(def_id, space, i);
+                    relate_with_variance_reason(relation, variance, reason, &a_r, &b_r)
+                }
+                None => relation.relate_with_variance(variance, &a_r, &b_r),
+            }
         })
         .collect()
 }
@@ -255,23 +738,35 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::FnSig<'tcx> {
                  -> RelateResult<'tcx, ty::FnSig<'tcx>>
         where R: TypeRelation<'a,'tcx>
     {
-        if a.variadic != b.variadic {
+        let inputs = if a.variadic == b.variadic {
+            try!(relate_arg_vecs(relation,
+                                 &a.inputs,
+                                 &b.inputs))
+        } else if b.variadic && relation.variadic_subtyping() {
+            if a.inputs.len() < b.inputs.len() {
+                return Err(ty::terr_arg_count);
+            }
+            let (prefix, rest) = a.inputs.split_at(b.inputs.len());
+            let mut inputs = try!(relate_arg_vecs(relation, prefix, &b.inputs));
+            inputs.extend(rest.iter().cloned());
+            inputs
+        } else {
             return Err(ty::terr_variadic_mismatch(
                 expected_found(relation, &a.variadic, &b.variadic)));
-        }
-
-        let inputs = try!(relate_arg_vecs(relation,
-                                          &a.inputs,
-                                          &b.inputs));
+        };
 
         let output = try!(match (a.output, b.output) {
-            (ty::FnConverging(a_ty), ty::FnConverging(b_ty)) =>
-                Ok(ty::FnConverging(try!(relation.relate(&a_ty, &b_ty)))),
+            (ty::FnConverging(a_ty), ty::FnConverging(b_ty)) => {
+                let tcx = relation.tcx();
+                let ty = try!(relation.relate(&a_ty, &b_ty).map_err(|err| {
+                    push_diff_step(tcx, ty::TypeDiffStep::FnOutput, err)
+                }));
+                Ok(ty::FnConverging(ty))
+            }
             (ty::FnDiverging, ty::FnDiverging) =>
                 Ok(ty::FnDiverging),
             (a, b) =>
-                Err(ty::terr_convergence_mismatch(
-                    expected_found(relation, &(a != ty::FnDiverging), &(b != ty::FnDiverging)))),
+                Err(ty::terr_convergence_mismatch(expected_found(relation, &a, &b))),
         });
 
         return Ok(ty::FnSig {inputs: inputs,
@@ -290,8 +785,12 @@ fn relate_arg_vecs<'a,'tcx:'a,R>(relation: &mut R,
         return Err(ty::terr_arg_count);
     }
 
-    a_args.iter().zip(b_args)
-          .map(|(a, b)| relation.relate_with_variance(ty::Contravariant, a, b))
+    a_args.iter().zip(b_args).enumerate()
+          .map(|(i, (a, b))| {
+              relate_component(relation, ty::TypeDiffStep::FnArg(i), a, |relation| {
+                  relation.relate_with_variance(ty::Contravariant, a, b)
+              })
+          })
           .collect()
 }
 
@@ -317,7 +816,7 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for abi::Abi {
                  -> RelateResult<'tcx, abi::Abi>
         where R: TypeRelation<'a,'tcx>
     {
-        if a == b {
+        if relation.abi_compat(*a, *b) {
             Ok(*a)
         } else {
             Err(ty::terr_abi_mismatch(expected_found(relation, a, b)))
@@ -355,28 +854,6 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::ProjectionPredicate<'tcx> {
     }
 }
 
-impl<'a,'tcx:'a> Relate<'a,'tcx> for Vec<ty::PolyProjectionPredicate<'tcx>> {
-    fn relate<R>(relation: &mut R,
-                 a: &Vec<ty::PolyProjectionPredicate<'tcx>>,
-                 b: &Vec<ty::PolyProjectionPredicate<'tcx>>)
-                 -> RelateResult<'tcx, Vec<ty::PolyProjectionPredicate<'tcx>>>
-        where R: TypeRelation<'a,'tcx>
-    {
-        // To be compatible, `a` and `b` must be for precisely the
-        // same set of traits and item names. We always require that
-        // projection bounds lists are sorted by trait-def-id and item-name,
-        // so we can just iterate through the lists pairwise, so long as they are the
-        // same length.
-        if a.len() != b.len() {
-            Err(ty::terr_projection_bounds_length(expected_found(relation, &a.len(), &b.len())))
-        } else {
-            a.iter().zip(b)
-                .map(|(a, b)| relation.relate(a, b))
-                .collect()
-        }
-    }
-}
-
 impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::ExistentialBounds<'tcx> {
     fn relate<R>(relation: &mut R,
                  a: &ty::ExistentialBounds<'tcx>,
@@ -394,7 +871,9 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::ExistentialBounds<'tcx> {
                                                          &a.region_bound,
                                                          &b.region_bound)));
         let nb = try!(relation.relate(&a.builtin_bounds, &b.builtin_bounds));
-        let pb = try!(relation.relate(&a.projection_bounds, &b.projection_bounds));
+        let pb = try!(relate_projection_bounds(relation,
+                                               &a.projection_bounds,
+                                               &b.projection_bounds));
         Ok(ty::ExistentialBounds { region_bound: r,
                                    builtin_bounds: nb,
                                    projection_bounds: pb,
@@ -402,6 +881,36 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::ExistentialBounds<'tcx> {
     }
 }
 
+/// Relates the projection bounds of two object types, pairing up the
+/// bounds on the same trait and associated item. The lists are sorted
+/// here, so callers need not keep them in canonical order.
+fn relate_projection_bounds<'a,'tcx:'a,R>(relation: &mut R,
+                                          a: &[ty::PolyProjectionPredicate<'tcx>],
+                                          b: &[ty::PolyProjectionPredicate<'tcx>])
+                                          -> RelateResult<'tcx,
+                                                          Vec<ty::PolyProjectionPredicate<'tcx>>>
+    where R: TypeRelation<'a,'tcx>
+{
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    ty::sort_bounds_list(&mut a);
+    ty::sort_bounds_list(&mut b);
+
+    // To be compatible, `a` and `b` must be for precisely the same set
+    // of traits and item names; name one that only one side binds.
+    let a_keys: Vec<_> = a.iter().map(|p| p.sort_key()).collect();
+    let b_keys: Vec<_> = b.iter().map(|p| p.sort_key()).collect();
+    if a_keys != b_keys {
+        let only_a = a_keys.iter().find(|k| !b_keys.contains(k)).map(|&(_, name)| name);
+        let only_b = b_keys.iter().find(|k| !a_keys.contains(k)).map(|&(_, name)| name);
+        return Err(ty::terr_projection_bound_mismatch(expected_found(relation,
+                                                                     &only_a,
+                                                                     &only_b)));
+    }
+
+    relation.relate(&a, &b)
+}
+
 impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::BuiltinBounds {
     fn relate<R>(relation: &mut R,
                  a: &ty::BuiltinBounds,
@@ -409,16 +918,40 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::BuiltinBounds {
                  -> RelateResult<'tcx, ty::BuiltinBounds>
         where R: TypeRelation<'a,'tcx>
     {
-        // Two sets of builtin bounds are only relatable if they are
-        // precisely the same (but see the coercion code).
-        if a != b {
-            Err(ty::terr_builtin_bounds(expected_found(relation, a, b)))
-        } else {
-            Ok(*a)
-        }
+        relation.builtin_bounds(*a, *b)
     }
 }
 
+/// `builtin_bounds` for a subtyping relation: an object type with more
+/// builtin bounds is a subtype of one with fewer, so `a` must include
+/// every bound of `b`.
+pub fn sub_builtin_bounds<'a,'tcx:'a,R>(relation: &mut R,
+                                        a: ty::BuiltinBounds,
+                                        b: ty::BuiltinBounds)
+                                        -> RelateResult<'tcx, ty::BuiltinBounds>
+    where R: TypeRelation<'a,'tcx>
+{
+    if a.is_superset(&b) {
+        Ok(a)
+    } else {
+        Err(ty::terr_builtin_bounds(expected_found(relation, &a, &b)))
+    }
+}
+
+/// The builtin bounds of the least upper bound of two object types.
+pub fn lub_builtin_bounds(a: ty::BuiltinBounds, b: ty::BuiltinBounds) -> ty::BuiltinBounds {
+    let mut bounds = a;
+    *bounds = a.intersection(*b);
+    bounds
+}
+
+/// The builtin bounds of the greatest lower bound of two object types.
+pub fn glb_builtin_bounds(a: ty::BuiltinBounds, b: ty::BuiltinBounds) -> ty::BuiltinBounds {
+    let mut bounds = a;
+    *bounds = a.union(*b);
+    bounds
+}
+
 impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::TraitRef<'tcx> {
     fn relate<R>(relation: &mut R,
                  a: &ty::TraitRef<'tcx>,
@@ -430,7 +963,18 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::TraitRef<'tcx> {
         if a.def_id != b.def_id {
             Err(ty::terr_traits(expected_found(relation, &a.def_id, &b.def_id)))
         } else {
-            let substs = try!(relate_item_substs(relation, a.def_id, a.substs, b.substs));
+            // Only trait refs that leave out some defaulted parameters
+            // need the trait's generics.
+            let generics = if same_type_param_counts(a.substs, b.substs) {
+                None
+            } else {
+                Some(&ty::lookup_trait_def(relation.tcx(), a.def_id).generics)
+            };
+            let substs = try!(relate_item_substs(relation,
+                                                 a.def_id,
+                                                 generics,
+                                                 a.substs,
+                                                 b.substs));
             Ok(ty::TraitRef { def_id: a.def_id, substs: relation.tcx().mk_substs(substs) })
         }
     }
@@ -443,8 +987,80 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for Ty<'tcx> {
                  -> RelateResult<'tcx, Ty<'tcx>>
         where R: TypeRelation<'a,'tcx>
     {
-        relation.tys(a, b)
+        bump(&relation.tcx().sess.relation_stats.n_tys);
+        let tag = relation.tag();
+        if let Some(cached) = relation.relation_cache().and_then(|c| c.get(tag, *a, *b)) {
+            return Ok(cached);
+        }
+        let result = try!(relation.tys(a, b));
+        if let Some(cache) = relation.relation_cache() {
+            cache.insert(tag, *a, *b, result);
+        }
+        Ok(result)
+    }
+}
+
+fn is_numeric(ty: Ty) -> bool {
+    match ty.sty {
+        ty::TyInt(_) | ty::TyUint(_) | ty::TyFloat(_) => true,
+        _ => false,
+    }
+}
+
+/// Relates two distinct closure types by their kinds, signatures and the
+/// types of their upvars. Upvars are related invariantly, as the closure
+/// may both read and write them.
+fn relate_closures_structurally<'a,'tcx:'a,R>(relation: &mut R,
+                                              a: Ty<'tcx>,
+                                              b: Ty<'tcx>,
+                                              a_id: ast::DefId,
+                                              a_substs: &Substs<'tcx>,
+                                              b_id: ast::DefId,
+                                              b_substs: &Substs<'tcx>)
+                                              -> RelateResult<'tcx, ()>
+    where R: TypeRelation<'a,'tcx>
+{
+    let tcx = relation.tcx();
+
+    // Closures of a function still being type checked are only in that
+    // function's tables, so without a typer such a closure has to be
+    // treated as distinct rather than looked up in the tcx.
+    let known = |def_id: ast::DefId| {
+        tcx.closure_kinds.borrow().contains_key(&def_id) &&
+            tcx.closure_tys.borrow().contains_key(&def_id)
+    };
+    if relation.closure_typer().is_none() && !(known(a_id) && known(b_id)) {
+        return Err(ty::terr_sorts(expected_found(relation, &a, &b)));
+    }
+
+    let (a_kind, b_kind, a_fty, b_fty, a_upvars, b_upvars) = {
+        let param_env;
+        let typer: &ty::ClosureTyper<'tcx> = match relation.closure_typer() {
+            Some(typer) => typer,
+            None => {
+                param_env = ty::empty_parameter_environment(tcx);
+                &param_env
+            }
+        };
+        (typer.closure_kind(a_id), typer.closure_kind(b_id),
+         typer.closure_type(a_id, a_substs), typer.closure_type(b_id, b_substs),
+         typer.closure_upvars(a_id, a_substs), typer.closure_upvars(b_id, b_substs))
+    };
+    debug!("relate_closures_structurally: a_kind={:?} b_kind={:?}", a_kind, b_kind);
+
+    let (a_upvars, b_upvars) = match (a_kind, b_kind, a_upvars, b_upvars) {
+        (Some(a_kind), Some(b_kind), Some(a_upvars), Some(b_upvars))
+            if a_kind == b_kind && a_upvars.len() == b_upvars.len() => (a_upvars, b_upvars),
+        _ => return Err(ty::terr_sorts(expected_found(relation, &a, &b))),
+    };
+
+    try!(relation.relate(&a_fty.unsafety, &b_fty.unsafety));
+    try!(relation.relate(&a_fty.abi, &b_fty.abi));
+    try!(relation.relate(&a_fty.sig, &b_fty.sig));
+    for (a_upvar, b_upvar) in a_upvars.iter().zip(&b_upvars) {
+        try!(relation.relate_with_variance(ty::Invariant, &a_upvar.ty, &b_upvar.ty));
     }
+    Ok(())
 }
 
 /// The main "type relation" routine. Note that this does not handle
@@ -455,11 +1071,49 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
                                       b: Ty<'tcx>)
                                       -> RelateResult<'tcx, Ty<'tcx>>
     where R: TypeRelation<'a,'tcx>
+{
+    let limit = relation.recursion_limit();
+    let tag = relation.tag();
+    let overflow = match relation.relation_stack() {
+        Some(stack) => !stack.enter(limit, tag, a, b),
+        None => false,
+    };
+    if overflow {
+        debug!("super_relate_tys: overflow relating {:?} and {:?}", a, b);
+        return Err(ty::terr_relation_overflow(expected_found(relation, &a, &b)));
+    }
+
+    if let Some(observer) = relation.observer() {
+        observer.on_enter_tys(a, b);
+    }
+    let result = relate_ty_structures(relation, a, b);
+    if let Some(observer) = relation.observer() {
+        observer.on_exit_tys(a, b, &result);
+    }
+
+    if let Some(stack) = relation.relation_stack() {
+        stack.exit();
+    }
+    result
+}
+
+fn relate_ty_structures<'a,'tcx:'a,R>(relation: &mut R,
+                                      a: Ty<'tcx>,
+                                      b: Ty<'tcx>)
+                                      -> RelateResult<'tcx, Ty<'tcx>>
+    where R: TypeRelation<'a,'tcx>
 {
     let tcx = relation.tcx();
     let a_sty = &a.sty;
     let b_sty = &b.sty;
     debug!("super_tys: a_sty={:?} b_sty={:?}", a_sty, b_sty);
+
+    if is_numeric(a) && is_numeric(b) {
+        if let Some(result) = relation.relate_numeric(a, b) {
+            return result;
+        }
+    }
+
     match (a_sty, b_sty) {
         (&ty::TyInfer(_), _) |
         (_, &ty::TyInfer(_)) =>
@@ -493,7 +1147,7 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
         (&ty::TyEnum(a_id, a_substs), &ty::TyEnum(b_id, b_substs))
             if a_id == b_id =>
         {
-            let substs = try!(relate_item_substs(relation, a_id, a_substs, b_substs));
+            let substs = try!(relate_item_substs(relation, a_id, None, a_substs, b_substs));
             Ok(ty::mk_enum(tcx, a_id, tcx.mk_substs(substs)))
         }
 
@@ -507,7 +1161,7 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
         (&ty::TyStruct(a_id, a_substs), &ty::TyStruct(b_id, b_substs))
             if a_id == b_id =>
         {
-            let substs = try!(relate_item_substs(relation, a_id, a_substs, b_substs));
+            let substs = try!(relate_item_substs(relation, a_id, None, a_substs, b_substs));
             Ok(ty::mk_struct(tcx, a_id, tcx.mk_substs(substs)))
         }
 
@@ -518,10 +1172,18 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
             // All TyClosure types with the same id represent
             // the (anonymous) type of the same closure expression. So
             // all of their regions should be equated.
-            let substs = try!(relate_substs(relation, None, a_substs, b_substs));
+            let substs = try!(relate_substs(relation, None, None, a_substs, b_substs));
             Ok(ty::mk_closure(tcx, a_id, tcx.mk_substs(substs)))
         }
 
+        (&ty::TyClosure(a_id, a_substs),
+         &ty::TyClosure(b_id, b_substs))
+            if relation.relate_closures_structurally() =>
+        {
+            try!(relate_closures_structurally(relation, a, b, a_id, a_substs, b_id, b_substs));
+            Ok(a)
+        }
+
         (&ty::TyBox(a_inner), &ty::TyBox(b_inner)) =>
         {
             let typ = try!(relation.relate(&a_inner, &b_inner));
@@ -530,7 +1192,7 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
 
         (&ty::TyRawPtr(ref a_mt), &ty::TyRawPtr(ref b_mt)) =>
         {
-            let mt = try!(relation.relate(a_mt, b_mt));
+            let mt = try!(relate_mt(relation, InvarianceReason::MutRawPtr, a_mt, b_mt));
             Ok(ty::mk_ptr(tcx, mt))
         }
 
@@ -544,11 +1206,8 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
         (&ty::TyArray(a_t, sz_a), &ty::TyArray(b_t, sz_b)) =>
         {
             let t = try!(relation.relate(&a_t, &b_t));
-            if sz_a == sz_b {
-                Ok(ty::mk_vec(tcx, t, Some(sz_a)))
-            } else {
-                Err(ty::terr_fixed_array_size(expected_found(relation, &sz_a, &sz_b)))
-            }
+            let sz = try!(relation.consts(sz_a, sz_b));
+            Ok(ty::mk_vec(tcx, t, Some(sz)))
         }
 
         (&ty::TySlice(a_t), &ty::TySlice(b_t)) =>
@@ -557,16 +1216,37 @@ pub fn super_relate_tys<'a,'tcx:'a,R>(relation: &mut R,
             Ok(ty::mk_vec(tcx, t, None))
         }
 
+        (&ty::TyArray(..), &ty::TySlice(_)) if relation.relates_unsized() =>
+        {
+            relation.unsize(a, b)
+        }
+
         (&ty::TyTuple(ref as_), &ty::TyTuple(ref bs)) =>
         {
             if as_.len() == bs.len() {
-                let ts = try!(as_.iter().zip(bs)
-                                 .map(|(a, b)| relation.relate(a, b))
+                let ts = try!(as_.iter().zip(bs).enumerate()
+                                 .map(|(i, (a, b))| {
+                                     relate_component(relation, ty::TypeDiffStep::TupleElem(i), a,
+                                                      |relation| relation.relate(a, b))
+                                 })
                                  .collect::<Result<_, _>>());
                 Ok(ty::mk_tup(tcx, ts))
             } else if !(as_.is_empty() || bs.is_empty()) {
-                Err(ty::terr_tuple_size(
-                    expected_found(relation, &as_.len(), &bs.len())))
+                // probe the elements both tuples have, so the error can
+                // say how much of them already agrees without unifying
+                // anything on behalf of a relation that fails anyway
+                let prefix_matches = as_.iter().zip(bs)
+                                        .map(|(a, b)| {
+                                            relation.probe(|relation| {
+                                                relation.relate(a, b).is_ok()
+                                            })
+                                        })
+                                        .collect();
+                let sizes = expected_found(relation, &as_.len(), &bs.len());
+                Err(ty::terr_tuple_size_detail(tcx.mk_tuple_size_diff(ty::TupleSizeDiff {
+                    sizes: sizes,
+                    prefix_matches: prefix_matches,
+                })))
             } else {
                 Err(ty::terr_sorts(expected_found(relation, &a, &b)))
             }
@@ -599,6 +1279,7 @@ impl<'a,'tcx:'a> Relate<'a,'tcx> for ty::Region {
                  -> RelateResult<'tcx, ty::Region>
         where R: TypeRelation<'a,'tcx>
     {
+        bump(&relation.tcx().sess.relation_stats.n_regions);
         relation.regions(*a, *b)
     }
 }
@@ -612,6 +1293,7 @@ impl<'a,'tcx:'a,T> Relate<'a,'tcx> for ty::Binder<T>
                  -> RelateResult<'tcx, ty::Binder<T>>
         where R: TypeRelation<'a,'tcx>
     {
+        bump(&relation.tcx().sess.relation_stats.n_binders);
         relation.binders(a, b)
     }
 }
@@ -646,6 +1328,77 @@ impl<'a,'tcx:'a,T> Relate<'a,'tcx> for Box<T>
     }
 }
 
+impl<'a,'tcx:'a,A,B> Relate<'a,'tcx> for (A, B)
+    where A: Relate<'a,'tcx>, B: Relate<'a,'tcx>
+{
+    fn relate<R>(relation: &mut R,
+                 a: &(A, B),
+                 b: &(A, B))
+                 -> RelateResult<'tcx, (A, B)>
+        where R: TypeRelation<'a,'tcx>
+    {
+        Ok((try!(relation.relate(&a.0, &b.0)),
+            try!(relation.relate(&a.1, &b.1))))
+    }
+}
+
+impl<'a,'tcx:'a,A,B,C> Relate<'a,'tcx> for (A, B, C)
+    where A: Relate<'a,'tcx>, B: Relate<'a,'tcx>, C: Relate<'a,'tcx>
+{
+    fn relate<R>(relation: &mut R,
+                 a: &(A, B, C),
+                 b: &(A, B, C))
+                 -> RelateResult<'tcx, (A, B, C)>
+        where R: TypeRelation<'a,'tcx>
+    {
+        Ok((try!(relation.relate(&a.0, &b.0)),
+            try!(relation.relate(&a.1, &b.1)),
+            try!(relation.relate(&a.2, &b.2))))
+    }
+}
+
+impl<'a,'tcx:'a,T> Relate<'a,'tcx> for Option<T>
+    where T: Relate<'a,'tcx>
+{
+    fn relate<R>(relation: &mut R,
+                 a: &Option<T>,
+                 b: &Option<T>)
+                 -> RelateResult<'tcx, Option<T>>
+        where R: TypeRelation<'a,'tcx>
+    {
+        match (a, b) {
+            (&Some(ref a), &Some(ref b)) => Ok(Some(try!(relation.relate(a, b)))),
+            (&None, &None) => Ok(None),
+            _ => Err(ty::terr_presence_mismatch(
+                expected_found(relation, &a.is_some(), &b.is_some()))),
+        }
+    }
+}
+
+/// Relates two vectors element by element. They must be the same length.
+impl<'a,'tcx:'a,T> Relate<'a,'tcx> for Vec<T>
+    where T: Relate<'a,'tcx>
+{
+    fn relate<R>(relation: &mut R,
+                 a: &Vec<T>,
+                 b: &Vec<T>)
+                 -> RelateResult<'tcx, Vec<T>>
+        where R: TypeRelation<'a,'tcx>
+    {
+        if a.len() != b.len() {
+            return Err(ty::terr_length_mismatch(expected_found(relation, &a.len(), &b.len())));
+        }
+        a.iter().zip(b)
+            .map(|(a, b)| relation.relate(a, b))
+            .collect()
+    }
+}
+
+/// Increments one of the session's `relation_stats`.
+fn bump(counter: &Cell<usize>) {
+    counter.set(counter.get() + 1);
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Error handling
 
@@ -655,7 +1408,14 @@ pub fn expected_found<'a,'tcx:'a,R,T>(relation: &mut R,
                                       -> ty::expected_found<T>
     where R: TypeRelation<'a,'tcx>, T: Clone
 {
-    expected_found_bool(relation.a_is_expected(), a, b)
+    bump(&relation.tcx().sess.relation_stats.n_errors);
+    let mut values = expected_found_bool(relation.a_is_expected(), a, b);
+    values.origin = Some(ty::ExpectedOrigin {
+        tag: relation.tag(),
+        span: relation.expected_span(),
+        def_id: relation.expected_def_id(),
+    });
+    values
 }
 
 pub fn expected_found_bool<T>(a_is_expected: bool,
@@ -667,8 +1427,8 @@ pub fn expected_found_bool<T>(a_is_expected: bool,
     let a = a.clone();
     let b = b.clone();
     if a_is_expected {
-        ty::expected_found {expected: a, found: b}
+        ty::expected_found {expected: a, found: b, origin: None}
     } else {
-        ty::expected_found {expected: b, found: a}
+        ty::expected_found {expected: b, found: a, origin: None}
     }
 }
@@ -55,3 +55,4 @@ pub use self::registry::Registry;
 pub mod registry;
 pub mod load;
 pub mod build;
+pub mod typeck;
@@ -11,6 +11,7 @@
 //! Used by plugin crates to tell `rustc` about the plugins they provide.
 
 use lint::{LintPassObject, LintId, Lint};
+use plugin::typeck::TypeckPassObject;
 use session::Session;
 
 use syntax::ext::base::{SyntaxExtension, NamedSyntaxExtension, NormalTT};
@@ -53,6 +54,9 @@ pub struct Registry<'a> {
     #[doc(hidden)]
     pub lint_groups: HashMap<&'static str, Vec<LintId>>,
 
+    #[doc(hidden)]
+    pub typeck_passes: Vec<TypeckPassObject>,
+
     #[doc(hidden)]
     pub llvm_passes: Vec<String>,
 
@@ -70,6 +74,7 @@ impl<'a> Registry<'a> {
             syntax_exts: vec!(),
             lint_passes: vec!(),
             lint_groups: HashMap::new(),
+            typeck_passes: vec!(),
             llvm_passes: vec!(),
             attributes: vec!(),
         }
@@ -129,6 +134,15 @@ impl<'a> Registry<'a> {
         self.lint_groups.insert(name, to.into_iter().map(|x| LintId::of(x)).collect());
     }
 
+    /// Register a pass to run over function bodies after type checking.
+    ///
+    /// Unlike a lint pass, a typeck pass is handed each body together with
+    /// its written-back types, adjustments and method callees, so it can
+    /// look at what inference decided without redoing it.
+    pub fn register_typeck_pass(&mut self, pass: TypeckPassObject) {
+        self.typeck_passes.push(pass);
+    }
+
     /// Register an LLVM pass.
     ///
     /// Registration with LLVM itself is handled through static C++ objects with
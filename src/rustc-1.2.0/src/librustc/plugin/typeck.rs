@@ -0,0 +1,150 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Plugin passes that run over function bodies once type checking is done.
+//!
+//! By the time these passes run, writeback has recorded the final type of
+//! every node, the adjustments applied to each expression and the method
+//! chosen for each method call, so a pass can inspect them without
+//! re-running inference. Passes are registered with
+//! `Registry::register_typeck_pass`.
+//!
+//! A pass can also ask to be shown each node type as writeback resolves
+//! it, which is enough for checks that only care about the type of a
+//! single expression or pattern and saves them walking the body again.
+
+use middle::infer;
+use middle::ty::{self, Ty};
+use session::Session;
+
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::visit::{self, Visitor, FnKind};
+
+use std::mem;
+
+/// A pass over type-checked function bodies.
+pub trait TypeckPass {
+    /// Called for every function, method and closure body in the crate,
+    /// outer bodies before the closures they contain.
+    fn check_body<'a, 'tcx>(&mut self,
+                            cx: &BodyContext<'a, 'tcx>,
+                            decl: &ast::FnDecl,
+                            body: &ast::Block,
+                            span: Span,
+                            id: ast::NodeId);
+
+    /// Called by writeback with the final type of each expression,
+    /// pattern and statement, as it is written back. The type is free of
+    /// inference variables. The body's tables are not installed in the
+    /// `ty::ctxt` yet, so `cx` cannot be used to look up other nodes of
+    /// the same body. Lints found here can be reported through
+    /// `Session::add_lint`.
+    fn check_node_type<'a, 'tcx>(&mut self,
+                                 _: &BodyContext<'a, 'tcx>,
+                                 _: ast::NodeId,
+                                 _: Span,
+                                 _: Ty<'tcx>) { }
+}
+
+pub type TypeckPassObject = Box<TypeckPass + 'static>;
+
+/// The resolved tables of the crate being compiled, as seen by a
+/// `TypeckPass`.
+pub struct BodyContext<'a, 'tcx: 'a> {
+    pub tcx: &'a ty::ctxt<'tcx>,
+}
+
+impl<'a, 'tcx> BodyContext<'a, 'tcx> {
+    pub fn sess(&self) -> &'a Session {
+        &self.tcx.sess
+    }
+
+    /// The type written back for `id`, if it has one.
+    pub fn node_ty(&self, id: ast::NodeId) -> Option<Ty<'tcx>> {
+        ty::node_id_to_type_opt(self.tcx, id)
+    }
+
+    /// The type of `expr` after its adjustments have been applied.
+    pub fn expr_ty_adjusted(&self, expr: &ast::Expr) -> Ty<'tcx> {
+        ty::expr_ty_adjusted(self.tcx, expr)
+    }
+
+    /// The adjustment applied to the expression `id`, if any.
+    pub fn adjustment(&self, id: ast::NodeId) -> Option<ty::AutoAdjustment<'tcx>> {
+        self.tcx.adjustments.borrow().get(&id).cloned()
+    }
+
+    /// The method selected for an overloaded call, operator, deref or
+    /// index.
+    pub fn method_callee(&self, call: ty::MethodCall) -> Option<ty::MethodCallee<'tcx>> {
+        self.tcx.method_map.borrow().get(&call).cloned()
+    }
+
+    /// How the method call expression `id` was resolved, if it is one.
+    pub fn method_resolution(&self, id: ast::NodeId) -> Option<ty::ResolvedMethod<'tcx>> {
+        self.tcx.method_call_resolutions.borrow().get(&id).cloned()
+    }
+
+    /// The types the method call receiver `id` was autoderef'd through,
+    /// starting with its own type.
+    pub fn autoderef_chain(&self, id: ast::NodeId) -> Option<Vec<ty::AutoderefStep<'tcx>>> {
+        self.tcx.autoderef_chains.borrow().get(&id).cloned()
+    }
+
+    /// Whether `a` is a subtype of `b`. Both types must be free of
+    /// inference variables, as all written-back types are.
+    pub fn is_subtype(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool {
+        let infcx = infer::new_infer_ctxt(self.tcx);
+        infer::can_mk_subty(&infcx, a, b).is_ok()
+    }
+
+    /// Whether `a` and `b` are the same type, up to regions that would
+    /// be related anyway.
+    pub fn is_same_type(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool {
+        let infcx = infer::new_infer_ctxt(self.tcx);
+        infer::can_mk_eqty(&infcx, a, b).is_ok()
+    }
+}
+
+/// Runs the plugin typeck passes registered with the session over every
+/// body in `krate`.
+pub fn run_passes(tcx: &ty::ctxt, krate: &ast::Crate) {
+    let mut passes = mem::replace(&mut *tcx.sess.plugin_typeck_passes.borrow_mut(),
+                                  Vec::new());
+    if passes.is_empty() {
+        return;
+    }
+
+    {
+        let mut visitor = BodyVisitor {
+            cx: BodyContext { tcx: tcx },
+            passes: &mut passes,
+        };
+        visit::walk_crate(&mut visitor, krate);
+    }
+
+    *tcx.sess.plugin_typeck_passes.borrow_mut() = passes;
+}
+
+struct BodyVisitor<'a, 'tcx: 'a, 'p> {
+    cx: BodyContext<'a, 'tcx>,
+    passes: &'p mut Vec<TypeckPassObject>,
+}
+
+impl<'a, 'tcx, 'p, 'v> Visitor<'v> for BodyVisitor<'a, 'tcx, 'p> {
+    fn visit_fn(&mut self, fk: FnKind<'v>, decl: &'v ast::FnDecl,
+                body: &'v ast::Block, span: Span, id: ast::NodeId) {
+        for pass in self.passes.iter_mut() {
+            pass.check_body(&self.cx, decl, body, span, id);
+        }
+        visit::walk_fn(self, fk, decl, body, span);
+    }
+}
@@ -102,6 +102,9 @@ pub struct Options {
     pub parse_only: bool,
     pub no_trans: bool,
     pub treat_err_as_bug: bool,
+    /// Stop emitting type errors once this many have been reported.
+    /// Errors past the limit are still counted.
+    pub error_limit: Option<usize>,
     pub no_analysis: bool,
     pub debugging_opts: DebuggingOptions,
     /// Whether to write dependency files. It's (enabled, optional filename).
@@ -213,6 +216,7 @@ pub fn basic_options() -> Options {
         parse_only: false,
         no_trans: false,
         treat_err_as_bug: false,
+        error_limit: None,
         no_analysis: false,
         debugging_opts: basic_debugging_options(),
         write_dependency_info: (false, None),
@@ -517,6 +521,8 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "Optimize with possible levels 0-3"),
     debug_assertions: Option<bool> = (None, parse_opt_bool,
         "explicitly enable the cfg(debug_assertions) directive"),
+    overflow_checks: Option<bool> = (None, parse_opt_bool,
+        "use overflow checks for integer arithmetic"),
 }
 
 
@@ -539,6 +545,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "skip LLVM verification"),
     borrowck_stats: bool = (false, parse_bool,
         "gather borrowck statistics"),
+    relate_stats: bool = (false, parse_bool,
+        "print statistics about type relations"),
+    trace_relations: bool = (false, parse_bool,
+        "print the tree of types related during type inference"),
     no_landing_pads: bool = (false, parse_bool,
         "omit landing pads for unwinding"),
     debug_llvm: bool = (false, parse_bool,
@@ -594,6 +604,20 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "Force drop flag checks on or off"),
     trace_macros: bool = (false, parse_bool,
           "For every macro invocation, print its name and arguments"),
+    cache_relations: bool = (false, parse_bool,
+          "Reuse the result of relating the same pair of types during inference"),
+    autoderef_limit: Option<usize> = (None, parse_opt_uint,
+          "Maximum number of dereferences tried while auto-dereferencing \
+           (defaults to the recursion limit)"),
+    time_method_resolution: bool = (false, parse_bool,
+          "Measure the time spent confirming method calls and print it per function as JSON"),
+    writeback_fingerprints: bool = (false, parse_bool,
+          "Hash the inference tables of each fn body at writeback and print the hashes"),
+    emit_unresolved_types: Option<String> = (None, parse_opt_string,
+          "Write a JSON record of every type that could not be inferred to the given file"),
+    dump_region_graph: Option<String> = (None, parse_opt_string,
+          "Write the region constraint graph of every fn body, with the value of each \
+           variable, to the given directory as graphviz and JSON"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -813,6 +837,9 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             auto   = colorize, if output goes to a tty (default);
             always = always colorize output;
             never  = never colorize output", "auto|always|never"),
+        opt::opt("", "error-limit", "Stop emitting type errors after N of them have been
+                                     reported (checking continues, so the final
+                                     error count stays accurate)", "N"),
 
         opt::flagopt_u("", "pretty",
                    "Pretty-print the input instead of compiling;
@@ -1000,6 +1027,17 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         }
     };
 
+    let error_limit = matches.opt_str("error-limit").map(|arg| {
+        match arg.parse() {
+            Ok(n) => n,
+            Err(_) => {
+                early_error(&format!("argument for --error-limit must be a \
+                                      non-negative integer (instead was `{}`)",
+                                     arg))
+            }
+        }
+    });
+
     let mut externs = HashMap::new();
     for arg in &matches.opt_strs("extern") {
         let mut parts = arg.splitn(2, '=');
@@ -1033,6 +1071,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         parse_only: parse_only,
         no_trans: no_trans,
         treat_err_as_bug: treat_err_as_bug,
+        error_limit: error_limit,
         no_analysis: no_analysis,
         debugging_opts: debugging_opts,
         write_dependency_info: write_dependency_info,
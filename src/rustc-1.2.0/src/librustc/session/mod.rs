@@ -11,6 +11,7 @@
 use lint;
 use metadata::cstore::CStore;
 use metadata::filesearch;
+use plugin::typeck::TypeckPassObject;
 use session::search_paths::PathKind;
 use util::nodemap::NodeMap;
 
@@ -55,6 +56,7 @@ pub struct Session {
     pub lint_store: RefCell<lint::LintStore>,
     pub lints: RefCell<NodeMap<Vec<(lint::LintId, codemap::Span, String)>>>,
     pub plugin_llvm_passes: RefCell<Vec<String>>,
+    pub plugin_typeck_passes: RefCell<Vec<TypeckPassObject>>,
     pub plugin_attributes: RefCell<Vec<(String, AttributeType)>>,
     pub crate_types: RefCell<Vec<config::CrateType>>,
     pub crate_metadata: RefCell<Vec<String>>,
@@ -68,9 +70,49 @@ pub struct Session {
 
     pub can_print_warnings: bool,
 
+    /// The number of function bodies in which type checking reported
+    /// at least one error; used for the summary printed when aborting.
+    pub fns_with_errors: Cell<usize>,
+
+    /// The number of errors that were counted but not emitted because
+    /// the `--error-limit` had been reached.
+    pub suppressed_errors: Cell<usize>,
+
+    /// While type checking is running, the error count when it started.
+    /// The `--error-limit` only applies to the errors reported after it.
+    type_errors_start: Cell<Option<usize>>,
+
+    /// True if the most recent error was suppressed, in which case the
+    /// notes and help messages that follow it are suppressed as well.
+    suppressing_notes: Cell<bool>,
+
+    /// Counts of the work done by type relations, printed with
+    /// `-Z relate-stats`.
+    pub relation_stats: RelationStats,
+
     next_node_id: Cell<ast::NodeId>
 }
 
+/// How many values of each kind type relations have been asked to
+/// relate over the whole compilation, and how many mismatches they found.
+pub struct RelationStats {
+    pub n_tys: Cell<usize>,
+    pub n_regions: Cell<usize>,
+    pub n_binders: Cell<usize>,
+    pub n_errors: Cell<usize>,
+}
+
+impl RelationStats {
+    fn new() -> RelationStats {
+        RelationStats {
+            n_tys: Cell::new(0),
+            n_regions: Cell::new(0),
+            n_binders: Cell::new(0),
+            n_errors: Cell::new(0),
+        }
+    }
+}
+
 impl Session {
     pub fn span_fatal(&self, sp: Span, msg: &str) -> ! {
         if self.opts.treat_err_as_bug {
@@ -94,6 +136,9 @@ impl Session {
         if self.opts.treat_err_as_bug {
             self.span_bug(sp, msg);
         }
+        if self.suppress_error() {
+            return;
+        }
         match split_msg_into_multilines(msg) {
             Some(msg) => self.diagnostic().span_err(sp, &msg[..]),
             None => self.diagnostic().span_err(sp, msg)
@@ -103,6 +148,9 @@ impl Session {
         if self.opts.treat_err_as_bug {
             self.span_bug(sp, msg);
         }
+        if self.suppress_error() {
+            return;
+        }
         match split_msg_into_multilines(msg) {
             Some(msg) => self.diagnostic().span_err_with_code(sp, &msg[..], code),
             None => self.diagnostic().span_err_with_code(sp, msg, code)
@@ -112,8 +160,35 @@ impl Session {
         if self.opts.treat_err_as_bug {
             self.bug(msg);
         }
+        if self.suppress_error() {
+            return;
+        }
         self.diagnostic().handler().err(msg)
     }
+    /// Checks the `--error-limit`. If it has been reached by the errors
+    /// type checking has reported so far, the error about to be reported
+    /// is counted but not emitted, and true is returned.
+    fn suppress_error(&self) -> bool {
+        let suppress = match (self.opts.error_limit, self.type_errors_start.get()) {
+            (Some(limit), Some(start)) => self.err_count() - start >= limit,
+            _ => false,
+        };
+        if suppress {
+            self.diagnostic().handler().bump_err_count();
+            self.suppressed_errors.set(self.suppressed_errors.get() + 1);
+        }
+        self.suppressing_notes.set(suppress);
+        suppress
+    }
+    /// Runs `f`, which does type checking, with the `--error-limit`
+    /// applying to the errors it reports.
+    pub fn limiting_type_errors<T, F>(&self, f: F) -> T where F: FnOnce() -> T {
+        self.type_errors_start.set(Some(self.err_count()));
+        let result = f();
+        self.type_errors_start.set(None);
+        self.suppressing_notes.set(false);
+        result
+    }
     pub fn err_count(&self) -> usize {
         self.diagnostic().handler().err_count()
     }
@@ -121,7 +196,18 @@ impl Session {
         self.diagnostic().handler().has_errors()
     }
     pub fn abort_if_errors(&self) {
-        self.diagnostic().handler().abort_if_errors();
+        let mut context = String::new();
+        let fns = self.fns_with_errors.get();
+        if fns > 0 {
+            context.push_str(&format!(" in {} function{}",
+                                      fns, if fns == 1 { "" } else { "s" }));
+        }
+        let suppressed = self.suppressed_errors.get();
+        if suppressed > 0 {
+            context.push_str(&format!(" ({} not shown due to --error-limit)",
+                                      suppressed));
+        }
+        self.diagnostic().handler().abort_if_errors_with_context(&context);
 
         let delayed_bug = self.delayed_span_bug.borrow();
         match *delayed_bug {
@@ -132,16 +218,19 @@ impl Session {
         }
     }
     pub fn span_warn(&self, sp: Span, msg: &str) {
+        self.suppressing_notes.set(false);
         if self.can_print_warnings {
             self.diagnostic().span_warn(sp, msg)
         }
     }
     pub fn span_warn_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.suppressing_notes.set(false);
         if self.can_print_warnings {
             self.diagnostic().span_warn_with_code(sp, msg, code)
         }
     }
     pub fn warn(&self, msg: &str) {
+        self.suppressing_notes.set(false);
         if self.can_print_warnings {
             self.diagnostic().handler().warn(msg)
         }
@@ -153,9 +242,15 @@ impl Session {
         }
     }
     pub fn span_note(&self, sp: Span, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().span_note(sp, msg)
     }
     pub fn span_end_note(&self, sp: Span, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().span_end_note(sp, msg)
     }
 
@@ -163,21 +258,39 @@ impl Session {
     ///
     /// See `diagnostic::RenderSpan::Suggestion` for more information.
     pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().span_suggestion(sp, msg, suggestion)
     }
     pub fn span_help(&self, sp: Span, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().span_help(sp, msg)
     }
     pub fn fileline_note(&self, sp: Span, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().fileline_note(sp, msg)
     }
     pub fn fileline_help(&self, sp: Span, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().fileline_help(sp, msg)
     }
     pub fn note(&self, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().handler().note(msg)
     }
     pub fn help(&self, msg: &str) {
+        if self.suppressing_notes.get() {
+            return;
+        }
         self.diagnostic().handler().help(msg)
     }
     pub fn opt_span_bug(&self, opt_sp: Option<Span>, msg: &str) -> ! {
@@ -257,6 +370,20 @@ impl Session {
     pub fn asm_comments(&self) -> bool { self.opts.debugging_opts.asm_comments }
     pub fn no_verify(&self) -> bool { self.opts.debugging_opts.no_verify }
     pub fn borrowck_stats(&self) -> bool { self.opts.debugging_opts.borrowck_stats }
+    pub fn relate_stats(&self) -> bool { self.opts.debugging_opts.relate_stats }
+    pub fn trace_relations(&self) -> bool { self.opts.debugging_opts.trace_relations }
+    pub fn time_method_resolution(&self) -> bool {
+        self.opts.debugging_opts.time_method_resolution
+    }
+    pub fn emit_unresolved_types(&self) -> Option<&str> {
+        self.opts.debugging_opts.emit_unresolved_types.as_ref().map(|s| &s[..])
+    }
+    pub fn dump_region_graph(&self) -> Option<&str> {
+        self.opts.debugging_opts.dump_region_graph.as_ref().map(|s| &s[..])
+    }
+    pub fn writeback_fingerprints(&self) -> bool {
+        self.opts.debugging_opts.writeback_fingerprints
+    }
     pub fn print_llvm_passes(&self) -> bool {
         self.opts.debugging_opts.print_llvm_passes
     }
@@ -269,6 +396,15 @@ impl Session {
     pub fn unstable_options(&self) -> bool {
         self.opts.debugging_opts.unstable_options
     }
+    /// Whether integer overflow is checked at runtime; overflow in a
+    /// constant expression is an error either way. `-C overflow-checks`
+    /// takes precedence over `-Z force-overflow-checks`, and both default
+    /// to `debug_assertions`.
+    pub fn overflow_checks(&self) -> bool {
+        self.opts.cg.overflow_checks
+            .or(self.opts.debugging_opts.force_overflow_checks)
+            .unwrap_or(self.opts.debug_assertions)
+    }
     pub fn print_enum_sizes(&self) -> bool {
         self.opts.debugging_opts.print_enum_sizes
     }
@@ -427,6 +563,7 @@ pub fn build_session_(sopts: config::Options,
         lint_store: RefCell::new(lint::LintStore::new()),
         lints: RefCell::new(NodeMap()),
         plugin_llvm_passes: RefCell::new(Vec::new()),
+        plugin_typeck_passes: RefCell::new(Vec::new()),
         plugin_attributes: RefCell::new(Vec::new()),
         crate_types: RefCell::new(Vec::new()),
         crate_metadata: RefCell::new(Vec::new()),
@@ -434,6 +571,11 @@ pub fn build_session_(sopts: config::Options,
         features: RefCell::new(feature_gate::Features::new()),
         recursion_limit: Cell::new(64),
         can_print_warnings: can_print_warnings,
+        fns_with_errors: Cell::new(0),
+        suppressed_errors: Cell::new(0),
+        type_errors_start: Cell::new(None),
+        suppressing_notes: Cell::new(false),
+        relation_stats: RelationStats::new(),
         next_node_id: Cell::new(1)
     };
 
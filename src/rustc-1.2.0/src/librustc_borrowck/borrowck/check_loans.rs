@@ -555,6 +555,14 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
                 old_loan.span,
                 &format!("{}; {}", borrow_summary, rule_summary));
 
+            if let euv::ClosureCapture(_) = old_loan.cause {
+                // The loan of a closure capture starts at the closure
+                // expression itself.
+                if let LpVar(var_id) = old_loan.loan_path.kind {
+                    self.bccx.note_capture_forcing_use(var_id, old_loan.gen_scope.node_id());
+                }
+            }
+
             let old_loan_span = self.tcx().map.span(old_loan.kill_scope.node_id());
             self.bccx.span_end_note(old_loan_span,
                                     "previous borrow ends here");
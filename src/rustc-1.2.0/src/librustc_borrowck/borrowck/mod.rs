@@ -737,6 +737,10 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                             expr_ty,
                             suggestion));
                 self.tcx.sess.fileline_help(expr_span, help);
+
+                if let LpVar(var_id) = moved_lp.kind {
+                    self.note_capture_forcing_use(var_id, the_move.id);
+                }
             }
         }
 
@@ -792,6 +796,32 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
         self.tcx.sess.span_note(s, m);
     }
 
+    /// If the closure `closure_expr_id` captures `var_id` by value or by a
+    /// unique or mutable borrow because of some use in its body, points at
+    /// that use.
+    pub fn note_capture_forcing_use(&self, var_id: ast::NodeId, closure_expr_id: ast::NodeId) {
+        let upvar_id = ty::UpvarId { var_id: var_id, closure_expr_id: closure_expr_id };
+        let span = match self.tcx.upvar_capture_spans.borrow().get(&upvar_id) {
+            Some(&span) => span,
+            None => return
+        };
+        let how = match self.tcx.upvar_capture_map.borrow().get(&upvar_id) {
+            Some(&ty::UpvarCapture::ByValue) => "by value",
+            Some(&ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::MutBorrow, .. })) => {
+                "by mutable reference"
+            }
+            Some(&ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::UniqueImmBorrow, .. })) => {
+                "by unique reference"
+            }
+            _ => return
+        };
+        self.tcx.sess.span_note(
+            span,
+            &format!("`{}` is captured {} because of this use in the closure",
+                     ty::local_var_name_str(self.tcx, var_id),
+                     how));
+    }
+
     pub fn span_end_note(&self, s: Span, m: &str) {
         self.tcx.sess.span_end_note(s, m);
     }
@@ -460,7 +460,7 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     });
 
     let Registry { syntax_exts, lint_passes, lint_groups,
-                   llvm_passes, attributes, .. } = registry;
+                   typeck_passes, llvm_passes, attributes, .. } = registry;
 
     {
         let mut ls = sess.lint_store.borrow_mut();
@@ -472,6 +472,7 @@ pub fn phase_2_configure_and_expand(sess: &Session,
             ls.register_group(Some(sess), true, name, to);
         }
 
+        *sess.plugin_typeck_passes.borrow_mut() = typeck_passes;
         *sess.plugin_llvm_passes.borrow_mut() = llvm_passes;
         *sess.plugin_attributes.borrow_mut() = attributes.clone();
     }
@@ -662,6 +663,9 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: Session,
         // passes are timed inside typeck
         typeck::check_crate(tcx, trait_map);
 
+        time(time_passes, "plugin typeck passes", (), |_|
+            plugin::typeck::run_passes(tcx, krate));
+
         time(time_passes, "const checking", (), |_|
             middle::check_const::check_crate(tcx));
 
@@ -722,6 +726,15 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: Session,
         // The above three passes generate errors w/o aborting
         tcx.sess.abort_if_errors();
 
+        if tcx.sess.relate_stats() {
+            let stats = &tcx.sess.relation_stats;
+            println!("--- relate stats ---");
+            println!("n_tys: {}", stats.n_tys.get());
+            println!("n_regions: {}", stats.n_regions.get());
+            println!("n_binders: {}", stats.n_binders.get());
+            println!("n_errors: {}", stats.n_errors.get());
+        }
+
         f(tcx, ty::CrateAnalysis {
             export_map: export_map,
             exported_items: exported_items,
@@ -15,6 +15,7 @@ use diagnostic::Emitter;
 use driver;
 use rustc_lint;
 use rustc_resolve as resolve;
+use rustc_typeck::check::writeback;
 use rustc_typeck::middle::lang_items;
 use rustc_typeck::middle::free_region::FreeRegionMap;
 use rustc_typeck::middle::region::{self, CodeExtent, DestructionScopeData};
@@ -22,21 +23,27 @@ use rustc_typeck::middle::resolve_lifetime;
 use rustc_typeck::middle::stability;
 use rustc_typeck::middle::subst;
 use rustc_typeck::middle::subst::Subst;
-use rustc_typeck::middle::ty::{self, Ty};
+use rustc_typeck::middle::traits;
+use rustc_typeck::middle::ty::{self, AsPredicate, Ty};
+use rustc_typeck::middle::ty_fold;
+use rustc_typeck::middle::ty_relate::{self, Relate, RelateObserver, RelateResult, RelationCache};
 use rustc_typeck::middle::ty_relate::TypeRelation;
 use rustc_typeck::middle::infer;
 use rustc_typeck::middle::infer::lub::Lub;
 use rustc_typeck::middle::infer::glb::Glb;
 use rustc_typeck::middle::infer::sub::Sub;
 use rustc::ast_map;
+use rustc::util::nodemap::FnvHashMap;
 use rustc::session::{self,config};
-use syntax::{abi, ast};
+use syntax::{abi, ast, ast_util};
 use syntax::codemap;
 use syntax::codemap::{Span, CodeMap, DUMMY_SP};
 use syntax::diagnostic::{Level, RenderSpan, Bug, Fatal, Error, Warning, Note, Help};
 use syntax::parse::token;
 use syntax::feature_gate::UnstableFeatures;
 
+use std::cell::{Cell, RefCell};
+
 struct Env<'a, 'tcx: 'a> {
     infcx: &'a infer::InferCtxt<'a, 'tcx>,
 }
@@ -96,13 +103,24 @@ fn errors(msgs: &[&str]) -> (Box<Emitter+Send>, usize) {
 }
 
 fn test_env<F>(source_string: &str,
-               (emitter, expected_err_count): (Box<Emitter+Send>, usize),
+               errors: (Box<Emitter+Send>, usize),
                body: F) where
     F: FnOnce(Env),
+{
+    test_env_with_options(source_string, errors, |_| {}, body)
+}
+
+fn test_env_with_options<O, F>(source_string: &str,
+                               (emitter, expected_err_count): (Box<Emitter+Send>, usize),
+                               configure: O,
+                               body: F) where
+    O: FnOnce(&mut config::Options),
+    F: FnOnce(Env),
 {
     let mut options =
         config::basic_options();
     options.debugging_opts.verbose = true;
+    configure(&mut options);
     options.unstable_features = UnstableFeatures::Allow;
     let codemap =
         CodeMap::new();
@@ -507,6 +525,124 @@ fn sub_free_bound_false_infer() {
     })
 }
 
+#[test]
+fn sub_all_errors_reports_each_component() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_static = |t| ty::mk_imm_rptr(tcx, tcx.mk_region(ty::ReStatic), t);
+        let a = ty::mk_tup(tcx, vec![tcx.types.u8, tcx.types.bool, t_static(tcx.types.isize)]);
+        let b = ty::mk_tup(tcx, vec![tcx.types.u8, tcx.types.isize, t_static(tcx.types.bool)]);
+
+        match env.infcx.sub_types_all_errors(true, infer::Misc(DUMMY_SP), a, b) {
+            Ok(()) => panic!("{} should not be a subtype of {}", a, b),
+            Err(errs) => assert_eq!(errs.len(), 2),
+        }
+        assert!(env.infcx.sub_types_all_errors(true, infer::Misc(DUMMY_SP), a, a).is_ok());
+    })
+}
+
+#[test]
+fn sub_sorts_error_records_path() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_inner = |t| ty::mk_tup(tcx, vec![tcx.types.bool, t]);
+        let a = ty::mk_tup(tcx, vec![tcx.types.u8, t_inner(tcx.types.isize)]);
+        let b = ty::mk_tup(tcx, vec![tcx.types.u8, t_inner(tcx.types.char)]);
+
+        match infer::can_mk_subty(env.infcx, a, b) {
+            Err(ty::terr_sorts_at(diff)) => {
+                assert_eq!(diff.values.expected, tcx.types.isize);
+                assert_eq!(diff.values.found, tcx.types.char);
+                assert_eq!(diff.path, [ty::TypeDiffStep::TupleElem(1),
+                                       ty::TypeDiffStep::TupleElem(1)]);
+            }
+            r => panic!("unexpected result relating {} and {}: {:?}", a, b, r),
+        }
+    })
+}
+
+#[test]
+fn sub_with_cause_nests() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let mut sub = env.sub();
+        assert!(sub.causes().is_empty());
+        sub.with_cause(ty_relate::Cause::ExistentialRegionBound(false), |sub| {
+            let reason = ty_relate::InvarianceReason::MutRef;
+            sub.with_cause(ty_relate::Cause::InvariantPosition(reason), |sub| {
+                let causes = sub.causes();
+                assert_eq!(causes.len(), 2);
+                match (&causes[0], &causes[1]) {
+                    (&ty_relate::Cause::ExistentialRegionBound(false),
+                     &ty_relate::Cause::InvariantPosition(r)) => assert_eq!(r, reason),
+                    _ => panic!("unexpected cause stack {:?}", causes),
+                }
+            });
+            assert_eq!(sub.causes().len(), 1);
+        });
+        assert!(sub.causes().is_empty());
+    })
+}
+
+#[test]
+fn sub_overflows_recursion_limit() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        // 21 levels of nesting, between the depths allowed by limits of
+        // 1 and 2
+        let mut a = env.t_nil();
+        let mut b = env.tcx().types.isize;
+        for _ in 0..20 {
+            a = env.t_pair(a, a);
+            b = env.t_pair(b, b);
+        }
+
+        env.tcx().sess.recursion_limit.set(1);
+        match env.sub().relate(&a, &b) {
+            Err(ty::terr_relation_overflow(_)) => {}
+            r => panic!("expected overflow, found {:?}", r),
+        }
+
+        // the pairs entered before giving up have all been left again
+        env.tcx().sess.recursion_limit.set(2);
+        match env.sub().relate(&a, &b) {
+            Err(ty::terr_sorts_at(_)) => {}
+            r => panic!("expected a type mismatch, found {:?}", r),
+        }
+    })
+}
+
+#[test]
+fn builtin_bounds_superset_is_subtype() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let mut send = ty::BuiltinBounds::empty();
+        send.insert(ty::BoundSend);
+        let mut send_sync = send;
+        send_sync.insert(ty::BoundSync);
+
+        assert!(env.sub().relate(&send_sync, &send).is_ok());
+        assert!(env.sub().relate(&send, &send_sync).is_err());
+        assert_eq!(env.lub().relate(&send_sync, &send).ok(), Some(send));
+        assert_eq!(env.glb().relate(&send_sync, &send).ok(), Some(send_sync));
+
+        // invariant positions still need the bounds to match exactly
+        assert!(env.infcx.equate(true, env.dummy_type_trace()).relate(&send_sync, &send).is_err());
+    })
+}
+
+#[test]
+fn relation_errors_record_origin() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        match env.sub().relate(&tcx.types.u8, &tcx.types.bool) {
+            Err(ty::terr_sorts(values)) => {
+                let origin = values.origin.expect("no origin recorded");
+                assert_eq!(origin.tag, "Sub");
+                assert!(origin.span.is_none() && origin.def_id.is_none());
+            }
+            r => panic!("unexpected result relating u8 and bool: {:?}", r),
+        }
+    })
+}
+
 #[test]
 fn lub_free_bound_infer() {
     //! Test result of:
@@ -597,6 +733,212 @@ fn lub_returning_scope() {
              })
 }
 
+#[test]
+fn lub_tuple_of_values() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let t_u8 = env.tcx().types.u8;
+        let t_isize = env.tcx().types.isize;
+        let t_rptr_free1 = env.t_rptr_free(0, 1);
+        let t_rptr_static = env.t_rptr_static();
+
+        match env.lub().relate(&(t_u8, t_rptr_free1), &(t_u8, t_rptr_static)) {
+            Ok((t0, t1)) => {
+                env.assert_eq(t0, t_u8);
+                env.assert_eq(t1, t_rptr_static);
+            }
+            Err(ref e) => panic!("unexpected error computing LUB of pairs: {}", e),
+        }
+
+        assert!(env.lub().relate(&(t_u8, t_isize, t_u8), &(t_u8, t_u8, t_u8)).is_err());
+    })
+}
+
+#[test]
+fn lub_options_and_vecs() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let t_u8 = env.tcx().types.u8;
+        let t_rptr_free1 = env.t_rptr_free(0, 1);
+        let t_rptr_static = env.t_rptr_static();
+
+        match env.lub().relate(&Some(t_rptr_free1), &Some(t_rptr_static)) {
+            Ok(Some(t)) => env.assert_eq(t, t_rptr_static),
+            r => panic!("unexpected LUB of options: {:?}", r),
+        }
+        assert_eq!(env.lub().relate(&None::<Ty>, &None).ok(), Some(None));
+        match env.lub().relate(&Some(t_u8), &None) {
+            Err(ty::terr_presence_mismatch(_)) => {}
+            r => panic!("expected a presence mismatch, found {:?}", r),
+        }
+
+        match env.lub().relate(&vec![t_u8, t_rptr_free1], &vec![t_u8, t_rptr_static]) {
+            Ok(ref tys) => {
+                env.assert_eq(tys[0], t_u8);
+                env.assert_eq(tys[1], t_rptr_static);
+            }
+            Err(ref e) => panic!("unexpected error computing LUB of vectors: {}", e),
+        }
+        match env.lub().relate(&vec![t_u8], &vec![t_u8, t_u8]) {
+            Err(ty::terr_length_mismatch(values)) => {
+                assert_eq!((values.expected, values.found), (1, 2));
+            }
+            r => panic!("expected a length mismatch, found {:?}", r),
+        }
+    })
+}
+
+#[test]
+fn resolved_lattice_without_inference() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let free_regions = FreeRegionMap::new();
+        let t_rptr_free1 = env.t_rptr_free(0, 1);
+        let t_rptr_static = env.t_rptr_static();
+        let t_isize = tcx.types.isize;
+
+        let mut lub = ty_relate::lattice::Lub::new(tcx, &free_regions);
+        let mut glb = ty_relate::lattice::Glb::new(tcx, &free_regions);
+        assert_eq!(lub.relate(&t_rptr_free1, &t_rptr_static).ok(), Some(t_rptr_static));
+        assert_eq!(glb.relate(&t_rptr_free1, &t_rptr_static).ok(), Some(t_rptr_free1));
+
+        // argument types are combined in the opposite direction
+        assert_eq!(lub.relate(&env.t_fn(&[t_rptr_free1], t_isize),
+                              &env.t_fn(&[t_rptr_static], t_isize)).ok(),
+                   Some(env.t_fn(&[t_rptr_free1], t_isize)));
+
+        assert!(lub.relate(&t_isize, &tcx.types.u8).is_err());
+    })
+}
+
+#[test]
+fn relate_substs_with_chosen_variances() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let free_regions = FreeRegionMap::new();
+        let t_rptr_free1 = env.t_rptr_free(0, 1);
+        let t_rptr_static = env.t_rptr_static();
+        let a = subst::Substs::new_type(vec![t_rptr_free1, t_rptr_free1], vec![]);
+        let b = subst::Substs::new_type(vec![t_rptr_static, t_rptr_static], vec![]);
+        let variances = ty::ItemVariances {
+            types: subst::VecPerParamSpace::new(vec![ty::Covariant, ty::Contravariant],
+                                                vec![], vec![]),
+            regions: subst::VecPerParamSpace::empty(),
+        };
+
+        let mut lub = ty_relate::lattice::Lub::new(tcx, &free_regions);
+        match ty_relate::relate_substs_with_variances(&mut lub, &variances, &a, &b) {
+            Ok(substs) => {
+                assert_eq!(substs.types.get_slice(subst::TypeSpace),
+                           [t_rptr_static, t_rptr_free1]);
+            }
+            Err(ref e) => panic!("unexpected error relating substs: {}", e),
+        }
+    })
+}
+
+#[test]
+fn relate_trait_refs_filling_in_defaults() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_u8 = tcx.types.u8;
+        let t_bool = tcx.types.bool;
+        let trait_ref = |def_id, types: Vec<Ty>| {
+            let substs = subst::Substs::new_trait(types, vec![], t_u8);
+            ty::TraitRef::new(def_id, tcx.mk_substs(substs))
+        };
+
+        // Trait refs with as many parameters as each other relate without
+        // looking up the trait, which here has no definition at all.
+        let undefined = ast::DefId { krate: ast::LOCAL_CRATE, node: 1000 };
+        assert!(env.sub().relate(&trait_ref(undefined, vec![t_u8]),
+                                 &trait_ref(undefined, vec![t_u8])).is_ok());
+
+        // `trait Foo<A, B = A>`: `Foo<u8>` is `Foo<u8, u8>`, not `Foo<u8, bool>`.
+        let foo = ast::DefId { krate: ast::LOCAL_CRATE, node: 1001 };
+        let param = |index, default| ty::TypeParameterDef {
+            name: token::intern(if index == 0 { "A" } else { "B" }),
+            def_id: ast::DefId { krate: ast::LOCAL_CRATE, node: 1002 + index },
+            space: subst::TypeSpace,
+            index: index,
+            default: default,
+            object_lifetime_default: ty::ObjectLifetimeDefault::BaseDefault,
+        };
+        let mut generics = ty::Generics::empty();
+        generics.types.push(subst::SelfSpace, ty::TypeParameterDef {
+            space: subst::SelfSpace,
+            ..param(0, None)
+        });
+        generics.types.push(subst::TypeSpace, param(0, None));
+        generics.types.push(subst::TypeSpace,
+                            param(1, Some(env.t_param(subst::TypeSpace, 0))));
+        tcx.intern_trait_def(ty::TraitDef {
+            unsafety: ast::Unsafety::Normal,
+            paren_sugar: false,
+            generics: generics,
+            trait_ref: trait_ref(foo, vec![]),
+            associated_type_names: vec![],
+            nonblanket_impls: RefCell::new(FnvHashMap()),
+            blanket_impls: RefCell::new(vec![]),
+            flags: Cell::new(ty::TraitFlags::NO_TRAIT_FLAGS),
+        });
+
+        assert!(env.sub().relate(&trait_ref(foo, vec![t_u8]),
+                                 &trait_ref(foo, vec![t_u8, t_u8])).is_ok());
+        match env.sub().relate(&trait_ref(foo, vec![t_u8]),
+                               &trait_ref(foo, vec![t_u8, t_bool])) {
+            Err(ty::terr_type_param_default(..)) => {}
+            r => panic!("unexpected result relating `Foo<u8>` and `Foo<u8, bool>`: {:?}", r),
+        }
+    })
+}
+
+#[test]
+fn upcast_modulo_regions_drops_duplicates() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_self = env.t_param(subst::SelfSpace, 0);
+        let trait_ref = |def_id, self_ty, types: Vec<Ty>| {
+            let substs = subst::Substs::new_trait(types, vec![], self_ty);
+            ty::Binder(ty::TraitRef::new(def_id, tcx.mk_substs(substs)))
+        };
+
+        // Upcasts from `trait Sub: Base<A> + Base<B>` to `Base`.
+        let base = ast::DefId { krate: ast::LOCAL_CRATE, node: 1000 };
+        let upcast = |node, a, b| {
+            let sub = ast::DefId { krate: ast::LOCAL_CRATE, node: node };
+            let supertraits = vec![trait_ref(base, t_self, vec![a]).as_predicate(),
+                                   trait_ref(base, t_self, vec![b]).as_predicate()];
+            tcx.super_predicates.borrow_mut().insert(sub, ty::GenericPredicates {
+                predicates: subst::VecPerParamSpace::new(supertraits, vec![], vec![])
+            });
+            traits::upcast_modulo_regions(tcx, trait_ref(sub, tcx.types.u8, vec![]), base)
+        };
+
+        assert_eq!(upcast(1001, env.t_rptr_static(), env.t_rptr_free(1, 1)),
+                   vec![trait_ref(base, tcx.types.u8, vec![env.t_rptr_static()])]);
+        assert_eq!(upcast(1002, tcx.types.u8, tcx.types.u16).len(), 2);
+    })
+}
+
+#[test]
+fn alpha_eq_renames_late_bound_regions() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_isize = tcx.types.isize;
+        let t_rptr_bound1 = env.t_rptr_late_bound(1);
+        let t_rptr_bound2 = env.t_rptr_late_bound(2);
+        let alpha_eq = |a: Ty, b: Ty| ty_relate::alpha_eq::alpha_eq(tcx, &a, &b);
+
+        assert!(alpha_eq(env.t_fn(&[t_rptr_bound1], t_isize),
+                         env.t_fn(&[t_rptr_bound2], t_isize)));
+        assert!(alpha_eq(env.t_fn(&[t_rptr_bound1, t_rptr_bound2], t_isize),
+                         env.t_fn(&[t_rptr_bound2, t_rptr_bound1], t_isize)));
+        assert!(!alpha_eq(env.t_fn(&[t_rptr_bound1, t_rptr_bound1], t_isize),
+                          env.t_fn(&[t_rptr_bound1, t_rptr_bound2], t_isize)));
+        assert!(!alpha_eq(env.t_fn(&[t_rptr_bound1], t_isize),
+                          env.t_fn(&[env.t_rptr_static()], t_isize)));
+    })
+}
+
 #[test]
 fn glb_free_free_with_common_scope() {
     test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
@@ -850,3 +1192,637 @@ fn walk_ty_skip_subtree() {
         assert!(expected.is_empty());
     })
 }
+
+#[test]
+fn checkpoints_roll_back_and_commit() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_var = env.infcx.next_ty_var();
+
+        let checkpoint = env.infcx.checkpoint("rolled back");
+        assert!(env.sub().relate(&t_var, &tcx.types.u8).is_ok());
+        env.infcx.rollback_to(checkpoint);
+        assert_eq!(env.infcx.resolve_type_vars_if_possible(&t_var), t_var);
+
+        let outer = env.infcx.checkpoint("outer");
+        let inner = env.infcx.checkpoint("inner");
+        assert!(env.sub().relate(&t_var, &tcx.types.u8).is_ok());
+        env.infcx.commit_checkpoint(inner);
+        env.infcx.commit_checkpoint(outer);
+        assert_eq!(env.infcx.resolve_type_vars_if_possible(&t_var), tcx.types.u8);
+    })
+}
+
+#[test]
+fn checkpoints_roll_back_obligations() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_var = env.infcx.next_ty_var();
+        let fulfillment_cx = RefCell::new(traits::FulfillmentContext::new(true));
+        let equate = |t| {
+            let predicate = ty::Binder(ty::EquatePredicate(t_var, t)).as_predicate();
+            traits::Obligation::new(traits::ObligationCause::dummy(), predicate)
+        };
+        fulfillment_cx.borrow_mut().register_predicate_obligation(env.infcx,
+                                                                  equate(tcx.types.u8));
+
+        let checkpoint = env.infcx.checkpoint_with_obligations("dry run", &fulfillment_cx);
+        fulfillment_cx.borrow_mut().register_predicate_obligation(env.infcx,
+                                                                  equate(tcx.types.bool));
+        fulfillment_cx.borrow_mut().register_region_obligation(tcx.types.u8, ty::ReStatic,
+                                                               traits::ObligationCause::dummy());
+        assert!(env.sub().relate(&t_var, &tcx.types.bool).is_ok());
+        assert_eq!(fulfillment_cx.borrow().pending_obligations().len(), 2);
+        assert_eq!(fulfillment_cx.borrow().region_obligations(0).len(), 1);
+        env.infcx.rollback_to(checkpoint);
+
+        // The bindings and the obligations from the dry run are undone
+        // together; those from before it are kept.
+        assert_eq!(env.infcx.resolve_type_vars_if_possible(&t_var), t_var);
+        assert_eq!(fulfillment_cx.borrow().pending_obligations().len(), 1);
+        assert!(fulfillment_cx.borrow().region_obligations(0).is_empty());
+    })
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "checkpoint `outer` released while `inner` is still open")]
+fn checkpoints_released_out_of_order() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let outer = env.infcx.checkpoint("outer");
+        let _inner = env.infcx.checkpoint("inner");
+        env.infcx.rollback_to(outer);
+    })
+}
+
+#[test]
+fn resolve_in_infcx_passes_failures_to_callback() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_known = env.infcx.next_ty_var();
+        let vid = env.infcx.next_ty_var_id(false);
+        let t_unknown = ty::mk_var(tcx, vid);
+        assert!(env.sub().relate(&t_known, &tcx.types.u8).is_ok());
+
+        let mut failures = vec![];
+        let resolved = writeback::resolve_in_infcx(env.infcx, &t_known,
+                                                   |e, partial| failures.push((e, partial)));
+        assert_eq!(resolved, tcx.types.u8);
+        assert!(failures.is_empty());
+
+        // The unresolved variable becomes an error, and the callback is
+        // given the type resolved as far as it goes.
+        let pair = env.t_pair(t_known, t_unknown);
+        let resolved = writeback::resolve_in_infcx(env.infcx, &pair,
+                                                   |e, partial| failures.push((e, partial)));
+        assert_eq!(resolved, env.t_pair(tcx.types.u8, tcx.types.err));
+        assert_eq!(failures, vec![(infer::unresolved_ty(vid),
+                                   Some(env.t_pair(tcx.types.u8, t_unknown)))]);
+    })
+}
+
+/// A relation requiring types to be structurally identical, which logs
+/// every pair of types it relates. If `any_numeric` is set, any two
+/// numeric types are also accepted; if `closures` is set, distinct
+/// closures are related structurally; if `variadic` is set, a fn may be
+/// related to a variadic fn taking a prefix of its arguments. Results are
+/// looked up in `cache`, if there is one.
+struct Traced<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    log: TraceLog<'tcx>,
+    any_numeric: bool,
+    unsizes: bool,
+    closures: bool,
+    variadic: bool,
+    c_cdecl: bool,
+    cache: Option<RelationCache<'tcx>>,
+}
+
+impl<'a, 'tcx> Traced<'a, 'tcx> {
+    fn new(tcx: &'a ty::ctxt<'tcx>) -> Traced<'a, 'tcx> {
+        Traced { tcx: tcx, log: TraceLog { events: vec![] }, any_numeric: false, unsizes: false,
+                 closures: false, variadic: false, c_cdecl: false, cache: None }
+    }
+}
+
+struct TraceLog<'tcx> {
+    events: Vec<(bool, Ty<'tcx>, Ty<'tcx>)>,
+}
+
+impl<'tcx> RelateObserver<'tcx> for TraceLog<'tcx> {
+    fn on_enter_tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) {
+        self.events.push((true, a, b));
+    }
+
+    fn on_exit_tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>, _: &RelateResult<'tcx, Ty<'tcx>>) {
+        self.events.push((false, a, b));
+    }
+}
+
+impl<'a, 'tcx> TypeRelation<'a, 'tcx> for Traced<'a, 'tcx> {
+    fn tcx(&self) -> &'a ty::ctxt<'tcx> { self.tcx }
+    fn tag(&self) -> &'static str { "Traced" }
+    fn a_is_expected(&self) -> bool { true }
+    fn will_change(&mut self, _: bool, _: bool) -> bool { false }
+
+    fn observer(&mut self) -> Option<&mut RelateObserver<'tcx>> {
+        Some(&mut self.log)
+    }
+
+    fn relate_numeric(&mut self, a: Ty<'tcx>, _: Ty<'tcx>)
+                      -> Option<RelateResult<'tcx, Ty<'tcx>>> {
+        if self.any_numeric { Some(Ok(a)) } else { None }
+    }
+
+    fn relates_unsized(&self) -> bool { self.unsizes }
+
+    fn relate_closures_structurally(&self) -> bool { self.closures }
+
+    fn variadic_subtyping(&self) -> bool { self.variadic }
+
+    fn abi_compat(&self, a: abi::Abi, b: abi::Abi) -> bool {
+        a == b || self.c_cdecl && match (a, b) {
+            (abi::C, abi::Cdecl) | (abi::Cdecl, abi::C) => true,
+            _ => false,
+        }
+    }
+
+    fn relation_cache(&self) -> Option<&RelationCache<'tcx>> { self.cache.as_ref() }
+
+    fn relate_with_variance<T: Relate<'a, 'tcx>>(&mut self,
+                                                 _: ty::Variance,
+                                                 a: &T,
+                                                 b: &T)
+                                                 -> RelateResult<'tcx, T> {
+        self.relate(a, b)
+    }
+
+    fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        ty_relate::super_relate_tys(self, a, b)
+    }
+
+    fn regions(&mut self, a: ty::Region, b: ty::Region) -> RelateResult<'tcx, ty::Region> {
+        if a == b { Ok(a) } else { Err(ty::terr_regions_not_same(a, b)) }
+    }
+
+    fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
+                  -> RelateResult<'tcx, ty::Binder<T>>
+        where T: Relate<'a, 'tcx>
+    {
+        Ok(ty::Binder(try!(self.relate(a.skip_binder(), b.skip_binder()))))
+    }
+}
+
+#[test]
+fn observer_sees_relation_tree() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_inner = env.t_pair(tcx.types.bool, tcx.types.isize);
+        let t_outer = env.t_pair(tcx.types.u8, t_inner);
+
+        let mut traced = Traced::new(tcx);
+        assert!(traced.relate(&t_outer, &t_outer).is_ok());
+
+        let expected: Vec<_> = vec![(true, t_outer),
+                                    (true, tcx.types.u8),
+                                    (false, tcx.types.u8),
+                                    (true, t_inner),
+                                    (true, tcx.types.bool),
+                                    (false, tcx.types.bool),
+                                    (true, tcx.types.isize),
+                                    (false, tcx.types.isize),
+                                    (false, t_inner),
+                                    (false, t_outer)];
+        let seen: Vec<_> = traced.log.events.iter().map(|&(enter, a, _)| (enter, a)).collect();
+        assert_eq!(seen, expected);
+    })
+}
+
+#[test]
+fn relate_numeric_hook() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let a = env.t_pair(tcx.types.u8, tcx.types.bool);
+        let b = env.t_pair(tcx.types.u32, tcx.types.bool);
+        let c = env.t_pair(tcx.types.u8, tcx.types.u8);
+
+        let mut traced = Traced::new(tcx);
+        assert!(traced.relate(&a, &b).is_err());
+
+        traced.any_numeric = true;
+        assert!(traced.relate(&a, &b).is_ok());
+        assert!(traced.relate(&a, &c).is_err());
+    })
+}
+
+#[test]
+fn relates_unsized_hook() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let array = ty::mk_uniq(tcx, ty::mk_vec(tcx, tcx.types.u8, Some(3)));
+        let slice = ty::mk_uniq(tcx, ty::mk_vec(tcx, tcx.types.u8, None));
+
+        let mut traced = Traced::new(tcx);
+        assert!(traced.relate(&array, &slice).is_err());
+
+        traced.unsizes = true;
+        assert_eq!(traced.relate(&array, &slice).ok(), Some(slice));
+        assert!(traced.relate(&slice, &array).is_err());
+    })
+}
+
+#[test]
+fn relation_cache_hook() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let t_inner = env.t_pair(tcx.types.bool, tcx.types.isize);
+        let t_outer = env.t_pair(t_inner, t_inner);
+        let entered = |traced: &Traced, t| {
+            traced.log.events.iter().filter(|&&(enter, a, _)| enter && a == t).count()
+        };
+
+        let mut traced = Traced::new(tcx);
+        assert!(traced.relate(&t_outer, &t_outer).is_ok());
+        assert_eq!(entered(&traced, t_inner), 2);
+
+        // The second occurrence of `t_inner` is found in the cache, as is
+        // the whole pair the next time round.
+        traced.cache = Some(RelationCache::new());
+        traced.log.events.clear();
+        assert!(traced.relate(&t_outer, &t_outer).is_ok());
+        assert_eq!(entered(&traced, t_inner), 1);
+        traced.log.events.clear();
+        assert!(traced.relate(&t_outer, &t_outer).is_ok());
+        assert!(traced.log.events.is_empty());
+
+        // Failures are not cached.
+        let t_other = env.t_pair(tcx.types.bool, tcx.types.u8);
+        assert!(traced.relate(&t_inner, &t_other).is_err());
+        traced.log.events.clear();
+        assert!(traced.relate(&t_inner, &t_other).is_err());
+        assert_eq!(entered(&traced, t_inner), 1);
+
+        traced.cache.as_ref().unwrap().clear();
+        traced.log.events.clear();
+        assert!(traced.relate(&t_outer, &t_outer).is_ok());
+        assert_eq!(entered(&traced, t_inner), 1);
+    })
+}
+
+#[test]
+fn relation_cache_cleared_on_rollback() {
+    test_env_with_options(EMPTY_SOURCE_STR, errors(&[]),
+                          |options| options.debugging_opts.cache_relations = true,
+                          |env| {
+        let tcx = env.tcx();
+        let t_var = env.infcx.next_ty_var();
+
+        // Binding `t_var` to `u8` is undone, so must not be remembered.
+        env.infcx.probe(|_| assert!(env.sub().relate(&t_var, &tcx.types.u8).is_ok()));
+
+        assert!(env.sub().relate(&t_var, &tcx.types.bool).is_ok());
+        assert!(env.sub().relate(&t_var, &tcx.types.u8).is_err());
+    })
+}
+
+#[test]
+fn relate_closures_structurally_hook() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        // Closures without freevars, so each has no upvars.
+        let closures: Vec<_> = [(100, ty::FnClosureKind, tcx.types.u8),
+                                (101, ty::FnClosureKind, tcx.types.u8),
+                                (102, ty::FnMutClosureKind, tcx.types.u8),
+                                (103, ty::FnClosureKind, tcx.types.bool)]
+            .iter()
+            .map(|&(id, kind, input)| {
+                let def_id = ast_util::local_def(id);
+                tcx.closure_kinds.borrow_mut().insert(def_id, kind);
+                tcx.closure_tys.borrow_mut().insert(def_id, ty::ClosureTy {
+                    unsafety: ast::Unsafety::Normal,
+                    abi: abi::RustCall,
+                    sig: ty::Binder(ty::FnSig {
+                        inputs: vec![input],
+                        output: ty::FnConverging(ty::mk_nil(tcx)),
+                        variadic: false,
+                    }),
+                });
+                ty::mk_closure(tcx, def_id, tcx.mk_substs(subst::Substs::empty()))
+            })
+            .collect();
+
+        let mut traced = Traced::new(tcx);
+        assert!(traced.relate(&closures[0], &closures[1]).is_err());
+
+        traced.closures = true;
+        assert!(traced.relate(&closures[0], &closures[1]).is_ok());
+        assert!(traced.relate(&closures[0], &closures[2]).is_err());
+        assert!(traced.relate(&closures[0], &closures[3]).is_err());
+
+        // A closure whose kind and type are not in the tcx yet, as for one
+        // whose enclosing function is still being type checked.
+        let unknown = ty::mk_closure(tcx, ast_util::local_def(104),
+                                     tcx.mk_substs(subst::Substs::empty()));
+        assert!(traced.relate(&closures[0], &unknown).is_err());
+        assert!(traced.relate(&unknown, &closures[0]).is_err());
+    })
+}
+
+#[test]
+fn variadic_subtyping_hook() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let sig = |inputs: Vec<_>, variadic| ty::FnSig {
+            inputs: inputs,
+            output: ty::FnConverging(ty::mk_nil(tcx)),
+            variadic: variadic,
+        };
+        let wrapper = sig(vec![tcx.types.u8, tcx.types.bool], false);
+        let c_fn = sig(vec![tcx.types.u8], true);
+
+        let mut traced = Traced::new(tcx);
+        match traced.relate(&wrapper, &c_fn) {
+            Err(ty::terr_variadic_mismatch(..)) => {}
+            r => panic!("unexpected result relating a fn to a variadic fn: {:?}",
+                        r.map(|sig| sig.to_string())),
+        }
+
+        traced.variadic = true;
+        let related = traced.relate(&wrapper, &c_fn).unwrap();
+        assert_eq!(related.inputs, wrapper.inputs);
+        assert!(!related.variadic);
+
+        // The variadic fn must be the supertype, and its fixed arguments
+        // must all be passed.
+        assert!(traced.relate(&c_fn, &wrapper).is_err());
+        assert!(traced.relate(&sig(vec![tcx.types.bool, tcx.types.bool], false), &c_fn).is_err());
+        match traced.relate(&sig(vec![], false), &c_fn) {
+            Err(ty::terr_arg_count) => {}
+            r => panic!("unexpected result relating `fn()` to a variadic fn: {:?}",
+                        r.map(|sig| sig.to_string())),
+        }
+    })
+}
+
+#[test]
+fn abi_compat_hook() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let fn_ty = |abi| ty::BareFnTy {
+            unsafety: ast::Unsafety::Normal,
+            abi: abi,
+            sig: ty::Binder(ty::FnSig {
+                inputs: vec![tcx.types.u8],
+                output: ty::FnConverging(ty::mk_nil(tcx)),
+                variadic: false,
+            }),
+        };
+        let c_fn = fn_ty(abi::C);
+        let cdecl_fn = fn_ty(abi::Cdecl);
+
+        let mut traced = Traced::new(tcx);
+        match traced.relate(&c_fn, &cdecl_fn) {
+            Err(ty::terr_abi_mismatch(_)) => {}
+            r => panic!("unexpected result relating C and cdecl fns: {:?}", r),
+        }
+
+        // The related fn keeps the ABI of `a`.
+        traced.c_cdecl = true;
+        assert_eq!(traced.relate(&c_fn, &cdecl_fn).unwrap().abi, abi::C);
+        assert_eq!(traced.relate(&cdecl_fn, &c_fn).unwrap().abi, abi::Cdecl);
+        assert!(traced.relate(&c_fn, &fn_ty(abi::Rust)).is_err());
+    })
+}
+
+#[test]
+fn relate_projection_bounds_in_any_order() {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let substs = subst::Substs::new_trait(vec![], vec![], tcx.types.u8);
+        let trait_ref = ty::TraitRef::new(ast::DefId { krate: ast::LOCAL_CRATE, node: 1000 },
+                                          tcx.mk_substs(substs));
+        let bound = |name: &str, ty| ty::Binder(ty::ProjectionPredicate {
+            projection_ty: ty::ProjectionTy {
+                trait_ref: trait_ref,
+                item_name: token::intern(name),
+            },
+            ty: ty,
+        });
+        let bounds = |projection_bounds| ty::ExistentialBounds {
+            region_bound: ty::ReStatic,
+            builtin_bounds: ty::BuiltinBounds::empty(),
+            projection_bounds: projection_bounds,
+            region_bound_will_change: false,
+        };
+        let a_u8 = bound("A", tcx.types.u8);
+        let b_bool = bound("B", tcx.types.bool);
+        let c_bool = bound("C", tcx.types.bool);
+
+        let mut traced = Traced::new(tcx);
+        let related = traced.relate(&bounds(vec![a_u8.clone(), b_bool.clone()]),
+                                    &bounds(vec![b_bool.clone(), a_u8.clone()]));
+        assert_eq!(related.ok().map(|b| b.projection_bounds),
+                   Some(vec![a_u8.clone(), b_bool.clone()]));
+
+        match traced.relate(&bounds(vec![a_u8.clone()]),
+                            &bounds(vec![a_u8.clone(), b_bool.clone()])) {
+            Err(err @ ty::terr_projection_bound_mismatch(..)) => {
+                assert_eq!(err.to_string(), "found an unexpected binding for associated type `B`");
+            }
+            r => panic!("unexpected result relating `A` to `A` and `B`: {:?}", r),
+        }
+
+        match traced.relate(&bounds(vec![a_u8.clone(), b_bool.clone()]),
+                            &bounds(vec![c_bool.clone(), a_u8.clone()])) {
+            Err(err @ ty::terr_projection_bound_mismatch(..)) => {
+                assert_eq!(err.to_string(),
+                           "expected a binding for associated type `B`, found one for `C`");
+            }
+            r => panic!("unexpected result relating `A` and `B` to `C` and `A`: {:?}", r),
+        }
+    })
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Relation properties
+//
+// Random types are drawn from a small grammar (scalars, tuples, boxes,
+// references over static, free and late-bound regions, fn types and type
+// parameters) and the relations are checked against the laws they must
+// obey. Each check runs in a probe, so region constraints never leak
+// into the final region resolution. When a law fails, the types are
+// shrunk to the smallest components that still break it before being
+// reported, along with the seed that produced them.
+
+/// Scope of the free regions used by generated types.
+const PROPERTY_SCOPE: ast::NodeId = 0;
+const PROPERTY_SEEDS: u64 = 200;
+const PROPERTY_MAX_DEPTH: u32 = 3;
+
+/// A xorshift generator, so that a failure can be replayed from its seed.
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> XorShift {
+        // xorshift never leaves zero
+        XorShift(seed.wrapping_mul(0x9E3779B97F4A7C15) | 1)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x % n
+    }
+}
+
+impl<'a, 'tcx> Env<'a, 'tcx> {
+    /// Generates a type whose late-bound regions are all bound by one of
+    /// the `binders` fn types enclosing it.
+    fn gen_ty(&self, rng: &mut XorShift, depth: u32, binders: u32) -> Ty<'tcx> {
+        let choices = if depth >= PROPERTY_MAX_DEPTH { 4 } else { 8 };
+        match rng.below(choices) {
+            0 => self.tcx().types.isize,
+            1 => self.tcx().types.bool,
+            2 => self.t_nil(),
+            3 => self.t_param(subst::TypeSpace, rng.below(2) as u32),
+            4 => self.t_pair(self.gen_ty(rng, depth + 1, binders),
+                             self.gen_ty(rng, depth + 1, binders)),
+            5 => ty::mk_uniq(self.tcx(), self.gen_ty(rng, depth + 1, binders)),
+            6 => {
+                let r = self.gen_region(rng, binders);
+                let ty = self.gen_ty(rng, depth + 1, binders);
+                ty::mk_imm_rptr(self.tcx(), self.tcx().mk_region(r), ty)
+            }
+            _ => {
+                let inputs: Vec<_> = (0..rng.below(3)).map(|_| {
+                    self.gen_ty(rng, depth + 1, binders + 1)
+                }).collect();
+                let output = self.gen_ty(rng, depth + 1, binders + 1);
+                self.t_fn(&inputs, output)
+            }
+        }
+    }
+
+    fn gen_region(&self, rng: &mut XorShift, binders: u32) -> ty::Region {
+        let choices = if binders > 0 { 3 } else { 2 };
+        match rng.below(choices) {
+            0 => ty::ReStatic,
+            1 => self.re_free(PROPERTY_SCOPE, rng.below(2) as u32),
+            _ => {
+                let debruijn = ty::DebruijnIndex::new(1 + rng.below(binders as u64) as u32);
+                self.re_late_bound_with_debruijn(rng.below(2) as u32, debruijn)
+            }
+        }
+    }
+
+    /// A type with the same shape as `ty` but its free and static regions
+    /// redrawn, so that pairs of related types come up often.
+    fn gen_variant(&self, rng: &mut XorShift, ty: Ty<'tcx>) -> Ty<'tcx> {
+        ty_fold::fold_regions(self.tcx(), &ty, |r, _| {
+            match r {
+                ty::ReLateBound(..) => r,
+                _ => self.gen_region(rng, 0),
+            }
+        })
+    }
+
+    fn lub_of(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        self.lub().relate(&a, &b).ok()
+    }
+
+    fn glb_of(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        self.glb().relate(&a, &b).ok()
+    }
+
+    fn is_eq(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool {
+        infer::can_mk_eqty(self.infcx, a, b).is_ok()
+    }
+
+    /// Shrinks a counterexample to `holds` by replacing either type with
+    /// one of its components for as long as the law still fails.
+    fn minimize<F>(&self, mut a: Ty<'tcx>, mut b: Ty<'tcx>, holds: F) -> (Ty<'tcx>, Ty<'tcx>)
+        where F: Fn(&Env<'a, 'tcx>, Ty<'tcx>, Ty<'tcx>) -> bool
+    {
+        let fails = |a: Ty<'tcx>, b: Ty<'tcx>| !self.infcx.probe(|_| holds(self, a, b));
+        'shrink: loop {
+            for a2 in a.walk_shallow().filter(|&t| !ty::type_has_escaping_regions(t)) {
+                if fails(a2, b) { a = a2; continue 'shrink; }
+            }
+            for b2 in b.walk_shallow().filter(|&t| !ty::type_has_escaping_regions(t)) {
+                if fails(a, b2) { b = b2; continue 'shrink; }
+            }
+            return (a, b);
+        }
+    }
+
+    /// Checks `holds` on `a` and `b`, panicking with a minimized
+    /// counterexample if it fails.
+    fn check_law<F>(&self, law: &str, seed: u64, a: Ty<'tcx>, b: Ty<'tcx>, holds: F)
+        where F: Fn(&Env<'a, 'tcx>, Ty<'tcx>, Ty<'tcx>) -> bool
+    {
+        if self.infcx.probe(|_| holds(self, a, b)) {
+            return;
+        }
+        let (a, b) = self.minimize(a, b, holds);
+        panic!("{} violated (seed {}): a = {}, b = {}", law, seed, a, b);
+    }
+}
+
+fn for_each_seed<F>(mut f: F) where F: FnMut(&Env, &mut XorShift, u64) {
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        for seed in 0..PROPERTY_SEEDS {
+            let mut rng = XorShift::new(seed);
+            f(&env, &mut rng, seed);
+        }
+    })
+}
+
+#[test]
+fn property_eq_reflexive_and_symmetric() {
+    for_each_seed(|env, rng, seed| {
+        let a = env.gen_ty(rng, 0, 0);
+        let b = env.gen_variant(rng, a);
+        env.check_law("eq reflexivity", seed, a, a, |env, a, _| env.is_eq(a, a));
+        env.check_law("eq symmetry", seed, a, b,
+                      |env, a, b| env.is_eq(a, b) == env.is_eq(b, a));
+    })
+}
+
+#[test]
+fn property_sub_reflexive_and_transitive() {
+    for_each_seed(|env, rng, seed| {
+        let a = env.gen_ty(rng, 0, 0);
+        let b = env.gen_variant(rng, a);
+        env.check_law("sub reflexivity", seed, a, a, |env, a, _| env.is_subtype(a, a));
+        // b <: LUB(a, b) <: LUB(LUB(a, b), a), so b must be a subtype of
+        // the outer LUB too.
+        env.check_law("sub transitivity", seed, a, b, |env, a, b| {
+            let mid = match env.lub_of(a, b) { Some(t) => t, None => return true };
+            let top = match env.lub_of(mid, a) { Some(t) => t, None => return true };
+            !(env.is_subtype(b, mid) && env.is_subtype(mid, top)) || env.is_subtype(b, top)
+        });
+    })
+}
+
+#[test]
+fn property_lub_glb_are_bounds() {
+    for_each_seed(|env, rng, seed| {
+        let a = env.gen_ty(rng, 0, 0);
+        let b = env.gen_variant(rng, a);
+        env.check_law("LUB is an upper bound", seed, a, b, |env, a, b| {
+            match env.lub_of(a, b) {
+                Some(l) => env.is_subtype(a, l) && env.is_subtype(b, l),
+                None => true,
+            }
+        });
+        env.check_law("GLB is a lower bound", seed, a, b, |env, a, b| {
+            match env.glb_of(a, b) {
+                Some(g) => env.is_subtype(g, a) && env.is_subtype(g, b),
+                None => true,
+            }
+        });
+    })
+}
@@ -30,6 +30,7 @@
 
 use metadata::{csearch, decoder};
 use middle::def::*;
+use middle::infer::error_reporting::note_and_explain_region;
 use middle::subst::Substs;
 use middle::ty::{self, Ty};
 use middle::{def, pat_util, stability};
@@ -288,7 +289,9 @@ impl LintPass for TypeLimits {
                 ast::TyI8 =>    (i8::MIN  as i64, i8::MAX  as i64),
                 ast::TyI16 =>   (i16::MIN as i64, i16::MAX as i64),
                 ast::TyI32 =>   (i32::MIN as i64, i32::MAX as i64),
-                ast::TyI64 =>   (i64::MIN,        i64::MAX)
+                ast::TyI64 =>   (i64::MIN,        i64::MAX),
+                // literals are parsed as u64, so this is as wide as they get
+                ast::TyI128 =>  (i64::MIN,        i64::MAX)
             }
         }
 
@@ -298,7 +301,8 @@ impl LintPass for TypeLimits {
                 ast::TyU8 =>    (u8::MIN   as u64, u8::MAX   as u64),
                 ast::TyU16 =>   (u16::MIN  as u64, u16::MAX  as u64),
                 ast::TyU32 =>   (u32::MIN  as u64, u32::MAX  as u64),
-                ast::TyU64 =>   (u64::MIN,         u64::MAX)
+                ast::TyU64 =>   (u64::MIN,         u64::MAX),
+                ast::TyU128 =>  (u64::MIN,         u64::MAX)
             }
         }
 
@@ -315,7 +319,8 @@ impl LintPass for TypeLimits {
                 ast::TyI8 =>    i8::BITS  as u64,
                 ast::TyI16 =>   i16::BITS as u64,
                 ast::TyI32 =>   i32::BITS as u64,
-                ast::TyI64 =>   i64::BITS as u64
+                ast::TyI64 =>   i64::BITS as u64,
+                ast::TyI128 =>  128
             }
         }
 
@@ -325,7 +330,8 @@ impl LintPass for TypeLimits {
                 ast::TyU8 =>    u8::BITS  as u64,
                 ast::TyU16 =>   u16::BITS as u64,
                 ast::TyU32 =>   u32::BITS as u64,
-                ast::TyU64 =>   u64::BITS as u64
+                ast::TyU64 =>   u64::BITS as u64,
+                ast::TyU128 =>  128
             }
         }
 
@@ -1163,7 +1169,7 @@ impl UnusedParens {
                     contains_exterior_struct_lit(&**x)
                 }
 
-                ast::ExprMethodCall(_, _, ref exprs) => {
+                ast::ExprMethodCall(_, _, _, ref exprs) => {
                     // X { y: 1 }.bar(...)
                     contains_exterior_struct_lit(&*exprs[0])
                 }
@@ -1786,12 +1792,6 @@ impl LintPass for MissingDebugImplementations {
     }
 }
 
-declare_lint! {
-    DEPRECATED,
-    Warn,
-    "detects use of #[deprecated] items"
-}
-
 /// Checks for use of items with `#[deprecated]` attributes
 #[derive(Copy, Clone)]
 pub struct Stability;
@@ -1824,7 +1824,9 @@ impl Stability {
 
 impl LintPass for Stability {
     fn get_lints(&self) -> LintArray {
-        lint_array!(DEPRECATED)
+        // `DEPRECATED` is registered with the hardwired lints, as typeck
+        // reports deprecated methods when it picks them.
+        lint_array!()
     }
 
     fn check_item(&mut self, cx: &Context, item: &ast::Item) {
@@ -1833,6 +1835,9 @@ impl LintPass for Stability {
     }
 
     fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+        if cx.tcx.stability.borrow().deprecation_reported(e.id) {
+            return
+        }
         stability::check_expr(cx.tcx, e,
                               &mut |id, sp, stab| self.lint(cx, id, sp, stab));
     }
@@ -2019,7 +2024,7 @@ impl LintPass for UnconditionalRecursion {
                         ty::MethodParam { impl_def_id: Some(impl_def_id), .. }) => {
 
                         let name = match tcx.map.expect_expr(id).node {
-                            ast::ExprMethodCall(ref sp_ident, _, _) => sp_ident.node,
+                            ast::ExprMethodCall(ref sp_ident, _, _, _) => sp_ident.node,
                             _ => tcx.sess.span_bug(
                                 tcx.map.span(id),
                                 "non-method call expr behaving like a method call?")
@@ -2284,3 +2289,39 @@ impl LintPass for DropWithReprExtern {
         }
     }
 }
+
+declare_lint! {
+    OBJECT_LIFETIME_DEFAULT_CHANGE,
+    Allow,
+    "object types whose default lifetime bound will change to `'static`"
+}
+
+/// Lists the object types whose meaning will change with the switch to
+/// `'static` as the default lifetime bound (RFC 1156).
+#[derive(Copy, Clone)]
+pub struct ObjectLifetimeDefaultChange;
+
+impl LintPass for ObjectLifetimeDefaultChange {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(OBJECT_LIFETIME_DEFAULT_CHANGE)
+    }
+
+    fn check_crate(&mut self, ctx: &Context, _: &ast::Crate) {
+        // A type converted more than once is recorded each time.
+        let mut seen = HashSet::new();
+        for default in ctx.tcx.future_compat_region_defaults.borrow().iter() {
+            if !seen.insert(default.span) {
+                continue;
+            }
+            ctx.span_lint(OBJECT_LIFETIME_DEFAULT_CHANGE,
+                          default.span,
+                          "the default lifetime bound of this object type will \
+                           change to `'static` in Rust 1.3");
+            // FIXME #19668: could be span_lint_note instead of manual guard.
+            if ctx.current_level(OBJECT_LIFETIME_DEFAULT_CHANGE) != Level::Allow {
+                note_and_explain_region(ctx.tcx, "the bound is currently ",
+                                        default.region_bound, "");
+            }
+        }
+    }
+}
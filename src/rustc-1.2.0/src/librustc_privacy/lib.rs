@@ -902,7 +902,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivacyVisitor<'a, 'tcx> {
                     self.check_field(expr.span, id, UnnamedField(idx.node));
                 }
             }
-            ast::ExprMethodCall(ident, _, _) => {
+            ast::ExprMethodCall(ident, _, _, _) => {
                 let method_call = MethodCall::expr(expr.id);
                 match self.tcx.method_map.borrow().get(&method_call) {
                     None => {
@@ -805,10 +805,14 @@ impl <'l, 'tcx> DumpCsvVisitor<'l, 'tcx> {
                 } else {
                     None
                 };
+                // A method named by path has no receiver, so there is
+                // nothing to dispatch on or adjust.
                 self.fmt.meth_call_str(span,
                                        sub_span,
                                        defid,
                                        Some(declid),
+                                       "path",
+                                       "",
                                        self.cur_scope);
             },
             def::DefFn(def_id, _) => {
@@ -890,7 +894,7 @@ impl <'l, 'tcx> DumpCsvVisitor<'l, 'tcx> {
                            args: &Vec<P<ast::Expr>>) {
         let method_map = self.tcx.method_map.borrow();
         let method_callee = method_map.get(&ty::MethodCall::expr(ex.id)).unwrap();
-        let (def_id, decl_id) = match method_callee.origin {
+        let (def_id, decl_id, dispatch) = match method_callee.origin {
             ty::MethodStatic(def_id) |
             ty::MethodStaticClosure(def_id) => {
                 // method invoked on an object with a concrete type (not a static method)
@@ -911,28 +915,40 @@ impl <'l, 'tcx> DumpCsvVisitor<'l, 'tcx> {
                                        "save::process_method_call: non-method \
                                         DefId in MethodStatic or MethodStaticClosure"),
                 };
-                (Some(def_id), decl_id)
+                let dispatch = match method_callee.origin {
+                    ty::MethodStaticClosure(_) => "static_closure",
+                    _ => "static",
+                };
+                (Some(def_id), decl_id, dispatch)
             }
             ty::MethodTypeParam(ref mp) => {
                 // method invoked on a type parameter
                 let trait_item = ty::trait_item(self.tcx,
                                                 mp.trait_ref.def_id,
                                                 mp.method_num);
-                (None, Some(trait_item.def_id()))
+                (None, Some(trait_item.def_id()), "type_param")
             }
             ty::MethodTraitObject(ref mo) => {
                 // method invoked on a trait instance
                 let trait_item = ty::trait_item(self.tcx,
                                                 mo.trait_ref.def_id,
                                                 mo.method_num);
-                (None, Some(trait_item.def_id()))
+                (None, Some(trait_item.def_id()), "trait_object")
             }
         };
+        // args[0] is the receiver; record how it was adjusted to match
+        // the method's self type.
+        let adjustment = match self.tcx.adjustments.borrow().get(&args[0].id) {
+            Some(adj) => adjustment_to_string(adj),
+            None => String::new(),
+        };
         let sub_span = self.span.sub_span_for_meth_name(ex.span);
         self.fmt.meth_call_str(ex.span,
                                sub_span,
                                def_id,
                                decl_id,
+                               dispatch,
+                               &adjustment,
                                self.cur_scope);
 
         // walk receiver and args
@@ -1228,7 +1244,7 @@ impl<'l, 'tcx, 'v> Visitor<'v> for DumpCsvVisitor<'l, 'tcx> {
             }
             ast::ExprStruct(ref path, ref fields, ref base) =>
                 self.process_struct_lit(ex, path, fields, base),
-            ast::ExprMethodCall(_, _, ref args) => self.process_method_call(ex, args),
+            ast::ExprMethodCall(_, _, _, ref args) => self.process_method_call(ex, args),
             ast::ExprField(ref sub_ex, _) => {
                 if generated_code(sub_ex.span) {
                     return
@@ -1408,3 +1424,30 @@ impl<'l, 'tcx, 'v> Visitor<'v> for DumpCsvVisitor<'l, 'tcx> {
         visit::walk_expr_opt(self, &l.init);
     }
 }
+
+/// Describes a receiver adjustment for the `method_call` row, e.g.
+/// `deref(1),&mut` for a receiver that was dereferenced once and then
+/// mutably borrowed.
+fn adjustment_to_string(adj: &ty::AutoAdjustment) -> String {
+    match *adj {
+        ty::AdjustReifyFnPointer => "reify_fn_ptr".to_string(),
+        ty::AdjustUnsafeFnPointer => "unsafe_fn_ptr".to_string(),
+        ty::AdjustDerefRef(ref adr) => {
+            let mut parts = vec![];
+            if adr.autoderefs > 0 {
+                parts.push(format!("deref({})", adr.autoderefs));
+            }
+            match adr.autoref {
+                Some(ty::AutoPtr(_, ast::MutImmutable)) => parts.push("&".to_string()),
+                Some(ty::AutoPtr(_, ast::MutMutable)) => parts.push("&mut".to_string()),
+                Some(ty::AutoUnsafe(ast::MutImmutable)) => parts.push("*const".to_string()),
+                Some(ty::AutoUnsafe(ast::MutMutable)) => parts.push("*mut".to_string()),
+                None => {}
+            }
+            if let Some(target) = adr.unsize {
+                parts.push(format!("unsize({})", target));
+            }
+            parts.connect(",")
+        }
+    }
+}
@@ -138,7 +138,8 @@ impl<'a> FmtStrs<'a> {
                             vec!("base","basecrate","derived","derivedcrate"),
                             true, false),
             MethodCall => ("method_call",
-                           vec!("refid","refidcrate","declid","declidcrate","scopeid"),
+                           vec!("refid","refidcrate","declid","declidcrate","dispatch",
+                                "adjustment","scopeid"),
                            true, true),
             Typedef => ("typedef", vec!("id","qualname","value"), true, true),
             ExternalCrate => ("external_crate", vec!("name","crate","file_name"), false, false),
@@ -538,6 +539,8 @@ impl<'a> FmtStrs<'a> {
                          sub_span: Option<Span>,
                          defid: Option<DefId>,
                          declid: Option<DefId>,
+                         dispatch: &str,
+                         adjustment: &str,
                          scope_id: NodeId) {
         let (dfn, dfk) = match defid {
             Some(defid) => (defid.node, defid.krate),
@@ -550,7 +553,7 @@ impl<'a> FmtStrs<'a> {
         self.check_and_record(MethodCall,
                               span,
                               sub_span,
-                              svec!(dfn, dfk, dcn, dck, scope_id));
+                              svec!(dfn, dfk, dcn, dck, dispatch, adjustment, scope_id));
     }
 
     pub fn sub_mod_ref_str(&mut self,
@@ -581,8 +581,10 @@ fn cast_shift_rhs<F, G>(op: ast::BinOp_,
     }
 }
 
+/// Returns the LLVM type of the signed integer type `val_t` along with a
+/// constant holding its minimum value.
 pub fn llty_and_min_for_signed_ty<'blk, 'tcx>(cx: Block<'blk, 'tcx>,
-                                              val_t: Ty<'tcx>) -> (Type, u64) {
+                                              val_t: Ty<'tcx>) -> (Type, ValueRef) {
     match val_t.sty {
         ty::TyInt(t) => {
             let llty = Type::int_from_ty(cx.ccx(), t);
@@ -593,8 +595,13 @@ pub fn llty_and_min_for_signed_ty<'blk, 'tcx>(cx: Block<'blk, 'tcx>,
                 ast::TyI16 => i16::MIN as u64,
                 ast::TyI32 => i32::MIN as u64,
                 ast::TyI64 => i64::MIN as u64,
+                // `i128::MIN` does not fit in a u64, so build it as `1 << 127`.
+                ast::TyI128 => unsafe {
+                    return (llty, llvm::LLVMConstShl(C_integral(llty, 1, false),
+                                                     C_integral(llty, 127, false)));
+                },
             };
-            (llty, min)
+            (llty, C_integral(llty, min, true))
         }
         _ => unreachable!(),
     }
@@ -657,8 +664,7 @@ pub fn fail_if_zero_or_overflows<'blk, 'tcx>(
         let minus_one = ICmp(bcx, llvm::IntEQ, rhs,
                              C_integral(llty, !0, false), debug_loc);
         with_cond(bcx, minus_one, |bcx| {
-            let is_min = ICmp(bcx, llvm::IntEQ, lhs,
-                              C_integral(llty, min, true), debug_loc);
+            let is_min = ICmp(bcx, llvm::IntEQ, lhs, min, debug_loc);
             with_cond(bcx, is_min, |bcx| {
                 controlflow::trans_fail(bcx,
                                         call_info,
@@ -2660,11 +2666,7 @@ pub fn trans_crate(tcx: &ty::ctxt, analysis: ty::CrateAnalysis) -> CrateTranslat
     let ty::CrateAnalysis { export_map, reachable, name, .. } = analysis;
     let krate = tcx.map.krate();
 
-    let check_overflow = if let Some(v) = tcx.sess.opts.debugging_opts.force_overflow_checks {
-        v
-    } else {
-        tcx.sess.opts.debug_assertions
-    };
+    let check_overflow = tcx.sess.overflow_checks();
 
     let check_dropflag = if let Some(v) = tcx.sess.opts.debugging_opts.force_dropflag_checks {
         v
@@ -1081,8 +1081,8 @@ pub fn trans_args<'a, 'blk, 'tcx>(cx: Block<'blk, 'tcx>,
                                   llargs);
 
             assert_eq!(arg_tys.len(), 1 + rhs.len());
-            for (rhs, rhs_id) in rhs {
-                bcx = trans_arg_datum(bcx, arg_tys[1], rhs,
+            for (i, (rhs, rhs_id)) in rhs.into_iter().enumerate() {
+                bcx = trans_arg_datum(bcx, arg_tys[1 + i], rhs,
                                       arg_cleanup_scope,
                                       if autoref { DoAutorefArg(rhs_id) } else { DontAutorefArg },
                                       llargs);
@@ -895,7 +895,7 @@ fn const_expr_unadjusted<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                   _ => cx.sess().span_bug(e.span, "expected a struct, variant, or const fn def")
               }
           }
-          ast::ExprMethodCall(_, _, ref args) => {
+          ast::ExprMethodCall(_, _, _, ref args) => {
               let arg_vals = map_list(args);
               let method_call = ty::MethodCall::expr(e.id);
               let method_did = match cx.tcx().method_map.borrow()[&method_call].origin {
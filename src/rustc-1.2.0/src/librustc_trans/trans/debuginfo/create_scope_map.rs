@@ -452,7 +452,7 @@ fn walk_expr(cx: &CrateContext,
             }
         }
 
-        ast::ExprMethodCall(_, _, ref args) => {
+        ast::ExprMethodCall(_, _, _, ref args) => {
             for arg_exp in args {
                 walk_expr(cx, &**arg_exp, scope_stack, scope_map);
             }
@@ -981,6 +981,21 @@ fn trans_rvalue_stmt_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
         ast::ExprLoop(ref body, _) => {
             controlflow::trans_loop(bcx, expr, &**body)
         }
+        ast::ExprAssign(ref dst, ref src) if is_index_assign(bcx, &**dst) => {
+            // `container[index] = value` resolved to `IndexAssign`: the
+            // method receives the autoref'd container, the index and the
+            // value, so there is no lvalue to store into.
+            let (base, idx) = match dst.node {
+                ast::ExprIndex(ref base, ref idx) => (base, idx),
+                _ => unreachable!()
+            };
+            let base_datum = unpack_datum!(bcx, trans(bcx, &**base));
+            let ix_datum = unpack_datum!(bcx, trans(bcx, &**idx));
+            let src_datum = unpack_datum!(bcx, trans(bcx, &**src));
+            trans_overloaded_op(bcx, expr, MethodCall::expr(dst.id), base_datum,
+                                vec![(ix_datum, idx.id), (src_datum, src.id)],
+                                None, false).bcx
+        }
         ast::ExprAssign(ref dst, ref src) => {
             let src_datum = unpack_datum!(bcx, trans(bcx, &**src));
             let dst_datum = unpack_datum!(bcx, trans_to_lvalue(bcx, &**dst, "assign"));
@@ -1168,7 +1183,7 @@ fn trans_rvalue_dps_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                    dest)
             }
         }
-        ast::ExprMethodCall(_, _, ref args) => {
+        ast::ExprMethodCall(_, _, _, ref args) => {
             callee::trans_method_call(bcx,
                                       expr,
                                       &*args[0],
@@ -1634,8 +1649,7 @@ fn trans_unary<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                     let result = Neg(bcx, val, debug_loc);
                     let bcx = if bcx.ccx().check_overflow() && is_signed {
                         let (llty, min) = base::llty_and_min_for_signed_ty(bcx, un_ty);
-                        let is_min = ICmp(bcx, llvm::IntEQ, val,
-                                          C_integral(llty, min, true), debug_loc);
+                        let is_min = ICmp(bcx, llvm::IntEQ, val, min, debug_loc);
                         with_cond(bcx, is_min, |bcx| {
                             let msg = InternedString::new(
                                 "attempted to negate with overflow");
@@ -1935,6 +1949,18 @@ fn trans_binary<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     }
 }
 
+fn is_index_assign(bcx: Block, dst: &ast::Expr) -> bool {
+    if let ast::ExprIndex(..) = dst.node {
+        let method_map = bcx.tcx().method_map.borrow();
+        match method_map.get(&MethodCall::expr(dst.id)) {
+            Some(callee) => ty::is_index_assign_method(bcx.tcx(), &callee.origin),
+            None => false
+        }
+    } else {
+        false
+    }
+}
+
 fn trans_overloaded_op<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                    expr: &ast::Expr,
                                    method_call: MethodCall,
@@ -2375,11 +2401,13 @@ impl OverflowOpViaIntrinsic {
                 TyInt(TyI16) => "llvm.sadd.with.overflow.i16",
                 TyInt(TyI32) => "llvm.sadd.with.overflow.i32",
                 TyInt(TyI64) => "llvm.sadd.with.overflow.i64",
+                TyInt(TyI128) => "llvm.sadd.with.overflow.i128",
 
                 TyUint(TyU8) => "llvm.uadd.with.overflow.i8",
                 TyUint(TyU16) => "llvm.uadd.with.overflow.i16",
                 TyUint(TyU32) => "llvm.uadd.with.overflow.i32",
                 TyUint(TyU64) => "llvm.uadd.with.overflow.i64",
+                TyUint(TyU128) => "llvm.uadd.with.overflow.i128",
 
                 _ => unreachable!(),
             },
@@ -2388,11 +2416,13 @@ impl OverflowOpViaIntrinsic {
                 TyInt(TyI16) => "llvm.ssub.with.overflow.i16",
                 TyInt(TyI32) => "llvm.ssub.with.overflow.i32",
                 TyInt(TyI64) => "llvm.ssub.with.overflow.i64",
+                TyInt(TyI128) => "llvm.ssub.with.overflow.i128",
 
                 TyUint(TyU8) => "llvm.usub.with.overflow.i8",
                 TyUint(TyU16) => "llvm.usub.with.overflow.i16",
                 TyUint(TyU32) => "llvm.usub.with.overflow.i32",
                 TyUint(TyU64) => "llvm.usub.with.overflow.i64",
+                TyUint(TyU128) => "llvm.usub.with.overflow.i128",
 
                 _ => unreachable!(),
             },
@@ -2401,11 +2431,13 @@ impl OverflowOpViaIntrinsic {
                 TyInt(TyI16) => "llvm.smul.with.overflow.i16",
                 TyInt(TyI32) => "llvm.smul.with.overflow.i32",
                 TyInt(TyI64) => "llvm.smul.with.overflow.i64",
+                TyInt(TyI128) => "llvm.smul.with.overflow.i128",
 
                 TyUint(TyU8) => "llvm.umul.with.overflow.i8",
                 TyUint(TyU16) => "llvm.umul.with.overflow.i16",
                 TyUint(TyU32) => "llvm.umul.with.overflow.i32",
                 TyUint(TyU64) => "llvm.umul.with.overflow.i64",
+                TyUint(TyU128) => "llvm.umul.with.overflow.i128",
 
                 _ => unreachable!(),
             },
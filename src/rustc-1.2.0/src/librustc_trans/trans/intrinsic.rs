@@ -79,9 +79,11 @@ pub fn get_simple_intrinsic(ccx: &CrateContext, item: &ast::ForeignItem) -> Opti
         "ctpop16" => "llvm.ctpop.i16",
         "ctpop32" => "llvm.ctpop.i32",
         "ctpop64" => "llvm.ctpop.i64",
+        "ctpop128" => "llvm.ctpop.i128",
         "bswap16" => "llvm.bswap.i16",
         "bswap32" => "llvm.bswap.i32",
         "bswap64" => "llvm.bswap.i64",
+        "bswap128" => "llvm.bswap.i128",
         "assume" => "llvm.assume",
         _ => return None
     };
@@ -547,6 +549,10 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                                "llvm.ctlz.i64",
                                                llargs[0],
                                                call_debug_location),
+        (_, "ctlz128") => count_zeros_intrinsic(bcx,
+                                                "llvm.ctlz.i128",
+                                                llargs[0],
+                                                call_debug_location),
         (_, "cttz8") => count_zeros_intrinsic(bcx,
                                               "llvm.cttz.i8",
                                               llargs[0],
@@ -563,6 +569,10 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                                "llvm.cttz.i64",
                                                llargs[0],
                                                call_debug_location),
+        (_, "cttz128") => count_zeros_intrinsic(bcx,
+                                                "llvm.cttz.i128",
+                                                llargs[0],
+                                                call_debug_location),
 
         (_, "i8_add_with_overflow") =>
             with_overflow_intrinsic(bcx,
@@ -592,6 +602,13 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                     llargs[0],
                                     llargs[1],
                                     call_debug_location),
+        (_, "i128_add_with_overflow") =>
+            with_overflow_intrinsic(bcx,
+                                    "llvm.sadd.with.overflow.i128",
+                                    ret_ty,
+                                    llargs[0],
+                                    llargs[1],
+                                    call_debug_location),
 
         (_, "u8_add_with_overflow") =>
             with_overflow_intrinsic(bcx,
@@ -621,6 +638,13 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                     llargs[0],
                                     llargs[1],
                                     call_debug_location),
+        (_, "u128_add_with_overflow") =>
+            with_overflow_intrinsic(bcx,
+                                    "llvm.uadd.with.overflow.i128",
+                                    ret_ty,
+                                    llargs[0],
+                                    llargs[1],
+                                    call_debug_location),
         (_, "i8_sub_with_overflow") =>
             with_overflow_intrinsic(bcx,
                                     "llvm.ssub.with.overflow.i8",
@@ -649,6 +673,13 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                     llargs[0],
                                     llargs[1],
                                     call_debug_location),
+        (_, "i128_sub_with_overflow") =>
+            with_overflow_intrinsic(bcx,
+                                    "llvm.ssub.with.overflow.i128",
+                                    ret_ty,
+                                    llargs[0],
+                                    llargs[1],
+                                    call_debug_location),
         (_, "u8_sub_with_overflow") =>
             with_overflow_intrinsic(bcx,
                                     "llvm.usub.with.overflow.i8",
@@ -677,6 +708,13 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                     llargs[0],
                                     llargs[1],
                                     call_debug_location),
+        (_, "u128_sub_with_overflow") =>
+            with_overflow_intrinsic(bcx,
+                                    "llvm.usub.with.overflow.i128",
+                                    ret_ty,
+                                    llargs[0],
+                                    llargs[1],
+                                    call_debug_location),
         (_, "i8_mul_with_overflow") =>
             with_overflow_intrinsic(bcx,
                                     "llvm.smul.with.overflow.i8",
@@ -705,6 +743,13 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                     llargs[0],
                                     llargs[1],
                                     call_debug_location),
+        (_, "i128_mul_with_overflow") =>
+            with_overflow_intrinsic(bcx,
+                                    "llvm.smul.with.overflow.i128",
+                                    ret_ty,
+                                    llargs[0],
+                                    llargs[1],
+                                    call_debug_location),
         (_, "u8_mul_with_overflow") =>
             with_overflow_intrinsic(bcx,
                                     "llvm.umul.with.overflow.i8",
@@ -733,6 +778,13 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
                                     llargs[0],
                                     llargs[1],
                                     call_debug_location),
+        (_, "u128_mul_with_overflow") =>
+            with_overflow_intrinsic(bcx,
+                                    "llvm.umul.with.overflow.i128",
+                                    ret_ty,
+                                    llargs[0],
+                                    llargs[1],
+                                    call_debug_location),
 
         (_, "unchecked_udiv") => UDiv(bcx, llargs[0], llargs[1], call_debug_location),
         (_, "unchecked_sdiv") => SDiv(bcx, llargs[0], llargs[1], call_debug_location),
@@ -814,15 +814,113 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                 }
             }
 
-            trans_fn_ref_with_substs(ccx,
-                                     impl_method_def_id,
-                                     ExprId(0),
-                                     param_substs,
-                                     substs.clone()).val
+            let llfn = trans_fn_ref_with_substs(ccx,
+                                                impl_method_def_id,
+                                                ExprId(0),
+                                                param_substs,
+                                                substs.clone()).val;
+
+            // A by-value `self` cannot be passed through the object, so
+            // the vtable gets a shim that takes the boxed value instead.
+            if trait_method_type.takes_self_by_value() {
+                trans_by_value_self_shim(ccx, llfn, &impl_method_type, &substs, param_substs)
+            } else {
+                llfn
+            }
         })
         .collect()
 }
 
+/// Generates the vtable entry for a method taking `self` by value. Such
+/// methods can only be called on a `Box<Trait>`, which typeck treats as if
+/// the method took `self: Box<Self>`. The shim therefore receives the
+/// box's data pointer, moves `Self` out of it into the call to `llfn`, and
+/// frees the allocation without dropping its contents.
+fn trans_by_value_self_shim<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                      llfn: ValueRef,
+                                      method: &ty::Method<'tcx>,
+                                      substs: &subst::Substs<'tcx>,
+                                      param_substs: &'tcx subst::Substs<'tcx>)
+                                      -> ValueRef
+{
+    let _icx = push_ctxt("trans_by_value_self_shim");
+    let tcx = ccx.tcx();
+
+    let fty = method.fty.subst(tcx, substs);
+    let fty = monomorphize::apply_param_substs(tcx, param_substs, &fty);
+    let fty = monomorphize::normalize_associated_type(tcx, &fty);
+    let sig = ty::erase_late_bound_regions(tcx, &fty.sig);
+    let self_ty = sig.inputs[0];
+
+    debug!("trans_by_value_self_shim(method={:?}, self_ty={:?})", method, self_ty);
+
+    let mut shim_inputs = sig.inputs.clone();
+    shim_inputs[0] = ty::mk_uniq(tcx, self_ty);
+    let shim_fn_ty = ty::mk_bare_fn(tcx, None, tcx.mk_bare_fn(ty::BareFnTy {
+        unsafety: fty.unsafety,
+        abi: fty.abi,
+        sig: ty::Binder(ty::FnSig {
+            inputs: shim_inputs,
+            output: sig.output,
+            variadic: sig.variadic,
+        }),
+    }));
+    let method_fn_ty = ty::mk_bare_fn(tcx, None, tcx.mk_bare_fn(fty));
+
+    let function_name =
+        link::mangle_internal_name_by_type_and_seq(ccx, shim_fn_ty, "by_value_self_shim");
+    let llshim = declare::define_internal_rust_fn(ccx, &function_name, shim_fn_ty)
+        .unwrap_or_else(|| {
+            ccx.sess().bug(&format!("symbol `{}` already defined", function_name));
+        });
+
+    let empty_substs = tcx.mk_substs(Substs::trans_empty());
+    let (block_arena, fcx): (TypedArena<_>, FunctionContext);
+    block_arena = TypedArena::new();
+    fcx = new_fn_ctxt(ccx,
+                      llshim,
+                      ast::DUMMY_NODE_ID,
+                      false,
+                      sig.output,
+                      empty_substs,
+                      None,
+                      &block_arena);
+    let mut bcx = init_function(&fcx, false, sig.output);
+
+    let llargs = get_params(fcx.llfn);
+    let self_idx = fcx.arg_offset();
+    let llbox = llargs[self_idx];
+
+    // Immediates are passed by value, anything else by a pointer the
+    // callee takes ownership of, which may as well be the box itself.
+    let llself = if type_is_immediate(ccx, self_ty) {
+        load_ty(bcx, llbox, self_ty)
+    } else {
+        llbox
+    };
+    let mut call_args = vec![llself];
+    call_args.extend(llargs[(self_idx + 1)..].iter().cloned());
+
+    assert!(!fcx.needs_ret_allocas);
+
+    let dest =
+        fcx.llretslotptr.get().map(
+            |_| expr::SaveIn(fcx.get_ret_slot(bcx, sig.output, "ret_slot")));
+
+    bcx = trans_call_inner(bcx,
+                           DebugLoc::None,
+                           method_fn_ty,
+                           |bcx, _| Callee { bcx: bcx, data: Fn(llfn) },
+                           ArgVals(&call_args),
+                           dest).bcx;
+
+    bcx = glue::trans_exchange_free_ty(bcx, llbox, self_ty, DebugLoc::None);
+
+    finish_fn(&fcx, bcx, sig.output, DebugLoc::None);
+
+    llshim
+}
+
 /// Replace the self type (&Self or Box<Self>) with an opaque pointer.
 pub fn opaque_method_ty<'tcx>(tcx: &ty::ctxt<'tcx>, method_ty: &ty::BareFnTy<'tcx>)
         -> &'tcx ty::BareFnTy<'tcx> {
@@ -67,6 +67,7 @@ use std::iter::repeat;
 use std::slice;
 use syntax::{abi, ast, ast_util};
 use syntax::codemap::{Span, Pos};
+use syntax::feature_gate;
 use syntax::parse::token;
 use syntax::print::pprust;
 
@@ -1445,6 +1446,16 @@ fn base_def_to_ty<'tcx>(this: &AstConv<'tcx>,
             opt_self_ty.expect("missing T in <T>::a::b::c")
         }
         def::DefPrimTy(prim_ty) => {
+            match prim_ty {
+                ast::TyInt(ast::TyI128) | ast::TyUint(ast::TyU128)
+                        if !tcx.sess.features.borrow().i128_type => {
+                    feature_gate::emit_feature_err(&tcx.sess.parse_sess.span_diagnostic,
+                                                   "i128_type",
+                                                   span,
+                                                   "128-bit integers are not stable");
+                }
+                _ => {}
+            }
             prim_ty_to_ty(tcx, base_segments, prim_ty)
         }
         _ => {
@@ -2032,6 +2043,11 @@ pub fn conv_existential_bounds_from_partitioned_bounds<'tcx>(
     debug!("region_bound: {:?} will_change: {:?}",
            region_bound, will_change);
 
+    if will_change {
+        this.tcx().future_compat_region_defaults.borrow_mut().push(
+            ty::FutureCompatRegionDefault { span: span, region_bound: region_bound });
+    }
+
     ty::sort_bounds_list(&mut projection_bounds);
 
     ty::ExistentialBounds {
@@ -26,6 +26,7 @@ use super::UnresolvedTypeAction;
 use super::write_call;
 
 use CrateCtxt;
+use middle::def;
 use middle::infer;
 use middle::ty::{self, Ty, ClosureTyper};
 use syntax::ast;
@@ -36,13 +37,22 @@ use syntax::ptr::P;
 /// Check that it is legal to call methods of the trait corresponding
 /// to `trait_id` (this only cares about the trait, not the specific
 /// method that is called)
-pub fn check_legal_trait_for_method_call(ccx: &CrateCtxt, span: Span, trait_id: ast::DefId) {
+///
+/// `dropped` is the call expression and the value it drops, if this is a
+/// method call that can be rewritten as a call to `std::mem::drop`.
+pub fn check_legal_trait_for_method_call(ccx: &CrateCtxt,
+                                         span: Span,
+                                         trait_id: ast::DefId,
+                                         dropped: Option<(&ast::Expr, &ast::Expr)>) {
     let tcx = ccx.tcx;
     let did = Some(trait_id);
     let li = &tcx.lang_items;
 
     if did == li.drop_trait() {
         span_err!(tcx.sess, span, E0040, "explicit use of destructor method");
+        if let Some((call_expr, value)) = dropped {
+            suggest_mem_drop(tcx, call_expr, value);
+        }
     } else if !tcx.sess.features.borrow().unboxed_closures {
         // the #[feature(unboxed_closures)] feature isn't
         // activated so we need to enforce the closure
@@ -66,6 +76,52 @@ pub fn check_legal_trait_for_method_call(ccx: &CrateCtxt, span: Span, trait_id:
     }
 }
 
+/// Suggests replacing `call_expr`, an explicit destructor call, with a call
+/// to `std::mem::drop` that takes `value` by value.
+fn suggest_mem_drop(tcx: &ty::ctxt, call_expr: &ast::Expr, value: &ast::Expr) {
+    match tcx.sess.codemap().span_to_snippet(value.span) {
+        Ok(snippet) => {
+            tcx.sess.span_suggestion(call_expr.span,
+                                     "use `std::mem::drop` to drop the value early:",
+                                     format!("std::mem::drop({})", snippet));
+        }
+        Err(_) => {
+            fileline_help!(tcx.sess, call_expr.span,
+                           "use `std::mem::drop` to drop the value early");
+        }
+    }
+}
+
+/// The value dropped by a call of the form `Drop::drop(&mut value)`, if
+/// `callee_expr` names a destructor. The error itself is reported when the
+/// path is checked.
+fn explicit_destructor_call_arg<'a>(tcx: &ty::ctxt,
+                                    callee_expr: &ast::Expr,
+                                    arg_exprs: &'a [P<ast::Expr>])
+                                    -> Option<&'a ast::Expr> {
+    match callee_expr.node {
+        ast::ExprPath(..) => {}
+        _ => return None
+    }
+    let is_destructor = match tcx.def_map.borrow().get(&callee_expr.id) {
+        Some(&def::PathResolution { base_def: def::DefMethod(did, _), depth: 0, .. }) => {
+            // Without a `Drop` lang item, an inherent method is not a
+            // destructor just because neither belongs to a trait.
+            let drop_trait = tcx.lang_items.drop_trait();
+            (drop_trait.is_some() && ty::trait_of_item(tcx, did) == drop_trait) ||
+                tcx.destructors.borrow().contains(&did)
+        }
+        _ => false
+    };
+    if !is_destructor || arg_exprs.len() != 1 {
+        return None;
+    }
+    match arg_exprs[0].node {
+        ast::ExprAddrOf(ast::MutMutable, ref value) => Some(&**value),
+        _ => None
+    }
+}
+
 pub fn check_call<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                             call_expr: &'tcx ast::Expr,
                             callee_expr: &'tcx ast::Expr,
@@ -73,6 +129,9 @@ pub fn check_call<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                             expected: Expectation<'tcx>)
 {
     check_expr(fcx, callee_expr);
+    if let Some(value) = explicit_destructor_call_arg(fcx.tcx(), callee_expr, arg_exprs) {
+        suggest_mem_drop(fcx.tcx(), call_expr, value);
+    }
     let original_callee_ty = fcx.expr_ty(callee_expr);
     let (callee_ty, _, result) =
         autoderef(fcx,
@@ -317,6 +376,10 @@ fn confirm_overloaded_call<'a,'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                     expected);
     write_call(fcx, call_expr, output_type);
 
+    // `FnMut::call_mut` borrows the callee mutably, so overloaded derefs and
+    // indexing on its path must be the mutable variants.
+    method::fixup_derefs_on_method_receiver_if_necessary(fcx, callee_expr, &method_callee);
+
     write_overloaded_call_method_map(fcx, call_expr, method_callee);
 }
 
@@ -377,6 +440,9 @@ impl<'tcx> DeferredCallResolution<'tcx> for CallResolution<'tcx> {
                                method_sig.output.unwrap_or(nilty),
                                self.fn_sig.output.unwrap_or(nilty));
 
+                method::fixup_derefs_on_method_receiver_if_necessary(fcx,
+                                                                     self.callee_expr,
+                                                                     &method_callee);
                 write_overloaded_call_method_map(fcx, self.call_expr, method_callee);
             }
             None => {
@@ -105,6 +105,22 @@ enum CastError {
     NonScalar,
 }
 
+/// The error to report for a cast that `CastKind::classify` rejects.
+fn cast_error<'tcx>(t_from: CastTy<'tcx>, t_cast: CastTy<'tcx>) -> CastError {
+    use middle::cast::IntTy::*;
+    use middle::cast::CastTy::*;
+
+    match (t_from, t_cast) {
+        (_, RPtr(_)) | (_, Int(CEnum)) | (_, FnPtr) => CastError::NonScalar,
+        (_, Int(Bool)) => CastError::CastToBool,
+        (_, Int(Char)) => CastError::CastToChar,
+        (Int(Bool), Float) | (Int(CEnum), Float) | (Int(Char), Float) => CastError::NeedViaInt,
+        (RPtr(_), Int(_)) | (RPtr(_), Float) => CastError::NeedViaPtr,
+        // prim -> ptr, ptr -> float and float -> ptr
+        _ => CastError::NeedViaUsize,
+    }
+}
+
 impl<'tcx> CastCheck<'tcx> {
     pub fn new(expr: ast::Expr, expr_ty: Ty<'tcx>, cast_ty: Ty<'tcx>, span: Span)
                -> CastCheck<'tcx> {
@@ -204,12 +220,11 @@ impl<'tcx> CastCheck<'tcx> {
         } else if self.try_coercion_cast(fcx) {
             self.trivial_cast_lint(fcx);
             debug!(" -> CoercionCast");
-            fcx.tcx().cast_kinds.borrow_mut().insert(self.expr.id,
-                                                     CastKind::CoercionCast);
+            fcx.inh.cast_kinds.borrow_mut().insert(self.expr.id, CastKind::CoercionCast);
         } else { match self.do_check(fcx) {
             Ok(k) => {
                 debug!(" -> {:?}", k);
-                fcx.tcx().cast_kinds.borrow_mut().insert(self.expr.id, k);
+                fcx.inh.cast_kinds.borrow_mut().insert(self.expr.id, k);
             }
             Err(e) => self.report_cast_error(fcx, e)
         };}
@@ -219,7 +234,6 @@ impl<'tcx> CastCheck<'tcx> {
     /// can return Ok and create type errors in the fcx rather than returning
     /// directly. coercion-cast is handled in check instead of here.
     fn do_check<'a>(&self, fcx: &FnCtxt<'a, 'tcx>) -> Result<CastKind, CastError> {
-        use middle::cast::IntTy::*;
         use middle::cast::CastTy::*;
 
         let (t_from, t_cast) = match (CastTy::from_ty(fcx.tcx(), self.expr_ty),
@@ -230,44 +244,19 @@ impl<'tcx> CastCheck<'tcx> {
             }
         };
 
-        match (t_from, t_cast) {
-            // These types have invariants! can't cast into them.
-            (_, RPtr(_)) | (_, Int(CEnum)) | (_, FnPtr) => Err(CastError::NonScalar),
-
-            // * -> Bool
-            (_, Int(Bool)) => Err(CastError::CastToBool),
-
-            // * -> Char
-            (Int(U(ast::TyU8)), Int(Char)) => Ok(CastKind::U8CharCast), // u8-char-cast
-            (_, Int(Char)) => Err(CastError::CastToChar),
-
-            // prim -> float,ptr
-            (Int(Bool), Float) | (Int(CEnum), Float) | (Int(Char), Float)
-                => Err(CastError::NeedViaInt),
-            (Int(Bool), Ptr(_)) | (Int(CEnum), Ptr(_)) | (Int(Char), Ptr(_))
-                => Err(CastError::NeedViaUsize),
-
-            // ptr -> *
-            (Ptr(m_e), Ptr(m_c)) => self.check_ptr_ptr_cast(fcx, m_e, m_c), // ptr-ptr-cast
-            (Ptr(m_expr), Int(_)) => self.check_ptr_addr_cast(fcx, m_expr), // ptr-addr-cast
-            (Ptr(_), Float) | (FnPtr, Float) => Err(CastError::NeedViaUsize),
-            (FnPtr, Int(_)) => Ok(CastKind::FnPtrAddrCast),
-            (RPtr(_), Int(_)) | (RPtr(_), Float) => Err(CastError::NeedViaPtr),
-            // * -> ptr
-            (Int(_), Ptr(mt)) => self.check_addr_ptr_cast(fcx, mt), // addr-ptr-cast
-            (FnPtr, Ptr(mt)) => self.check_fptr_ptr_cast(fcx, mt),
-            (Float, Ptr(_)) => Err(CastError::NeedViaUsize),
-            (RPtr(rmt), Ptr(mt)) => self.check_ref_cast(fcx, rmt, mt), // array-ptr-cast
-
-            // prim -> prim
-            (Int(CEnum), Int(_)) => Ok(CastKind::EnumCast),
-            (Int(Char), Int(_)) | (Int(Bool), Int(_)) => Ok(CastKind::PrimIntCast),
-
-            (Int(_), Int(_)) |
-            (Int(_), Float) |
-            (Float, Int(_)) |
-            (Float, Float) => Ok(CastKind::NumericCast),
+        let kind = match CastKind::classify(t_from, t_cast) {
+            Some(kind) => kind,
+            None => return Err(cast_error(t_from, t_cast))
+        };
 
+        // Casts between pointers are only valid for some pointee types.
+        match (kind, t_from, t_cast) {
+            (CastKind::PtrPtrCast, Ptr(m_e), Ptr(m_c)) => self.check_ptr_ptr_cast(fcx, m_e, m_c),
+            (CastKind::PtrAddrCast, Ptr(m_expr), _) => self.check_ptr_addr_cast(fcx, m_expr),
+            (CastKind::AddrPtrCast, _, Ptr(mt)) => self.check_addr_ptr_cast(fcx, mt),
+            (CastKind::FnPtrPtrCast, _, Ptr(mt)) => self.check_fptr_ptr_cast(fcx, mt),
+            (CastKind::ArrayPtrCast, RPtr(rmt), Ptr(mt)) => self.check_ref_cast(fcx, rmt, mt),
+            _ => Ok(kind)
         }
     }
 
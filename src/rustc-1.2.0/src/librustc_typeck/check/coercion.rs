@@ -65,7 +65,7 @@ use check::{autoderef, FnCtxt, LvaluePreference, UnresolvedTypeAction};
 use middle::infer::{self, Coercion};
 use middle::traits::{self, ObligationCause};
 use middle::traits::{predicate_for_trait_def, report_selection_error};
-use middle::ty::{AutoDerefRef, AdjustDerefRef};
+use middle::ty::{AdjustmentBuilder, AdjustDerefRef};
 use middle::ty::{self, mt, Ty};
 use middle::ty_relate::RelateResult;
 use util::common::indent;
@@ -215,11 +215,9 @@ impl<'f, 'tcx> Coerce<'f, 'tcx> {
 
         match success {
             Some(_) => {
-                Ok(Some(AdjustDerefRef(AutoDerefRef {
-                    autoderefs: autoderefs,
-                    autoref: autoref,
-                    unsize: None
-                })))
+                let adjustment = AdjustmentBuilder::autoderef(autoderefs)
+                                     .opt_autoref(autoref);
+                Ok(Some(AdjustDerefRef(adjustment.build())))
             }
             None => {
                 // Return original error as if overloaded deref was never
@@ -330,11 +328,10 @@ impl<'f, 'tcx> Coerce<'f, 'tcx> {
         assert!(obligations.is_empty());
         *obligations = leftover_predicates;
 
-        let adjustment = AutoDerefRef {
-            autoderefs: if reborrow.is_some() { 1 } else { 0 },
-            autoref: reborrow,
-            unsize: Some(target)
-        };
+        let adjustment = AdjustmentBuilder::autoderef(if reborrow.is_some() { 1 } else { 0 })
+                             .opt_autoref(reborrow)
+                             .unsize(target)
+                             .build();
         debug!("Success, coerced with {:?}", adjustment);
         Ok(Some(AdjustDerefRef(adjustment)))
     }
@@ -419,11 +416,9 @@ impl<'f, 'tcx> Coerce<'f, 'tcx> {
         // representation, we still register an AutoDerefRef so that
         // regionck knows that the region for `a` must be valid here.
         if is_ref {
-            Ok(Some(AdjustDerefRef(AutoDerefRef {
-                autoderefs: 1,
-                autoref: Some(ty::AutoUnsafe(mutbl_b)),
-                unsize: None
-            })))
+            let adjustment = AdjustmentBuilder::autoderef(1)
+                                 .autoref(ty::AutoUnsafe(mutbl_b));
+            Ok(Some(AdjustDerefRef(adjustment.build())))
         } else {
             Ok(None)
         }
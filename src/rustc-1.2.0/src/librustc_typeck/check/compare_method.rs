@@ -326,7 +326,7 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
         debug!("compare_impl_method: trait_fty={:?}",
                trait_fty);
 
-        try!(infer::mk_subty(&infcx, false, origin, impl_fty, trait_fty));
+        try!(infcx.sub_types_expected_from(false, origin, impl_fty, trait_fty, trait_m.def_id));
 
         infcx.leak_check(&skol_map, snapshot)
     });
@@ -341,6 +341,7 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
                       "method `{}` has an incompatible type for trait: {}",
                       token::get_name(trait_m.name),
                       terr);
+            ty::note_and_explain_type_err(tcx, &terr, impl_m_span);
             return;
         }
     }
@@ -473,7 +474,7 @@ pub fn compare_const_impl<'tcx>(tcx: &ty::ctxt<'tcx>,
         debug!("compare_const_impl: trait_ty={:?}",
                trait_ty);
 
-        infer::mk_subty(&infcx, false, origin, impl_ty, trait_ty)
+        infer::mk_subty(&infcx, false, origin, impl_ty, trait_ty).map_err(|e| e.err)
     });
 
     match err {
@@ -39,7 +39,7 @@ pub fn suptype_with_fn<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
                           ty_b, ty_a) {
       Ok(()) => { /* ok */ }
       Err(ref err) => {
-          handle_err(sp, ty_a, ty_b, err);
+          handle_err(sp, ty_a, ty_b, &err.err);
       }
     }
 }
@@ -48,7 +48,7 @@ pub fn eqtype<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>, sp: Span,
                         expected: Ty<'tcx>, actual: Ty<'tcx>) {
     match infer::mk_eqty(fcx.infcx(), false, infer::Misc(sp), actual, expected) {
         Ok(()) => { /* ok */ }
-        Err(ref err) => { fcx.report_mismatched_types(sp, expected, actual, err); }
+        Err(ref err) => { fcx.infcx().report_type_trace_error(err); }
     }
 }
 
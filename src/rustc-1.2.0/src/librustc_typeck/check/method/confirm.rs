@@ -9,27 +9,63 @@
 // except according to those terms.
 
 use super::probe;
+use super::stats::Phase;
 
-use check::{self, FnCtxt, NoPreference, PreferMutLvalue, callee, demand};
+use check::{self, FnCtxt, NoPreference, callee, demand};
 use check::UnresolvedTypeAction;
 use middle::mem_categorization::Typer;
+use middle::stability;
 use middle::subst::{self};
 use middle::traits;
 use middle::ty::{self, Ty};
 use middle::ty::{MethodCall, MethodCallee, MethodObject, MethodOrigin,
                  MethodParam, MethodStatic, MethodTraitObject, MethodTypeParam};
-use middle::ty_fold::TypeFoldable;
+use middle::ty_fold::{self, TypeFoldable, TypeFolder};
 use middle::infer;
 use middle::infer::InferCtxt;
+use middle::lang_items::LanguageItems;
 use syntax::ast;
 use syntax::codemap::Span;
-use std::iter::repeat;
+use syntax::parse::token;
+
+use std::mem;
+use std::rc::Rc;
 
 struct ConfirmContext<'a, 'tcx:'a> {
     fcx: &'a FnCtxt<'a, 'tcx>,
     span: Span,
     self_expr: &'tcx ast::Expr,
     call_expr: &'tcx ast::Expr,
+
+    /// In a dry run nothing is written to the tables and no errors are
+    /// reported. Obligations are still registered, so the caller must
+    /// arrange for them to be discarded.
+    dry_run: bool,
+
+    /// The substitutions of the impl's own parameters, when the method
+    /// was picked from a trait impl; see `record_resolution`.
+    impl_substs: Option<subst::Substs<'tcx>>,
+}
+
+/// The signature and bounds a method call would be confirmed with.
+#[derive(Clone, Debug)]
+pub struct ConfirmedSig<'tcx> {
+    /// Function signature of the method. The 0th argument is the
+    /// receiver.
+    pub method_sig: ty::FnSig<'tcx>,
+
+    /// Bounds on the method's parameters that the call would have to
+    /// satisfy.
+    pub method_predicates: ty::InstantiatedPredicates<'tcx>,
+}
+
+impl<'tcx> TypeFoldable<'tcx> for ConfirmedSig<'tcx> {
+    fn fold_with<F: TypeFolder<'tcx>>(&self, folder: &mut F) -> ConfirmedSig<'tcx> {
+        ConfirmedSig {
+            method_sig: self.method_sig.fold_with(folder),
+            method_predicates: self.method_predicates.fold_with(folder),
+        }
+    }
 }
 
 struct InstantiatedMethodSig<'tcx> {
@@ -52,16 +88,73 @@ pub fn confirm<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                          call_expr: &'tcx ast::Expr,
                          unadjusted_self_ty: Ty<'tcx>,
                          pick: probe::Pick<'tcx>,
-                         supplied_method_types: Vec<Ty<'tcx>>)
+                         supplied_method_types: Vec<Ty<'tcx>>,
+                         supplied_method_regions: Vec<ty::Region>)
                          -> MethodCallee<'tcx>
 {
-    debug!("confirm(unadjusted_self_ty={:?}, pick={:?}, supplied_method_types={:?})",
+    debug!("confirm(unadjusted_self_ty={:?}, pick={:?}, supplied_method_types={:?}, \
+            supplied_method_regions={:?})",
            unadjusted_self_ty,
            pick,
-           supplied_method_types);
+           supplied_method_types,
+           supplied_method_regions);
 
     let mut confirm_cx = ConfirmContext::new(fcx, span, self_expr, call_expr);
-    confirm_cx.confirm(unadjusted_self_ty, pick, supplied_method_types)
+    confirm_cx.time(Phase::Confirm, |cx| {
+        cx.confirm(unadjusted_self_ty, pick, supplied_method_types, supplied_method_regions)
+    })
+}
+
+/// Confirms `pick` as a dry run inside an inference checkpoint that is then
+/// rolled back, and returns the signature and bounds that `confirm` would
+/// have used. Obligations registered meanwhile, e.g. while normalizing the
+/// signature, go to a fulfillment context of their own that is thrown
+/// away, and no errors are reported. Regions in the result are erased, and
+/// types that inference had not settled within the checkpoint are replaced
+/// by `err`.
+pub fn confirm_for_diagnostics<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                         span: Span,
+                                         self_expr: &'tcx ast::Expr,
+                                         call_expr: &'tcx ast::Expr,
+                                         unadjusted_self_ty: Ty<'tcx>,
+                                         pick: probe::Pick<'tcx>,
+                                         supplied_method_types: Vec<Ty<'tcx>>,
+                                         supplied_method_regions: Vec<ty::Region>)
+                                         -> ConfirmedSig<'tcx>
+{
+    debug!("confirm_for_diagnostics(unadjusted_self_ty={:?}, pick={:?})",
+           unadjusted_self_ty,
+           pick);
+
+    let tcx = fcx.tcx();
+    let fulfillment_cx = mem::replace(&mut *fcx.inh.fulfillment_cx.borrow_mut(),
+                                      traits::FulfillmentContext::new(false));
+    let checkpoint = fcx.infcx().checkpoint("confirm_for_diagnostics");
+    let sig = {
+        let mut confirm_cx = ConfirmContext::new(fcx, span, self_expr, call_expr);
+        confirm_cx.dry_run = true;
+        let (_, InstantiatedMethodSig { method_sig, method_predicates, .. }) =
+            confirm_cx.instantiate_pick(unadjusted_self_ty, &pick,
+                                        supplied_method_types, supplied_method_regions);
+
+        // Nothing created inside the checkpoint may escape it.
+        let sig = ConfirmedSig {
+            method_sig: method_sig,
+            method_predicates: method_predicates,
+        };
+        let sig = fcx.infcx().resolve_type_vars_if_possible(&sig);
+        let sig = ty_fold::erase_regions(tcx, sig);
+        sig.fold_with(&mut ty_fold::BottomUpFolder {
+            tcx: tcx,
+            fldop: |ty| match ty.sty {
+                ty::TyInfer(_) => tcx.types.err,
+                _ => ty
+            }
+        })
+    };
+    fcx.infcx().rollback_to(checkpoint);
+    *fcx.inh.fulfillment_cx.borrow_mut() = fulfillment_cx;
+    sig
 }
 
 impl<'a,'tcx> ConfirmContext<'a,'tcx> {
@@ -71,37 +164,43 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
            call_expr: &'tcx ast::Expr)
            -> ConfirmContext<'a, 'tcx>
     {
-        ConfirmContext { fcx: fcx, span: span, self_expr: self_expr, call_expr: call_expr }
+        ConfirmContext {
+            fcx: fcx,
+            span: span,
+            self_expr: self_expr,
+            call_expr: call_expr,
+            dry_run: false,
+            impl_substs: None,
+        }
+    }
+
+    /// Runs `f`, charging the time it takes to `phase` of the enclosing
+    /// function if `-Z time-method-resolution` is on. Dry runs are not
+    /// counted.
+    fn time<T, F>(&mut self, phase: Phase, f: F) -> T where
+        F: FnOnce(&mut ConfirmContext<'a, 'tcx>) -> T,
+    {
+        let tcx = self.tcx();
+        if self.dry_run || !tcx.sess.time_method_resolution() {
+            return f(self);
+        }
+
+        let item_id = tcx.map.get_parent(self.fcx.body_id);
+        let stats = &self.fcx.ccx.method_stats;
+        stats.time(item_id, phase, || f(self))
     }
 
     fn confirm(&mut self,
                unadjusted_self_ty: Ty<'tcx>,
                pick: probe::Pick<'tcx>,
-               supplied_method_types: Vec<Ty<'tcx>>)
+               supplied_method_types: Vec<Ty<'tcx>>,
+               supplied_method_regions: Vec<ty::Region>)
                -> MethodCallee<'tcx>
     {
-        // Adjust the self expression the user provided and obtain the adjusted type.
-        let self_ty = self.adjust_self_ty(unadjusted_self_ty, &pick);
-
-        // Make sure nobody calls `drop()` explicitly.
-        self.enforce_illegal_method_limitations(&pick);
-
-        // Create substitutions for the method's type parameters.
-        let (rcvr_substs, method_origin) =
-            self.fresh_receiver_substs(self_ty, &pick);
-        let (method_types, method_regions) =
-            self.instantiate_method_substs(&pick, supplied_method_types);
-        let all_substs = rcvr_substs.with_method(method_types, method_regions);
-        debug!("all_substs={:?}", all_substs);
-
-        // Create the final signature for the method, replacing late-bound regions.
-        let InstantiatedMethodSig {
+        let (method_origin, InstantiatedMethodSig {
             method_sig, all_substs, method_predicates
-        } = self.instantiate_method_sig(&pick, all_substs);
-        let method_self_ty = method_sig.inputs[0];
-
-        // Unify the (adjusted) self type with what the method expects.
-        self.unify_receivers(self_ty, method_self_ty);
+        }) = self.instantiate_pick(unadjusted_self_ty, &pick,
+                                   supplied_method_types, supplied_method_regions);
 
         // Add any trait/regions obligations specified on the method's type parameters.
         self.add_obligations(&pick, &all_substs, &method_predicates);
@@ -122,11 +221,102 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
         // If this is an `&mut self` method, bias the receiver
         // expression towards mutability (this will switch
         // e.g. `Deref` to `DerefMut` in overloaded derefs and so on).
-        self.fixup_derefs_on_method_receiver_if_necessary(&callee);
+        fixup_derefs_on_method_receiver_if_necessary(self.fcx, self.self_expr, &callee);
+
+        self.record_resolution(&pick, &callee);
 
         callee
     }
 
+    /// Adjusts the receiver for `pick`, instantiates the method's
+    /// signature and unifies its `self` type with the adjusted receiver.
+    fn instantiate_pick(&mut self,
+                        unadjusted_self_ty: Ty<'tcx>,
+                        pick: &probe::Pick<'tcx>,
+                        supplied_method_types: Vec<Ty<'tcx>>,
+                        supplied_method_regions: Vec<ty::Region>)
+                        -> (MethodOrigin<'tcx>, InstantiatedMethodSig<'tcx>)
+    {
+        // Adjust the self expression the user provided and obtain the adjusted type.
+        let self_ty = self.adjust_self_ty(unadjusted_self_ty, pick);
+
+        if !self.dry_run {
+            // Make sure nobody calls `drop()` explicitly.
+            self.enforce_illegal_method_limitations(pick);
+            self.enforce_method_stability(pick);
+        }
+
+        // Create substitutions for the method's type parameters.
+        let (rcvr_substs, method_origin) =
+            self.time(Phase::FreshReceiverSubsts, |cx| cx.fresh_receiver_substs(self_ty, pick));
+        let (method_types, method_regions) =
+            self.instantiate_method_substs(pick, supplied_method_types, supplied_method_regions);
+        let all_substs = rcvr_substs.with_method(method_types, method_regions);
+        debug!("all_substs={:?}", all_substs);
+
+        // Create the final signature for the method, replacing late-bound regions.
+        let InstantiatedMethodSig {
+            mut method_sig, all_substs, method_predicates
+        } = self.time(Phase::InstantiateMethodSig,
+                      |cx| cx.instantiate_method_sig(pick, all_substs));
+
+        // Probe only picks a by-value `self` method on an object when the
+        // receiver is a `Box<Trait>`, which the vtable shim then takes in
+        // place of `self`.
+        if let probe::ObjectPick(..) = pick.kind {
+            let method = pick.item.as_opt_method().unwrap();
+            if method.takes_self_by_value() {
+                method_sig.inputs[0] = ty::mk_uniq(self.tcx(), method_sig.inputs[0]);
+            }
+        }
+        let method_self_ty = method_sig.inputs[0];
+
+        // Unify the (adjusted) self type with what the method expects.
+        self.unify_receivers(self_ty, method_self_ty);
+
+        (method_origin, InstantiatedMethodSig {
+            method_sig: method_sig,
+            all_substs: all_substs,
+            method_predicates: method_predicates,
+        })
+    }
+
+    /// Records how the call was resolved, for tools to find once writeback
+    /// has copied it into `tcx.method_call_resolutions`.
+    fn record_resolution(&self, pick: &probe::Pick<'tcx>, callee: &MethodCallee<'tcx>) {
+        let adjustment = match self.fcx.inh.adjustments.borrow().get(&self.self_expr.id) {
+            Some(&ty::AdjustDerefRef(adj)) => adj,
+            _ => {
+                self.tcx().sess.span_bug(self.self_expr.span,
+                                         "method receiver has no autoderef-ref adjustment");
+            }
+        };
+
+        // A call resolved to a trait impl goes to the impl's own method,
+        // unless the impl relies on the trait's default.
+        let impl_method = match pick.kind {
+            probe::ExtensionImplPick(impl_def_id, _) => {
+                impl_method_with_name(self.tcx(), impl_def_id, pick.item.name())
+            }
+            _ => None,
+        };
+        let (def_id, substs) = match (impl_method, &self.impl_substs) {
+            (Some(def_id), &Some(ref impl_substs)) => {
+                let method_types = callee.substs.types.get_slice(subst::FnSpace).to_vec();
+                let method_regions = callee.substs.regions().get_slice(subst::FnSpace).to_vec();
+                (def_id, impl_substs.clone().with_method(method_types, method_regions))
+            }
+            _ => (pick.item.def_id(), callee.substs.clone()),
+        };
+
+        self.fcx.inh.method_call_resolutions.borrow_mut().insert(self.call_expr.id,
+                                                                ty::ResolvedMethod {
+            def_id: def_id,
+            substs: substs,
+            adjustment: adjustment,
+        });
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // ADJUSTMENTS
 
@@ -135,9 +325,14 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
                       pick: &probe::Pick<'tcx>)
                       -> Ty<'tcx>
     {
-        let (autoref, unsize) = if let Some(mutbl) = pick.autoref {
-            let region = self.infcx().next_region_var(infer::Autoref(self.span));
-            let autoref = ty::AutoPtr(self.tcx().mk_region(region), mutbl);
+        let (autoref, unsize) = if let Some(pick_autoref) = pick.autoref {
+            let autoref = match pick_autoref {
+                probe::AutoRef(mutbl) => {
+                    let region = self.infcx().next_region_var(infer::Autoref(self.span));
+                    ty::AutoPtr(self.tcx().mk_region(region), mutbl)
+                }
+                probe::AutoRawPtr(mutbl) => ty::AutoUnsafe(mutbl),
+            };
             (Some(autoref), pick.unsize.map(|target| {
                 ty::adjust_ty_for_autoref(self.tcx(), target, Some(autoref))
             }))
@@ -152,13 +347,21 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
 
         // Commit the autoderefs by calling `autoderef again, but this
         // time writing the results into the various tables.
+        // A dry run only needs the resulting type.
+        let (base_expr, unresolved_type_action) = if self.dry_run {
+            (None, UnresolvedTypeAction::Ignore)
+        } else {
+            (Some(self.self_expr), UnresolvedTypeAction::Error)
+        };
+        let mut chain = Vec::new();
         let (autoderefd_ty, n, result) = check::autoderef(self.fcx,
                                                           self.span,
                                                           unadjusted_self_ty,
-                                                          Some(self.self_expr),
-                                                          UnresolvedTypeAction::Error,
+                                                          base_expr,
+                                                          unresolved_type_action,
                                                           NoPreference,
-                                                          |_, n| {
+                                                          |ty, n| {
+            chain.push(ty);
             if n == pick.autoderefs {
                 Some(())
             } else {
@@ -168,19 +371,41 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
         assert_eq!(n, pick.autoderefs);
         assert_eq!(result, Some(()));
 
-        // Write out the final adjustment.
-        self.fcx.write_adjustment(self.self_expr.id,
-                                  ty::AdjustDerefRef(ty::AutoDerefRef {
-            autoderefs: pick.autoderefs,
-            autoref: autoref,
-            unsize: unsize
-        }));
-
-        if let Some(target) = unsize {
+        let self_ty = if let Some(target) = unsize {
             target
         } else {
             ty::adjust_ty_for_autoref(self.tcx(), autoderefd_ty, autoref)
+        };
+
+        if self.dry_run {
+            return self_ty;
         }
+
+        self.fcx.inh.autoderef_chains.borrow_mut().insert(self.self_expr.id, chain);
+
+        // Write out the final adjustment.
+        let mut adjustment = ty::AdjustmentBuilder::autoderef(pick.autoderefs)
+                                 .opt_autoref(autoref);
+        if let Some(target) = unsize {
+            adjustment = adjustment.unsize_receiver(target);
+        }
+        self.fcx.write_adjustment(self.self_expr.id, ty::AdjustDerefRef(adjustment.build()));
+
+        // A receiver like `Rc<Self>` is passed by value, so the pick
+        // needs no adjustment beyond the autoderefs. Trait objects are
+        // only ever dispatched through `&` or `Box`, though, so such a
+        // method cannot be called on `Rc<Trait>`.
+        if let probe::ObjectPick(..) = pick.kind {
+            if ty::receiver_target(self.tcx(), self_ty).is_some() {
+                span_err!(self.tcx().sess, self.span, E0399,
+                          "the method `{}` cannot be called on a trait object \
+                           through the receiver type `{}`",
+                          pick.item.name(),
+                          self_ty);
+            }
+        }
+
+        self_ty
     }
 
     ///////////////////////////////////////////////////////////////////////////
@@ -199,10 +424,26 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
     {
         match pick.kind {
             probe::InherentImplPick(impl_def_id) => {
-                assert!(ty::impl_trait_ref(self.tcx(), impl_def_id).is_none(),
-                        "impl {:?} is not an inherent impl", impl_def_id);
+                if ty::impl_trait_ref(self.tcx(), impl_def_id).is_some() {
+                    self.tcx().sess.span_bug(
+                        self.span,
+                        &format!("impl {:?} is not an inherent impl", impl_def_id));
+                }
                 let impl_polytype = check::impl_self_ty(self.fcx, self.span, impl_def_id);
 
+                // Inherent impls on primitive types (possibly loaded from
+                // another crate, like the numeric impls in libcore) can only
+                // be the one attached through the type's lang item.
+                if let Some(lang_impl) = ty::primitive_inherent_impl(self.tcx(),
+                                                                     impl_polytype.ty) {
+                    if lang_impl != impl_def_id {
+                        self.tcx().sess.span_bug(
+                            self.span,
+                            &format!("impl {:?} is not the lang item impl {:?} for `{}`",
+                                     impl_def_id, lang_impl, impl_polytype.ty));
+                    }
+                }
+
                 (impl_polytype.substs, MethodStatic(pick.item.def_id()))
             }
 
@@ -221,7 +462,10 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
                     let original_poly_trait_ref =
                         data.principal_trait_ref_with_self_ty(this.tcx(), object_ty);
                     let upcast_poly_trait_ref =
-                        this.upcast(original_poly_trait_ref.clone(), trait_def_id);
+                        this.upcast(object_ty,
+                                    original_poly_trait_ref.clone(),
+                                    trait_def_id,
+                                    pick.item.name());
                     let upcast_trait_ref =
                         this.replace_late_bound_regions_with_fresh_var(&upcast_poly_trait_ref);
                     debug!("original_poly_trait_ref={:?} upcast_trait_ref={:?} target_trait={:?}",
@@ -258,6 +502,7 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
                 let origin = MethodTypeParam(MethodParam { trait_ref: impl_trait_ref.clone(),
                                                            method_num: method_num,
                                                            impl_def_id: Some(impl_def_id) });
+                self.impl_substs = Some(impl_polytype.substs);
                 (impl_trait_ref.substs.clone(), origin)
             }
 
@@ -328,7 +573,8 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
 
     fn instantiate_method_substs(&mut self,
                                  pick: &probe::Pick<'tcx>,
-                                 supplied_method_types: Vec<Ty<'tcx>>)
+                                 supplied_method_types: Vec<Ty<'tcx>>,
+                                 supplied_method_regions: Vec<ty::Region>)
                                  -> (Vec<Ty<'tcx>>, Vec<ty::Region>)
     {
         // Determine the values for the generic parameters of the method.
@@ -339,44 +585,100 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
                                    .generics.types.len(subst::FnSpace);
         let method_types = {
             if num_supplied_types == 0 {
-                self.fcx.infcx().next_ty_vars(num_method_types)
+                let method = pick.item.as_opt_method().unwrap();
+                method.generics.types.get_slice(subst::FnSpace).iter().map(|def| {
+                    let origin = infer::MethodTypeParameterVar(self.span, method.name, def.name);
+                    self.fcx.infcx().next_ty_var_with_origin(origin)
+                }).collect()
             } else if num_method_types == 0 {
-                span_err!(self.tcx().sess, self.span, E0035,
-                    "does not take type parameters");
+                if !self.dry_run {
+                    span_err!(self.tcx().sess, self.span, E0035,
+                        "does not take type parameters");
+                }
                 self.fcx.infcx().next_ty_vars(num_method_types)
             } else if num_supplied_types != num_method_types {
-                span_err!(self.tcx().sess, self.span, E0036,
-                    "incorrect number of type parameters given for this method");
-                repeat(self.tcx().types.err).take(num_method_types).collect()
+                if !self.dry_run {
+                    self.report_wrong_number_of_method_types(pick, &supplied_method_types);
+                }
+
+                // Keep the parameters that were given in the right place, so
+                // that only the missing ones are left to inference.
+                let mut method_types = supplied_method_types;
+                method_types.truncate(num_method_types);
+                let num_missing = num_method_types - method_types.len();
+                method_types.extend(self.fcx.infcx().next_ty_vars(num_missing).into_iter());
+                method_types
             } else {
                 supplied_method_types
             }
         };
 
-        // Create subst for early-bound lifetime parameters, combining
-        // parameters from the type and those from the method.
-        //
-        // FIXME -- permit users to manually specify lifetimes
-        let method_regions =
-            self.fcx.infcx().region_vars_for_defs(
-                self.span,
-                pick.item.as_opt_method().unwrap()
-                    .generics.regions.get_slice(subst::FnSpace));
+        // Likewise for the early-bound lifetime parameters of the
+        // method. Late-bound ones cannot be given explicitly.
+        let method_region_defs = pick.item.as_opt_method().unwrap()
+                                     .generics.regions.get_slice(subst::FnSpace);
+        let num_supplied_regions = supplied_method_regions.len();
+        let method_regions = {
+            if num_supplied_regions == 0 {
+                self.fcx.infcx().region_vars_for_defs(self.span, method_region_defs)
+            } else if num_supplied_regions != method_region_defs.len() {
+                if !self.dry_run {
+                    span_err!(self.tcx().sess, self.span, E0107,
+                        "wrong number of lifetime parameters: expected {}, found {}",
+                        method_region_defs.len(),
+                        num_supplied_regions);
+                }
+                self.fcx.infcx().region_vars_for_defs(self.span, method_region_defs)
+            } else {
+                supplied_method_regions
+            }
+        };
 
         (method_types, method_regions)
     }
 
+    fn report_wrong_number_of_method_types(&self,
+                                           pick: &probe::Pick<'tcx>,
+                                           supplied_method_types: &[Ty<'tcx>]) {
+        let type_defs = pick.item.as_opt_method().unwrap()
+                            .generics.types.get_slice(subst::FnSpace);
+        span_err!(self.tcx().sess, self.span, E0036,
+            "incorrect number of type parameters given for this method: expected {}, found {}",
+            type_defs.len(),
+            supplied_method_types.len());
+
+        let (label, names) = if supplied_method_types.len() < type_defs.len() {
+            ("missing", type_defs[supplied_method_types.len()..].iter()
+                            .map(|def| format!("`{}`", token::get_name(def.name)))
+                            .collect::<Vec<_>>())
+        } else {
+            ("extra", supplied_method_types[type_defs.len()..].iter()
+                          .map(|ty| format!("`{}`", ty))
+                          .collect::<Vec<_>>())
+        };
+        self.tcx().sess.fileline_note(
+            self.span,
+            &format!("{} type parameter{}: {}",
+                     label,
+                     if names.len() == 1 { "" } else { "s" },
+                     names.connect(", ")));
+    }
+
     fn unify_receivers(&mut self,
                        self_ty: Ty<'tcx>,
                        method_self_ty: Ty<'tcx>)
     {
-        match self.fcx.mk_subty(false, infer::Misc(self.span), self_ty, method_self_ty) {
-            Ok(_) => {}
-            Err(_) => {
-                self.tcx().sess.span_bug(
-                    self.span,
-                    &format!("{} was a subtype of {} but now is not?",
-                             self_ty, method_self_ty));
+        // Probing found `self_ty` to be a subtype of the receiver with
+        // fresh variables for the method's own type parameters. Those the
+        // user supplied explicitly can still make the two disagree, as in
+        // `x.m::<i32>()` with `fn m<U>(self: Tagged<Self, U>)`, so report
+        // the mismatch rather than aborting.
+        if let Err(ref e) = self.fcx.mk_subty(false, infer::Misc(self.span),
+                                              self_ty, method_self_ty) {
+            debug!("unify_receivers: {:?} is no longer a subtype of {:?}: {:?}",
+                   self_ty, method_self_ty, e);
+            if !self.dry_run {
+                self.infcx().report_type_trace_error(e);
             }
         }
     }
@@ -435,8 +737,22 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
                all_substs,
                method_predicates);
 
+        // Bounds that fail once the receiver type is known are reported
+        // against the method as instantiated for this call, and against
+        // the impl it was resolved to.
+        let parent_code = match pick.kind {
+            probe::ExtensionImplPick(impl_def_id, _) => {
+                traits::MethodImplObligation(impl_def_id, pick.item.name())
+            }
+            _ => traits::MiscObligation
+        };
+        let code = traits::MethodObligation(traits::MethodObligationCause {
+            method_def_id: pick.item.def_id(),
+            substs: all_substs.clone(),
+            parent_code: Rc::new(parent_code),
+        });
         self.fcx.add_obligations_for_parameters(
-            traits::ObligationCause::misc(self.span, self.fcx.body_id),
+            traits::ObligationCause::new(self.span, self.fcx.body_id, code),
             method_predicates);
 
         self.fcx.add_default_region_param_bounds(
@@ -444,167 +760,6 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
             self.call_expr);
     }
 
-    ///////////////////////////////////////////////////////////////////////////
-    // RECONCILIATION
-
-    /// When we select a method with an `&mut self` receiver, we have to go convert any
-    /// auto-derefs, indices, etc from `Deref` and `Index` into `DerefMut` and `IndexMut`
-    /// respectively.
-    fn fixup_derefs_on_method_receiver_if_necessary(&self,
-                                                    method_callee: &MethodCallee) {
-        let sig = match method_callee.ty.sty {
-            ty::TyBareFn(_, ref f) => f.sig.clone(),
-            _ => return,
-        };
-
-        match sig.0.inputs[0].sty {
-            ty::TyRef(_, ty::mt {
-                ty: _,
-                mutbl: ast::MutMutable,
-            }) => {}
-            _ => return,
-        }
-
-        // Gather up expressions we want to munge.
-        let mut exprs = Vec::new();
-        exprs.push(self.self_expr);
-        loop {
-            let last = exprs[exprs.len() - 1];
-            match last.node {
-                ast::ExprParen(ref expr) |
-                ast::ExprField(ref expr, _) |
-                ast::ExprTupField(ref expr, _) |
-                ast::ExprIndex(ref expr, _) |
-                ast::ExprUnary(ast::UnDeref, ref expr) => exprs.push(&**expr),
-                _ => break,
-            }
-        }
-
-        debug!("fixup_derefs_on_method_receiver_if_necessary: exprs={:?}",
-               exprs);
-
-        // Fix up autoderefs and derefs.
-        for (i, &expr) in exprs.iter().rev().enumerate() {
-            // Count autoderefs.
-            let autoderef_count = match self.fcx
-                                            .inh
-                                            .adjustments
-                                            .borrow()
-                                            .get(&expr.id) {
-                Some(&ty::AdjustDerefRef(ref adj)) => adj.autoderefs,
-                Some(_) | None => 0,
-            };
-
-            debug!("fixup_derefs_on_method_receiver_if_necessary: i={} expr={:?} \
-                                                                  autoderef_count={}",
-                   i, expr, autoderef_count);
-
-            if autoderef_count > 0 {
-                check::autoderef(self.fcx,
-                                 expr.span,
-                                 self.fcx.expr_ty(expr),
-                                 Some(expr),
-                                 UnresolvedTypeAction::Error,
-                                 PreferMutLvalue,
-                                 |_, autoderefs| {
-                                     if autoderefs == autoderef_count + 1 {
-                                         Some(())
-                                     } else {
-                                         None
-                                     }
-                                 });
-            }
-
-            // Don't retry the first one or we might infinite loop!
-            if i != 0 {
-                match expr.node {
-                    ast::ExprIndex(ref base_expr, ref index_expr) => {
-                        // If this is an overloaded index, the
-                        // adjustment will include an extra layer of
-                        // autoref because the method is an &self/&mut
-                        // self method. We have to peel it off to get
-                        // the raw adjustment that `try_index_step`
-                        // expects. This is annoying and horrible. We
-                        // ought to recode this routine so it doesn't
-                        // (ab)use the normal type checking paths.
-                        let adj = self.fcx.inh.adjustments.borrow().get(&base_expr.id).cloned();
-                        let (autoderefs, unsize) = match adj {
-                            Some(ty::AdjustDerefRef(adr)) => match adr.autoref {
-                                None => {
-                                    assert!(adr.unsize.is_none());
-                                    (adr.autoderefs, None)
-                                }
-                                Some(ty::AutoPtr(_, _)) => {
-                                    (adr.autoderefs, adr.unsize.map(|target| {
-                                        ty::deref(target, false)
-                                            .expect("fixup: AutoPtr is not &T").ty
-                                    }))
-                                }
-                                Some(_) => {
-                                    self.tcx().sess.span_bug(
-                                        base_expr.span,
-                                        &format!("unexpected adjustment autoref {:?}",
-                                                adr));
-                                }
-                            },
-                            None => (0, None),
-                            Some(_) => {
-                                self.tcx().sess.span_bug(
-                                    base_expr.span,
-                                    "unexpected adjustment type");
-                            }
-                        };
-
-                        let (adjusted_base_ty, unsize) = if let Some(target) = unsize {
-                            (target, true)
-                        } else {
-                            (self.fcx.adjust_expr_ty(base_expr,
-                                Some(&ty::AdjustDerefRef(ty::AutoDerefRef {
-                                    autoderefs: autoderefs,
-                                    autoref: None,
-                                    unsize: None
-                                }))), false)
-                        };
-                        let index_expr_ty = self.fcx.expr_ty(&**index_expr);
-
-                        let result = check::try_index_step(
-                            self.fcx,
-                            MethodCall::expr(expr.id),
-                            expr,
-                            &**base_expr,
-                            adjusted_base_ty,
-                            autoderefs,
-                            unsize,
-                            PreferMutLvalue,
-                            index_expr_ty);
-
-                        if let Some((input_ty, return_ty)) = result {
-                            demand::suptype(self.fcx, index_expr.span, input_ty, index_expr_ty);
-
-                            let expr_ty = self.fcx.expr_ty(&*expr);
-                            demand::suptype(self.fcx, expr.span, expr_ty, return_ty);
-                        }
-                    }
-                    ast::ExprUnary(ast::UnDeref, ref base_expr) => {
-                        // if this is an overloaded deref, then re-evaluate with
-                        // a preference for mut
-                        let method_call = MethodCall::expr(expr.id);
-                        if self.fcx.inh.method_map.borrow().contains_key(&method_call) {
-                            check::try_overloaded_deref(
-                                self.fcx,
-                                expr.span,
-                                Some(method_call),
-                                Some(&**base_expr),
-                                self.fcx.expr_ty(&**base_expr),
-                                PreferMutLvalue);
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-
     ///////////////////////////////////////////////////////////////////////////
     // MISCELLANY
 
@@ -620,7 +775,14 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
         // Disallow calls to the method `drop` defined in the `Drop` trait.
         match pick.item.container() {
             ty::TraitContainer(trait_def_id) => {
-                callee::check_legal_trait_for_method_call(self.fcx.ccx, self.span, trait_def_id)
+                // `x.drop()` can become `std::mem::drop(x)`, unless the
+                // receiver is a reference that cannot be moved out of.
+                let dropped = match self.fcx.expr_ty(self.self_expr).sty {
+                    ty::TyRef(..) | ty::TyRawPtr(..) => None,
+                    _ => Some((self.call_expr, self.self_expr))
+                };
+                callee::check_legal_trait_for_method_call(self.fcx.ccx, self.span,
+                                                          trait_def_id, dropped)
             }
             ty::ImplContainer(..) => {
                 // Since `drop` is a trait method, we expect that any
@@ -632,26 +794,52 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
         }
     }
 
+    fn enforce_method_stability(&self, pick: &probe::Pick<'tcx>) {
+        // Report unstable methods here, where the call is known, rather
+        // than leaving them to the crate-wide stability check.
+        if let Some(stab) = pick.stability {
+            stability::check_method_call(self.tcx(), self.call_expr.id, self.span, stab);
+        }
+    }
+
     fn upcast(&mut self,
+              object_ty: Ty<'tcx>,
               source_trait_ref: ty::PolyTraitRef<'tcx>,
-              target_trait_def_id: ast::DefId)
+              target_trait_def_id: ast::DefId,
+              item_name: ast::Name)
               -> ty::PolyTraitRef<'tcx>
     {
-        let upcast_trait_refs = traits::upcast(self.tcx(),
-                                               source_trait_ref.clone(),
-                                               target_trait_def_id);
+        let tcx = self.tcx();
+        let mut upcast_trait_refs = traits::upcast_modulo_regions(tcx,
+                                                                  source_trait_ref.clone(),
+                                                                  target_trait_def_id);
 
-        // must be exactly one trait ref or we'd get an ambig error etc
-        if upcast_trait_refs.len() != 1 {
-            self.tcx().sess.span_bug(
+        if upcast_trait_refs.is_empty() {
+            tcx.sess.span_bug(
                 self.span,
-                &format!("cannot uniquely upcast `{:?}` to `{:?}`: `{:?}`",
+                &format!("cannot upcast `{:?}` to `{:?}`",
                          source_trait_ref,
-                         target_trait_def_id,
-                         upcast_trait_refs));
+                         target_trait_def_id));
+        }
+
+        if upcast_trait_refs.len() > 1 && !self.dry_run {
+            span_err!(tcx.sess, self.span, E0401,
+                      "cannot uniquely upcast `{}` to the trait `{}`",
+                      object_ty,
+                      ty::item_path_str(tcx, target_trait_def_id));
+            for trait_ref in &upcast_trait_refs {
+                tcx.sess.fileline_note(
+                    self.span,
+                    &format!("`{}` is reachable from `{}` as a supertrait",
+                             trait_ref, source_trait_ref));
+            }
+            tcx.sess.fileline_help(
+                self.span,
+                &format!("name the supertrait to use explicitly, e.g. `<{} as {}>::{}(...)`",
+                         object_ty, upcast_trait_refs[0], item_name));
         }
 
-        upcast_trait_refs.into_iter().next().unwrap()
+        upcast_trait_refs.swap_remove(0)
     }
 
     fn replace_late_bound_regions_with_fresh_var<T>(&self, value: &ty::Binder<T>) -> T
@@ -661,3 +849,185 @@ impl<'a,'tcx> ConfirmContext<'a,'tcx> {
             self.span, infer::FnCall, value).0
     }
 }
+
+///////////////////////////////////////////////////////////////////////////
+// RECONCILIATION
+
+/// When we select a method with an `&mut self` receiver, we have to go convert any
+/// auto-derefs, indices, etc from `Deref` and `Index` into `DerefMut` and `IndexMut`
+/// respectively.
+///
+/// This is done by rewriting the tables already written for the receiver's lvalue
+/// path rather than by type-checking it again: every overloaded deref or index
+/// recorded along the path, including each step of a recorded autoderef chain, has
+/// its callee replaced by the mutable variant, and the autoref that feeds it is made
+/// mutable to match.
+///
+/// The callee of an overloaded call that resolved to `FnMut::call_mut` is fixed up
+/// the same way, as it is the receiver of that method.
+pub fn fixup_derefs_on_method_receiver_if_necessary<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                                          receiver_expr: &ast::Expr,
+                                                          method_callee: &MethodCallee) {
+    let sig = match method_callee.ty.sty {
+        ty::TyBareFn(_, ref f) => f.sig.clone(),
+        _ => return,
+    };
+
+    match sig.0.inputs[0].sty {
+        ty::TyRef(_, ty::mt {
+            ty: _,
+            mutbl: ast::MutMutable,
+        }) => {}
+        _ => return,
+    }
+
+    // Gather up the expressions making up the receiver's lvalue path.
+    let mut exprs = Vec::new();
+    exprs.push(receiver_expr);
+    loop {
+        let last = exprs[exprs.len() - 1];
+        match last.node {
+            ast::ExprParen(ref expr) |
+            ast::ExprField(ref expr, _) |
+            ast::ExprTupField(ref expr, _) |
+            ast::ExprIndex(ref expr, _) |
+            ast::ExprUnary(ast::UnDeref, ref expr) => exprs.push(&**expr),
+            _ => break,
+        }
+    }
+
+    debug!("fixup_derefs_on_method_receiver_if_necessary: exprs={:?}",
+           exprs);
+
+    for &expr in &exprs {
+        // Overloaded derefs performed while autoderefing this expression.
+        let autoderefs = match fcx.inh.adjustments.borrow().get(&expr.id) {
+            Some(&ty::AdjustDerefRef(ref adj)) => adj.autoderefs,
+            Some(_) | None => 0,
+        };
+        for autoderef in 0..autoderefs {
+            select_mutable_lvalue_op(fcx,
+                                     expr.span,
+                                     MethodCall::autoderef(expr.id, autoderef as u32),
+                                     None);
+        }
+
+        // An overloaded deref or index of the expression itself.
+        match expr.node {
+            ast::ExprIndex(ref base_expr, _) |
+            ast::ExprUnary(ast::UnDeref, ref base_expr) => {
+                select_mutable_lvalue_op(fcx,
+                                         expr.span,
+                                         MethodCall::expr(expr.id),
+                                         Some(&**base_expr));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The method named `name` that the impl `impl_def_id` defines itself, if
+/// any.
+fn impl_method_with_name(tcx: &ty::ctxt, impl_def_id: ast::DefId, name: ast::Name)
+                         -> Option<ast::DefId> {
+    tcx.impl_items.borrow().get(&impl_def_id).and_then(|impl_items| {
+        impl_items.iter()
+                  .map(|item| item.def_id())
+                  .find(|&def_id| ty::impl_or_trait_item(tcx, def_id).name() == name)
+    })
+}
+
+/// The overloaded lvalue operators that have a variant taking `&mut self`: each
+/// entry pairs a trait with that variant and the name of its method. Traits whose
+/// method takes `&mut self` already, such as `IndexMut` and `IndexAssign`, have no
+/// entry, so their callees are left as they are.
+fn mutable_lvalue_ops(lang_items: &LanguageItems)
+                      -> [(Option<ast::DefId>, Option<ast::DefId>, &'static str); 2] {
+    [(lang_items.deref_trait(), lang_items.deref_mut_trait(), "deref_mut"),
+     (lang_items.index_trait(), lang_items.index_mut_trait(), "index_mut")]
+}
+
+/// Looks `trait_def_id` up in `mutable_lvalue_ops`, returning the mutable trait
+/// and its method name.
+fn mutable_lvalue_op(lang_items: &LanguageItems, trait_def_id: ast::DefId)
+                     -> Option<(ast::DefId, &'static str)> {
+    mutable_lvalue_ops(lang_items).iter()
+        .find(|&&(trait_id, _, _)| trait_id == Some(trait_def_id))
+        .and_then(|&(_, mut_trait_id, method_name)| {
+            mut_trait_id.map(|def_id| (def_id, method_name))
+        })
+}
+
+/// Replaces the operator callee recorded for `method_call`, if any, by its
+/// mutable variant from `mutable_lvalue_ops`, selected for the same self and
+/// index types. `base_expr` is the expression whose recorded autoref
+/// supplies the receiver; overloaded autoderefs have no such record.
+fn select_mutable_lvalue_op<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                      span: Span,
+                                      method_call: MethodCall,
+                                      base_expr: Option<&ast::Expr>) {
+    let old_callee = match fcx.inh.method_map.borrow().get(&method_call) {
+        Some(callee) => callee.clone(),
+        None => return, // a builtin deref or index
+    };
+
+    let trait_ref = match old_callee.origin {
+        MethodTypeParam(MethodParam { ref trait_ref, .. }) => trait_ref.clone(),
+        _ => return,
+    };
+    let (mut_trait_def_id, mut_method_name) =
+        match mutable_lvalue_op(&fcx.tcx().lang_items, trait_ref.def_id) {
+            Some(op) => op,
+            None => return, // already mutable
+        };
+
+    let self_ty = trait_ref.self_ty();
+    let input_tys = trait_ref.substs.types.get_slice(subst::TypeSpace).to_vec();
+    let new_callee = match super::lookup_in_trait(fcx,
+                                                  span,
+                                                  None,
+                                                  token::intern(mut_method_name),
+                                                  mut_trait_def_id,
+                                                  self_ty,
+                                                  Some(input_tys)) {
+        Some(callee) => callee,
+        // Not implemented; borrowck will report the immutable borrow.
+        None => return,
+    };
+
+    debug!("select_mutable_lvalue_op: method_call={:?} old_callee={:?} new_callee={:?}",
+           method_call,
+           old_callee,
+           new_callee);
+
+    // Both methods return a reference to the same lvalue, whose type has
+    // already been recorded; make sure the mutable one agrees.
+    let lvalue_ty = |callee: &MethodCallee<'tcx>| {
+        let ret_ty = ty::no_late_bound_regions(fcx.tcx(), &ty::ty_fn_ret(callee.ty))
+            .unwrap().unwrap();
+        ty::deref(ret_ty, true).unwrap().ty
+    };
+    demand::eqtype(fcx, span, lvalue_ty(&old_callee), lvalue_ty(&new_callee));
+
+    // The receiver of the new method is `&mut`, so the autoref producing
+    // it must be too.
+    if let Some(base_expr) = base_expr {
+        let new_self_ty = ty::ty_fn_args(new_callee.ty).0[0];
+        let adjustment = fcx.inh.adjustments.borrow().get(&base_expr.id).cloned();
+        match (adjustment, &new_self_ty.sty) {
+            (Some(ty::AdjustDerefRef(adj)), &ty::TyRef(region, _)) => {
+                if let Some(ty::AutoPtr(..)) = adj.autoref {
+                    let mut adjustment = ty::AdjustmentBuilder::autoderef(adj.autoderefs)
+                                             .autoref(ty::AutoPtr(region, ast::MutMutable));
+                    if adj.unsize.is_some() {
+                        adjustment = adjustment.unsize_receiver(new_self_ty);
+                    }
+                    fcx.write_adjustment(base_expr.id, ty::AdjustDerefRef(adjustment.build()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fcx.inh.method_map.borrow_mut().insert(method_call, new_callee);
+}
@@ -18,6 +18,7 @@ use middle::subst;
 use middle::traits;
 use middle::ty::{self, AsPredicate, ToPolyTraitRef, TraitRef};
 use middle::infer;
+use util::nodemap::FnvHashMap;
 
 use syntax::ast::DefId;
 use syntax::ast;
@@ -26,18 +27,22 @@ use syntax::codemap::Span;
 pub use self::MethodError::*;
 pub use self::CandidateSource::*;
 
+pub use self::confirm::{ConfirmedSig, fixup_derefs_on_method_receiver_if_necessary};
+pub use self::stats::MethodResolutionStats;
 pub use self::suggest::{report_error, AllTraitsVec};
 
 mod confirm;
 mod probe;
+pub mod stats;
 mod suggest;
 
 pub enum MethodError<'tcx> {
     // Did not find an applicable method, but we did find various near-misses that may work.
     NoMatch(NoMatchData<'tcx>),
 
-    // Multiple methods might apply.
-    Ambiguity(Vec<CandidateSource>),
+    // Multiple methods might apply; each is given with the adjustments
+    // its receiver would need.
+    Ambiguity(Vec<probe::Pick<'tcx>>),
 
     // Using a `Fn`/`FnMut`/etc method on a raw closure type before we have inferred its kind.
     ClosureAmbiguity(/* DefId of fn trait */ ast::DefId),
@@ -49,6 +54,7 @@ pub struct NoMatchData<'tcx> {
     pub static_candidates: Vec<CandidateSource>,
     pub unsatisfied_predicates: Vec<TraitRef<'tcx>>,
     pub out_of_scope_traits: Vec<ast::DefId>,
+    pub receiver_suggestions: Vec<ReceiverSuggestion<'tcx>>,
     pub mode: probe::Mode
 }
 
@@ -61,11 +67,25 @@ impl<'tcx> NoMatchData<'tcx> {
             static_candidates: static_candidates,
             unsatisfied_predicates: unsatisfied_predicates,
             out_of_scope_traits: out_of_scope_traits,
+            receiver_suggestions: Vec::new(),
             mode: mode
         }
     }
 }
 
+// A different receiver under which the item would have been found. Used
+// for error reporting only.
+#[derive(Clone, Debug)]
+pub enum ReceiverSuggestion<'tcx> {
+    // The item exists for `&mut T` (the second type), but the receiver
+    // is `&T` (the first type).
+    MutRef(ty::Ty<'tcx>, ty::Ty<'tcx>),
+
+    // The method exists for `&U` (the second type), which is reached by
+    // calling `as_ref()` on the receiver, autoderef'd to the first type.
+    AsRef(ty::Ty<'tcx>, ty::Ty<'tcx>),
+}
+
 // A pared down enum describing just the places from which a method
 // candidate can arise. Used for error reporting only.
 #[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
@@ -76,6 +96,23 @@ pub enum CandidateSource {
 
 type ItemIndex = usize; // just for doc purposes
 
+/// Picks already made for method calls in the current function body.
+///
+/// A pick depends only on the receiver type, the method name and the
+/// traits in scope at the call, so a call whose receiver type contains no
+/// type variables can reuse the pick of an earlier identical call rather
+/// than probing again. Only picks made outside of any inference snapshot
+/// are kept, as nothing they depend on can be rolled back afterwards.
+pub struct PickCache<'tcx> {
+    picks: FnvHashMap<(ty::Ty<'tcx>, ast::Name, Vec<DefId>), probe::Pick<'tcx>>,
+}
+
+impl<'tcx> PickCache<'tcx> {
+    pub fn new() -> PickCache<'tcx> {
+        PickCache { picks: FnvHashMap() }
+    }
+}
+
 /// Determines whether the type `self_ty` supports a method name `method_name` or not.
 pub fn exists<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                         span: Span,
@@ -99,19 +136,21 @@ pub fn exists<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
 ///
 /// # Arguments
 ///
-/// Given a method call like `foo.bar::<T1,...Tn>(...)`:
+/// Given a method call like `foo.bar::<'a1,...'am,T1,...Tn>(...)`:
 ///
 /// * `fcx`:                   the surrounding `FnCtxt` (!)
 /// * `span`:                  the span for the method call
 /// * `method_name`:           the name of the method being called (`bar`)
 /// * `self_ty`:               the (unadjusted) type of the self expression (`foo`)
 /// * `supplied_method_types`: the explicit method type parameters, if any (`T1..Tn`)
+/// * `supplied_method_regions`: the explicit method lifetime parameters, if any (`'a1..'am`)
 /// * `self_expr`:             the self expression (`foo`)
 pub fn lookup<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                         span: Span,
                         method_name: ast::Name,
                         self_ty: ty::Ty<'tcx>,
                         supplied_method_types: Vec<ty::Ty<'tcx>>,
+                        supplied_method_regions: Vec<ty::Region>,
                         call_expr: &'tcx ast::Expr,
                         self_expr: &'tcx ast::Expr)
                         -> Result<ty::MethodCallee<'tcx>, MethodError<'tcx>>
@@ -124,8 +163,66 @@ pub fn lookup<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
 
     let mode = probe::Mode::MethodCall;
     let self_ty = fcx.infcx().resolve_type_vars_if_possible(&self_ty);
-    let pick = try!(probe::probe(fcx, span, mode, method_name, self_ty, call_expr.id));
-    Ok(confirm::confirm(fcx, span, self_expr, call_expr, self_ty, pick, supplied_method_types))
+    let pick = try!(cached_probe(fcx, span, method_name, self_ty, call_expr.id));
+    Ok(confirm::confirm(fcx, span, self_expr, call_expr, self_ty, pick,
+                        supplied_method_types, supplied_method_regions))
+}
+
+/// Like `lookup`, but only reports the signature and bounds the call would
+/// be confirmed with. No adjustments, method callees or obligations are
+/// recorded and no errors are reported for the confirmation, so this can
+/// be used to ask about a call without committing to it.
+pub fn confirm_for_diagnostics<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                         span: Span,
+                                         method_name: ast::Name,
+                                         self_ty: ty::Ty<'tcx>,
+                                         supplied_method_types: Vec<ty::Ty<'tcx>>,
+                                         supplied_method_regions: Vec<ty::Region>,
+                                         call_expr: &'tcx ast::Expr,
+                                         self_expr: &'tcx ast::Expr)
+                                         -> Result<ConfirmedSig<'tcx>, MethodError<'tcx>>
+{
+    debug!("confirm_for_diagnostics(method_name={}, self_ty={:?}, call_expr={:?})",
+           method_name,
+           self_ty,
+           call_expr);
+
+    let self_ty = fcx.infcx().resolve_type_vars_if_possible(&self_ty);
+    let pick = try!(cached_probe(fcx, span, method_name, self_ty, call_expr.id));
+    Ok(confirm::confirm_for_diagnostics(fcx, span, self_expr, call_expr, self_ty, pick,
+                                        supplied_method_types, supplied_method_regions))
+}
+
+/// Probes for a method call, reusing the pick of an earlier call with the
+/// same fully resolved receiver type, method name and traits in scope.
+fn cached_probe<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                          span: Span,
+                          method_name: ast::Name,
+                          self_ty: ty::Ty<'tcx>,
+                          call_expr_id: ast::NodeId)
+                          -> probe::PickResult<'tcx>
+{
+    let mode = probe::Mode::MethodCall;
+    if ty::type_has_ty_infer(self_ty) {
+        return probe::probe(fcx, span, mode, method_name, self_ty, call_expr_id);
+    }
+
+    let traits_in_scope = fcx.ccx.trait_map.get(&call_expr_id).cloned().unwrap_or(Vec::new());
+    let key = (self_ty, method_name, traits_in_scope);
+    if let Some(pick) = fcx.inh.method_picks.borrow().picks.get(&key) {
+        debug!("cached_probe: reusing {:?}", pick);
+        let tcx = fcx.tcx();
+        if tcx.sess.time_method_resolution() {
+            fcx.ccx.method_stats.count_cached_pick(tcx.map.get_parent(fcx.body_id));
+        }
+        return Ok(pick.clone());
+    }
+
+    let pick = try!(probe::probe(fcx, span, mode, method_name, self_ty, call_expr_id));
+    if !fcx.infcx().in_snapshot() {
+        fcx.inh.method_picks.borrow_mut().picks.insert(key, pick.clone());
+    }
+    Ok(pick)
 }
 
 pub fn lookup_in_trait<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
@@ -275,16 +372,13 @@ pub fn lookup_in_trait_adjusted<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                     // autoref. Pull the region etc out of the type of first argument.
                     match transformed_self_ty.sty {
                         ty::TyRef(region, ty::mt { mutbl, ty: _ }) => {
+                            let mut adjustment = ty::AdjustmentBuilder::autoderef(autoderefs)
+                                                     .autoref(ty::AutoPtr(region, mutbl));
+                            if unsize {
+                                adjustment = adjustment.unsize_receiver(transformed_self_ty);
+                            }
                             fcx.write_adjustment(self_expr.id,
-                                ty::AdjustDerefRef(ty::AutoDerefRef {
-                                    autoderefs: autoderefs,
-                                    autoref: Some(ty::AutoPtr(region, mutbl)),
-                                    unsize: if unsize {
-                                        Some(transformed_self_ty)
-                                    } else {
-                                        None
-                                    }
-                                }));
+                                                 ty::AdjustDerefRef(adjustment.build()));
                         }
 
                         _ => {
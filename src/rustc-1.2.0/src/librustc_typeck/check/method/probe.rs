@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use super::MethodError;
-use super::NoMatchData;
+use super::{NoMatchData, ReceiverSuggestion};
 use super::ItemIndex;
 use super::{CandidateSource, ImplSource, TraitSource};
 use super::suggest;
@@ -17,6 +17,7 @@ use super::suggest;
 use check;
 use check::{FnCtxt, NoPreference, UnresolvedTypeAction};
 use middle::fast_reject;
+use middle::stability;
 use middle::subst;
 use middle::subst::Subst;
 use middle::traits;
@@ -25,6 +26,7 @@ use middle::ty_fold::TypeFoldable;
 use middle::infer;
 use middle::infer::InferCtxt;
 use syntax::ast;
+use syntax::attr;
 use syntax::codemap::{Span, DUMMY_SP};
 use std::collections::HashSet;
 use std::mem;
@@ -32,6 +34,7 @@ use std::rc::Rc;
 
 use self::CandidateKind::*;
 pub use self::PickKind::*;
+pub use self::PickAutoref::*;
 
 struct ProbeContext<'a, 'tcx:'a> {
     fcx: &'a FnCtxt<'a, 'tcx>,
@@ -80,7 +83,7 @@ enum CandidateKind<'tcx> {
     ProjectionCandidate(ast::DefId, ItemIndex),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Pick<'tcx> {
     pub item: ty::ImplOrTraitItem<'tcx>,
     pub kind: PickKind<'tcx>,
@@ -92,8 +95,8 @@ pub struct Pick<'tcx> {
 
     // Indicates that an autoref is applied after the optional autoderefs
     //
-    // B = A | &A | &mut A
-    pub autoref: Option<ast::Mutability>,
+    // B = A | &A | &mut A | *const A | *mut A
+    pub autoref: Option<PickAutoref>,
 
     // Indicates that the source expression should be "unsized" to a
     // target type. This should probably eventually go away in favor
@@ -101,6 +104,10 @@ pub struct Pick<'tcx> {
     //
     // C = B | unsize(B)
     pub unsize: Option<Ty<'tcx>>,
+
+    // The stability of the picked item, if it comes from another crate
+    // that is part of the staged API. Filled in once the pick is made.
+    pub stability: Option<&'tcx attr::Stability>,
 }
 
 #[derive(Clone,Debug)]
@@ -112,8 +119,50 @@ pub enum PickKind<'tcx> {
     WhereClausePick(/* Trait */ ty::PolyTraitRef<'tcx>, ItemIndex),
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PickAutoref {
+    // `&A` or `&mut A`
+    AutoRef(ast::Mutability),
+
+    // `*const A` or `*mut A`; only used to reach inherent methods
+    // declared with a raw pointer `self`
+    AutoRawPtr(ast::Mutability),
+}
+
 pub type PickResult<'tcx> = Result<Pick<'tcx>, MethodError<'tcx>>;
 
+impl<'tcx> Pick<'tcx> {
+    pub fn to_source(&self) -> CandidateSource {
+        match self.kind {
+            InherentImplPick(def_id) => ImplSource(def_id),
+            ObjectPick(def_id, _, _) => TraitSource(def_id),
+            ExtensionImplPick(def_id, _) => ImplSource(def_id),
+            TraitPick(def_id, _) => TraitSource(def_id),
+            WhereClausePick(ref trait_ref, _) => TraitSource(trait_ref.def_id()),
+        }
+    }
+}
+
+/// Applies the adjustment `f` to the pick of a successful result, or to
+/// each of the picks that made it ambiguous.
+fn map_picks<'tcx, F>(result: PickResult<'tcx>, mut f: F) -> PickResult<'tcx>
+    where F: FnMut(&mut Pick<'tcx>)
+{
+    match result {
+        Ok(mut pick) => {
+            f(&mut pick);
+            Ok(pick)
+        }
+        Err(MethodError::Ambiguity(mut picks)) => {
+            for pick in &mut picks {
+                f(pick);
+            }
+            Err(MethodError::Ambiguity(picks))
+        }
+        Err(e) => Err(e),
+    }
+}
+
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum Mode {
     // An expression of the form `receiver.method_name(...)`.
@@ -146,20 +195,51 @@ pub fn probe<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     // it ride, although it's really not great, and in fact could I
     // think cause spurious errors. Really though this part should
     // take place in the `fcx.infcx().probe` below.
-    let steps = if mode == Mode::MethodCall {
-        match create_steps(fcx, span, self_ty) {
-            Some(steps) => steps,
-            None =>return Err(MethodError::NoMatch(NoMatchData::new(Vec::new(), Vec::new(),
-                                                                    Vec::new(), mode))),
+    let steps = match steps_for_mode(fcx, span, mode, self_ty) {
+        Some(steps) => steps,
+        None =>return Err(MethodError::NoMatch(NoMatchData::new(Vec::new(), Vec::new(),
+                                                                Vec::new(), mode))),
+    };
+    let step_tys: Vec<_> = steps.iter().map(|step| step.self_ty).collect();
+
+    match probe_steps(fcx, span, mode, item_name, steps, scope_expr_id) {
+        Ok(mut pick) => {
+            pick.stability = stability::lookup_cross_crate(fcx.tcx(), pick.item.def_id());
+            Ok(pick)
+        }
+        Err(MethodError::NoMatch(mut no_match_data)) => {
+            no_match_data.receiver_suggestions =
+                receiver_suggestions(fcx, span, mode, item_name, &step_tys, scope_expr_id);
+            Err(MethodError::NoMatch(no_match_data))
         }
+        result => result
+    }
+}
+
+fn steps_for_mode<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                            span: Span,
+                            mode: Mode,
+                            self_ty: Ty<'tcx>)
+                            -> Option<Vec<CandidateStep<'tcx>>> {
+    if mode == Mode::MethodCall {
+        create_steps(fcx, span, self_ty)
     } else {
-        vec![CandidateStep {
+        Some(vec![CandidateStep {
             self_ty: self_ty,
             autoderefs: 0,
             unsize: false
-        }]
-    };
+        }])
+    }
+}
 
+fn probe_steps<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                         span: Span,
+                         mode: Mode,
+                         item_name: ast::Name,
+                         steps: Vec<CandidateStep<'tcx>>,
+                         scope_expr_id: ast::NodeId)
+                         -> PickResult<'tcx>
+{
     // Create a list of simplified self types, if we can.
     let mut simplified_steps = Vec::new();
     for step in &steps {
@@ -175,9 +255,7 @@ pub fn probe<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             Some(simplified_steps)
         };
 
-    debug!("ProbeContext: steps for self_ty={:?} are {:?}",
-           self_ty,
-           steps);
+    debug!("ProbeContext: steps are {:?}", steps);
 
     // this creates one big transaction so that all type variables etc
     // that we create during the probe process are removed later
@@ -194,6 +272,97 @@ pub fn probe<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     })
 }
 
+/// Looks for receivers close to the one that was given under which
+/// `item_name` would have been found, so that the error can point the
+/// user at them. Only called once lookup has already failed.
+///
+/// The alternative receivers are probed with steps built directly from
+/// the failed lookup's steps rather than with `create_steps`, which has
+/// side effects (see the FIXME in `probe`).
+fn receiver_suggestions<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                  span: Span,
+                                  mode: Mode,
+                                  item_name: ast::Name,
+                                  step_tys: &[Ty<'tcx>],
+                                  scope_expr_id: ast::NodeId)
+                                  -> Vec<ReceiverSuggestion<'tcx>>
+{
+    let tcx = fcx.tcx();
+    let found = |steps: Vec<(Ty<'tcx>, usize)>| {
+        let steps = steps.into_iter().map(|(self_ty, autoderefs)| {
+            CandidateStep { self_ty: self_ty, autoderefs: autoderefs, unsize: false }
+        }).collect();
+        match probe_steps(fcx, span, mode, item_name, steps, scope_expr_id) {
+            Ok(_) | Err(MethodError::Ambiguity(..)) => true,
+            Err(_) => false
+        }
+    };
+
+    let mut suggestions = vec![];
+    for &step_ty in step_tys {
+        // A method call would already have tried `&mut T` by autoref'ing
+        // the step after `&T`, so this only turns up anything for paths,
+        // which have no autoderef steps.
+        if mode == Mode::Path {
+            if let ty::TyRef(region, ty::mt { ty, mutbl: ast::MutImmutable }) = step_ty.sty {
+                let mut_ty = ty::mk_mut_rptr(tcx, region, ty);
+                if found(vec![(mut_ty, 0)]) {
+                    suggestions.push(ReceiverSuggestion::MutRef(step_ty, mut_ty));
+                }
+            }
+        }
+
+        // `as_ref()` returns `&U`, which autoderefs to `U`.
+        if mode == Mode::MethodCall {
+            for target in as_ref_targets(fcx, span, step_ty) {
+                let ref_ty = ty::mk_imm_rptr(tcx, tcx.mk_region(ty::ReStatic), target);
+                if found(vec![(ref_ty, 0), (target, 1)]) {
+                    suggestions.push(ReceiverSuggestion::AsRef(step_ty, target));
+                }
+            }
+        }
+    }
+    suggestions
+}
+
+/// Returns each `U` for which an impl of `AsRef<U>` applies to `self_ty`.
+/// Impls that leave `U` undetermined are skipped.
+fn as_ref_targets<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                            span: Span,
+                            self_ty: Ty<'tcx>)
+                            -> Vec<Ty<'tcx>> {
+    let tcx = fcx.tcx();
+    let trait_id = match tcx.lang_items.as_ref_trait() {
+        Some(trait_id) => trait_id,
+        None => return vec![]
+    };
+
+    let mut targets = vec![];
+    ty::lookup_trait_def(tcx, trait_id).for_each_relevant_impl(tcx, self_ty, |impl_def_id| {
+        let target = fcx.infcx().probe(|_| {
+            let impl_generics = ty::lookup_item_type(tcx, impl_def_id).generics;
+            let impl_substs = subst::Substs::new(
+                impl_generics.types.map(|_| fcx.infcx().next_ty_var()),
+                impl_generics.regions.map(|_| ty::ReStatic));
+            let trait_ref = ty::impl_trait_ref(tcx, impl_def_id).unwrap();
+            let trait_ref = trait_ref.subst(tcx, &impl_substs);
+            if infer::mk_eqty(fcx.infcx(), false, infer::Misc(span),
+                              self_ty, trait_ref.self_ty()).is_err() {
+                return None;
+            }
+            let target = *trait_ref.substs.types.get(subst::TypeSpace, 0);
+            let target = fcx.infcx().resolve_type_vars_if_possible(&target);
+            if ty::type_needs_infer(target) { None } else { Some(target) }
+        });
+        if let Some(target) = target {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    });
+    targets
+}
+
 fn create_steps<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                           span: Span,
                           self_ty: Ty<'tcx>)
@@ -302,87 +471,14 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
             ty::TyParam(p) => {
                 self.assemble_inherent_candidates_from_param(self_ty, p);
             }
-            ty::TyChar => {
-                let lang_def_id = self.tcx().lang_items.char_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyStr => {
-                let lang_def_id = self.tcx().lang_items.str_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TySlice(_) => {
-                let lang_def_id = self.tcx().lang_items.slice_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyRawPtr(ty::mt { ty: _, mutbl: ast::MutImmutable }) => {
-                let lang_def_id = self.tcx().lang_items.const_ptr_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyRawPtr(ty::mt { ty: _, mutbl: ast::MutMutable }) => {
-                let lang_def_id = self.tcx().lang_items.mut_ptr_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyInt(ast::TyI8) => {
-                let lang_def_id = self.tcx().lang_items.i8_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyInt(ast::TyI16) => {
-                let lang_def_id = self.tcx().lang_items.i16_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyInt(ast::TyI32) => {
-                let lang_def_id = self.tcx().lang_items.i32_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyInt(ast::TyI64) => {
-                let lang_def_id = self.tcx().lang_items.i64_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyInt(ast::TyIs) => {
-                let lang_def_id = self.tcx().lang_items.isize_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyUint(ast::TyU8) => {
-                let lang_def_id = self.tcx().lang_items.u8_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyUint(ast::TyU16) => {
-                let lang_def_id = self.tcx().lang_items.u16_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyUint(ast::TyU32) => {
-                let lang_def_id = self.tcx().lang_items.u32_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyUint(ast::TyU64) => {
-                let lang_def_id = self.tcx().lang_items.u64_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyUint(ast::TyUs) => {
-                let lang_def_id = self.tcx().lang_items.usize_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyFloat(ast::TyF32) => {
-                let lang_def_id = self.tcx().lang_items.f32_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
-            ty::TyFloat(ast::TyF64) => {
-                let lang_def_id = self.tcx().lang_items.f64_impl();
-                self.assemble_inherent_impl_for_primitive(lang_def_id);
-            }
             _ => {
+                if let Some(impl_def_id) = ty::primitive_inherent_impl(self.tcx(), self_ty) {
+                    self.assemble_inherent_impl_probe(impl_def_id);
+                }
             }
         }
     }
 
-    fn assemble_inherent_impl_for_primitive(&mut self, lang_def_id: Option<ast::DefId>) {
-        if let Some(impl_def_id) = lang_def_id {
-            ty::populate_implementations_for_primitive_if_necessary(self.tcx(), impl_def_id);
-
-            self.assemble_inherent_impl_probe(impl_def_id);
-        }
-    }
-
     fn assemble_inherent_impl_candidates_for_type(&mut self, def_id: ast::DefId) {
         // Read the inherent implementation candidates for this type from the
         // metadata if necessary.
@@ -463,6 +559,16 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
                                                    new_trait_ref.self_ty(),
                                                    new_trait_ref.substs);
 
+            // An object can't be passed by value, so a method taking
+            // `self` is instead called on a `Box<Trait>`, whose vtable
+            // entry moves the value out of the box.
+            let xform_self_ty = match item.as_opt_method() {
+                Some(ref method) if method.takes_self_by_value() => {
+                    ty::mk_uniq(tcx, xform_self_ty)
+                }
+                _ => xform_self_ty
+            };
+
             this.inherent_candidates.push(Candidate {
                 xform_self_ty: xform_self_ty,
                 item: item,
@@ -890,8 +996,8 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
 
         let out_of_scope_traits = match self.pick_core() {
             Some(Ok(p)) => vec![p.item.container().id()],
-            Some(Err(MethodError::Ambiguity(v))) => v.into_iter().map(|source| {
-                match source {
+            Some(Err(MethodError::Ambiguity(v))) => v.into_iter().map(|pick| {
+                match pick.to_source() {
                     TraitSource(id) => id,
                     ImplSource(impl_id) => {
                         match ty::trait_id_of_impl(tcx, impl_id) {
@@ -958,16 +1064,14 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
             return None;
         }
 
-        self.pick_method(step.self_ty).map(|r| r.map(|mut pick| {
+        self.pick_method(step.self_ty).map(|r| map_picks(r, |pick| {
             pick.autoderefs = step.autoderefs;
 
             // Insert a `&*` or `&mut *` if this is a reference type:
             if let ty::TyRef(_, mt) = step.self_ty.sty {
                 pick.autoderefs += 1;
-                pick.autoref = Some(mt.mutbl);
+                pick.autoref = Some(AutoRef(mt.mutbl));
             }
-
-            pick
         }))
     }
 
@@ -981,21 +1085,34 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
         // `impl_self_ty()` for an explanation.
         let region = tcx.mk_region(ty::ReStatic);
 
-        // Search through mutabilities in order to find one where pick works:
-        [ast::MutImmutable, ast::MutMutable].iter().filter_map(|&m| {
-            let autoref_ty = ty::mk_rptr(tcx, region, ty::mt {
-                ty: step.self_ty,
-                mutbl: m
-            });
-            self.pick_method(autoref_ty).map(|r| r.map(|mut pick| {
+        // Search through mutabilities in order to find one where pick
+        // works, trying raw pointers only after references:
+        [AutoRef(ast::MutImmutable), AutoRef(ast::MutMutable),
+         AutoRawPtr(ast::MutImmutable), AutoRawPtr(ast::MutMutable)].iter().filter_map(|&a| {
+            let autoref_ty = match a {
+                AutoRef(m) => ty::mk_rptr(tcx, region, ty::mt { ty: step.self_ty, mutbl: m }),
+                AutoRawPtr(m) => ty::mk_ptr(tcx, ty::mt { ty: step.self_ty, mutbl: m }),
+            };
+            let result = self.pick_method(autoref_ty);
+
+            // Trait methods taking a raw pointer `self` are implemented
+            // for the pointer type itself, and are not reached by autoref.
+            match (a, &result) {
+                (AutoRawPtr(_), &Some(Ok(ref pick))) => match pick.kind {
+                    InherentImplPick(..) => {}
+                    _ => return None,
+                },
+                _ => {}
+            }
+
+            result.map(|r| map_picks(r, |pick| {
                 pick.autoderefs = step.autoderefs;
-                pick.autoref = Some(m);
+                pick.autoref = Some(a);
                 pick.unsize = if step.unsize {
                     Some(step.self_ty)
                 } else {
                     None
                 };
-                pick
             }))
         }).nth(0)
     }
@@ -1044,8 +1161,8 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
         }
 
         if applicable_candidates.len() > 1 {
-            let sources = probes.iter().map(|p| p.to_source()).collect();
-            return Some(Err(MethodError::Ambiguity(sources)));
+            let picks = applicable_candidates.iter().map(|p| p.to_unadjusted_pick()).collect();
+            return Some(Err(MethodError::Ambiguity(picks)));
         }
 
         applicable_candidates.pop().map(|probe| {
@@ -1156,7 +1273,8 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
             kind: TraitPick(trait_def_id, item_num),
             autoderefs: 0,
             autoref: None,
-            unsize: None
+            unsize: None,
+            stability: None
         })
     }
 
@@ -1367,18 +1485,8 @@ impl<'tcx> Candidate<'tcx> {
             },
             autoderefs: 0,
             autoref: None,
-            unsize: None
-        }
-    }
-
-    fn to_source(&self) -> CandidateSource {
-        match self.kind {
-            InherentImplCandidate(def_id, _, _) => ImplSource(def_id),
-            ObjectCandidate(def_id, _, _) => TraitSource(def_id),
-            ExtensionImplCandidate(def_id, _, _, _, _) => ImplSource(def_id),
-            ClosureCandidate(trait_def_id, _) => TraitSource(trait_def_id),
-            WhereClauseCandidate(ref trait_ref, _) => TraitSource(trait_ref.def_id()),
-            ProjectionCandidate(trait_def_id, _) => TraitSource(trait_def_id),
+            unsize: None,
+            stability: None
         }
     }
 
@@ -0,0 +1,118 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Statistics about method confirmation, and about how many probes were
+//! answered from the pick cache, gathered per function with
+//! `-Z time-method-resolution` and printed as JSON once type checking
+//! is done.
+
+use middle::ty;
+use util::nodemap::NodeMap;
+
+use rustc_serialize::json;
+use syntax::ast;
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::time::Duration;
+
+/// The parts of method confirmation that are timed. The times are
+/// inclusive: `Confirm` covers the other two.
+#[derive(Copy, Clone, Debug)]
+pub enum Phase {
+    Confirm,
+    FreshReceiverSubsts,
+    InstantiateMethodSig,
+}
+
+#[derive(Copy, Clone, Default, RustcEncodable)]
+pub struct PhaseStats {
+    pub calls: usize,
+    pub nanos: u64,
+}
+
+#[derive(Copy, Clone, Default)]
+struct FnStats {
+    confirm: PhaseStats,
+    fresh_receiver_substs: PhaseStats,
+    instantiate_method_sig: PhaseStats,
+    cached_picks: usize,
+}
+
+/// One entry of the JSON report.
+#[derive(RustcEncodable)]
+struct FnReport {
+    function: String,
+    confirm: PhaseStats,
+    fresh_receiver_substs: PhaseStats,
+    instantiate_method_sig: PhaseStats,
+    cached_picks: usize,
+}
+
+pub struct MethodResolutionStats {
+    /// Keyed by the item whose body contains the method calls.
+    fns: RefCell<NodeMap<FnStats>>,
+}
+
+impl MethodResolutionStats {
+    pub fn new() -> MethodResolutionStats {
+        MethodResolutionStats { fns: RefCell::new(NodeMap()) }
+    }
+
+    /// Runs `f` and charges the time it took to `phase` of `item_id`.
+    pub fn time<T, F>(&self, item_id: ast::NodeId, phase: Phase, f: F) -> T where
+        F: FnOnce() -> T,
+    {
+        let mut rv = None;
+        let dur = {
+            let ref mut rvp = rv;
+            Duration::span(move || {
+                *rvp = Some(f())
+            })
+        };
+
+        let mut fns = self.fns.borrow_mut();
+        let stats = fns.entry(item_id).or_insert(FnStats::default());
+        let phase_stats = match phase {
+            Phase::Confirm => &mut stats.confirm,
+            Phase::FreshReceiverSubsts => &mut stats.fresh_receiver_substs,
+            Phase::InstantiateMethodSig => &mut stats.instantiate_method_sig,
+        };
+        phase_stats.calls += 1;
+        phase_stats.nanos += dur.secs() * 1_000_000_000 + dur.extra_nanos() as u64;
+
+        rv.unwrap()
+    }
+
+    /// Notes that a method call in `item_id` reused a cached pick.
+    pub fn count_cached_pick(&self, item_id: ast::NodeId) {
+        let mut fns = self.fns.borrow_mut();
+        fns.entry(item_id).or_insert(FnStats::default()).cached_picks += 1;
+    }
+
+    /// Prints the statistics as a JSON array, the functions that spent
+    /// the most time confirming method calls first.
+    pub fn print(&self, tcx: &ty::ctxt) {
+        let mut report: Vec<_> = self.fns.borrow().iter().map(|(&id, stats)| {
+            FnReport {
+                function: tcx.map.path_to_string(id),
+                confirm: stats.confirm,
+                fresh_receiver_substs: stats.fresh_receiver_substs,
+                instantiate_method_sig: stats.instantiate_method_sig,
+                cached_picks: stats.cached_picks,
+            }
+        }).collect();
+        report.sort_by(|a, b| match b.confirm.nanos.cmp(&a.confirm.nanos) {
+            Ordering::Equal => a.function.cmp(&b.function),
+            ord => ord,
+        });
+        println!("{}", json::as_pretty_json(&report));
+    }
+}
@@ -28,15 +28,22 @@ use syntax::print::pprust;
 
 use std::cell;
 use std::cmp::Ordering;
+use std::iter::repeat;
 
-use super::{MethodError, NoMatchData, CandidateSource, impl_item, trait_item};
-use super::probe::Mode;
+use super::{MethodError, NoMatchData, ReceiverSuggestion, CandidateSource};
+use super::{impl_item, trait_item};
+use super::confirm;
+use super::probe::{self, Mode, Pick};
 
+/// Reports `error`, which arose while looking up `item_name` on `rcvr_ty`.
+/// For a method call, `rcvr_expr` and `call_expr` are the receiver and the
+/// call; for a path they are both `None`.
 pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                               span: Span,
                               rcvr_ty: Ty<'tcx>,
                               item_name: ast::Name,
-                              rcvr_expr: Option<&ast::Expr>,
+                              rcvr_expr: Option<&'tcx ast::Expr>,
+                              call_expr: Option<&'tcx ast::Expr>,
                               error: MethodError<'tcx>)
 {
     // avoid suggestions when we don't know what's going on.
@@ -48,6 +55,7 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         MethodError::NoMatch(NoMatchData { static_candidates: static_sources,
                                            unsatisfied_predicates,
                                            out_of_scope_traits,
+                                           receiver_suggestions,
                                            mode }) => {
             let cx = fcx.tcx();
 
@@ -143,15 +151,44 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                              bound_list));
             }
 
+            for suggestion in &receiver_suggestions {
+                match *suggestion {
+                    ReceiverSuggestion::MutRef(found, expected) => {
+                        cx.sess.fileline_help(
+                            span,
+                            &format!("the associated item `{}` exists for `{}`, \
+                                      but the receiver type is `{}`",
+                                     item_name, expected, found));
+                    }
+                    ReceiverSuggestion::AsRef(from, target) => {
+                        let rcvr_snippet = rcvr_expr.and_then(|expr| {
+                            cx.sess.codemap().span_to_snippet(expr.span).ok()
+                        });
+                        let msg = format!("the method `{}` exists for `&{}`, which `{}` can be \
+                                           converted to with `as_ref()`",
+                                          item_name, target, from);
+                        match rcvr_snippet {
+                            Some(rcvr_snippet) => {
+                                cx.sess.fileline_help(
+                                    span,
+                                    &format!("{}; try `{}.as_ref().{}(...)`",
+                                             msg, rcvr_snippet, item_name));
+                            }
+                            None => cx.sess.fileline_help(span, &msg)
+                        }
+                    }
+                }
+            }
+
             suggest_traits_to_import(fcx, span, rcvr_ty, item_name,
                                      rcvr_expr, out_of_scope_traits)
         }
 
-        MethodError::Ambiguity(sources) => {
+        MethodError::Ambiguity(picks) => {
             span_err!(fcx.sess(), span, E0034,
                       "multiple applicable items in scope");
 
-            report_candidates(fcx, span, item_name, sources);
+            report_candidates(fcx, span, rcvr_ty, item_name, rcvr_expr, call_expr, picks);
         }
 
         MethodError::ClosureAmbiguity(trait_def_id) => {
@@ -170,15 +207,24 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         }
     }
 
-    fn report_candidates(fcx: &FnCtxt,
-                         span: Span,
-                         item_name: ast::Name,
-                         mut sources: Vec<CandidateSource>) {
-        sources.sort();
-        sources.dedup();
+    fn report_candidates<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                   span: Span,
+                                   rcvr_ty: Ty<'tcx>,
+                                   item_name: ast::Name,
+                                   rcvr_expr: Option<&'tcx ast::Expr>,
+                                   call_expr: Option<&'tcx ast::Expr>,
+                                   mut picks: Vec<Pick<'tcx>>) {
+        picks.sort_by(|a, b| a.to_source().cmp(&b.to_source()));
+        let mut sources = Vec::new();
+        picks.retain(|pick| {
+            let source = pick.to_source();
+            let first = !sources.contains(&source);
+            sources.push(source);
+            first
+        });
 
-        for (idx, source) in sources.iter().enumerate() {
-            match *source {
+        for (idx, pick) in picks.iter().enumerate() {
+            match pick.to_source() {
                 CandidateSource::ImplSource(impl_did) => {
                     // Provide the best span we can. Use the item, if local to crate, else
                     // the impl, if local to crate (item may be defaulted), else the call site.
@@ -210,6 +256,104 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                ty::item_path_str(fcx.tcx(), trait_did));
                 }
             }
+
+            // Show how to name this candidate with UFCS, along with the
+            // adjustments its receiver would have needed.
+            let tcx = fcx.tcx();
+            let trait_did = match pick.to_source() {
+                CandidateSource::TraitSource(trait_did) => Some(trait_did),
+                CandidateSource::ImplSource(impl_did) => {
+                    ty::impl_trait_ref(tcx, impl_did).map(|trait_ref| trait_ref.def_id)
+                }
+            };
+            let msg = match rcvr_expr {
+                Some(rcvr_expr) => {
+                    let path = match trait_did {
+                        Some(trait_did) => ty::item_path_str(tcx, trait_did),
+                        None => inherent_impl_path(fcx, pick),
+                    };
+                    let has_args = pick.item.as_opt_method().map_or(false, |method| {
+                        method.fty.sig.0.inputs.len() > 1
+                    });
+                    format!("to use candidate #{}, write `{}::{}({}{})`",
+                            idx + 1,
+                            path,
+                            item_name,
+                            adjusted_receiver(pick, rcvr_expr),
+                            if has_args { ", ..." } else { "" })
+                }
+                None => {
+                    let qself = match trait_did {
+                        Some(trait_did) => {
+                            format!("<{} as {}>", rcvr_ty, ty::item_path_str(tcx, trait_did))
+                        }
+                        None => format!("<{}>", rcvr_ty),
+                    };
+                    format!("to use candidate #{}, write `{}::{}`", idx + 1, qself, item_name)
+                }
+            };
+            fcx.sess().fileline_help(span, &msg);
+
+            // For a method call, also show what confirming the candidate
+            // would have made of the rest of the signature.
+            if let (Some(rcvr_expr), Some(call_expr)) = (rcvr_expr, call_expr) {
+                let sig = confirm::confirm_for_diagnostics(fcx, span, rcvr_expr, call_expr,
+                                                           rcvr_ty, pick.clone(),
+                                                           Vec::new(), Vec::new());
+                let args: Vec<_> = sig.method_sig.inputs[1..].iter()
+                                                             .map(|ty| ty.to_string())
+                                                             .collect();
+                let output = match sig.method_sig.output {
+                    ty::FnConverging(ty) => ty.to_string(),
+                    ty::FnDiverging => "!".to_string(),
+                };
+                fcx.sess().fileline_note(
+                    span,
+                    &format!("candidate #{} would be called with arguments `({})` \
+                              and return `{}`",
+                             idx + 1, args.connect(", "), output));
+            }
+        }
+    }
+
+    // The path naming the self type of the inherent impl of `pick`.
+    fn inherent_impl_path(fcx: &FnCtxt, pick: &Pick) -> String {
+        let impl_did = match pick.to_source() {
+            CandidateSource::ImplSource(impl_did) => impl_did,
+            CandidateSource::TraitSource(..) => unreachable!(),
+        };
+        let impl_ty = ty::lookup_item_type(fcx.tcx(), impl_did).ty;
+        match impl_ty.sty {
+            ty::TyStruct(did, _) | ty::TyEnum(did, _) => ty::item_path_str(fcx.tcx(), did),
+            _ => format!("<{}>", impl_ty),
+        }
+    }
+
+    // The receiver expression with the autoderefs and autoref of `pick`
+    // written out, e.g. `&mut **x`.
+    fn adjusted_receiver(pick: &Pick, rcvr_expr: &ast::Expr) -> String {
+        let mut rcvr = pprust::expr_to_string(rcvr_expr);
+        if pick.autoderefs == 0 && pick.autoref.is_none() {
+            return rcvr;
+        }
+
+        match rcvr_expr.node {
+            ast::ExprPath(..) | ast::ExprField(..) | ast::ExprTupField(..) |
+            ast::ExprIndex(..) | ast::ExprCall(..) | ast::ExprMethodCall(..) |
+            ast::ExprParen(..) => {}
+            _ => rcvr = format!("({})", rcvr),
+        }
+        let derefs: String = repeat('*').take(pick.autoderefs).collect();
+        match pick.autoref {
+            Some(probe::AutoRef(ast::MutMutable)) => format!("&mut {}{}", derefs, rcvr),
+            Some(probe::AutoRef(ast::MutImmutable)) => format!("&{}{}", derefs, rcvr),
+            Some(probe::AutoRawPtr(ast::MutMutable)) => {
+                format!("&mut {}{} as *mut _", derefs, rcvr)
+            }
+            Some(probe::AutoRawPtr(ast::MutImmutable)) => {
+                format!("&{}{} as *const _", derefs, rcvr)
+            }
+            None => format!("{}{}", derefs, rcvr),
         }
     }
 }
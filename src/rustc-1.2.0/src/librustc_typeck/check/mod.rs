@@ -85,6 +85,7 @@ use astconv::{self, ast_region_to_region, ast_ty_to_ty, AstConv, PathParamMode};
 use check::_match::pat_ctxt;
 use fmt_macros::{Parser, Piece, Position};
 use middle::astconv_util::{check_path_args, NO_TPS, NO_REGIONS};
+use middle::cast::CastKind;
 use middle::def;
 use middle::infer;
 use middle::mem_categorization as mc;
@@ -99,7 +100,7 @@ use middle::ty::{Disr, ParamTy, ParameterEnvironment};
 use middle::ty::{self, HasProjectionTypes, RegionEscape, ToPolyTraitRef, Ty};
 use middle::ty::liberate_late_bound_regions;
 use middle::ty::{MethodCall, MethodCallee, MethodMap};
-use middle::ty_fold::{TypeFolder, TypeFoldable};
+use middle::ty_fold::{self, TypeFolder, TypeFoldable};
 use rscope::RegionScope;
 use session::Session;
 use {CrateCtxt, lookup_full_def, require_same_types};
@@ -134,6 +135,7 @@ pub mod coercion;
 pub mod demand;
 pub mod method;
 mod upvar;
+pub mod unresolved;
 pub mod wf;
 mod cast;
 mod closure;
@@ -160,7 +162,10 @@ pub struct Inherited<'a, 'tcx: 'a> {
     item_substs: RefCell<NodeMap<ty::ItemSubsts<'tcx>>>,
     adjustments: RefCell<NodeMap<ty::AutoAdjustment<'tcx>>>,
     method_map: MethodMap<'tcx>,
+    method_call_resolutions: RefCell<NodeMap<ty::ResolvedMethod<'tcx>>>,
+    autoderef_chains: RefCell<NodeMap<Vec<Ty<'tcx>>>>,
     upvar_capture_map: RefCell<ty::UpvarCaptureMap>,
+    upvar_capture_spans: RefCell<ty::UpvarCaptureSpanMap>,
     closure_tys: RefCell<DefIdMap<ty::ClosureTy<'tcx>>>,
     closure_kinds: RefCell<DefIdMap<ty::ClosureKind>>,
 
@@ -182,6 +187,14 @@ pub struct Inherited<'a, 'tcx: 'a> {
     deferred_call_resolutions: RefCell<DefIdMap<Vec<DeferredCallResolutionHandler<'tcx>>>>,
 
     deferred_cast_checks: RefCell<Vec<cast::CastCheck<'tcx>>>,
+
+    // The kinds of the casts that passed their deferred check, keyed on
+    // the id of the expression being cast. Writeback settles the final
+    // kind once the types involved are fully resolved.
+    cast_kinds: RefCell<NodeMap<CastKind>>,
+
+    // Method picks already made in this body; see `method::PickCache`.
+    method_picks: RefCell<method::PickCache<'tcx>>,
 }
 
 trait DeferredCallResolution<'tcx> {
@@ -280,8 +293,8 @@ impl UnsafetyState {
 pub struct FnCtxt<'a, 'tcx: 'a> {
     body_id: ast::NodeId,
 
-    // This flag is set to true if, during the writeback phase, we encounter
-    // a type error in this function.
+    // This flag is set to true if the writeback phase found types in this
+    // function that it could not resolve.
     writeback_errors: Cell<bool>,
 
     // Number of errors that had been reported when we started
@@ -381,13 +394,18 @@ impl<'a, 'tcx> Inherited<'a, 'tcx> {
             item_substs: RefCell::new(NodeMap()),
             adjustments: RefCell::new(NodeMap()),
             method_map: RefCell::new(FnvHashMap()),
+            method_call_resolutions: RefCell::new(NodeMap()),
+            autoderef_chains: RefCell::new(NodeMap()),
             upvar_capture_map: RefCell::new(FnvHashMap()),
+            upvar_capture_spans: RefCell::new(FnvHashMap()),
             closure_tys: RefCell::new(DefIdMap()),
             closure_kinds: RefCell::new(DefIdMap()),
             fn_sig_map: RefCell::new(NodeMap()),
             fulfillment_cx: RefCell::new(traits::FulfillmentContext::new(true)),
             deferred_call_resolutions: RefCell::new(DefIdMap()),
             deferred_cast_checks: RefCell::new(Vec::new()),
+            cast_kinds: RefCell::new(NodeMap()),
+            method_picks: RefCell::new(method::PickCache::new()),
         }
     }
 
@@ -462,6 +480,14 @@ impl<'a, 'tcx> Visitor<'tcx> for CheckItemBodiesVisitor<'a, 'tcx> {
     }
 }
 
+// Writes the report asked for with `-Z emit-unresolved-types`, once every
+// body has been written back or type checking is about to stop.
+fn emit_unresolved_types(ccx: &CrateCtxt) {
+    if let Some(path) = ccx.tcx.sess.emit_unresolved_types() {
+        ccx.unresolved_types.write(ccx.tcx, path);
+    }
+}
+
 pub fn check_item_types(ccx: &CrateCtxt) {
     let krate = ccx.tcx.map.krate();
     let mut visit = wf::CheckTypeWellFormedVisitor::new(ccx);
@@ -474,11 +500,17 @@ pub fn check_item_types(ccx: &CrateCtxt) {
     let mut visit = CheckItemTypesVisitor { ccx: ccx };
     visit::walk_crate(&mut visit, krate);
 
+    // Types left unresolved are errors, so the report has to be written
+    // before aborting on them.
+    if ccx.tcx.sess.has_errors() {
+        emit_unresolved_types(ccx);
+    }
     ccx.tcx.sess.abort_if_errors();
 
     let mut visit = CheckItemBodiesVisitor { ccx: ccx };
     visit::walk_crate(&mut visit, krate);
 
+    emit_unresolved_types(ccx);
     ccx.tcx.sess.abort_if_errors();
 
     for drop_method_did in ccx.tcx.destructors.borrow().iter() {
@@ -506,6 +538,7 @@ fn check_bare_fn<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
 {
     match raw_fty.sty {
         ty::TyBareFn(_, ref fn_ty) => {
+            let err_count_on_entry = ccx.tcx.sess.err_count();
             let inh = Inherited::new(ccx.tcx, param_env);
 
             // Compute the fty from point of view of inside fn.
@@ -529,7 +562,20 @@ fn check_bare_fn<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
             fcx.select_all_obligations_or_error(); // Casts can introduce new obligations.
 
             regionck::regionck_fn(&fcx, fn_id, fn_span, decl, body);
-            writeback::resolve_type_vars_in_fn(&fcx, decl, body);
+            writeback::resolve_type_vars_in_fn(&fcx, fn_id, decl, body);
+
+            // For unit testing: report the written-back adjustments of
+            // every expression in functions marked `#[rustc_dump_adjustments]`.
+            if ty::has_attr(ccx.tcx, local_def(fn_id), "rustc_dump_adjustments") {
+                writeback::dump_adjustments(ccx.tcx, body);
+            }
+
+            // Keep track of how many functions had errors, for the
+            // summary printed when we abort.
+            if ccx.tcx.sess.err_count() > err_count_on_entry {
+                let fns_with_errors = &ccx.tcx.sess.fns_with_errors;
+                fns_with_errors.set(fns_with_errors.get() + 1);
+            }
         }
         _ => ccx.tcx.sess.impossible_case(body.span,
                                  "check_bare_fn: function type expected")
@@ -1242,8 +1288,8 @@ impl<'a, 'tcx> AstConv<'tcx> for FnCtxt<'a, 'tcx> {
         trait_def.associated_type_names.contains(&assoc_name)
     }
 
-    fn ty_infer(&self, _span: Span) -> Ty<'tcx> {
-        self.infcx().next_ty_var()
+    fn ty_infer(&self, span: Span) -> Ty<'tcx> {
+        self.infcx().next_ty_var_with_origin(infer::TypeInferenceVar(span))
     }
 
     fn projected_ty_from_poly_trait_ref(&self,
@@ -1371,25 +1417,19 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     }
 
     /// Apply "fallbacks" to some types
-    /// ! gets replaced with (), unconstrained ints with i32, and unconstrained floats with f64.
+    /// ! gets replaced with (), and unconstrained ints and floats with the types of the
+    /// crate's `LiteralFallback` (`i32` and `f64` unless `#![default_integer_type]` is used).
     pub fn default_type_parameters(&self) {
-        use middle::ty::UnconstrainedNumeric::{UnconstrainedInt, UnconstrainedFloat, Neither};
         for (_, &mut ref ty) in &mut *self.inh.node_types.borrow_mut() {
             let resolved = self.infcx().resolve_type_vars_if_possible(ty);
             if self.infcx().type_var_diverges(resolved) {
                 demand::eqtype(self, codemap::DUMMY_SP, *ty, ty::mk_nil(self.tcx()));
-            } else {
-                match self.infcx().type_is_unconstrained_numeric(resolved) {
-                    UnconstrainedInt => {
-                        demand::eqtype(self, codemap::DUMMY_SP, *ty, self.tcx().types.i32)
-                    },
-                    UnconstrainedFloat => {
-                        demand::eqtype(self, codemap::DUMMY_SP, *ty, self.tcx().types.f64)
-                    }
-                    Neither => { }
-                }
             }
         }
+
+        let node_types = self.inh.node_types.borrow();
+        self.infcx().apply_literal_fallback(&self.ccx.literal_fallback,
+                                            node_types.iter().map(|(&id, &ty)| (id, ty)));
     }
 
     #[inline]
@@ -1415,11 +1455,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                                       derefs: usize) {
         self.write_adjustment(
             node_id,
-            ty::AdjustDerefRef(ty::AutoDerefRef {
-                autoderefs: derefs,
-                autoref: None,
-                unsize: None
-            })
+            ty::AdjustDerefRef(ty::AdjustmentBuilder::autoderef(derefs).build())
         );
     }
 
@@ -1694,7 +1730,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                     origin: infer::TypeOrigin,
                     sub: Ty<'tcx>,
                     sup: Ty<'tcx>)
-                    -> Result<(), ty::type_err<'tcx>> {
+                    -> infer::TraceResult<'tcx> {
         infer::mk_subty(self.infcx(), a_is_expected, origin, sub, sup)
     }
 
@@ -1703,7 +1739,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                    origin: infer::TypeOrigin,
                    sub: Ty<'tcx>,
                    sup: Ty<'tcx>)
-                   -> Result<(), ty::type_err<'tcx>> {
+                   -> infer::TraceResult<'tcx> {
         infer::mk_eqty(self.infcx(), a_is_expected, origin, sub, sup)
     }
 
@@ -1944,8 +1980,15 @@ pub fn autoderef<'a, 'tcx, T, F>(fcx: &FnCtxt<'a, 'tcx>,
            opt_expr,
            lvalue_pref);
 
+    let tcx = fcx.tcx();
+    let limit = tcx.sess.opts.debugging_opts.autoderef_limit
+                   .unwrap_or(tcx.sess.recursion_limit.get());
+
+    // The types seen so far, with regions erased for comparison.
+    let mut chain: Vec<(Ty<'tcx>, Ty<'tcx>)> = Vec::new();
+
     let mut t = base_ty;
-    for autoderefs in 0..fcx.tcx().sess.recursion_limit.get() {
+    for autoderefs in 0..limit {
         let resolved_t = match unresolved_type_action {
             UnresolvedTypeAction::Error => {
                 structurally_resolved_type(fcx, sp, t)
@@ -1967,6 +2010,16 @@ pub fn autoderef<'a, 'tcx, T, F>(fcx: &FnCtxt<'a, 'tcx>,
             None => {}
         }
 
+        // A type that dereferences back to itself would only run into the
+        // limit, so report the cycle as soon as it closes.
+        let erased_t = ty_fold::erase_regions(tcx, resolved_t);
+        if let Some(start) = chain.iter().position(|&(erased, _)| erased == erased_t) {
+            let cycle: Vec<_> = chain[start..].iter().map(|&(_, ty)| ty).collect();
+            report_autoderef_cycle(fcx, sp, base_ty, &cycle);
+            return (tcx.types.err, 0, None);
+        }
+        chain.push((erased_t, resolved_t));
+
         // Otherwise, deref if type is derefable:
         let mt = match ty::deref(resolved_t, false) {
             Some(mt) => Some(mt),
@@ -2000,10 +2053,30 @@ pub fn autoderef<'a, 'tcx, T, F>(fcx: &FnCtxt<'a, 'tcx>,
     }
 
     // We've reached the recursion limit, error gracefully.
-    span_err!(fcx.tcx().sess, sp, E0055,
+    span_err!(tcx.sess, sp, E0055,
         "reached the recursion limit while auto-dereferencing {:?}",
         base_ty);
-    (fcx.tcx().types.err, 0, None)
+    tcx.sess.fileline_note(sp, &format!("gave up after {} dereferences, at `{}`; the limit \
+                                          can be raised with the `recursion_limit` attribute \
+                                          or `-Z autoderef-limit`",
+                                         limit, t));
+    (tcx.types.err, 0, None)
+}
+
+/// Reports that auto-dereferencing `base_ty` runs into `cycle`, a sequence of
+/// types each of which dereferences to the next, and the last to the first.
+fn report_autoderef_cycle<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                    sp: Span,
+                                    base_ty: Ty<'tcx>,
+                                    cycle: &[Ty<'tcx>]) {
+    let tcx = fcx.tcx();
+    span_err!(tcx.sess, sp, E0402,
+              "cyclic `Deref` implementations while auto-dereferencing `{}`",
+              base_ty);
+    for (i, &ty) in cycle.iter().enumerate() {
+        let target = cycle[(i + 1) % cycle.len()];
+        tcx.sess.fileline_note(sp, &format!("`{}` dereferences to `{}`", ty, target));
+    }
 }
 
 fn try_overloaded_deref<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
@@ -2068,7 +2141,8 @@ fn lookup_indexing<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                              base_expr: &'tcx ast::Expr,
                              base_ty: Ty<'tcx>,
                              idx_ty: Ty<'tcx>,
-                             lvalue_pref: LvaluePreference)
+                             lvalue_pref: LvaluePreference,
+                             assign: bool)
                              -> Option<(/*index type*/ Ty<'tcx>, /*element type*/ Ty<'tcx>)>
 {
     // FIXME(#18741) -- this is almost but not quite the same as the
@@ -2083,7 +2157,7 @@ fn lookup_indexing<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                                lvalue_pref,
                                                |adj_ty, idx| {
         try_index_step(fcx, MethodCall::expr(expr.id), expr, base_expr,
-                       adj_ty, idx, false, lvalue_pref, idx_ty)
+                       adj_ty, idx, false, lvalue_pref, assign, idx_ty)
     });
 
     if final_mt.is_some() {
@@ -2095,7 +2169,7 @@ fn lookup_indexing<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     if let ty::TyArray(element_ty, _) = ty.sty {
         let adjusted_ty = ty::mk_vec(fcx.tcx(), element_ty, None);
         try_index_step(fcx, MethodCall::expr(expr.id), expr, base_expr,
-                       adjusted_ty, autoderefs, true, lvalue_pref, idx_ty)
+                       adjusted_ty, autoderefs, true, lvalue_pref, assign, idx_ty)
     } else {
         None
     }
@@ -2105,6 +2179,10 @@ fn lookup_indexing<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
 /// `base_expr`, looking for a type which either supports builtin indexing or overloaded indexing.
 /// This loop implements one step in that search; the autoderef loop is implemented by
 /// `lookup_indexing`.
+///
+/// If `assign` is set, the index expression is the left-hand side of an assignment, and
+/// `IndexAssign` is tried before the other indexing traits. The "element type" of such an index
+/// is the type of the value being assigned.
 fn try_index_step<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                             method_call: MethodCall,
                             expr: &ast::Expr,
@@ -2113,6 +2191,7 @@ fn try_index_step<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                             autoderefs: usize,
                             unsize: bool,
                             lvalue_pref: LvaluePreference,
+                            assign: bool,
                             index_ty: Ty<'tcx>)
                             -> Option<(/*index type*/ Ty<'tcx>, /*element type*/ Ty<'tcx>)>
 {
@@ -2140,46 +2219,61 @@ fn try_index_step<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         _ => {}
     }
 
-    // Try `IndexMut` first, if preferred.
-    let method = match (lvalue_pref, tcx.lang_items.index_mut_trait()) {
-        (PreferMutLvalue, Some(trait_did)) => {
-            method::lookup_in_trait_adjusted(fcx,
-                                             expr.span,
-                                             Some(&*base_expr),
-                                             token::intern("index_mut"),
-                                             trait_did,
-                                             autoderefs,
-                                             unsize,
-                                             adjusted_ty,
-                                             Some(vec![input_ty]))
-        }
-        _ => None,
-    };
+    // The overloaded indexing traits to try, in order of preference, and
+    // whether their method also takes the value being assigned.
+    let mut index_traits = Vec::new();
+    if assign {
+        index_traits.push((tcx.lang_items.index_assign_trait(), "index_assign", true));
+    }
+    if lvalue_pref == PreferMutLvalue {
+        index_traits.push((tcx.lang_items.index_mut_trait(), "index_mut", false));
+    }
+    index_traits.push((tcx.lang_items.index_trait(), "index", false));
 
-    // Otherwise, fall back to `Index`.
-    let method = match (method, tcx.lang_items.index_trait()) {
-        (None, Some(trait_did)) => {
-            method::lookup_in_trait_adjusted(fcx,
-                                             expr.span,
-                                             Some(&*base_expr),
-                                             token::intern("index"),
-                                             trait_did,
-                                             autoderefs,
-                                             unsize,
-                                             adjusted_ty,
-                                             Some(vec![input_ty]))
-        }
-        (method, _) => method,
-    };
+    for &(trait_did, method_name, takes_value) in &index_traits {
+        let trait_did = match trait_did {
+            Some(trait_did) => trait_did,
+            None => continue
+        };
+        let value_ty = fcx.infcx().next_ty_var();
+        let input_tys = if takes_value {
+            vec![input_ty, value_ty]
+        } else {
+            vec![input_ty]
+        };
+        let method = match method::lookup_in_trait_adjusted(fcx,
+                                                            expr.span,
+                                                            Some(&*base_expr),
+                                                            token::intern(method_name),
+                                                            trait_did,
+                                                            autoderefs,
+                                                            unsize,
+                                                            adjusted_ty,
+                                                            Some(input_tys)) {
+            Some(method) => method,
+            None => continue
+        };
 
-    // If some lookup succeeds, write callee into table and extract index/element
-    // type from the method signature.
-    // If some lookup succeeded, install method in table
-    method.and_then(|method| {
+        if takes_value {
+            debug!("try_index_step: success, using overloaded index assignment");
+            if !tcx.sess.features.borrow().index_assign {
+                feature_gate::emit_feature_err(&tcx.sess.parse_sess.span_diagnostic,
+                                               "index_assign",
+                                               expr.span,
+                                               "overloaded index assignment is unstable");
+            }
+            fcx.inh.method_map.borrow_mut().insert(method_call, method);
+            return Some((input_ty, value_ty));
+        }
+
+        // Write callee into table and extract index/element type from the
+        // method signature.
         debug!("try_index_step: success, using overloaded indexing");
-        make_overloaded_lvalue_return_type(fcx, Some(method_call), Some(method)).
-            map(|ret| (input_ty, ret.ty))
-    })
+        return make_overloaded_lvalue_return_type(fcx, Some(method_call), Some(method)).
+            map(|ret| (input_ty, ret.ty));
+    }
+
+    None
 }
 
 fn check_method_argument_types<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
@@ -2629,11 +2723,54 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
     debug!(">> typechecking: expr={:?} expected={:?}",
            expr, expected);
 
+    // Checks `base[idx]`, which is the left-hand side of an assignment if
+    // `assign` is set.
+    fn check_index_expr<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                  expr: &'tcx ast::Expr,
+                                  base: &'tcx ast::Expr,
+                                  idx: &'tcx ast::Expr,
+                                  lvalue_pref: LvaluePreference,
+                                  assign: bool) {
+        check_expr_with_lvalue_pref(fcx, base, lvalue_pref);
+        check_expr(fcx, idx);
+
+        let base_t = fcx.expr_ty(base);
+        let idx_t = fcx.expr_ty(idx);
+
+        if ty::type_is_error(base_t) {
+            fcx.write_ty(expr.id, base_t);
+        } else if ty::type_is_error(idx_t) {
+            fcx.write_ty(expr.id, idx_t);
+        } else {
+            let base_t = structurally_resolved_type(fcx, expr.span, base_t);
+            match lookup_indexing(fcx, expr, base, base_t, idx_t, lvalue_pref, assign) {
+                Some((index_ty, element_ty)) => {
+                    let idx_expr_ty = fcx.expr_ty(idx);
+                    demand::eqtype(fcx, expr.span, index_ty, idx_expr_ty);
+                    fcx.write_ty(expr.id, element_ty);
+                }
+                None => {
+                    check_expr_has_type(fcx, idx, fcx.tcx().types.err);
+                    fcx.type_error_message(
+                        expr.span,
+                        |actual| {
+                            format!("cannot index a value of type `{}`",
+                                    actual)
+                        },
+                        base_t,
+                        None);
+                    fcx.write_ty(expr.id, fcx.tcx().types.err);
+                }
+            }
+        }
+    }
+
     // Checks a method call.
     fn check_method_call<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                    expr: &'tcx ast::Expr,
                                    method_name: ast::SpannedIdent,
                                    args: &'tcx [P<ast::Expr>],
+                                   lifetimes: &[ast::Lifetime],
                                    tps: &[P<ast::Ty>],
                                    expected: Expectation<'tcx>,
                                    lvalue_pref: LvaluePreference) {
@@ -2646,11 +2783,15 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
                                                 fcx.expr_ty(&*rcvr));
 
         let tps = tps.iter().map(|ast_ty| fcx.to_ty(&**ast_ty)).collect::<Vec<_>>();
+        let regions = lifetimes.iter()
+                               .map(|lifetime| ast_region_to_region(fcx.tcx(), lifetime))
+                               .collect::<Vec<_>>();
         let fn_ty = match method::lookup(fcx,
                                          method_name.span,
                                          method_name.node.name,
                                          expr_t,
                                          tps,
+                                         regions,
                                          expr,
                                          rcvr) {
             Ok(method) => {
@@ -2661,7 +2802,7 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
             }
             Err(error) => {
                 method::report_error(fcx, method_name.span, expr_t,
-                                     method_name.node.name, Some(rcvr), error);
+                                     method_name.node.name, Some(rcvr), Some(expr), error);
                 fcx.write_error(expr.id);
                 fcx.tcx().types.err
             }
@@ -3342,7 +3483,13 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
         fcx.write_ty(id, fcx.expr_ty(&**a));
       }
       ast::ExprAssign(ref lhs, ref rhs) => {
-        check_expr_with_lvalue_pref(fcx, &**lhs, PreferMutLvalue);
+        // `base[idx] = rhs` may be an overloaded index assignment, which
+        // is picked while checking the index.
+        if let ast::ExprIndex(ref base, ref idx) = lhs.node {
+            check_index_expr(fcx, &**lhs, &**base, &**idx, PreferMutLvalue, true);
+        } else {
+            check_expr_with_lvalue_pref(fcx, &**lhs, PreferMutLvalue);
+        }
 
         let tcx = fcx.tcx();
         if !ty::expr_is_lval(tcx, &**lhs) {
@@ -3408,8 +3555,9 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
       ast::ExprCall(ref callee, ref args) => {
           callee::check_call(fcx, expr, &**callee, &args[..], expected);
       }
-      ast::ExprMethodCall(ident, ref tps, ref args) => {
-        check_method_call(fcx, expr, ident, &args[..], &tps[..], expected, lvalue_pref);
+      ast::ExprMethodCall(ident, ref lifetimes, ref tps, ref args) => {
+        check_method_call(fcx, expr, ident, &args[..], &lifetimes[..], &tps[..],
+                          expected, lvalue_pref);
         let arg_tys = args.iter().map(|a| fcx.expr_ty(&**a));
         let  args_err = arg_tys.fold(false,
              |rest_err, a| {
@@ -3462,7 +3610,7 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
                 uty
             }
             None => {
-                let t: Ty = fcx.infcx().next_ty_var();
+                let t: Ty = fcx.infcx().next_ty_var_with_origin(infer::ArrayElementVar(expr.span));
                 for e in args {
                     check_expr_has_type(fcx, &**e, t);
                 }
@@ -3614,7 +3762,7 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
                                             .ty_to_string(
                                                 actual_structure_type),
                                          type_error);
-                    ty::note_and_explain_type_err(tcx, &type_error, path.span);
+                    ty::note_and_explain_type_err(tcx, &type_error.err, path.span);
                 }
             }
         }
@@ -3628,38 +3776,7 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
         check_tup_field(fcx, expr, lvalue_pref, &**base, idx);
       }
       ast::ExprIndex(ref base, ref idx) => {
-          check_expr_with_lvalue_pref(fcx, &**base, lvalue_pref);
-          check_expr(fcx, &**idx);
-
-          let base_t = fcx.expr_ty(&**base);
-          let idx_t = fcx.expr_ty(&**idx);
-
-          if ty::type_is_error(base_t) {
-              fcx.write_ty(id, base_t);
-          } else if ty::type_is_error(idx_t) {
-              fcx.write_ty(id, idx_t);
-          } else {
-              let base_t = structurally_resolved_type(fcx, expr.span, base_t);
-              match lookup_indexing(fcx, expr, base, base_t, idx_t, lvalue_pref) {
-                  Some((index_ty, element_ty)) => {
-                      let idx_expr_ty = fcx.expr_ty(idx);
-                      demand::eqtype(fcx, expr.span, index_ty, idx_expr_ty);
-                      fcx.write_ty(id, element_ty);
-                  }
-                  None => {
-                      check_expr_has_type(fcx, &**idx, fcx.tcx().types.err);
-                      fcx.type_error_message(
-                          expr.span,
-                          |actual| {
-                              format!("cannot index a value of type `{}`",
-                                      actual)
-                          },
-                          base_t,
-                          None);
-                      fcx.write_ty(id, fcx.tcx().types.err);
-                  }
-              }
-          }
+          check_index_expr(fcx, expr, &**base, &**idx, lvalue_pref, false);
        }
        ast::ExprRange(ref start, ref end) => {
           let t_start = start.as_ref().map(|e| {
@@ -3819,7 +3936,7 @@ pub fn resolve_ty_and_def_ufcs<'a, 'b, 'tcx>(fcx: &FnCtxt<'b, 'tcx>,
             }
             Err(error) => {
                 method::report_error(fcx, span, ty,
-                                     item_name, None, error);
+                                     item_name, None, None, error);
                 fcx.write_error(node_id);
                 None
             }
@@ -4097,7 +4214,8 @@ fn check_const_in_type<'a,'tcx>(ccx: &'a CrateCtxt<'a,'tcx>,
                                 expected_type: Ty<'tcx>) {
     let inh = static_inherited_fields(ccx);
     let fcx = blank_fn_ctxt(ccx, &inh, ty::FnConverging(expected_type), expr.id);
-    check_const_with_ty(&fcx, expr.span, expr, expected_type);
+    check_const_with_ty(&fcx, expr.span, expr, expected_type,
+                        Some(writeback::AnonConstKind::ArrayLength));
 }
 
 fn check_const<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
@@ -4108,25 +4226,37 @@ fn check_const<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
     let rty = ty::node_id_to_type(ccx.tcx, id);
     let fcx = blank_fn_ctxt(ccx, &inh, ty::FnConverging(rty), e.id);
     let declty = fcx.ccx.tcx.tcache.borrow().get(&local_def(id)).unwrap().ty;
-    check_const_with_ty(&fcx, sp, e, declty);
+    check_const_with_ty(&fcx, sp, e, declty, None);
 }
 
 fn check_const_with_ty<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                  _: Span,
                                  e: &'tcx ast::Expr,
-                                 declty: Ty<'tcx>) {
+                                 declty: Ty<'tcx>,
+                                 anon_const: Option<writeback::AnonConstKind>) {
     // Gather locals in statics (because of block expressions).
     // This is technically unnecessary because locals in static items are forbidden,
     // but prevents type checking from blowing up before const checking can properly
     // emit a error.
     GatherLocalsVisitor { fcx: fcx }.visit_expr(e);
 
-    check_expr_with_hint(fcx, e, declty);
-    demand::coerce(fcx, e.span, declty, e);
+    match anon_const {
+        // An anonymous constant has exactly the type it is expected to
+        // have, so that e.g. an unsuffixed literal used as an array length
+        // is a `usize` rather than falling back to `i32`.
+        Some(_) => check_expr_has_type(fcx, e, declty),
+        None => {
+            check_expr_with_hint(fcx, e, declty);
+            demand::coerce(fcx, e.span, declty, e);
+        }
+    }
     fcx.select_all_obligations_or_error();
     fcx.check_casts();
     regionck::regionck_expr(fcx, e);
-    writeback::resolve_type_vars_in_expr(fcx, e);
+    match anon_const {
+        Some(kind) => writeback::resolve_type_vars_in_anon_const(fcx, e, kind),
+        None => writeback::resolve_type_vars_in_expr(fcx, e),
+    }
 }
 
 /// Checks whether a type can be represented in memory. In particular, it
@@ -4228,7 +4358,7 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                 ast::TyU8 => disr as u8 as Disr == disr,
                 ast::TyU16 => disr as u16 as Disr == disr,
                 ast::TyU32 => disr as u32 as Disr == disr,
-                ast::TyU64 => disr as u64 as Disr == disr,
+                ast::TyU64 | ast::TyU128 => disr as u64 as Disr == disr,
                 ast::TyUs => uint_in_range(ccx, ccx.tcx.sess.target.uint_type, disr)
             }
         }
@@ -4237,7 +4367,7 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                 ast::TyI8 => disr as i8 as Disr == disr,
                 ast::TyI16 => disr as i16 as Disr == disr,
                 ast::TyI32 => disr as i32 as Disr == disr,
-                ast::TyI64 => disr as i64 as Disr == disr,
+                ast::TyI64 | ast::TyI128 => disr as i64 as Disr == disr,
                 ast::TyIs => int_in_range(ccx, ccx.tcx.sess.target.int_type, disr)
             }
         }
@@ -4262,7 +4392,8 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
         let (_, repr_type_ty) = ty::enum_repr_type(ccx.tcx, Some(&hint));
         for v in vs {
             if let Some(ref e) = v.node.disr_expr {
-                check_const_with_ty(&fcx, e.span, e, repr_type_ty);
+                check_const_with_ty(&fcx, e.span, e, repr_type_ty,
+                                    Some(writeback::AnonConstKind::Discriminant));
             }
         }
 
@@ -4477,9 +4608,16 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         def::DefMethod(_, provenance) => {
             match provenance {
                 def::FromTrait(trait_did) => {
-                    callee::check_legal_trait_for_method_call(fcx.ccx, span, trait_did)
+                    callee::check_legal_trait_for_method_call(fcx.ccx, span, trait_did, None)
+                }
+                def::FromImpl(_) => {
+                    // A path can name the `drop` of a `Drop` impl directly.
+                    let did = def.def_id();
+                    if fcx.tcx().destructors.borrow().contains(&did) {
+                        span_err!(fcx.tcx().sess, span, E0040,
+                                  "explicit use of destructor method");
+                    }
                 }
-                def::FromImpl(_) => {}
             }
 
             if segments.len() >= 2 {
@@ -4497,7 +4635,7 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         def::DefAssociatedConst(_, provenance) => {
             match provenance {
                 def::FromTrait(trait_did) => {
-                    callee::check_legal_trait_for_method_call(fcx.ccx, span, trait_did)
+                    callee::check_legal_trait_for_method_call(fcx.ccx, span, trait_did, None)
                 }
                 def::FromImpl(_) => {}
             }
@@ -4787,7 +4925,10 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         // Nothing specified at all: supply inference variables for
         // everything.
         if provided_len == 0 && !(require_type_space && space == subst::TypeSpace) {
-            substs.types.replace(space, fcx.infcx().next_ty_vars(desired.len()));
+            let tps = desired.iter().map(|def| {
+                fcx.infcx().next_ty_var_with_origin(infer::TypeParameterVar(span, def.name))
+            }).collect();
+            substs.types.replace(space, tps);
             return;
         }
 
@@ -5156,17 +5297,21 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
             "ctpop16"      => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "ctpop32"      => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "ctpop64"      => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "ctpop128"     => (0, vec!( tcx.types.u128 ), tcx.types.u128),
             "ctlz8"        => (0, vec!( tcx.types.u8  ), tcx.types.u8),
             "ctlz16"       => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "ctlz32"       => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "ctlz64"       => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "ctlz128"      => (0, vec!( tcx.types.u128 ), tcx.types.u128),
             "cttz8"        => (0, vec!( tcx.types.u8  ), tcx.types.u8),
             "cttz16"       => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "cttz32"       => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "cttz64"       => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "cttz128"      => (0, vec!( tcx.types.u128 ), tcx.types.u128),
             "bswap16"      => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "bswap32"      => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "bswap64"      => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "bswap128"     => (0, vec!( tcx.types.u128 ), tcx.types.u128),
 
             "volatile_load" =>
                 (1, vec!( ty::mk_imm_ptr(tcx, param(ccx, 0)) ), param(ccx, 0)),
@@ -5189,6 +5334,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 (0, vec!(tcx.types.i64, tcx.types.i64),
                 ty::mk_tup(tcx, vec!(tcx.types.i64, tcx.types.bool))),
 
+            "i128_add_with_overflow" | "i128_sub_with_overflow" | "i128_mul_with_overflow" =>
+                (0, vec!(tcx.types.i128, tcx.types.i128),
+                ty::mk_tup(tcx, vec!(tcx.types.i128, tcx.types.bool))),
+
             "u8_add_with_overflow" | "u8_sub_with_overflow" | "u8_mul_with_overflow" =>
                 (0, vec!(tcx.types.u8, tcx.types.u8),
                 ty::mk_tup(tcx, vec!(tcx.types.u8, tcx.types.bool))),
@@ -5205,6 +5354,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 (0, vec!(tcx.types.u64, tcx.types.u64),
                 ty::mk_tup(tcx, vec!(tcx.types.u64, tcx.types.bool))),
 
+            "u128_add_with_overflow" | "u128_sub_with_overflow" | "u128_mul_with_overflow" =>
+                (0, vec!(tcx.types.u128, tcx.types.u128),
+                ty::mk_tup(tcx, vec!(tcx.types.u128, tcx.types.bool))),
+
             "unchecked_udiv" | "unchecked_sdiv" | "unchecked_urem" | "unchecked_srem" =>
                 (1, vec![param(ccx, 0), param(ccx, 0)], param(ccx, 0)),
 
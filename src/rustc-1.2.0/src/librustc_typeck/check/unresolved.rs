@@ -0,0 +1,95 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Machine-readable records of the types that writeback could not
+//! resolve, gathered with `-Z emit-unresolved-types=PATH` and written
+//! to `PATH` as a JSON array once type checking is done. Editors can
+//! use them to offer to insert a type annotation.
+
+use middle::infer;
+use middle::ty;
+
+use rustc_serialize::json;
+use syntax::codemap::Span;
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Write;
+
+/// One entry of the JSON report. Lines are 1-based and columns 0-based,
+/// as in the compiler's own diagnostics.
+#[derive(RustcEncodable)]
+struct UnresolvedTypeRecord {
+    /// What was being resolved: `expr`, `local`, `pattern`, `anon_const`,
+    /// `upvar` or `closure`.
+    reason: String,
+    file: String,
+    line_lo: usize,
+    col_lo: usize,
+    line_hi: usize,
+    col_hi: usize,
+    message: String,
+    /// The type as far as it could be inferred, with `_` for the parts
+    /// that could not. Absent when a region could not be resolved.
+    partial_ty: Option<String>,
+    /// The types that inference would have fallen back to, which for
+    /// literals honour `#![default_integer_type]`.
+    candidate_defaults: Vec<String>,
+}
+
+pub struct UnresolvedTypes {
+    records: RefCell<Vec<UnresolvedTypeRecord>>,
+}
+
+impl UnresolvedTypes {
+    pub fn new() -> UnresolvedTypes {
+        UnresolvedTypes { records: RefCell::new(Vec::new()) }
+    }
+
+    pub fn record<'tcx>(&self,
+                        tcx: &ty::ctxt<'tcx>,
+                        reason: &'static str,
+                        span: Span,
+                        err: infer::fixup_err,
+                        partial_ty: Option<String>,
+                        fallback: &infer::LiteralFallback<'tcx>) {
+        let codemap = tcx.sess.codemap();
+        let lo = codemap.lookup_char_pos(span.lo);
+        let hi = codemap.lookup_char_pos(span.hi);
+        let candidate_defaults = match err {
+            infer::unresolved_int_ty(_) => vec![fallback.int_ty.to_string()],
+            infer::unresolved_float_ty(_) => vec![fallback.float_ty.to_string()],
+            infer::unresolved_ty(_) => vec![],
+        };
+
+        self.records.borrow_mut().push(UnresolvedTypeRecord {
+            reason: reason.to_string(),
+            file: lo.file.name.clone(),
+            line_lo: lo.line,
+            col_lo: lo.col.0,
+            line_hi: hi.line,
+            col_hi: hi.col.0,
+            message: infer::fixup_err_to_string(err),
+            partial_ty: partial_ty,
+            candidate_defaults: candidate_defaults,
+        });
+    }
+
+    /// Writes the records to `path`, in the order they were found.
+    pub fn write(&self, tcx: &ty::ctxt, path: &str) {
+        let records = self.records.borrow();
+        let result = File::create(path).and_then(|mut file| {
+            write!(file, "{}", json::as_pretty_json(&*records))
+        });
+        if let Err(e) = result {
+            tcx.sess.err(&format!("could not write unresolved types to `{}`: {}", path, e));
+        }
+    }
+}
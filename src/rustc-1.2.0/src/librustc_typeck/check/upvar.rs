@@ -237,6 +237,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
     }
 
     fn adjust_upvar_borrow_kind_for_consume(&self,
+                                            span: Span,
                                             cmt: mc::cmt<'tcx>,
                                             mode: euv::ConsumeMode)
     {
@@ -268,7 +269,11 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
                         self.adjust_closure_kind(upvar_id.closure_expr_id, ty::FnOnceClosureKind);
 
                         let mut upvar_capture_map = self.fcx.inh.upvar_capture_map.borrow_mut();
-                        upvar_capture_map.insert(upvar_id, ty::UpvarCapture::ByValue);
+                        let old_capture =
+                            upvar_capture_map.insert(upvar_id, ty::UpvarCapture::ByValue);
+                        if old_capture != Some(ty::UpvarCapture::ByValue) {
+                            self.record_capture_span(upvar_id, span);
+                        }
                     }
                     mc::NoteClosureEnv(upvar_id) => {
                         // we get just a closureenv ref if this is a
@@ -290,7 +295,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
     /// Indicates that `cmt` is being directly mutated (e.g., assigned
     /// to). If cmt contains any by-ref upvars, this implies that
     /// those upvars must be borrowed using an `&mut` borrow.
-    fn adjust_upvar_borrow_kind_for_mut(&mut self, cmt: mc::cmt<'tcx>) {
+    fn adjust_upvar_borrow_kind_for_mut(&mut self, span: Span, cmt: mc::cmt<'tcx>) {
         debug!("adjust_upvar_borrow_kind_for_mut(cmt={:?})",
                cmt);
 
@@ -300,17 +305,17 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
             mc::cat_downcast(base, _) => {
                 // Interior or owned data is mutable if base is
                 // mutable, so iterate to the base.
-                self.adjust_upvar_borrow_kind_for_mut(base);
+                self.adjust_upvar_borrow_kind_for_mut(span, base);
             }
 
             mc::cat_deref(base, _, mc::BorrowedPtr(..)) |
             mc::cat_deref(base, _, mc::Implicit(..)) => {
-                if !self.try_adjust_upvar_deref(&cmt.note, ty::MutBorrow) {
+                if !self.try_adjust_upvar_deref(span, &cmt.note, ty::MutBorrow) {
                     // assignment to deref of an `&mut`
                     // borrowed pointer implies that the
                     // pointer itself must be unique, but not
                     // necessarily *mutable*
-                    self.adjust_upvar_borrow_kind_for_unique(base);
+                    self.adjust_upvar_borrow_kind_for_unique(span, base);
                 }
             }
 
@@ -324,7 +329,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
         }
     }
 
-    fn adjust_upvar_borrow_kind_for_unique(&self, cmt: mc::cmt<'tcx>) {
+    fn adjust_upvar_borrow_kind_for_unique(&self, span: Span, cmt: mc::cmt<'tcx>) {
         debug!("adjust_upvar_borrow_kind_for_unique(cmt={:?})",
                cmt);
 
@@ -334,15 +339,15 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
             mc::cat_downcast(base, _) => {
                 // Interior or owned data is unique if base is
                 // unique.
-                self.adjust_upvar_borrow_kind_for_unique(base);
+                self.adjust_upvar_borrow_kind_for_unique(span, base);
             }
 
             mc::cat_deref(base, _, mc::BorrowedPtr(..)) |
             mc::cat_deref(base, _, mc::Implicit(..)) => {
-                if !self.try_adjust_upvar_deref(&cmt.note, ty::UniqueImmBorrow) {
+                if !self.try_adjust_upvar_deref(span, &cmt.note, ty::UniqueImmBorrow) {
                     // for a borrowed pointer to be unique, its
                     // base must be unique
-                    self.adjust_upvar_borrow_kind_for_unique(base);
+                    self.adjust_upvar_borrow_kind_for_unique(span, base);
                 }
             }
 
@@ -356,6 +361,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
     }
 
     fn try_adjust_upvar_deref(&self,
+                              span: Span,
                               note: &mc::Note,
                               borrow_kind: ty::BorrowKind)
                               -> bool
@@ -376,7 +382,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
                 // is inferred to mutable if necessary
                 let mut upvar_capture_map = self.fcx.inh.upvar_capture_map.borrow_mut();
                 let ub = upvar_capture_map.get_mut(&upvar_id).unwrap();
-                self.adjust_upvar_borrow_kind(upvar_id, ub, borrow_kind);
+                self.adjust_upvar_borrow_kind(span, upvar_id, ub, borrow_kind);
 
                 // also need to be in an FnMut closure since this is not an ImmBorrow
                 self.adjust_closure_kind(upvar_id.closure_expr_id, ty::FnMutClosureKind);
@@ -402,6 +408,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
     /// (but never right to left). Here the argument `mutbl` is the borrow_kind that is required by
     /// some particular use.
     fn adjust_upvar_borrow_kind(&self,
+                                span: Span,
                                 upvar_id: ty::UpvarId,
                                 upvar_capture: &mut ty::UpvarCapture,
                                 kind: ty::BorrowKind) {
@@ -419,6 +426,7 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
                     (ty::ImmBorrow, ty::MutBorrow) |
                     (ty::UniqueImmBorrow, ty::MutBorrow) => {
                         upvar_borrow.kind = kind;
+                        self.record_capture_span(upvar_id, span);
                     }
                     // Take LHS:
                     (ty::ImmBorrow, ty::ImmBorrow) |
@@ -431,6 +439,13 @@ impl<'a,'tcx> AdjustBorrowKind<'a,'tcx> {
         }
     }
 
+    /// Remembers `span` as the use that forced the current capture kind
+    /// of `upvar_id`, for borrowck to point at.
+    fn record_capture_span(&self, upvar_id: ty::UpvarId, span: Span) {
+        debug!("record_capture_span(upvar_id={:?})", upvar_id);
+        self.fcx.inh.upvar_capture_spans.borrow_mut().insert(upvar_id, span);
+    }
+
     fn adjust_closure_kind(&self,
                            closure_id: ast::NodeId,
                            new_kind: ty::ClosureKind) {
@@ -489,12 +504,12 @@ impl<'a, 'tcx, 'v> Visitor<'v> for AdjustBorrowKind<'a, 'tcx> {
 impl<'a,'tcx> euv::Delegate<'tcx> for AdjustBorrowKind<'a,'tcx> {
     fn consume(&mut self,
                _consume_id: ast::NodeId,
-               _consume_span: Span,
+               consume_span: Span,
                cmt: mc::cmt<'tcx>,
                mode: euv::ConsumeMode)
     {
         debug!("consume(cmt={:?},mode={:?})", cmt, mode);
-        self.adjust_upvar_borrow_kind_for_consume(cmt, mode);
+        self.adjust_upvar_borrow_kind_for_consume(consume_span, cmt, mode);
     }
 
     fn matched_pat(&mut self,
@@ -504,17 +519,17 @@ impl<'a,'tcx> euv::Delegate<'tcx> for AdjustBorrowKind<'a,'tcx> {
     {}
 
     fn consume_pat(&mut self,
-                   _consume_pat: &ast::Pat,
+                   consume_pat: &ast::Pat,
                    cmt: mc::cmt<'tcx>,
                    mode: euv::ConsumeMode)
     {
         debug!("consume_pat(cmt={:?},mode={:?})", cmt, mode);
-        self.adjust_upvar_borrow_kind_for_consume(cmt, mode);
+        self.adjust_upvar_borrow_kind_for_consume(consume_pat.span, cmt, mode);
     }
 
     fn borrow(&mut self,
               borrow_id: ast::NodeId,
-              _borrow_span: Span,
+              borrow_span: Span,
               cmt: mc::cmt<'tcx>,
               _loan_region: ty::Region,
               bk: ty::BorrowKind,
@@ -526,10 +541,10 @@ impl<'a,'tcx> euv::Delegate<'tcx> for AdjustBorrowKind<'a,'tcx> {
         match bk {
             ty::ImmBorrow => { }
             ty::UniqueImmBorrow => {
-                self.adjust_upvar_borrow_kind_for_unique(cmt);
+                self.adjust_upvar_borrow_kind_for_unique(borrow_span, cmt);
             }
             ty::MutBorrow => {
-                self.adjust_upvar_borrow_kind_for_mut(cmt);
+                self.adjust_upvar_borrow_kind_for_mut(borrow_span, cmt);
             }
         }
     }
@@ -541,13 +556,13 @@ impl<'a,'tcx> euv::Delegate<'tcx> for AdjustBorrowKind<'a,'tcx> {
 
     fn mutate(&mut self,
               _assignment_id: ast::NodeId,
-              _assignment_span: Span,
+              assignment_span: Span,
               assignee_cmt: mc::cmt<'tcx>,
               _mode: euv::MutateMode)
     {
         debug!("mutate(assignee_cmt={:?})",
                assignee_cmt);
 
-        self.adjust_upvar_borrow_kind_for_mut(assignee_cmt);
+        self.adjust_upvar_borrow_kind_for_mut(assignment_span, assignee_cmt);
     }
 }
@@ -12,17 +12,27 @@
 // unresolved type variables and replaces "ty_var" types with their
 // substitutions.
 use self::ResolveReason::*;
+use self::ClosureComponent::*;
+use self::Written::*;
+use self::AnonConstKind::*;
 
 use astconv::AstConv;
 use check::FnCtxt;
+use check::unresolved::UnresolvedTypes;
+use middle::cast::CastKind;
 use middle::pat_util;
-use middle::ty::{self, Ty, MethodCall, MethodCallee};
-use middle::ty_fold::{TypeFolder,TypeFoldable};
+use middle::traits;
+use middle::ty::{self, Ty, MethodCall, MethodCallee, ToPolyTraitRef};
+use middle::ty_fold::{self, TypeFolder, TypeFoldable};
 use middle::infer;
-use write_substs_to_tcx;
-use write_ty_to_tcx;
+use rustc::plugin::typeck::BodyContext;
 
-use std::cell::Cell;
+use util::nodemap::FnvHashMap;
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher, SipHasher};
+use std::mem;
 
 use syntax::ast;
 use syntax::ast_util;
@@ -40,12 +50,34 @@ pub fn resolve_type_vars_in_expr(fcx: &FnCtxt, e: &ast::Expr) {
     wbcx.visit_expr(e);
     wbcx.visit_upvar_borrow_map();
     wbcx.visit_closures();
+    wbcx.report_unresolved();
+    wbcx.validate();
+    fcx.tcx().install_typeck_tables(wbcx.tables.into_inner());
+}
+
+pub fn resolve_type_vars_in_anon_const(fcx: &FnCtxt, e: &ast::Expr, kind: AnonConstKind) {
+    assert_eq!(fcx.writeback_errors.get(), false);
+    let mut wbcx = WritebackCx::new(fcx);
+    wbcx.visit_anon_const(e, kind);
+    wbcx.visit_upvar_borrow_map();
+    wbcx.visit_closures();
+    wbcx.report_unresolved();
+    wbcx.validate();
+    fcx.tcx().install_typeck_tables(wbcx.tables.into_inner());
 }
 
 pub fn resolve_type_vars_in_fn(fcx: &FnCtxt,
+                               fn_id: ast::NodeId,
                                decl: &ast::FnDecl,
                                blk: &ast::Block) {
     assert_eq!(fcx.writeback_errors.get(), false);
+
+    let tcx = fcx.tcx();
+    if tcx.sess.writeback_fingerprints() {
+        let fingerprint = fingerprint_tables(fcx);
+        tcx.writeback_fingerprints.borrow_mut().insert(fn_id, fingerprint);
+    }
+
     let mut wbcx = WritebackCx::new(fcx);
     wbcx.visit_block(blk);
     for arg in &decl.inputs {
@@ -60,23 +92,365 @@ pub fn resolve_type_vars_in_fn(fcx: &FnCtxt,
     }
     wbcx.visit_upvar_borrow_map();
     wbcx.visit_closures();
+    wbcx.report_unresolved();
+    wbcx.validate();
+    tcx.install_typeck_tables(wbcx.tables.into_inner());
+}
+
+/// Hashes the contents of the inference tables of `fcx`, with types
+/// resolved as far as inference got. The entries are hashed in their
+/// printed form and sorted, so the result doesn't depend on insertion
+/// order or on where types were interned. An unchanged body therefore
+/// gets the same fingerprint in another run, as long as its node ids and
+/// spans stay the same too.
+fn fingerprint_tables(fcx: &FnCtxt) -> u64 {
+    let infcx = fcx.infcx();
+    let inh = fcx.inh;
+    let mut state = SipHasher::new();
+
+    hash_table(&mut state, "node_types", &inh.node_types.borrow(), |ty| {
+        infcx.resolve_type_vars_if_possible(ty)
+    });
+    hash_table(&mut state, "item_substs", &inh.item_substs.borrow(), |item_substs| {
+        infcx.resolve_type_vars_if_possible(item_substs)
+    });
+    hash_table(&mut state, "adjustments", &inh.adjustments.borrow(), |adjustment| {
+        match *adjustment {
+            ty::AdjustDerefRef(ref adj) => ty::AdjustDerefRef(ty::AutoDerefRef {
+                autoderefs: adj.autoderefs,
+                autoref: infcx.resolve_type_vars_if_possible(&adj.autoref),
+                unsize: infcx.resolve_type_vars_if_possible(&adj.unsize),
+            }),
+            adjustment => adjustment
+        }
+    });
+    hash_table(&mut state, "method_map", &inh.method_map.borrow(), |method| {
+        MethodCallee {
+            origin: infcx.resolve_type_vars_if_possible(&method.origin),
+            ty: infcx.resolve_type_vars_if_possible(&method.ty),
+            substs: infcx.resolve_type_vars_if_possible(&method.substs),
+        }
+    });
+    hash_table(&mut state, "method_call_resolutions", &inh.method_call_resolutions.borrow(),
+               |resolution| {
+        (resolution.def_id,
+         infcx.resolve_type_vars_if_possible(&resolution.substs),
+         ty::AdjustDerefRef(ty::AutoDerefRef {
+             autoderefs: resolution.adjustment.autoderefs,
+             autoref: infcx.resolve_type_vars_if_possible(&resolution.adjustment.autoref),
+             unsize: infcx.resolve_type_vars_if_possible(&resolution.adjustment.unsize),
+         }))
+    });
+    hash_table(&mut state, "autoderef_chains", &inh.autoderef_chains.borrow(), |tys| {
+        infcx.resolve_type_vars_if_possible(tys)
+    });
+    hash_table(&mut state, "upvar_capture_map", &inh.upvar_capture_map.borrow(), |capture| {
+        *capture
+    });
+    hash_table(&mut state, "upvar_capture_spans", &inh.upvar_capture_spans.borrow(), |span| {
+        *span
+    });
+    hash_table(&mut state, "closure_tys", &inh.closure_tys.borrow(), |closure_ty| {
+        infcx.resolve_type_vars_if_possible(closure_ty)
+    });
+    hash_table(&mut state, "closure_kinds", &inh.closure_kinds.borrow(), |kind| *kind);
+    hash_table(&mut state, "cast_kinds", &inh.cast_kinds.borrow(), |kind| *kind);
+
+    state.finish()
+}
+
+/// Prints the fingerprint of each fn body taken with
+/// `-Z writeback-fingerprints`, one per line after the fn's path, sorted
+/// by path.
+pub fn print_fingerprints(tcx: &ty::ctxt) {
+    let mut fingerprints: Vec<_> = tcx.writeback_fingerprints.borrow().iter().map(|(&id, &fp)| {
+        (tcx.map.path_to_string(id), fp)
+    }).collect();
+    fingerprints.sort();
+    for (path, fingerprint) in fingerprints {
+        println!("{} {:016x}", path, fingerprint);
+    }
+}
+
+fn hash_table<K, V, R, F>(state: &mut SipHasher,
+                          name: &str,
+                          table: &FnvHashMap<K, V>,
+                          resolve: F)
+    where K: Debug + Eq + Hash, R: Debug, F: Fn(&V) -> R
+{
+    let mut entries: Vec<_> = table.iter().map(|(key, value)| {
+        (format!("{:?}", key), format!("{:?}", resolve(value)))
+    }).collect();
+    entries.sort();
+
+    name.hash(state);
+    entries.hash(state);
+}
+
+/// Emits a note on every expression in `blk` that has an adjustment in the
+/// tcx tables, describing its autoderefs, autoref and unsize target.
+/// Closure bodies are included; nested items are not.
+pub fn dump_adjustments(tcx: &ty::ctxt, blk: &ast::Block) {
+    AdjustmentDumper { tcx: tcx }.visit_block(blk);
+}
+
+struct AdjustmentDumper<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for AdjustmentDumper<'a, 'tcx> {
+    fn visit_item(&mut self, _: &ast::Item) {
+        // Ignore items
+    }
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        let description = match self.tcx.adjustments.borrow().get(&e.id) {
+            None => None,
+            Some(&ty::AdjustReifyFnPointer) => Some("reify fn pointer".to_string()),
+            Some(&ty::AdjustUnsafeFnPointer) => Some("unsafe fn pointer".to_string()),
+            Some(&ty::AdjustDerefRef(ref adj)) => {
+                let autoref = match adj.autoref {
+                    None => "none",
+                    Some(ty::AutoPtr(_, ast::MutImmutable)) => "&",
+                    Some(ty::AutoPtr(_, ast::MutMutable)) => "&mut",
+                    Some(ty::AutoUnsafe(ast::MutImmutable)) => "*const",
+                    Some(ty::AutoUnsafe(ast::MutMutable)) => "*mut",
+                };
+                let unsize = match adj.unsize {
+                    None => "none".to_string(),
+                    Some(target) => target.to_string(),
+                };
+                Some(format!("autoderefs: {}, autoref: {}, unsize: {}",
+                             adj.autoderefs, autoref, unsize))
+            }
+        };
+        if let Some(description) = description {
+            self.tcx.sess.span_note(e.span, &format!("adjustments: {}", description));
+        }
+        visit::walk_expr(self, e);
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////
 // The Writerback context. This visitor walks the AST, checking the
 // fn-specific tables to find references to types or regions. It
-// resolves those regions to remove inference variables and writes the
-// final result back into the master tables in the tcx. Here and
-// there, it applies a few ad-hoc checks that were not convenient to
-// do elsewhere.
+// resolves those regions to remove inference variables and collects
+// the final result into the `TypeckTables` of the body, which are then
+// installed in the tcx. Here and there, it applies a few ad-hoc checks
+// that were not convenient to do elsewhere.
 
 struct WritebackCx<'cx, 'tcx: 'cx> {
     fcx: &'cx FnCtxt<'cx, 'tcx>,
+
+    // The resolved tables of the body.
+    tables: RefCell<ty::TypeckTables<'tcx>>,
+
+    // With debug assertions on, every entry written to `tables`, so
+    // that `validate` can check that no inference variables escaped.
+    written: RefCell<Vec<Written>>,
+
+    // The inference variables that could not be resolved, in the order
+    // they were first found. Each is reported once, in `report_unresolved`.
+    unresolved: RefCell<Vec<UnresolvedVar<'tcx>>>,
+}
+
+// An inference variable that writeback could not resolve, with the reason
+// it was first needed and the other places whose type also depends on it.
+// `partial_ty` is the type first needed, as far as it was inferred.
+struct UnresolvedVar<'tcx> {
+    err: infer::fixup_err,
+    reason: ResolveReason,
+    partial_ty: Option<Ty<'tcx>>,
+    also_needed_at: Vec<Span>,
+}
+
+// An entry of one of the tables written by writeback.
+#[derive(Copy, Clone)]
+enum Written {
+    WrittenNodeType(ast::NodeId),
+    WrittenItemSubsts(ast::NodeId),
+    WrittenAdjustment(ast::NodeId),
+    WrittenMethodCallee(MethodCall),
+    WrittenMethodCallResolution(ast::NodeId),
+    WrittenAutoderefChain(ast::NodeId),
+    WrittenUpvarCapture(ty::UpvarId),
+    WrittenClosureTy(ast::DefId),
+}
+
+impl Written {
+    // The node the entry belongs to. For a closure type from another
+    // crate there is none, and DUMMY_NODE_ID is returned.
+    fn node_id(&self) -> ast::NodeId {
+        match *self {
+            WrittenNodeType(id) |
+            WrittenItemSubsts(id) |
+            WrittenAdjustment(id) |
+            WrittenMethodCallResolution(id) |
+            WrittenAutoderefChain(id) => id,
+            WrittenMethodCallee(method_call) => method_call.expr_id,
+            WrittenUpvarCapture(upvar_id) => upvar_id.closure_expr_id,
+            WrittenClosureTy(def_id) => {
+                if def_id.krate == ast::LOCAL_CRATE {
+                    def_id.node
+                } else {
+                    ast::DUMMY_NODE_ID
+                }
+            }
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match *self {
+            WrittenNodeType(_) => "type",
+            WrittenItemSubsts(_) => "substitutions",
+            WrittenAdjustment(_) => "adjustment",
+            WrittenMethodCallee(_) => "method callee",
+            WrittenMethodCallResolution(_) => "method call resolution",
+            WrittenAutoderefChain(_) => "autoderef chain",
+            WrittenUpvarCapture(_) => "upvar capture",
+            WrittenClosureTy(_) => "closure type",
+        }
+    }
 }
 
 impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
     fn new(fcx: &'cx FnCtxt<'cx, 'tcx>) -> WritebackCx<'cx, 'tcx> {
-        WritebackCx { fcx: fcx }
+        WritebackCx {
+            fcx: fcx,
+            tables: RefCell::new(ty::TypeckTables::new()),
+            written: RefCell::new(Vec::new()),
+            unresolved: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn write_ty(&self, id: ast::NodeId, ty: Ty<'tcx>) {
+        debug!("write_ty({}, {:?})", id, ty);
+        assert!(!ty::type_needs_infer(ty));
+        self.tables.borrow_mut().node_types.insert(id, ty);
+        self.note_written(WrittenNodeType(id));
+    }
+
+    fn write_substs(&self, id: ast::NodeId, item_substs: ty::ItemSubsts<'tcx>) {
+        if !item_substs.is_noop() {
+            debug!("write_substs({}, {:?})", id, item_substs);
+            assert!(item_substs.substs.types.all(|t| !ty::type_needs_infer(*t)));
+            self.tables.borrow_mut().item_substs.insert(id, item_substs);
+            self.note_written(WrittenItemSubsts(id));
+        }
+    }
+
+    fn note_written(&self, written: Written) {
+        if cfg!(debug_assertions) {
+            self.written.borrow_mut().push(written);
+        }
+    }
+
+    /// Reports each inference variable that could not be resolved, once,
+    /// at the place it was first needed, with a note for every other place
+    /// whose type also depends on it. Nothing is reported if errors were
+    /// already emitted, as those are the likely cause.
+    fn report_unresolved(&self) {
+        let unresolved = mem::replace(&mut *self.unresolved.borrow_mut(), Vec::new());
+        if unresolved.is_empty() {
+            return;
+        }
+
+        self.fcx.writeback_errors.set(true);
+        let tcx = self.tcx();
+        if tcx.sess.has_errors() {
+            return;
+        }
+
+        for var in &unresolved {
+            UnresolvedReporter::new(self.fcx, &self.unresolved, var.reason).emit_error(var.err);
+            // The type written back keeps this structure, with errors for
+            // the unknown parts.
+            match var.partial_ty {
+                Some(partial_ty) if !ty::type_is_ty_var(partial_ty) => {
+                    tcx.sess.span_note(var.reason.span(tcx),
+                                       &format!("the type is only known to be `{}`",
+                                                self.fcx.infcx().ty_to_string(partial_ty)));
+                }
+                _ => {}
+            }
+            for &span in &var.also_needed_at {
+                tcx.sess.span_note(span, "the type here could not be determined \
+                                          for the same reason");
+            }
+        }
+    }
+
+    /// Re-reads every entry written to the tables and reports a bug,
+    /// naming the node and the table, if a type or region inference
+    /// variable is left in any of them. Such leaks would otherwise only
+    /// surface as ICEs in later passes. Does nothing unless debug
+    /// assertions are on, or if writeback already reported an error.
+    fn validate(&self) {
+        if self.fcx.writeback_errors.get() {
+            return;
+        }
+
+        let tcx = self.tcx();
+        let tables = self.tables.borrow();
+        for &written in self.written.borrow().iter() {
+            let leak = match written {
+                WrittenNodeType(id) => {
+                    tables.node_types.get(&id).and_then(|ty| find_inference_leak(tcx, ty))
+                }
+                WrittenItemSubsts(id) => {
+                    tables.item_substs.get(&id)
+                       .and_then(|item_substs| find_inference_leak(tcx, item_substs))
+                }
+                WrittenAdjustment(id) => {
+                    match tables.adjustments.get(&id) {
+                        Some(&ty::AdjustDerefRef(ref adj)) => {
+                            find_inference_leak(tcx, &adj.autoref)
+                                .or_else(|| find_inference_leak(tcx, &adj.unsize))
+                        }
+                        _ => None
+                    }
+                }
+                WrittenMethodCallee(method_call) => {
+                    tables.method_map.get(&method_call).and_then(|method| {
+                        find_inference_leak(tcx, &method.origin)
+                            .or_else(|| find_inference_leak(tcx, &method.ty))
+                            .or_else(|| find_inference_leak(tcx, &method.substs))
+                    })
+                }
+                WrittenMethodCallResolution(id) => {
+                    tables.method_call_resolutions.get(&id).and_then(|resolution| {
+                        find_inference_leak(tcx, &resolution.substs)
+                            .or_else(|| find_inference_leak(tcx, &resolution.adjustment.autoref))
+                            .or_else(|| find_inference_leak(tcx, &resolution.adjustment.unsize))
+                    })
+                }
+                WrittenAutoderefChain(id) => {
+                    tables.autoderef_chains.get(&id).and_then(|chain| {
+                        chain.iter().filter_map(|step| find_inference_leak(tcx, &step.ty)).next()
+                    })
+                }
+                WrittenUpvarCapture(upvar_id) => {
+                    match tables.upvar_capture_map.get(&upvar_id) {
+                        Some(&ty::UpvarCapture::ByRef(ref borrow)) => {
+                            find_inference_leak(tcx, &borrow.region)
+                        }
+                        _ => None
+                    }
+                }
+                WrittenClosureTy(def_id) => {
+                    tables.closure_tys.get(&def_id)
+                       .and_then(|closure_ty| find_inference_leak(tcx, closure_ty))
+                }
+            };
+
+            if let Some(leak) = leak {
+                let id = written.node_id();
+                let span = tcx.map.opt_span(id).unwrap_or(DUMMY_SP);
+                tcx.sess.span_bug(span,
+                                  &format!("writeback left {} in the {} of node {}",
+                                           leak, written.description(), id));
+            }
+        }
     }
 
     fn tcx(&self) -> &'cx ty::ctxt<'tcx> {
@@ -87,6 +461,10 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
     // as potentially overloaded. But then, during writeback, if
     // we observe that something like `a+b` is (known to be)
     // operating on scalars, we clear the overload.
+    //
+    // Unary operators and compound assignments need no such cleanup:
+    // their operand types are structurally resolved before the operator
+    // is checked, so scalar operands never get a method entry.
     fn fix_scalar_binary_expr(&mut self, e: &ast::Expr) {
         if let ast::ExprBinary(ref op, ref lhs, ref rhs) = e.node {
             let lhs_ty = self.fcx.node_ty(lhs.id);
@@ -124,74 +502,75 @@ impl<'cx, 'tcx, 'v> Visitor<'v> for WritebackCx<'cx, 'tcx> {
     }
 
     fn visit_stmt(&mut self, s: &ast::Stmt) {
-        if self.fcx.writeback_errors.get() {
-            return;
-        }
-
         self.visit_node_id(ResolvingExpr(s.span), ty::stmt_node_id(s));
         visit::walk_stmt(self, s);
     }
 
     fn visit_expr(&mut self, e: &ast::Expr) {
-        if self.fcx.writeback_errors.get() {
-            return;
-        }
-
         self.fix_scalar_binary_expr(e);
 
         self.visit_node_id(ResolvingExpr(e.span), e.id);
         self.visit_method_map_entry(ResolvingExpr(e.span),
                                     MethodCall::expr(e.id));
+        self.visit_method_call_resolution(ResolvingExpr(e.span), e.id);
+        self.visit_autoderef_chain(ResolvingExpr(e.span), e.id);
 
-        if let ast::ExprClosure(_, ref decl, _) = e.node {
+        if let ast::ExprClosure(_, ref decl, ref body) = e.node {
             for input in &decl.inputs {
                 self.visit_node_id(ResolvingExpr(e.span), input.id);
             }
+            self.visit_closure_sig(e.id, decl, body);
         }
 
-        visit::walk_expr(self, e);
-    }
+        match e.node {
+            ast::ExprRepeat(ref element, ref count_expr) => {
+                self.visit_expr(&**element);
+                self.visit_anon_const(&**count_expr, RepeatCount);
+            }
+            _ => visit::walk_expr(self, e)
+        }
 
-    fn visit_block(&mut self, b: &ast::Block) {
-        if self.fcx.writeback_errors.get() {
-            return;
+        // The operands have been written back by now.
+        match e.node {
+            ast::ExprInlineAsm(ref ia) => self.visit_inline_asm(e.id, ia),
+            ast::ExprCast(ref from, _) => self.visit_cast_kind(e.id, &**from),
+            _ => {}
         }
+    }
 
+    fn visit_block(&mut self, b: &ast::Block) {
         self.visit_node_id(ResolvingExpr(b.span), b.id);
         visit::walk_block(self, b);
     }
 
     fn visit_pat(&mut self, p: &ast::Pat) {
-        if self.fcx.writeback_errors.get() {
-            return;
-        }
-
         self.visit_node_id(ResolvingPattern(p.span), p.id);
+        // A pattern that dereferences its scrutinee through an overloaded
+        // `Deref` records the call under the pattern's own id.
+        self.visit_method_map_entry(ResolvingPattern(p.span),
+                                    MethodCall::expr(p.id));
 
         debug!("Type for pattern binding {} (id {}) resolved to {:?}",
                pat_to_string(p),
                p.id,
-               ty::node_id_to_type(self.tcx(), p.id));
+               self.tables.borrow().node_types.get(&p.id));
 
         visit::walk_pat(self, p);
     }
 
     fn visit_local(&mut self, l: &ast::Local) {
-        if self.fcx.writeback_errors.get() {
-            return;
-        }
-
         let var_ty = self.fcx.local_ty(l.span, l.id);
         let var_ty = self.resolve(&var_ty, ResolvingLocal(l.span));
-        write_ty_to_tcx(self.tcx(), l.id, var_ty);
+        self.write_ty(l.id, var_ty);
         visit::walk_local(self, l);
     }
 
     fn visit_ty(&mut self, t: &ast::Ty) {
         match t.node {
-            ast::TyFixedLengthVec(ref ty, ref count_expr) => {
+            ast::TyFixedLengthVec(ref ty, _) => {
+                // The length was checked and written back on its own, by
+                // `check_const_in_type`.
                 self.visit_ty(&**ty);
-                write_ty_to_tcx(self.tcx(), count_expr.id, self.tcx().types.usize);
             }
             _ => visit::walk_ty(self, t)
         }
@@ -199,12 +578,118 @@ impl<'cx, 'tcx, 'v> Visitor<'v> for WritebackCx<'cx, 'tcx> {
 }
 
 impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
-    fn visit_upvar_borrow_map(&self) {
-        if self.fcx.writeback_errors.get() {
-            return;
+    /// Writes back a constant expression that is evaluated at compile
+    /// time as part of something else: an array length, a repeat count
+    /// or an enum discriminant. These were checked to have the type the
+    /// constant is expected to have, so a type that is still unresolved
+    /// here is reported as such rather than as an ordinary expression.
+    fn visit_anon_const(&mut self, e: &ast::Expr, kind: AnonConstKind) {
+        let ty = self.fcx.node_ty(e.id);
+        let ty = self.resolve(&ty, ResolvingAnonConst(e.span, kind));
+        debug!("{} {} resolved to {:?}", kind.description(), e.id, ty);
+        self.visit_expr(e);
+    }
+
+    /// Records the kind of the cast `from as T` with id `cast_id`. The
+    /// deferred cast check classified it while some of the types involved
+    /// may still have been inference variables, so any cast other than a
+    /// coercion-cast is classified again from the resolved types.
+    fn visit_cast_kind(&self, cast_id: ast::NodeId, from: &ast::Expr) {
+        let checked = match self.fcx.inh.cast_kinds.borrow().get(&from.id) {
+            Some(&kind) => kind,
+            None => return
+        };
+
+        let kind = match checked {
+            CastKind::CoercionCast => checked,
+            _ => {
+                let t_from = self.resolve(&self.fcx.expr_ty(from), ResolvingExpr(from.span));
+                let t_cast = self.resolve(&self.fcx.node_ty(cast_id), ResolvingExpr(from.span));
+                CastKind::of_valid_cast(self.tcx(), t_from, t_cast).unwrap_or(checked)
+            }
+        };
+
+        debug!("Cast {} resolved to {:?} (checked as {:?})", cast_id, kind, checked);
+        self.tables.borrow_mut().cast_kinds.insert(from.id, kind);
+    }
+
+    fn visit_inline_asm(&self, id: ast::NodeId, ia: &ast::InlineAsm) {
+        let outputs = ia.outputs.iter().map(|&(ref constraint, ref out, _)| {
+            self.check_asm_operand(constraint, out)
+        }).collect();
+        let inputs = ia.inputs.iter().map(|&(ref constraint, ref input)| {
+            self.check_asm_operand(constraint, input)
+        }).collect();
+
+        let operand_tys = ty::InlineAsmOperandTys { outputs: outputs, inputs: inputs };
+        debug!("Inline asm {} has operand types {:?}", id, operand_tys);
+        self.tables.borrow_mut().inline_asm_operand_tys.insert(id, operand_tys);
+    }
+
+    /// Checks that the resolved type of an asm operand fits in a general
+    /// purpose register if its constraint asks for one, and returns that
+    /// type. Explicit registers (`{eax}`) and the other register classes are
+    /// left for LLVM to check.
+    fn check_asm_operand(&self, constraint: &str, operand: &ast::Expr) -> Ty<'tcx> {
+        let tcx = self.tcx();
+        let ty = match self.tables.borrow().node_types.get(&operand.id) {
+            Some(&ty) => ty,
+            None => return tcx.types.err
+        };
+        if ty::type_is_error(ty) {
+            return ty;
+        }
+
+        let letters = constraint.trim_left_matches(|c| c == '=' || c == '+' || c == '&');
+        if letters.starts_with('{') || !letters.contains('r') || letters.contains('m') {
+            return ty;
         }
 
-        for (upvar_id, upvar_capture) in self.fcx.inh.upvar_capture_map.borrow().iter() {
+        let pointer_bits = match &tcx.sess.target.target.target_pointer_width[..] {
+            "32" => 32,
+            "64" => 64,
+            w => tcx.sess.bug(&format!("unexpected target pointer width {}", w))
+        };
+        let sized = |ty: Ty<'tcx>| {
+            ty::type_is_sized(Some(&self.fcx.inh.param_env), tcx, operand.span, ty)
+        };
+        let bits = match ty.sty {
+            ty::TyBool | ty::TyInt(ast::TyI8) | ty::TyUint(ast::TyU8) => Some(8),
+            ty::TyInt(ast::TyI16) | ty::TyUint(ast::TyU16) => Some(16),
+            ty::TyChar | ty::TyInt(ast::TyI32) | ty::TyUint(ast::TyU32) |
+            ty::TyFloat(ast::TyF32) => Some(32),
+            ty::TyInt(ast::TyI64) | ty::TyUint(ast::TyU64) | ty::TyFloat(ast::TyF64) => Some(64),
+            ty::TyInt(ast::TyI128) | ty::TyUint(ast::TyU128) => Some(128),
+            ty::TyInt(ast::TyIs) | ty::TyUint(ast::TyUs) | ty::TyBareFn(..) => Some(pointer_bits),
+            ty::TyRawPtr(ty::mt { ty: pointee, .. }) |
+            ty::TyRef(_, ty::mt { ty: pointee, .. }) if sized(pointee) => Some(pointer_bits),
+            _ => None
+        };
+
+        let fits = match bits {
+            Some(bits) => bits <= pointer_bits,
+            None => false
+        };
+        if !fits {
+            span_err!(tcx.sess, operand.span, E0403,
+                      "cannot pass a value of type `{}` to inline assembly \
+                       through the register constraint `{}`",
+                      ty, constraint);
+            if let Some(bits) = bits {
+                tcx.sess.span_note(
+                    operand.span,
+                    &format!("the value is {} bits wide, but general purpose registers \
+                              on this target are {} bits wide",
+                             bits, pointer_bits));
+            }
+        }
+
+        ty
+    }
+
+    fn visit_upvar_borrow_map(&self) {
+        let upvar_capture_map = self.fcx.inh.upvar_capture_map.borrow();
+        for (upvar_id, upvar_capture) in sorted_by_key(upvar_capture_map.iter()) {
             let new_upvar_capture = match *upvar_capture {
                 ty::UpvarCapture::ByValue => ty::UpvarCapture::ByValue,
                 ty::UpvarCapture::ByRef(ref upvar_borrow) => {
@@ -217,22 +702,123 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
             debug!("Upvar capture for {:?} resolved to {:?}",
                    upvar_id,
                    new_upvar_capture);
-            self.fcx.tcx().upvar_capture_map.borrow_mut().insert(*upvar_id, new_upvar_capture);
+            self.tables.borrow_mut().upvar_capture_map.insert(*upvar_id, new_upvar_capture);
+            self.note_written(WrittenUpvarCapture(*upvar_id));
+        }
+
+        let upvar_capture_spans = self.fcx.inh.upvar_capture_spans.borrow();
+        for (upvar_id, &span) in sorted_by_key(upvar_capture_spans.iter()) {
+            self.tables.borrow_mut().upvar_capture_spans.insert(*upvar_id, span);
         }
     }
 
+    // Resolves the signature of the closure `closure_id` and records it,
+    // along with whether its body diverges. A closure whose return type
+    // was left to inference, whose body never reaches its end and which
+    // has no `return` of its own gets a diverging signature, unless
+    // inference settled on a return type other than `()`; either way its
+    // `FnOnce::Output` stays what it was.
+    fn visit_closure_sig(&self, closure_id: ast::NodeId, decl: &ast::FnDecl, body: &ast::Block) {
+        let def_id = ast_util::local_def(closure_id);
+        let mut closure_ty = match self.fcx.inh.closure_tys.borrow().get(&def_id) {
+            Some(closure_ty) => self.resolve_closure_ty(def_id, closure_ty),
+            None => return
+        };
+
+        let inferred_output = match decl.output {
+            ast::DefaultReturn(..) => true,
+            ast::Return(ref ty) => ty.node == ast::TyInfer,
+            ast::NoReturn(..) => false,
+        };
+        let returns_nil = match closure_ty.sig.0.output {
+            ty::FnConverging(output) => ty::type_is_nil(output),
+            ty::FnDiverging => false,
+        };
+        if inferred_output && returns_nil && self.block_diverges(body) &&
+                !block_returns(body) {
+            debug!("closure {:?} diverges, recording a diverging signature", def_id);
+            closure_ty.sig.0.output = ty::FnDiverging;
+        }
+
+        self.tables.borrow_mut().closure_tys.insert(def_id, closure_ty);
+        self.note_written(WrittenClosureTy(def_id));
+    }
+
+    // Whether control never reaches the end of `b`: either one of its
+    // statements diverges, or its trailing expression does.
+    fn block_diverges(&self, b: &ast::Block) -> bool {
+        let infcx = self.fcx.infcx();
+        infcx.type_var_diverges(self.fcx.node_ty(b.id)) ||
+            b.expr.as_ref().map_or(false, |e| infcx.type_var_diverges(self.fcx.node_ty(e.id)))
+    }
+
     fn visit_closures(&self) {
-        if self.fcx.writeback_errors.get() {
-            return
+        let closure_tys = self.fcx.inh.closure_tys.borrow();
+        for (def_id, closure_ty) in sorted_by_key(closure_tys.iter()) {
+            // Closures in the body have been recorded by `visit_closure_sig`.
+            if self.tables.borrow().closure_tys.contains_key(def_id) {
+                continue;
+            }
+
+            let closure_ty = self.resolve_closure_ty(*def_id, closure_ty);
+            self.tables.borrow_mut().closure_tys.insert(*def_id, closure_ty);
+            self.note_written(WrittenClosureTy(*def_id));
         }
 
-        for (def_id, closure_ty) in self.fcx.inh.closure_tys.borrow().iter() {
-            let closure_ty = self.resolve(closure_ty, ResolvingClosure(*def_id));
-            self.fcx.tcx().closure_tys.borrow_mut().insert(*def_id, closure_ty);
+        let closure_kinds = self.fcx.inh.closure_kinds.borrow();
+        for (def_id, &closure_kind) in sorted_by_key(closure_kinds.iter()) {
+            self.tables.borrow_mut().closure_kinds.insert(*def_id, closure_kind);
         }
+    }
 
-        for (def_id, &closure_kind) in self.fcx.inh.closure_kinds.borrow().iter() {
-            self.fcx.tcx().closure_kinds.borrow_mut().insert(*def_id, closure_kind);
+    // Resolves the signature of a closure one component at a time, so
+    // that if something cannot be resolved we can tell the user
+    // whether it was a parameter, the return type, or a capture.
+    fn resolve_closure_ty(&self,
+                          def_id: ast::DefId,
+                          closure_ty: &ty::ClosureTy<'tcx>)
+                          -> ty::ClosureTy<'tcx> {
+        let sig = &closure_ty.sig.0;
+
+        // The arguments of a closure are tupled up into a single input.
+        let inputs = sig.inputs.iter().map(|&input| {
+            match input.sty {
+                ty::TyTuple(ref args) => {
+                    let args = args.iter().enumerate().map(|(i, arg)| {
+                        self.resolve(arg, ResolvingClosure(def_id, ClosureParam(i)))
+                    }).collect();
+                    ty::mk_tup(self.tcx(), args)
+                }
+                _ => self.resolve(&input, ResolvingClosure(def_id, ClosureParam(0))),
+            }
+        }).collect();
+
+        let output = match sig.output {
+            ty::FnConverging(output) => {
+                ty::FnConverging(self.resolve(&output, ResolvingClosure(def_id, ClosureReturn)))
+            }
+            ty::FnDiverging => ty::FnDiverging,
+        };
+
+        if def_id.krate == ast::LOCAL_CRATE {
+            ty::with_freevars(self.tcx(), def_id.node, |freevars| {
+                for freevar in freevars {
+                    let var_id = freevar.def.local_node_id();
+                    let var_ty = self.fcx.local_ty(freevar.span, var_id);
+                    self.resolve(&var_ty,
+                                 ResolvingClosure(def_id, ClosureUpvar(var_id, freevar.span)));
+                }
+            });
+        }
+
+        ty::ClosureTy {
+            unsafety: closure_ty.unsafety,
+            abi: closure_ty.abi,
+            sig: ty::Binder(ty::FnSig {
+                inputs: inputs,
+                output: output,
+                variadic: sig.variadic,
+            }),
         }
     }
 
@@ -243,16 +829,31 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
         // Resolve the type of the node with id `id`
         let n_ty = self.fcx.node_ty(id);
         let n_ty = self.resolve(&n_ty, reason);
-        write_ty_to_tcx(self.tcx(), id, n_ty);
+        self.write_ty(id, n_ty);
         debug!("Node {} has type {:?}", id, n_ty);
+        self.show_typeck_passes(id, reason.span(self.tcx()), n_ty);
 
         // Resolve any substitutions
         self.fcx.opt_node_ty_substs(id, |item_substs| {
-            write_substs_to_tcx(self.tcx(), id,
-                                self.resolve(item_substs, reason));
+            self.write_substs(id, self.resolve(item_substs, reason));
         });
     }
 
+    // Hands a node type that was just written back to the plugin typeck
+    // passes; see `TypeckPass::check_node_type`.
+    fn show_typeck_passes(&self, id: ast::NodeId, span: Span, ty: Ty<'tcx>) {
+        let tcx = self.tcx();
+        let mut passes = tcx.sess.plugin_typeck_passes.borrow_mut();
+        if passes.is_empty() {
+            return;
+        }
+
+        let cx = BodyContext { tcx: tcx };
+        for pass in passes.iter_mut() {
+            pass.check_node_type(&cx, id, span, ty);
+        }
+    }
+
     fn visit_adjustments(&self, reason: ResolveReason, id: ast::NodeId) {
         match self.fcx.inh.adjustments.borrow_mut().remove(&id) {
             None => {
@@ -281,8 +882,8 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
                     }
                 };
                 debug!("Adjustments for node {}: {:?}", id, resolved_adjustment);
-                self.tcx().adjustments.borrow_mut().insert(
-                    id, resolved_adjustment);
+                self.tables.borrow_mut().adjustments.insert(id, resolved_adjustment);
+                self.note_written(WrittenAdjustment(id));
             }
         }
     }
@@ -302,16 +903,162 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
                     substs: self.resolve(&method.substs, reason),
                 };
 
-                self.tcx().method_map.borrow_mut().insert(
-                    method_call,
-                    new_method);
+                self.tables.borrow_mut().method_map.insert(method_call, new_method);
+                self.note_written(WrittenMethodCallee(method_call));
+            }
+            None => {}
+        }
+    }
+
+    fn visit_method_call_resolution(&self, reason: ResolveReason, id: ast::NodeId) {
+        match self.fcx.inh.method_call_resolutions.borrow_mut().remove(&id) {
+            Some(resolution) => {
+                let adj = resolution.adjustment;
+                let new_resolution = ty::ResolvedMethod {
+                    def_id: resolution.def_id,
+                    substs: self.resolve(&resolution.substs, reason),
+                    adjustment: ty::AutoDerefRef {
+                        autoderefs: adj.autoderefs,
+                        autoref: self.resolve(&adj.autoref, reason),
+                        unsize: self.resolve(&adj.unsize, reason),
+                    },
+                };
+
+                self.tables.borrow_mut().method_call_resolutions.insert(id, new_resolution);
+                self.note_written(WrittenMethodCallResolution(id));
             }
             None => {}
         }
     }
 
+    fn visit_autoderef_chain(&self, reason: ResolveReason, id: ast::NodeId) {
+        let tys = match self.fcx.inh.autoderef_chains.borrow_mut().remove(&id) {
+            Some(tys) => tys,
+            None => return
+        };
+
+        // The overloaded derefs have already been written back along
+        // with the adjustments of `id`.
+        let chain = tys.iter().enumerate().map(|(i, ty)| {
+            let origin = if i == 0 {
+                None
+            } else {
+                let method_call = MethodCall::autoderef(id, (i - 1) as u32);
+                self.tables.borrow().method_map.get(&method_call).map(|m| m.origin.clone())
+            };
+            let deref_impl = match origin {
+                Some(ty::MethodTypeParam(ref param)) => self.select_impl(param.trait_ref),
+                _ => None
+            };
+            ty::AutoderefStep {
+                ty: self.resolve(ty, reason),
+                overloaded: origin.is_some(),
+                deref_impl: deref_impl,
+            }
+        }).collect();
+
+        self.tables.borrow_mut().autoderef_chains.insert(id, chain);
+        self.note_written(WrittenAutoderefChain(id));
+    }
+
+    /// Finds the impl that `trait_ref` resolves to, if it is known here.
+    fn select_impl(&self, trait_ref: ty::TraitRef<'tcx>) -> Option<ast::DefId> {
+        let infcx = self.fcx.infcx();
+        let poly_trait_ref = trait_ref.to_poly_trait_ref();
+        let obligation = traits::Obligation::new(traits::ObligationCause::dummy(),
+                                                 poly_trait_ref.to_poly_trait_predicate());
+        infcx.probe(|_| {
+            let mut selcx = traits::SelectionContext::new(infcx, self.fcx);
+            match selcx.select(&obligation) {
+                Ok(Some(traits::VtableImpl(data))) => Some(data.impl_def_id),
+                _ => None
+            }
+        })
+    }
+
     fn resolve<T:TypeFoldable<'tcx>>(&self, t: &T, reason: ResolveReason) -> T {
-        t.fold_with(&mut Resolver::new(self.fcx, reason))
+        let reporter = UnresolvedReporter::new(self.fcx, &self.unresolved, reason);
+        resolve_in_infcx(self.fcx.infcx(), t, |e, partial_ty| reporter.report_error(e, partial_ty))
+    }
+}
+
+// Collects the entries of a hash map ordered by key, so that tables are
+// written back, and any errors reported, in the same order on every run.
+fn sorted_by_key<'a, K: Ord + 'a, V: 'a, I>(entries: I) -> Vec<(&'a K, &'a V)>
+    where I: Iterator<Item=(&'a K, &'a V)>
+{
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+// Whether `b` contains a `return` from the closure or fn whose body it is,
+// as opposed to one from a closure or item nested in it.
+fn block_returns(b: &ast::Block) -> bool {
+    let mut finder = ReturnFinder { found: false };
+    visit::walk_block(&mut finder, b);
+    finder.found
+}
+
+struct ReturnFinder {
+    found: bool,
+}
+
+impl<'v> Visitor<'v> for ReturnFinder {
+    fn visit_item(&mut self, _: &ast::Item) {
+        // Ignore items
+    }
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        match e.node {
+            ast::ExprRet(..) => self.found = true,
+            ast::ExprClosure(..) => {}
+            _ => visit::walk_expr(self, e),
+        }
+    }
+}
+
+// Returns a description of the first type or region inference variable
+// in `t`, if there is one.
+fn find_inference_leak<'tcx, T: TypeFoldable<'tcx>>(tcx: &ty::ctxt<'tcx>,
+                                                     t: &T)
+                                                     -> Option<String> {
+    let mut finder = InferenceLeakFinder { tcx: tcx, leak: None };
+    t.fold_with(&mut finder);
+    finder.leak
+}
+
+struct InferenceLeakFinder<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    leak: Option<String>,
+}
+
+impl<'a, 'tcx> TypeFolder<'tcx> for InferenceLeakFinder<'a, 'tcx> {
+    fn tcx(&self) -> &ty::ctxt<'tcx> {
+        self.tcx
+    }
+
+    fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
+        if self.leak.is_some() || !ty::type_needs_infer(t) {
+            return t;
+        }
+
+        match t.sty {
+            ty::TyInfer(v) => {
+                self.leak = Some(format!("the type inference variable `{:?}`", v));
+                t
+            }
+            _ => ty_fold::super_fold_ty(self, t)
+        }
+    }
+
+    fn fold_region(&mut self, r: ty::Region) -> ty::Region {
+        if let ty::ReInfer(v) = r {
+            if self.leak.is_none() {
+                self.leak = Some(format!("the region inference variable `{:?}`", v));
+            }
+        }
+        r
     }
 }
 
@@ -324,19 +1071,70 @@ enum ResolveReason {
     ResolvingLocal(Span),
     ResolvingPattern(Span),
     ResolvingUpvar(ty::UpvarId),
-    ResolvingClosure(ast::DefId),
+    ResolvingClosure(ast::DefId, ClosureComponent),
+    ResolvingAnonConst(Span, AnonConstKind),
+}
+
+/// The places a constant expression can appear outside of a const or
+/// static item.
+#[derive(Copy, Clone, Debug)]
+pub enum AnonConstKind {
+    /// The length in a fixed-length array type, `[T; N]`
+    ArrayLength,
+
+    /// The count in a repeat expression, `[e; N]`
+    RepeatCount,
+
+    /// The explicit discriminant of an enum variant, `V = N`
+    Discriminant,
+}
+
+impl AnonConstKind {
+    fn description(&self) -> &'static str {
+        match *self {
+            ArrayLength => "array length",
+            RepeatCount => "repeat count",
+            Discriminant => "enum discriminant",
+        }
+    }
+}
+
+// The part of a closure's type that is being resolved.
+#[derive(Copy, Clone)]
+enum ClosureComponent {
+    // The type of the parameter with the given index
+    ClosureParam(usize),
+
+    // The return type
+    ClosureReturn,
+
+    // The type of a captured variable, first used at the given span
+    ClosureUpvar(ast::NodeId, Span),
 }
 
 impl ResolveReason {
+    // The name of the reason in `-Z emit-unresolved-types` reports.
+    fn kind(&self) -> &'static str {
+        match *self {
+            ResolvingExpr(..) => "expr",
+            ResolvingLocal(..) => "local",
+            ResolvingPattern(..) => "pattern",
+            ResolvingAnonConst(..) => "anon_const",
+            ResolvingUpvar(..) => "upvar",
+            ResolvingClosure(..) => "closure",
+        }
+    }
+
     fn span(&self, tcx: &ty::ctxt) -> Span {
         match *self {
             ResolvingExpr(s) => s,
             ResolvingLocal(s) => s,
             ResolvingPattern(s) => s,
+            ResolvingAnonConst(s, _) => s,
             ResolvingUpvar(upvar_id) => {
                 ty::expr_span(tcx, upvar_id.closure_expr_id)
             }
-            ResolvingClosure(did) => {
+            ResolvingClosure(did, _) => {
                 if did.krate == ast::LOCAL_CRATE {
                     ty::expr_span(tcx, did.node)
                 } else {
@@ -348,88 +1146,241 @@ impl ResolveReason {
 }
 
 ///////////////////////////////////////////////////////////////////////////
-// The Resolver. This is the type folding engine that detects
-// unresolved types and so forth.
+// Reporting of the inference variables that writeback could not resolve.
 
-struct Resolver<'cx, 'tcx: 'cx> {
+struct UnresolvedReporter<'cx, 'tcx: 'cx> {
     tcx: &'cx ty::ctxt<'tcx>,
     infcx: &'cx infer::InferCtxt<'cx, 'tcx>,
-    writeback_errors: &'cx Cell<bool>,
+    unresolved: &'cx RefCell<Vec<UnresolvedVar<'tcx>>>,
+    unresolved_types: &'cx UnresolvedTypes,
+    literal_fallback: &'cx infer::LiteralFallback<'tcx>,
     reason: ResolveReason,
 }
 
-impl<'cx, 'tcx> Resolver<'cx, 'tcx> {
+impl<'cx, 'tcx> UnresolvedReporter<'cx, 'tcx> {
     fn new(fcx: &'cx FnCtxt<'cx, 'tcx>,
+           unresolved: &'cx RefCell<Vec<UnresolvedVar<'tcx>>>,
            reason: ResolveReason)
-           -> Resolver<'cx, 'tcx>
+           -> UnresolvedReporter<'cx, 'tcx>
     {
-        Resolver::from_infcx(fcx.infcx(), &fcx.writeback_errors, reason)
+        UnresolvedReporter { infcx: fcx.infcx(),
+                             tcx: fcx.infcx().tcx,
+                             unresolved: unresolved,
+                             unresolved_types: &fcx.ccx.unresolved_types,
+                             literal_fallback: &fcx.ccx.literal_fallback,
+                             reason: reason }
     }
 
-    fn from_infcx(infcx: &'cx infer::InferCtxt<'cx, 'tcx>,
-                  writeback_errors: &'cx Cell<bool>,
-                  reason: ResolveReason)
-                  -> Resolver<'cx, 'tcx>
-    {
-        Resolver { infcx: infcx,
-                   tcx: infcx.tcx,
-                   writeback_errors: writeback_errors,
-                   reason: reason }
-    }
-
-    fn report_error(&self, e: infer::fixup_err) {
-        self.writeback_errors.set(true);
-        if !self.tcx.sess.has_errors() {
-            match self.reason {
-                ResolvingExpr(span) => {
-                    span_err!(self.tcx.sess, span, E0101,
-                        "cannot determine a type for this expression: {}",
-                        infer::fixup_err_to_string(e));
-                }
+    // Records that `e` was hit while resolving for `self.reason`. Only the
+    // first place an inference variable is found gets an error; the others
+    // are remembered so they can be noted alongside it. With
+    // `-Z emit-unresolved-types`, every place is also recorded for the
+    // JSON report.
+    fn report_error(&self, e: infer::fixup_err, partial_ty: Option<Ty<'tcx>>) {
+        let span = self.reason.span(self.tcx);
+        if self.tcx.sess.emit_unresolved_types().is_some() {
+            self.unresolved_types.record(self.tcx, self.reason.kind(), span, e,
+                                         partial_ty.map(|t| t.to_string()),
+                                         self.literal_fallback);
+        }
 
-                ResolvingLocal(span) => {
-                    span_err!(self.tcx.sess, span, E0102,
-                        "cannot determine a type for this local variable: {}",
-                        infer::fixup_err_to_string(e));
-                }
+        let mut unresolved = self.unresolved.borrow_mut();
+        if let Some(var) = unresolved.iter_mut().find(|var| var.err == e) {
+            if var.reason.span(self.tcx) != span && !var.also_needed_at.contains(&span) {
+                var.also_needed_at.push(span);
+            }
+            return;
+        }
+        unresolved.push(UnresolvedVar {
+            err: e,
+            reason: self.reason,
+            partial_ty: partial_ty,
+            also_needed_at: vec![],
+        });
+    }
 
-                ResolvingPattern(span) => {
-                    span_err!(self.tcx.sess, span, E0103,
-                        "cannot determine a type for this pattern binding: {}",
-                        infer::fixup_err_to_string(e));
-                }
+    fn emit_error(&self, e: infer::fixup_err) {
+        match self.reason {
+            ResolvingExpr(span) => {
+                span_err!(self.tcx.sess, span, E0101,
+                    "cannot determine a type for this expression: {}",
+                    infer::fixup_err_to_string(e));
+                self.note_ty_var_origin(e);
+            }
 
-                ResolvingUpvar(upvar_id) => {
-                    let span = self.reason.span(self.tcx);
-                    span_err!(self.tcx.sess, span, E0104,
-                        "cannot resolve lifetime for captured variable `{}`: {}",
-                        ty::local_var_name_str(self.tcx, upvar_id.var_id).to_string(),
-                        infer::fixup_err_to_string(e));
-                }
+            ResolvingLocal(span) => {
+                span_err!(self.tcx.sess, span, E0102,
+                    "cannot determine a type for this local variable: {}",
+                    infer::fixup_err_to_string(e));
+                self.note_ty_var_origin(e);
+            }
+
+            ResolvingPattern(span) => {
+                span_err!(self.tcx.sess, span, E0103,
+                    "cannot determine a type for this pattern binding: {}",
+                    infer::fixup_err_to_string(e));
+                self.note_ty_var_origin(e);
+            }
+
+            ResolvingAnonConst(span, kind) => {
+                span_err!(self.tcx.sess, span, E0404,
+                    "cannot determine the type of this {}: {}",
+                    kind.description(),
+                    infer::fixup_err_to_string(e));
+            }
 
-                ResolvingClosure(_) => {
-                    let span = self.reason.span(self.tcx);
-                    span_err!(self.tcx.sess, span, E0196,
-                              "cannot determine a type for this closure")
+            ResolvingUpvar(upvar_id) => {
+                let span = self.reason.span(self.tcx);
+                span_err!(self.tcx.sess, span, E0104,
+                    "cannot resolve lifetime for captured variable `{}`: {}",
+                    ty::local_var_name_str(self.tcx, upvar_id.var_id).to_string(),
+                    infer::fixup_err_to_string(e));
+            }
+
+            ResolvingClosure(did, component) => {
+                let span = self.reason.span(self.tcx);
+                span_err!(self.tcx.sess, span, E0196,
+                          "cannot determine a type for this closure");
+                self.note_closure_component(did, component);
+            }
+        }
+    }
+
+    // Points at the code that introduced the type variable behind `e`, if
+    // its origin was recorded when it was created.
+    fn note_ty_var_origin(&self, e: infer::fixup_err) {
+        let origin = match e {
+            infer::unresolved_ty(vid) => self.infcx.ty_var_origin(vid),
+            _ => None
+        };
+
+        match origin {
+            Some(infer::TypeParameterVar(span, name)) => {
+                self.tcx.sess.span_note(
+                    span,
+                    &format!("the type parameter `{}` here is ambiguous; \
+                              consider giving it an explicit type",
+                             name));
+            }
+            Some(infer::MethodTypeParameterVar(span, method, name)) => {
+                self.tcx.sess.span_note(
+                    span,
+                    &format!("the type parameter `{}` of this `{}()` call is ambiguous; \
+                              consider an annotation here, e.g. `{}::<...>()`",
+                             name, method, method));
+            }
+            Some(infer::TypeInferenceVar(span)) => {
+                self.tcx.sess.span_note(
+                    span,
+                    "the type written as `_` here could not be inferred; \
+                     consider writing it out");
+            }
+            Some(infer::ArrayElementVar(span)) => {
+                self.tcx.sess.span_note(
+                    span,
+                    "the element type of this array is ambiguous; \
+                     consider giving it an explicit type");
+            }
+            None => {}
+        }
+    }
+
+    fn note_closure_component(&self, did: ast::DefId, component: ClosureComponent) {
+        if did.krate != ast::LOCAL_CRATE {
+            return;
+        }
+
+        let (decl, body) = match self.tcx.map.expect_expr(did.node).node {
+            ast::ExprClosure(_, ref decl, ref body) => (decl, body),
+            _ => return,
+        };
+
+        match component {
+            ClosureParam(index) => {
+                match decl.inputs.get(index) {
+                    Some(arg) => {
+                        self.tcx.sess.span_note(
+                            arg.pat.span,
+                            &format!("the type of parameter #{} (`{}`) could not be \
+                                      determined; consider giving it a type annotation",
+                                     index + 1,
+                                     pat_to_string(&*arg.pat)));
+                    }
+                    None => {
+                        self.tcx.sess.note(
+                            &format!("the type of parameter #{} could not be determined",
+                                     index + 1));
+                    }
                 }
             }
+
+            ClosureReturn => {
+                let span = match decl.output {
+                    ast::Return(ref ty) => ty.span,
+                    _ => body.span,
+                };
+                self.tcx.sess.span_note(
+                    span,
+                    "the return type of this closure could not be determined");
+            }
+
+            ClosureUpvar(var_id, span) => {
+                self.tcx.sess.span_note(
+                    span,
+                    &format!("the type of the captured variable `{}` could not be \
+                              determined",
+                             ty::local_var_name_str(self.tcx, var_id)));
+                self.tcx.sess.span_note(
+                    self.tcx.map.span(var_id),
+                    "consider giving this variable a type annotation");
+            }
         }
     }
 }
 
-impl<'cx, 'tcx> TypeFolder<'tcx> for Resolver<'cx, 'tcx> {
+///////////////////////////////////////////////////////////////////////////
+// The Resolver. This is the type folding engine that detects
+// unresolved types and so forth.
+
+/// Fully resolves `value` against `infcx`. Every variable that cannot be
+/// resolved is replaced by `TyError` (or `'static`, for a region) and the
+/// failure is passed to `on_error`, along with the type it was found in,
+/// resolved as far as possible; what it means is up to the caller.
+/// Writeback reports it as an error, but a pass that runs while inference
+/// is still in progress may just want to know whether anything was left.
+pub fn resolve_in_infcx<'cx, 'tcx, T, F>(infcx: &'cx infer::InferCtxt<'cx, 'tcx>,
+                                         value: &T,
+                                         on_error: F)
+                                         -> T
+    where T: TypeFoldable<'tcx>, F: FnMut(infer::fixup_err, Option<Ty<'tcx>>)
+{
+    value.fold_with(&mut Resolver { infcx: infcx, on_error: on_error })
+}
+
+struct Resolver<'cx, 'tcx: 'cx, F> {
+    infcx: &'cx infer::InferCtxt<'cx, 'tcx>,
+    on_error: F,
+}
+
+impl<'cx, 'tcx, F> TypeFolder<'tcx> for Resolver<'cx, 'tcx, F>
+    where F: FnMut(infer::fixup_err, Option<Ty<'tcx>>)
+{
     fn tcx<'a>(&'a self) -> &'a ty::ctxt<'tcx> {
-        self.tcx
+        self.infcx.tcx
     }
 
     fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
-        match self.infcx.fully_resolve(&t) {
-            Ok(t) => t,
-            Err(e) => {
-                debug!("Resolver::fold_ty: input type `{:?}` not fully resolvable",
-                       t);
-                self.report_error(e);
-                self.tcx().types.err
+        // Keep whatever structure could be resolved, so that later
+        // diagnostics on this node see e.g. `Vec<[error]>` and not just
+        // `[error]`.
+        match self.infcx.fully_resolve_with_fallback(&t) {
+            (resolved, None) => resolved,
+            (resolved, Some(e)) => {
+                debug!("Resolver::fold_ty: input type `{:?}` not fully resolvable, \
+                        falling back to `{:?}`",
+                       t, resolved);
+                (self.on_error)(e, Some(self.infcx.resolve_type_vars_if_possible(&t)));
+                resolved
             }
         }
     }
@@ -438,7 +1389,7 @@ impl<'cx, 'tcx> TypeFolder<'tcx> for Resolver<'cx, 'tcx> {
         match self.infcx.fully_resolve(&r) {
             Ok(r) => r,
             Err(e) => {
-                self.report_error(e);
+                (self.on_error)(e, None);
                 ty::ReStatic
             }
         }
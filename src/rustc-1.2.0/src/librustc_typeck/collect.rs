@@ -90,6 +90,7 @@ use syntax::abi;
 use syntax::ast;
 use syntax::ast_util::local_def;
 use syntax::codemap::Span;
+use syntax::feature_gate;
 use syntax::parse::token::special_idents;
 use syntax::parse::token;
 use syntax::ptr::P;
@@ -2176,9 +2177,39 @@ fn check_method_self_type<'a, 'tcx, RS:RegionScope>(
     let tcx = ccx.tcx;
     if let ast::SelfExplicit(ref ast_type, _) = explicit_self.node {
         let typ = ccx.icx(&method_type.predicates).to_ty(rs, &**ast_type);
+        // A smart pointer implementing `Receiver`, such as `Rc<Self>`,
+        // may wrap any of the receivers that are otherwise allowed.
+        let typ = match ty::receiver_target(tcx, typ) {
+            Some(target) => {
+                if !tcx.sess.features.borrow().arbitrary_self_types {
+                    feature_gate::emit_feature_err(
+                        &tcx.sess.parse_sess.span_diagnostic,
+                        "arbitrary_self_types",
+                        explicit_self.span,
+                        "smart pointer receivers other than `Box<Self>` are unstable");
+                }
+                target
+            }
+            None => typ,
+        };
         let base_type = match typ.sty {
             ty::TyRef(_, tm) => tm.ty,
             ty::TyBox(typ) => typ,
+            ty::TyRawPtr(tm) => {
+                // Raw pointers can only be dispatched on statically, so
+                // they are not allowed where an object might need them.
+                if let TraitContainer(_) = method_type.container {
+                    span_err!(tcx.sess, explicit_self.span, E0400,
+                              "raw pointer receivers are only allowed in inherent impls");
+                } else if !tcx.sess.features.borrow().arbitrary_self_types {
+                    feature_gate::emit_feature_err(
+                        &tcx.sess.parse_sess.span_diagnostic,
+                        "arbitrary_self_types",
+                        explicit_self.span,
+                        "raw pointer receivers are unstable");
+                }
+                tm.ty
+            }
             _ => typ,
         };
 
@@ -378,6 +378,26 @@ fn main() {
     x.drop(); // error: explicit use of destructor method
 }
 ```
+
+If you need to drop a value before the end of its scope, move it into
+`std::mem::drop` instead:
+
+```
+struct Foo {
+    x: i32,
+}
+
+impl Drop for Foo {
+    fn drop(&mut self) {
+        println!("kaboom");
+    }
+}
+
+fn main() {
+    let x = Foo { x: -7 };
+    std::mem::drop(x);
+}
+```
 "##,
 
 E0045: r##"
@@ -1607,6 +1627,13 @@ register_diagnostics! {
            // `#[lang = \"{}\"]` is allowed for the `{}` primitive
     E0391, // unsupported cyclic reference between types/traits detected
     E0392, // parameter `{}` is never used
-    E0393  // the type parameter `{}` must be explicitly specified in an object
+    E0393, // the type parameter `{}` must be explicitly specified in an object
            // type because its default value `{}` references the type `Self`"
+    E0399, // method with a smart pointer receiver called on a trait object
+    E0400, // raw pointer receiver on a trait method
+    E0401, // method on a trait object reaches its trait through ambiguous supertraits
+    E0402, // cyclic `Deref` implementations found while auto-dereferencing
+    E0403, // inline assembly operand does not fit its register constraint
+    E0404, // cannot determine the type of an array length, repeat count or discriminant
+    E0405  // malformed `default_integer_type` attribute
 }
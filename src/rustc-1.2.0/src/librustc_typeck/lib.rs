@@ -79,6 +79,8 @@ This API is completely unstable and subject to change.
 #![feature(box_patterns)]
 #![feature(box_syntax)]
 #![feature(drain)]
+#![feature(duration)]
+#![feature(duration_span)]
 #![feature(iter_cmp)]
 #![feature(iter_arith)]
 #![feature(quote)]
@@ -95,6 +97,7 @@ This API is completely unstable and subject to change.
 extern crate arena;
 extern crate fmt_macros;
 extern crate rustc;
+extern crate serialize as rustc_serialize; // used by deriving
 
 pub use rustc::lint;
 pub use rustc::metadata;
@@ -114,6 +117,7 @@ use syntax::codemap::Span;
 use syntax::print::pprust::*;
 use syntax::{ast, abi};
 use syntax::ast_util::local_def;
+use syntax::attr::AttrMetaMethods;
 
 use std::cell::RefCell;
 
@@ -142,6 +146,14 @@ pub struct CrateCtxt<'a, 'tcx: 'a> {
     /// error reporting, and so is lazily initialised and generally
     /// shouldn't taint the common path (hence the RefCell).
     pub all_traits: RefCell<Option<check::method::AllTraitsVec>>,
+    /// Time spent confirming method calls, gathered with
+    /// `-Z time-method-resolution`.
+    pub method_stats: check::method::MethodResolutionStats,
+    /// The types writeback could not resolve, gathered with
+    /// `-Z emit-unresolved-types`.
+    pub unresolved_types: check::unresolved::UnresolvedTypes,
+    /// The types unconstrained integer and float literals fall back to.
+    pub literal_fallback: infer::LiteralFallback<'tcx>,
     pub tcx: &'a ty::ctxt<'tcx>,
 }
 
@@ -152,20 +164,6 @@ fn write_ty_to_tcx<'tcx>(tcx: &ty::ctxt<'tcx>, node_id: ast::NodeId, ty: Ty<'tcx
     tcx.node_type_insert(node_id, ty);
 }
 
-fn write_substs_to_tcx<'tcx>(tcx: &ty::ctxt<'tcx>,
-                                 node_id: ast::NodeId,
-                                 item_substs: ty::ItemSubsts<'tcx>) {
-    if !item_substs.is_noop() {
-        debug!("write_substs_to_tcx({}, {:?})",
-               node_id,
-               item_substs);
-
-        assert!(item_substs.substs.types.all(|t| !ty::type_needs_infer(*t)));
-
-        tcx.item_substs.borrow_mut().insert(node_id, item_substs);
-    }
-}
-
 fn lookup_full_def(tcx: &ty::ctxt, sp: Span, id: ast::NodeId) -> def::Def {
     match tcx.def_map.borrow().get(&id) {
         Some(x) => x.full_def(),
@@ -199,7 +197,7 @@ fn require_same_types<'a, 'tcx, M>(tcx: &ty::ctxt<'tcx>,
         Ok(_) => true,
         Err(ref terr) => {
             span_err!(tcx.sess, span, E0211, "{}: {}", msg(), terr);
-            ty::note_and_explain_type_err(tcx, terr, span);
+            ty::note_and_explain_type_err(tcx, &terr.err, span);
             false
         }
     }
@@ -313,11 +311,55 @@ fn check_for_entry_fn(ccx: &CrateCtxt) {
     }
 }
 
+/// The types unconstrained literals fall back to: `i32` and `f64`, unless
+/// the crate picks another integer type with `#![default_integer_type(..)]`.
+fn literal_fallback<'tcx>(tcx: &ty::ctxt<'tcx>) -> infer::LiteralFallback<'tcx> {
+    let mut fallback = infer::LiteralFallback::new(tcx);
+    for attr in &tcx.map.krate().attrs {
+        if !attr.check_name("default_integer_type") {
+            continue;
+        }
+
+        let int_ty = match attr.meta_item_list() {
+            Some(items) if items.len() == 1 => match items[0].node {
+                ast::MetaWord(ref name) => match &name[..] {
+                    "isize" => Some(tcx.types.isize),
+                    "i8" => Some(tcx.types.i8),
+                    "i16" => Some(tcx.types.i16),
+                    "i32" => Some(tcx.types.i32),
+                    "i64" => Some(tcx.types.i64),
+                    "usize" => Some(tcx.types.usize),
+                    "u8" => Some(tcx.types.u8),
+                    "u16" => Some(tcx.types.u16),
+                    "u32" => Some(tcx.types.u32),
+                    "u64" => Some(tcx.types.u64),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match int_ty {
+            Some(ty) => fallback.int_ty = ty,
+            None => {
+                span_err!(tcx.sess, attr.span, E0405,
+                          "malformed `default_integer_type` attribute, expected an \
+                           integer type as in `#![default_integer_type(u64)]`");
+            }
+        }
+    }
+    fallback
+}
+
 pub fn check_crate(tcx: &ty::ctxt, trait_map: ty::TraitMap) {
     let time_passes = tcx.sess.time_passes();
     let ccx = CrateCtxt {
         trait_map: trait_map,
         all_traits: RefCell::new(None),
+        method_stats: check::method::MethodResolutionStats::new(),
+        unresolved_types: check::unresolved::UnresolvedTypes::new(),
+        literal_fallback: literal_fallback(tcx),
         tcx: tcx
     };
 
@@ -335,7 +377,14 @@ pub fn check_crate(tcx: &ty::ctxt, trait_map: ty::TraitMap) {
         coherence::check_coherence(&ccx));
 
     time(time_passes, "type checking", (), |_|
-        check::check_item_types(&ccx));
+        tcx.sess.limiting_type_errors(|| check::check_item_types(&ccx)));
+
+    if tcx.sess.time_method_resolution() {
+        ccx.method_stats.print(tcx);
+    }
+    if tcx.sess.writeback_fingerprints() {
+        check::writeback::print_fingerprints(tcx);
+    }
 
     check_for_entry_fn(&ccx);
     tcx.sess.abort_if_errors();
@@ -1437,8 +1437,8 @@ pub enum Type {
 
 #[derive(Clone, RustcEncodable, RustcDecodable, PartialEq, Eq, Hash, Copy, Debug)]
 pub enum PrimitiveType {
-    Isize, I8, I16, I32, I64,
-    Usize, U8, U16, U32, U64,
+    Isize, I8, I16, I32, I64, I128,
+    Usize, U8, U16, U32, U64, U128,
     F32, F64,
     Char,
     Bool,
@@ -1485,11 +1485,13 @@ impl PrimitiveType {
             "i16" => Some(I16),
             "i32" => Some(I32),
             "i64" => Some(I64),
+            "i128" => Some(I128),
             "usize" => Some(Usize),
             "u8" => Some(U8),
             "u16" => Some(U16),
             "u32" => Some(U32),
             "u64" => Some(U64),
+            "u128" => Some(U128),
             "bool" => Some(Bool),
             "char" => Some(Char),
             "str" => Some(Str),
@@ -1531,11 +1533,13 @@ impl PrimitiveType {
             I16 => "i16",
             I32 => "i32",
             I64 => "i64",
+            I128 => "i128",
             Usize => "usize",
             U8 => "u8",
             U16 => "u16",
             U32 => "u32",
             U64 => "u64",
+            U128 => "u128",
             F32 => "f32",
             F64 => "f64",
             Str => "str",
@@ -1625,11 +1629,13 @@ impl<'tcx> Clean<Type> for ty::Ty<'tcx> {
             ty::TyInt(ast::TyI16) => Primitive(I16),
             ty::TyInt(ast::TyI32) => Primitive(I32),
             ty::TyInt(ast::TyI64) => Primitive(I64),
+            ty::TyInt(ast::TyI128) => Primitive(I128),
             ty::TyUint(ast::TyUs) => Primitive(Usize),
             ty::TyUint(ast::TyU8) => Primitive(U8),
             ty::TyUint(ast::TyU16) => Primitive(U16),
             ty::TyUint(ast::TyU32) => Primitive(U32),
             ty::TyUint(ast::TyU64) => Primitive(U64),
+            ty::TyUint(ast::TyU128) => Primitive(U128),
             ty::TyFloat(ast::TyF32) => Primitive(F32),
             ty::TyFloat(ast::TyF64) => Primitive(F64),
             ty::TyStr => Primitive(Str),
@@ -2275,11 +2281,13 @@ fn build_deref_target_impls(cx: &DocContext,
             I16 => tcx.lang_items.i16_impl(),
             I32 => tcx.lang_items.i32_impl(),
             I64 => tcx.lang_items.i64_impl(),
+            I128 => tcx.lang_items.i128_impl(),
             Usize => tcx.lang_items.usize_impl(),
             U8 => tcx.lang_items.u8_impl(),
             U16 => tcx.lang_items.u16_impl(),
             U32 => tcx.lang_items.u32_impl(),
             U64 => tcx.lang_items.u64_impl(),
+            U128 => tcx.lang_items.u128_impl(),
             F32 => tcx.lang_items.f32_impl(),
             F64 => tcx.lang_items.f64_impl(),
             Char => tcx.lang_items.char_impl(),
@@ -2590,11 +2598,13 @@ fn resolve_type(cx: &DocContext,
             ast::TyInt(ast::TyI16) => return Primitive(I16),
             ast::TyInt(ast::TyI32) => return Primitive(I32),
             ast::TyInt(ast::TyI64) => return Primitive(I64),
+            ast::TyInt(ast::TyI128) => return Primitive(I128),
             ast::TyUint(ast::TyUs) => return Primitive(Usize),
             ast::TyUint(ast::TyU8) => return Primitive(U8),
             ast::TyUint(ast::TyU16) => return Primitive(U16),
             ast::TyUint(ast::TyU32) => return Primitive(U32),
             ast::TyUint(ast::TyU64) => return Primitive(U64),
+            ast::TyUint(ast::TyU128) => return Primitive(U128),
             ast::TyFloat(ast::TyF32) => return Primitive(F32),
             ast::TyFloat(ast::TyF64) => return Primitive(F64),
         },
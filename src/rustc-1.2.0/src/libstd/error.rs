@@ -144,6 +144,13 @@ impl Error for num::ParseIntError {
     }
 }
 
+#[unstable(feature = "int_try_from", reason = "recently added")]
+impl Error for num::TryFromIntError {
+    fn description(&self) -> &str {
+        self.__description()
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Error for num::ParseFloatError {
     fn description(&self) -> &str {
@@ -122,6 +122,7 @@
 #![feature(fnbox)]
 #![feature(heap_api)]
 #![feature(int_error_internals)]
+#![feature(int_try_from)]
 #![feature(into_cow)]
 #![feature(iter_order)]
 #![feature(lang_items)]
@@ -149,6 +150,9 @@
 #![feature(wrapping)]
 #![feature(zero_one)]
 #![cfg_attr(windows, feature(str_utf16))]
+#![cfg_attr(not(stage0), feature(i128, i128_type))]
+#![cfg_attr(test, feature(int_error_kind, int_parse_lenient, int_to_from_bytes, num_bits_bytes,
+                           num_checked_ops, num_euclid, num_midpoint, num_overflowing))]
 #![cfg_attr(test, feature(float_from_str_radix, range_inclusive, float_extras))]
 #![cfg_attr(test, feature(test, rustc_private, float_consts))]
 
@@ -253,12 +257,16 @@ mod uint_macros;
 #[path = "num/i16.rs"]  pub mod i16;
 #[path = "num/i32.rs"]  pub mod i32;
 #[path = "num/i64.rs"]  pub mod i64;
+#[cfg(not(stage0))]
+#[path = "num/i128.rs"] pub mod i128;
 
 #[path = "num/usize.rs"] pub mod usize;
 #[path = "num/u8.rs"]   pub mod u8;
 #[path = "num/u16.rs"]  pub mod u16;
 #[path = "num/u32.rs"]  pub mod u32;
 #[path = "num/u64.rs"]  pub mod u64;
+#[cfg(not(stage0))]
+#[path = "num/u128.rs"] pub mod u128;
 
 #[path = "num/f32.rs"]   pub mod f32;
 #[path = "num/f64.rs"]   pub mod f64;
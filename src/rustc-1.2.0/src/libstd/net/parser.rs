@@ -171,7 +171,7 @@ impl<'a> Parser<'a> {
                 return None;
             }
 
-            let octet = self.read_number(10, 3, 0x100).map(|n| n as u8);
+            let octet = self.read_number(10, 3, 0x100).and_then(|n| u8::try_from_u32(n).ok());
             match octet {
                 Some(d) => bs[i] = d,
                 None => return None,
@@ -217,7 +217,7 @@ impl<'a> Parser<'a> {
 
                 let group = p.read_atomically(|p| {
                     if i == 0 || p.read_given_char(':').is_some() {
-                        p.read_number(16, 4, 0x10000).map(|n| n as u16)
+                        p.read_number(16, 4, 0x10000).and_then(|n| u16::try_from_u32(n).ok())
                     } else {
                         None
                     }
@@ -278,7 +278,9 @@ impl<'a> Parser<'a> {
             p.read_or(&mut [Box::new(ipv4_p), Box::new(ipv6_p)])
         };
         let colon = |p: &mut Parser| p.read_given_char(':');
-        let port  = |p: &mut Parser| p.read_number(10, 5, 0x10000).map(|n| n as u16);
+        let port  = |p: &mut Parser| {
+            p.read_number(10, 5, 0x10000).and_then(|n| u16::try_from_u32(n).ok())
+        };
 
         // host, colon, port
         self.read_seq_3(ip_addr, colon, port).map(|t| {
@@ -10,6 +10,329 @@
 
 #![doc(hidden)]
 
-macro_rules! int_module { ($T:ty) => (
+macro_rules! int_module { ($T:ident) => (
+
+#[cfg(test)]
+mod tests {
+    use prelude::v1::*;
+
+    #[test]
+    fn test_parse_error_kinds() {
+        use num::IntErrorKind::*;
+
+        fn kind(s: &str) -> ::num::IntErrorKind {
+            *$T::from_str_radix(s, 10).unwrap_err().kind()
+        }
+
+        assert_eq!(kind(""), Empty);
+        assert_eq!(kind("-"), Empty);
+        assert_eq!(kind("x"), InvalidDigit { offset: 0 });
+        assert_eq!(kind("12x4"), InvalidDigit { offset: 2 });
+        assert_eq!(kind("-12x4"), InvalidDigit { offset: 3 });
+        // Too large even for the 128-bit types.
+        let big = "99999999999999999999999999999999999999999999999999";
+        assert_eq!(kind(big), Overflow);
+        assert_eq!(kind(&format!("-{}", big)), Underflow);
+    }
+
+    #[test]
+    fn test_from_str_radix_lenient() {
+        use num::IntErrorKind::*;
+
+        fn parse(s: &str, radix: u32) -> Result<$T, ::num::IntErrorKind> {
+            $T::from_str_radix_lenient(s, radix).map_err(|e| *e.kind())
+        }
+
+        assert_eq!(parse("1_2", 10), Ok(12));
+        assert_eq!(parse("-1_2", 10), Ok(-12));
+        assert_eq!(parse("1__2_", 10), Ok(12));
+        assert_eq!(parse("-0b1", 2), Err(InvalidDigit { offset: 2 }));
+        assert_eq!(parse("1_1", 2), Ok(3));
+        assert_eq!(parse("7_f", 16), Ok(0x7f));
+        assert_eq!(parse("_1", 10), Err(InvalidDigit { offset: 0 }));
+        assert_eq!(parse("-_1", 10), Err(InvalidDigit { offset: 1 }));
+        assert_eq!(parse("_", 10), Err(InvalidDigit { offset: 0 }));
+        assert_eq!(parse("1_x", 10), Err(InvalidDigit { offset: 2 }));
+        assert_eq!(parse(&format!("{}_0", $T::max_value()), 10), Err(Overflow));
+        assert_eq!(parse(&format!("{}_0", $T::min_value()), 10), Err(Underflow));
+        // The strict parser still rejects separators.
+        assert!($T::from_str_radix("1_2", 10).is_err());
+    }
+
+    #[test]
+    fn test_try_from() {
+        use num::IntErrorKind::*;
+
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        assert_eq!($T::try_from_i8(-1), Ok(-1));
+        assert_eq!($T::try_from_u8(127), Ok(127));
+        if ::$T::BITS <= 64 {
+            assert_eq!($T::try_from_i64(max as i64), Ok(max));
+            assert_eq!($T::try_from_i64(min as i64), Ok(min));
+            assert_eq!($T::try_from_u64(max as u64), Ok(max));
+            assert_eq!($T::try_from_u64(u64::max_value()).unwrap_err().kind(), &Overflow);
+        } else {
+            assert_eq!($T::try_from_u64(u64::max_value()), Ok(u64::max_value() as $T));
+        }
+        assert_eq!($T::try_from_isize(0), Ok(0));
+        assert_eq!($T::try_from_usize(1), Ok(1));
+        if ::$T::BITS < 64 {
+            assert_eq!($T::try_from_i64(max as i64 + 1).unwrap_err().kind(), &Overflow);
+            assert_eq!($T::try_from_i64(min as i64 - 1).unwrap_err().kind(), &Underflow);
+            assert_eq!($T::try_from_u64(max as u64 + 1).unwrap_err().kind(), &Overflow);
+        }
+
+        // Round-tripping through the unsigned type of the same width
+        // catches the values that `as` would reinterpret.
+        assert_eq!(u8::try_from_i8(-1).unwrap_err().kind(), &Underflow);
+        assert_eq!(u64::try_from_i8(-1).unwrap_err().kind(), &Underflow);
+        assert_eq!(i8::try_from_u8(128).unwrap_err().kind(), &Overflow);
+        assert_eq!(i8::try_from_i16(300).unwrap_err().to_string(),
+                   "number too large to fit in `i8`");
+        assert_eq!(u16::try_from_i32(-3).unwrap_err().to_string(),
+                   "number too small to fit in `u16`");
+    }
+
+    #[test]
+    fn test_checked_ops() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        assert_eq!((5 as $T).checked_add(2), Some(7));
+        assert_eq!(max.checked_add(1), None);
+        assert_eq!((-5 as $T).checked_sub(2), Some(-7));
+        assert_eq!(min.checked_sub(1), None);
+        assert_eq!((5 as $T).checked_mul(-2), Some(-10));
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!((7 as $T).checked_div(2), Some(3));
+        assert_eq!((7 as $T).checked_div(0), None);
+        assert_eq!(min.checked_div(-1), None);
+        assert_eq!((7 as $T).checked_rem(-2), Some(1));
+        assert_eq!((7 as $T).checked_rem(0), None);
+        assert_eq!(min.checked_rem(-1), None);
+        assert_eq!((5 as $T).checked_neg(), Some(-5));
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!((1 as $T).checked_shl(3), Some(8));
+        assert_eq!((1 as $T).checked_shl(::$T::BITS as u32), None);
+        assert_eq!((8 as $T).checked_shr(3), Some(1));
+        assert_eq!((8 as $T).checked_shr(::$T::BITS as u32), None);
+    }
+
+    #[test]
+    fn test_saturating_ops() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        assert_eq!(max.saturating_add(1), max);
+        assert_eq!(min.saturating_add(-1), min);
+        assert_eq!(min.saturating_sub(1), min);
+        assert_eq!(max.saturating_sub(-1), max);
+        assert_eq!(max.saturating_mul(2), max);
+        assert_eq!(max.saturating_mul(-2), min);
+        assert_eq!(min.saturating_mul(-2), max);
+        assert_eq!((-3 as $T).saturating_mul(4), -12);
+    }
+
+    #[test]
+    fn test_wrapping_ops() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        assert_eq!(max.wrapping_add(1), min);
+        assert_eq!(min.wrapping_sub(1), max);
+        assert_eq!(max.wrapping_mul(2), -2);
+        assert_eq!(min.wrapping_div(-1), min);
+        assert_eq!(min.wrapping_rem(-1), 0);
+        assert_eq!(min.wrapping_neg(), min);
+        assert_eq!((1 as $T).wrapping_shl(::$T::BITS as u32), 1);
+        assert_eq!((1 as $T).wrapping_shr(::$T::BITS as u32 + 1), 0);
+    }
+
+    #[test]
+    fn test_overflowing_ops() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        assert_eq!((5 as $T).overflowing_add(2), (7, false));
+        assert_eq!(max.overflowing_add(1), (min, true));
+        assert_eq!(min.overflowing_sub(1), (max, true));
+        assert_eq!(max.overflowing_mul(2), (-2, true));
+        assert_eq!(min.overflowing_div(-1), (min, true));
+        assert_eq!(min.overflowing_rem(-1), (0, true));
+        assert_eq!((5 as $T).overflowing_neg(), (-5, false));
+        assert_eq!(min.overflowing_neg(), (min, true));
+        assert_eq!((1 as $T).overflowing_shl(::$T::BITS as u32), (1, true));
+        assert_eq!((2 as $T).overflowing_shr(1), (1, false));
+    }
+
+    #[test]
+    fn test_pow_variants() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+        let bits = ::$T::BITS as u32;
+
+        assert_eq!((3 as $T).checked_pow(0), Some(1));
+        assert_eq!((0 as $T).checked_pow(0), Some(1));
+        assert_eq!((0 as $T).checked_pow(bits), Some(0));
+        assert_eq!(max.checked_pow(1), Some(max));
+        assert_eq!(max.checked_pow(2), None);
+        assert_eq!((1 as $T).checked_pow(u32::max_value()), Some(1));
+        assert_eq!((-1 as $T).checked_pow(u32::max_value()), Some(-1));
+        assert_eq!((-1 as $T).checked_pow(u32::max_value() - 1), Some(1));
+        // 2^(bits - 1) is one past `max`, but -2^(bits - 1) is `min`.
+        assert_eq!((2 as $T).checked_pow(bits - 2), Some(max / 2 + 1));
+        assert_eq!((2 as $T).checked_pow(bits - 1), None);
+        assert_eq!((-2 as $T).checked_pow(bits - 1), Some(min));
+        assert_eq!((-2 as $T).checked_pow(bits), None);
+
+        assert_eq!((2 as $T).saturating_pow(bits), max);
+        assert_eq!((-2 as $T).saturating_pow(bits), max);
+        assert_eq!((-2 as $T).saturating_pow(bits + 1), min);
+        assert_eq!((-3 as $T).saturating_pow(3), -27);
+
+        assert_eq!((2 as $T).overflowing_pow(bits - 1), (min, true));
+        assert_eq!((2 as $T).overflowing_pow(bits), (0, true));
+        assert_eq!((-2 as $T).overflowing_pow(bits - 1), (min, false));
+        assert_eq!((3 as $T).overflowing_pow(2), (9, false));
+        fn naive_wrapping_pow(x: $T, exp: u32) -> $T {
+            (0..exp).fold(1, |acc, _| acc.wrapping_mul(x))
+        }
+        for exp in 0..(bits + 2) {
+            for &x in &[0, 1, -1, 2, -2, 3, -3, 7, max, min] {
+                let (r, o) = x.overflowing_pow(exp);
+                assert_eq!(r, naive_wrapping_pow(x, exp));
+                assert_eq!(o, x.checked_pow(exp).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_euclid() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        for &(a, b) in &[(7, 4), (-7, 4), (7, -4), (-7, -4), (0, 3), (-8, 4),
+                         (min, 3), (min, -3), (max, -2), (min, max), (max, min)] {
+            let (q, r) = (a.div_euclid(b), a.rem_euclid(b));
+            assert!(r >= 0);
+            assert!(r.abs_diff(0) < b.abs_diff(0));
+            assert_eq!(q.wrapping_mul(b).wrapping_add(r), a);
+            assert_eq!(a.checked_div_euclid(b), Some(q));
+            assert_eq!(a.checked_rem_euclid(b), Some(r));
+        }
+        assert_eq!((-7 as $T).div_euclid(4), -2);
+        assert_eq!((-7 as $T).rem_euclid(4), 1);
+        assert_eq!(min.rem_euclid(-1), 0);
+        assert_eq!(min.checked_div_euclid(-1), None);
+        assert_eq!(min.checked_rem_euclid(-1), None);
+        assert_eq!((1 as $T).checked_div_euclid(0), None);
+        assert_eq!((1 as $T).checked_rem_euclid(0), None);
+    }
+
+    #[test]
+    fn test_midpoint_abs_diff() {
+        let max = $T::max_value();
+        let min = $T::min_value();
+
+        assert_eq!(max.midpoint(max), max);
+        assert_eq!(min.midpoint(min), min);
+        assert_eq!(min.midpoint(max), 0);
+        assert_eq!(max.midpoint(max - 2), max - 1);
+        assert_eq!((3 as $T).midpoint(-4), 0);
+        assert_eq!((-3 as $T).midpoint(-6), -4);
+        assert_eq!(max.abs_diff(min), !0);
+        assert_eq!(min.abs_diff(max), !0);
+        assert_eq!((-5 as $T).abs_diff(5), 10);
+    }
+
+    // Portable, bit-at-a-time versions of the intrinsic-backed bit
+    // manipulation methods. The properties below check the two agree on
+    // edge cases and on a batch of pseudo-random inputs.
+
+    fn bit(x: $T, i: usize) -> bool { (x >> i) & 1 != 0 }
+
+    fn naive_count_ones(x: $T) -> u32 {
+        (0..::$T::BITS).filter(|&i| bit(x, i)).count() as u32
+    }
+
+    fn naive_leading_zeros(x: $T) -> u32 {
+        (0..::$T::BITS).rev().take_while(|&i| !bit(x, i)).count() as u32
+    }
+
+    fn naive_trailing_zeros(x: $T) -> u32 {
+        (0..::$T::BITS).take_while(|&i| !bit(x, i)).count() as u32
+    }
+
+    fn samples() -> Vec<$T> {
+        use rand::{Rng, SeedableRng, XorShiftRng};
+
+        let mut v = vec![0, 1, 2, $T::max_value(), $T::min_value(),
+                         $T::max_value() - 1, $T::min_value() + 1];
+        // A fixed seed, so that any failure can be reproduced.
+        let mut rng: XorShiftRng = SeedableRng::from_seed([0x193a6754, 0xa8a7d469,
+                                                           0x97830e05, 0x113ba7bb]);
+        v.extend((0..1000).map(|_| rng.gen::<$T>()));
+        v
+    }
+
+    #[test]
+    fn test_bit_counts_match_fallbacks() {
+        for x in samples() {
+            assert_eq!(x.count_ones(), naive_count_ones(x));
+            assert_eq!(x.count_zeros(), ::$T::BITS as u32 - naive_count_ones(x));
+            assert_eq!(x.leading_zeros(), naive_leading_zeros(x));
+            assert_eq!(x.trailing_zeros(), naive_trailing_zeros(x));
+        }
+    }
+
+    #[test]
+    fn test_rotate_matches_fallback() {
+        let bits = ::$T::BITS;
+        for x in samples() {
+            for n in 0..(2 * bits as u32 + 1) {
+                let (l, r) = (x.rotate_left(n), x.rotate_right(n));
+                for i in 0..bits {
+                    let j = (i + n as usize) % bits;
+                    assert_eq!(bit(l, j), bit(x, i));
+                    assert_eq!(bit(r, i), bit(x, j));
+                }
+                assert_eq!(l.rotate_right(n), x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap_bytes_matches_fallback() {
+        let bytes = ::$T::BYTES;
+        for x in samples() {
+            let s = x.swap_bytes();
+            for i in 0..bytes {
+                for k in 0..8 {
+                    assert_eq!(bit(s, i * 8 + k), bit(x, (bytes - 1 - i) * 8 + k));
+                }
+            }
+            assert_eq!(s.swap_bytes(), x);
+        }
+    }
+
+    #[test]
+    fn test_endian_bytes() {
+        let bytes = ::$T::BYTES;
+        for x in samples() {
+            let (be, le, ne) = (x.to_be_bytes(), x.to_le_bytes(), x.to_ne_bytes());
+            for i in 0..bytes {
+                let b = ((x >> (i * 8)) & 0xff as $T) as u8;
+                assert_eq!(le[i], b);
+                assert_eq!(be[bytes - 1 - i], b);
+            }
+            assert_eq!(ne, if cfg!(target_endian = "little") { le } else { be });
+            assert_eq!($T::from_be_bytes(be), x);
+            assert_eq!($T::from_le_bytes(le), x);
+            assert_eq!($T::from_ne_bytes(ne), x);
+        }
+    }
+}
 
 ) }
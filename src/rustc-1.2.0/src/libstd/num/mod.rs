@@ -18,7 +18,10 @@
 
 pub use core::num::{Zero, One};
 pub use core::num::{FpCategory, ParseIntError, ParseFloatError};
+pub use core::num::{IntErrorKind, TryFromIntError};
 pub use core::num::{wrapping, Wrapping};
+pub use core::num::Saturating;
+pub use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64};
 
 #[cfg(test)] use cmp::PartialEq;
 #[cfg(test)] use fmt;
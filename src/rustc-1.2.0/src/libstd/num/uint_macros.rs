@@ -46,6 +46,271 @@ mod tests {
         assert_eq!($T::from_str_radix("Z", 10).ok(), None::<$T>);
         assert_eq!($T::from_str_radix("_", 2).ok(), None::<$T>);
     }
+
+    #[test]
+    fn test_parse_error_kinds() {
+        use num::IntErrorKind::*;
+
+        fn kind(s: &str) -> ::num::IntErrorKind {
+            *$T::from_str_radix(s, 10).unwrap_err().kind()
+        }
+
+        assert_eq!(kind(""), Empty);
+        assert_eq!(kind("x"), InvalidDigit { offset: 0 });
+        assert_eq!(kind("12x4"), InvalidDigit { offset: 2 });
+        assert_eq!(kind("-1"), InvalidDigit { offset: 0 });
+        // Too large even for the 128-bit types.
+        let big = "99999999999999999999999999999999999999999999999999";
+        assert_eq!(kind(big), Overflow);
+    }
+
+    #[test]
+    fn test_from_str_radix_lenient() {
+        use num::IntErrorKind::*;
+
+        fn parse(s: &str, radix: u32) -> Result<$T, ::num::IntErrorKind> {
+            $T::from_str_radix_lenient(s, radix).map_err(|e| *e.kind())
+        }
+
+        assert_eq!(parse("1_2", 10), Ok(12));
+        assert_eq!(parse("1__2_", 10), Ok(12));
+        assert_eq!(parse("1_1", 2), Ok(3));
+        assert_eq!(parse("f_f", 16), Ok(0xff));
+        assert_eq!(parse("_1", 10), Err(InvalidDigit { offset: 0 }));
+        assert_eq!(parse("_", 10), Err(InvalidDigit { offset: 0 }));
+        assert_eq!(parse("-1", 10), Err(InvalidDigit { offset: 0 }));
+        assert_eq!(parse(&format!("{}_0", $T::max_value()), 10), Err(Overflow));
+        // The strict parser still rejects separators.
+        assert!($T::from_str_radix("1_2", 10).is_err());
+    }
+
+    #[test]
+    fn test_try_from() {
+        use num::IntErrorKind::*;
+
+        let max = $T::max_value();
+
+        assert_eq!($T::try_from_u8(255), Ok(255));
+        assert_eq!($T::try_from_i8(0), Ok(0));
+        assert_eq!($T::try_from_i8(-1).unwrap_err().kind(), &Underflow);
+        assert_eq!($T::try_from_i64(i64::min_value()).unwrap_err().kind(), &Underflow);
+        if ::$T::BITS <= 64 {
+            assert_eq!($T::try_from_u64(max as u64), Ok(max));
+        }
+        assert_eq!($T::try_from_usize(1), Ok(1));
+        assert_eq!($T::try_from_isize(-1).unwrap_err().kind(), &Underflow);
+        if ::$T::BITS < 64 {
+            assert_eq!($T::try_from_u64(max as u64 + 1).unwrap_err().kind(), &Overflow);
+            assert_eq!($T::try_from_i64(max as i64 + 1).unwrap_err().kind(), &Overflow);
+        }
+    }
+
+    #[test]
+    fn test_checked_ops() {
+        let max = $T::max_value();
+
+        assert_eq!((5 as $T).checked_add(2), Some(7));
+        assert_eq!(max.checked_add(1), None);
+        assert_eq!((5 as $T).checked_sub(2), Some(3));
+        assert_eq!((0 as $T).checked_sub(1), None);
+        assert_eq!((5 as $T).checked_mul(2), Some(10));
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!((7 as $T).checked_div(2), Some(3));
+        assert_eq!((7 as $T).checked_div(0), None);
+        assert_eq!((7 as $T).checked_rem(2), Some(1));
+        assert_eq!((7 as $T).checked_rem(0), None);
+        assert_eq!((0 as $T).checked_neg(), Some(0));
+        assert_eq!((1 as $T).checked_neg(), None);
+        assert_eq!((1 as $T).checked_shl(3), Some(8));
+        assert_eq!((1 as $T).checked_shl(::$T::BITS as u32), None);
+        assert_eq!((8 as $T).checked_shr(3), Some(1));
+        assert_eq!((8 as $T).checked_shr(::$T::BITS as u32), None);
+    }
+
+    #[test]
+    fn test_saturating_ops() {
+        let max = $T::max_value();
+
+        assert_eq!(max.saturating_add(1), max);
+        assert_eq!((0 as $T).saturating_sub(1), 0);
+        assert_eq!(max.saturating_mul(2), max);
+        assert_eq!((3 as $T).saturating_mul(4), 12);
+    }
+
+    #[test]
+    fn test_wrapping_ops() {
+        let max = $T::max_value();
+
+        assert_eq!(max.wrapping_add(1), 0);
+        assert_eq!((0 as $T).wrapping_sub(1), max);
+        assert_eq!(max.wrapping_mul(2), max - 1);
+        assert_eq!((7 as $T).wrapping_div(2), 3);
+        assert_eq!((7 as $T).wrapping_rem(2), 1);
+        assert_eq!((1 as $T).wrapping_neg(), max);
+        assert_eq!((1 as $T).wrapping_shl(::$T::BITS as u32), 1);
+        assert_eq!((1 as $T).wrapping_shr(::$T::BITS as u32 + 1), 0);
+    }
+
+    #[test]
+    fn test_overflowing_ops() {
+        let max = $T::max_value();
+
+        assert_eq!((5 as $T).overflowing_add(2), (7, false));
+        assert_eq!(max.overflowing_add(1), (0, true));
+        assert_eq!((0 as $T).overflowing_sub(1), (max, true));
+        assert_eq!(max.overflowing_mul(2), (max - 1, true));
+        assert_eq!((7 as $T).overflowing_div(2), (3, false));
+        assert_eq!((7 as $T).overflowing_rem(2), (1, false));
+        assert_eq!((0 as $T).overflowing_neg(), (0, false));
+        assert_eq!((1 as $T).overflowing_neg(), (max, true));
+        assert_eq!((1 as $T).overflowing_shl(::$T::BITS as u32), (1, true));
+        assert_eq!((2 as $T).overflowing_shr(1), (1, false));
+    }
+
+    #[test]
+    fn test_pow_variants() {
+        let max = $T::max_value();
+        let bits = ::$T::BITS as u32;
+
+        assert_eq!((3 as $T).checked_pow(0), Some(1));
+        assert_eq!((0 as $T).checked_pow(0), Some(1));
+        assert_eq!((0 as $T).checked_pow(u32::max_value()), Some(0));
+        assert_eq!((1 as $T).checked_pow(u32::max_value()), Some(1));
+        assert_eq!(max.checked_pow(1), Some(max));
+        assert_eq!(max.checked_pow(2), None);
+        assert_eq!((2 as $T).checked_pow(bits - 1), Some(max / 2 + 1));
+        assert_eq!((2 as $T).checked_pow(bits), None);
+
+        assert_eq!((2 as $T).saturating_pow(bits), max);
+        assert_eq!((3 as $T).saturating_pow(3), 27);
+
+        assert_eq!((2 as $T).overflowing_pow(bits), (0, true));
+        assert_eq!((3 as $T).overflowing_pow(2), (9, false));
+
+        fn naive_wrapping_pow(x: $T, exp: u32) -> $T {
+            (0..exp).fold(1, |acc, _| acc.wrapping_mul(x))
+        }
+        for exp in 0..(bits + 2) {
+            for &x in &[0, 1, 2, 3, 7, max, max - 1] {
+                let (r, o) = x.overflowing_pow(exp);
+                assert_eq!(r, naive_wrapping_pow(x, exp));
+                assert_eq!(o, x.checked_pow(exp).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_euclid() {
+        let max = $T::max_value();
+
+        assert_eq!((7 as $T).div_euclid(4), 1);
+        assert_eq!((7 as $T).rem_euclid(4), 3);
+        assert_eq!(max.div_euclid(max), 1);
+        assert_eq!(max.rem_euclid(2), 1);
+        assert_eq!((7 as $T).checked_div_euclid(0), None);
+        assert_eq!((7 as $T).checked_rem_euclid(0), None);
+        assert_eq!((7 as $T).checked_rem_euclid(4), Some(3));
+    }
+
+    #[test]
+    fn test_midpoint_abs_diff() {
+        let max = $T::max_value();
+
+        assert_eq!(max.midpoint(max), max);
+        assert_eq!(max.midpoint(max - 2), max - 1);
+        assert_eq!(max.midpoint(0), max / 2);
+        assert_eq!((0 as $T).midpoint(5), 2);
+        assert_eq!(max.abs_diff(0), max);
+        assert_eq!((0 as $T).abs_diff(max), max);
+        assert_eq!((3 as $T).abs_diff(10), 7);
+    }
+
+    // Portable, bit-at-a-time versions of the intrinsic-backed bit
+    // manipulation methods. The properties below check the two agree on
+    // edge cases and on a batch of pseudo-random inputs.
+
+    fn bit(x: $T, i: usize) -> bool { (x >> i) & 1 != 0 }
+
+    fn naive_count_ones(x: $T) -> u32 {
+        (0..::$T::BITS).filter(|&i| bit(x, i)).count() as u32
+    }
+
+    fn naive_leading_zeros(x: $T) -> u32 {
+        (0..::$T::BITS).rev().take_while(|&i| !bit(x, i)).count() as u32
+    }
+
+    fn naive_trailing_zeros(x: $T) -> u32 {
+        (0..::$T::BITS).take_while(|&i| !bit(x, i)).count() as u32
+    }
+
+    fn samples() -> Vec<$T> {
+        use rand::{Rng, SeedableRng, XorShiftRng};
+
+        let mut v = vec![0, 1, 2, $T::max_value(), $T::min_value(),
+                         $T::max_value() - 1, $T::min_value() + 1];
+        // A fixed seed, so that any failure can be reproduced.
+        let mut rng: XorShiftRng = SeedableRng::from_seed([0x193a6754, 0xa8a7d469,
+                                                           0x97830e05, 0x113ba7bb]);
+        v.extend((0..1000).map(|_| rng.gen::<$T>()));
+        v
+    }
+
+    #[test]
+    fn test_bit_counts_match_fallbacks() {
+        for x in samples() {
+            assert_eq!(x.count_ones(), naive_count_ones(x));
+            assert_eq!(x.count_zeros(), ::$T::BITS as u32 - naive_count_ones(x));
+            assert_eq!(x.leading_zeros(), naive_leading_zeros(x));
+            assert_eq!(x.trailing_zeros(), naive_trailing_zeros(x));
+        }
+    }
+
+    #[test]
+    fn test_rotate_matches_fallback() {
+        let bits = ::$T::BITS;
+        for x in samples() {
+            for n in 0..(2 * bits as u32 + 1) {
+                let (l, r) = (x.rotate_left(n), x.rotate_right(n));
+                for i in 0..bits {
+                    let j = (i + n as usize) % bits;
+                    assert_eq!(bit(l, j), bit(x, i));
+                    assert_eq!(bit(r, i), bit(x, j));
+                }
+                assert_eq!(l.rotate_right(n), x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap_bytes_matches_fallback() {
+        let bytes = ::$T::BYTES;
+        for x in samples() {
+            let s = x.swap_bytes();
+            for i in 0..bytes {
+                for k in 0..8 {
+                    assert_eq!(bit(s, i * 8 + k), bit(x, (bytes - 1 - i) * 8 + k));
+                }
+            }
+            assert_eq!(s.swap_bytes(), x);
+        }
+    }
+
+    #[test]
+    fn test_endian_bytes() {
+        let bytes = ::$T::BYTES;
+        for x in samples() {
+            let (be, le, ne) = (x.to_be_bytes(), x.to_le_bytes(), x.to_ne_bytes());
+            for i in 0..bytes {
+                let b = ((x >> (i * 8)) & 0xff as $T) as u8;
+                assert_eq!(le[i], b);
+                assert_eq!(be[bytes - 1 - i], b);
+            }
+            assert_eq!(ne, if cfg!(target_endian = "little") { le } else { be });
+            assert_eq!($T::from_be_bytes(be), x);
+            assert_eq!($T::from_le_bytes(le), x);
+            assert_eq!($T::from_ne_bytes(ne), x);
+        }
+    }
 }
 
 ) }
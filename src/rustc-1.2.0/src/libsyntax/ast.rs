@@ -852,9 +852,9 @@ pub enum Expr_ {
     /// to the object on which the method is being called on (the receiver),
     /// and the remaining elements are the rest of the arguments.
     ///
-    /// Thus, `x.foo::<Bar, Baz>(a, b, c, d)` is represented as
-    /// `ExprMethodCall(foo, [Bar, Baz], [x, a, b, c, d])`.
-    ExprMethodCall(SpannedIdent, Vec<P<Ty>>, Vec<P<Expr>>),
+    /// Thus, `x.foo::<'a, Bar, Baz>(a, b, c, d)` is represented as
+    /// `ExprMethodCall(foo, ['a], [Bar, Baz], [x, a, b, c, d])`.
+    ExprMethodCall(SpannedIdent, Vec<Lifetime>, Vec<P<Ty>>, Vec<P<Expr>>),
     /// A tuple (`(a, b, c ,d)`)
     ExprTup(Vec<P<Expr>>),
     /// A binary operation (For example: `a + b`, `a * b`)
@@ -1319,6 +1319,7 @@ pub enum IntTy {
     TyI16,
     TyI32,
     TyI64,
+    TyI128,
 }
 
 impl fmt::Debug for IntTy {
@@ -1338,6 +1339,7 @@ impl IntTy {
         match *self {
             TyIs | TyI8 => 2,
             TyI16 | TyI32 | TyI64  => 3,
+            TyI128 => 4,
         }
     }
 }
@@ -1349,6 +1351,7 @@ pub enum UintTy {
     TyU16,
     TyU32,
     TyU64,
+    TyU128,
 }
 
 impl UintTy {
@@ -1356,6 +1359,7 @@ impl UintTy {
         match *self {
             TyUs | TyU8 => 2,
             TyU16 | TyU32 | TyU64  => 3,
+            TyU128 => 4,
         }
     }
 }
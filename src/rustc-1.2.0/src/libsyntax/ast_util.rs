@@ -128,7 +128,8 @@ pub fn int_ty_to_string(t: IntTy, val: Option<i64>) -> String {
         TyI8 => "i8",
         TyI16 => "i16",
         TyI32 => "i32",
-        TyI64 => "i64"
+        TyI64 => "i64",
+        TyI128 => "i128"
     };
 
     match val {
@@ -140,12 +141,15 @@ pub fn int_ty_to_string(t: IntTy, val: Option<i64>) -> String {
     }
 }
 
-pub fn int_ty_max(t: IntTy) -> u64 {
+/// Get the magnitude of the minimum value of a signed int type, or `None`
+/// if it does not fit in a `u64`.
+pub fn int_ty_max(t: IntTy) -> Option<u64> {
     match t {
-        TyI8 => 0x80,
-        TyI16 => 0x8000,
-        TyIs | TyI32 => 0x80000000, // actually ni about TyIs
-        TyI64 => 0x8000000000000000
+        TyI8 => Some(0x80),
+        TyI16 => Some(0x8000),
+        TyIs | TyI32 => Some(0x80000000), // actually ni about TyIs
+        TyI64 => Some(0x8000000000000000),
+        TyI128 => None
     }
 }
 
@@ -157,7 +161,8 @@ pub fn uint_ty_to_string(t: UintTy, val: Option<u64>) -> String {
         TyU8 => "u8",
         TyU16 => "u16",
         TyU32 => "u32",
-        TyU64 => "u64"
+        TyU64 => "u64",
+        TyU128 => "u128"
     };
 
     match val {
@@ -166,12 +171,15 @@ pub fn uint_ty_to_string(t: UintTy, val: Option<u64>) -> String {
     }
 }
 
-pub fn uint_ty_max(t: UintTy) -> u64 {
+/// Get the maximum value of an unsigned int type, or `None` if it does not
+/// fit in a `u64`.
+pub fn uint_ty_max(t: UintTy) -> Option<u64> {
     match t {
-        TyU8 => 0xff,
-        TyU16 => 0xffff,
-        TyUs | TyU32 => 0xffffffff, // actually ni about TyUs
-        TyU64 => 0xffffffffffffffff
+        TyU8 => Some(0xff),
+        TyU16 => Some(0xffff),
+        TyUs | TyU32 => Some(0xffffffff), // actually ni about TyUs
+        TyU64 => Some(0xffffffffffffffff),
+        TyU128 => None
     }
 }
 
@@ -582,6 +582,8 @@ fn int_type_of_word(s: &str) -> Option<IntType> {
         "u32" => Some(UnsignedInt(ast::TyU32)),
         "i64" => Some(SignedInt(ast::TyI64)),
         "u64" => Some(UnsignedInt(ast::TyU64)),
+        "i128" => Some(SignedInt(ast::TyI128)),
+        "u128" => Some(UnsignedInt(ast::TyU128)),
         "isize" => Some(SignedInt(ast::TyIs)),
         "usize" => Some(UnsignedInt(ast::TyUs)),
         _ => None
@@ -627,6 +629,7 @@ impl IntType {
             SignedInt(ast::TyI16) | UnsignedInt(ast::TyU16) |
             SignedInt(ast::TyI32) | UnsignedInt(ast::TyU32) |
             SignedInt(ast::TyI64) | UnsignedInt(ast::TyU64) => true,
+            SignedInt(ast::TyI128) | UnsignedInt(ast::TyU128) |
             SignedInt(ast::TyIs) | UnsignedInt(ast::TyUs) => false
         }
     }
@@ -224,13 +224,18 @@ impl Handler {
         self.err_count.get() > 0
     }
     pub fn abort_if_errors(&self) {
+        self.abort_if_errors_with_context("")
+    }
+    /// Like `abort_if_errors`, but appends `context` (e.g. " in 3
+    /// functions") to the summary line.
+    pub fn abort_if_errors_with_context(&self, context: &str) {
         let s;
         match self.err_count.get() {
           0 => return,
-          1 => s = "aborting due to previous error".to_string(),
+          1 => s = format!("aborting due to previous error{}", context),
           _   => {
-            s = format!("aborting due to {} previous errors",
-                        self.err_count.get());
+            s = format!("aborting due to {} previous errors{}",
+                        self.err_count.get(), context);
           }
         }
         self.fatal(&s[..]);
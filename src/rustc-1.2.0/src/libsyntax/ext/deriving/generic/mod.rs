@@ -736,12 +736,14 @@ fn find_repr_type_name(diagnostic: &SpanHandler,
                 attr::ReprInt(_, attr::SignedInt(ast::TyI16)) => "i16",
                 attr::ReprInt(_, attr::SignedInt(ast::TyI32)) => "i32",
                 attr::ReprInt(_, attr::SignedInt(ast::TyI64)) => "i64",
+                attr::ReprInt(_, attr::SignedInt(ast::TyI128)) => "i128",
 
                 attr::ReprInt(_, attr::UnsignedInt(ast::TyUs)) => "usize",
                 attr::ReprInt(_, attr::UnsignedInt(ast::TyU8)) => "u8",
                 attr::ReprInt(_, attr::UnsignedInt(ast::TyU16)) => "u16",
                 attr::ReprInt(_, attr::UnsignedInt(ast::TyU32)) => "u32",
                 attr::ReprInt(_, attr::UnsignedInt(ast::TyU64)) => "u64",
+                attr::ReprInt(_, attr::UnsignedInt(ast::TyU128)) => "u128",
             }
         }
     }
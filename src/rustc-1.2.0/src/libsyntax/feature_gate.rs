@@ -158,6 +158,20 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Status)] = &[
 
     // Allows associated type defaults
     ("associated_type_defaults", "1.2.0", Active),
+
+    // Allows `self` to be a smart pointer implementing `Receiver`, as in
+    // `fn consume(self: Rc<Self>)`.
+    ("arbitrary_self_types", "1.2.0", Active),
+
+    // Allows `container[index] = value` to call `IndexAssign::index_assign`.
+    ("index_assign", "1.2.0", Active),
+
+    // Allows `#![default_integer_type(T)]`, which makes integer literals
+    // that nothing constrains fall back to `T` instead of `i32`.
+    ("default_integer_type", "1.2.0", Active),
+
+    // Allows the `i128` and `u128` types and their literal suffixes.
+    ("i128_type", "1.2.0", Active),
 ];
 // (changing above list without updating src/doc/reference.md makes @cmr sad)
 
@@ -239,6 +253,9 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
     ("rustc_move_fragments", Gated("rustc_attrs",
                                    "the `#[rustc_move_fragments]` attribute \
                                     is an experimental feature")),
+    ("rustc_dump_adjustments", Gated("rustc_attrs",
+                                     "the `#[rustc_dump_adjustments]` attribute \
+                                      is an experimental feature")),
 
     ("allow_internal_unstable", Gated("allow_internal_unstable",
                                       EXPLAIN_ALLOW_INTERNAL_UNSTABLE)),
@@ -247,6 +264,10 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
                           "the `#[fundamental]` attribute \
                            is an experimental feature")),
 
+    ("default_integer_type", Gated("default_integer_type",
+                                   "the `#[default_integer_type]` attribute \
+                                    is an experimental feature")),
+
     // FIXME: #14408 whitelist docs since rustdoc looks at them
     ("doc", Whitelisted),
 
@@ -332,6 +353,9 @@ pub struct Features {
     /// #![feature] attrs for non-language (library) features
     pub declared_lib_features: Vec<(InternedString, Span)>,
     pub const_fn: bool,
+    pub arbitrary_self_types: bool,
+    pub index_assign: bool,
+    pub i128_type: bool,
 }
 
 impl Features {
@@ -353,6 +377,9 @@ impl Features {
             declared_stable_lang_features: Vec::new(),
             declared_lib_features: Vec::new(),
             const_fn: false,
+            arbitrary_self_types: false,
+            index_assign: false,
+            i128_type: false,
         }
     }
 }
@@ -612,6 +639,17 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
                                   "box expression syntax is experimental; \
                                    you can call `Box::new` instead.");
             }
+            ast::ExprLit(ref lit) => {
+                match lit.node {
+                    ast::LitInt(_, ast::SignedIntLit(ast::TyI128, _)) |
+                    ast::LitInt(_, ast::UnsignedIntLit(ast::TyU128)) => {
+                        self.gate_feature("i128_type",
+                                          e.span,
+                                          "128-bit integers are not stable");
+                    }
+                    _ => {}
+                }
+            }
             _ => {}
         }
         visit::walk_expr(self, e);
@@ -796,6 +834,9 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler,
         declared_stable_lang_features: accepted_features,
         declared_lib_features: unknown_features,
         const_fn: cx.has_feature("const_fn"),
+        arbitrary_self_types: cx.has_feature("arbitrary_self_types"),
+        index_assign: cx.has_feature("index_assign"),
+        i128_type: cx.has_feature("i128_type"),
     }
 }
 
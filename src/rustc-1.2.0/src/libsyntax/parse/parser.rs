@@ -1912,10 +1912,11 @@ impl<'a> Parser<'a> {
 
     fn mk_method_call(&mut self,
                       ident: ast::SpannedIdent,
+                      lifetimes: Vec<ast::Lifetime>,
                       tps: Vec<P<Ty>>,
                       args: Vec<P<Expr>>)
                       -> ast::Expr_ {
-        ExprMethodCall(ident, tps, args)
+        ExprMethodCall(ident, lifetimes, tps, args)
     }
 
     pub fn mk_index(&mut self, expr: P<Expr>, idx: P<Expr>) -> ast::Expr_ {
@@ -2250,7 +2251,7 @@ impl<'a> Parser<'a> {
                     let dot = self.last_span.hi;
                     hi = self.span.hi;
                     try!(self.bump());
-                    let (_, tys, bindings) = if try!(self.eat(&token::ModSep) ){
+                    let (lifetimes, tys, bindings) = if try!(self.eat(&token::ModSep) ){
                         try!(self.expect_lt());
                         try!(self.parse_generic_values_after_lt())
                     } else {
@@ -2275,11 +2276,11 @@ impl<'a> Parser<'a> {
 
                             es.insert(0, e);
                             let id = spanned(dot, hi, i);
-                            let nd = self.mk_method_call(id, tys, es);
+                            let nd = self.mk_method_call(id, lifetimes, tys, es);
                             e = self.mk_expr(lo, hi, nd);
                         }
                         _ => {
-                            if !tys.is_empty() {
+                            if !lifetimes.is_empty() || !tys.is_empty() {
                                 let last_span = self.last_span;
                                 self.span_err(last_span,
                                               "field expressions may not \
@@ -1646,14 +1646,20 @@ impl<'a> State<'a> {
 
     fn print_expr_method_call(&mut self,
                               ident: ast::SpannedIdent,
+                              lifetimes: &[ast::Lifetime],
                               tys: &[P<ast::Ty>],
                               args: &[P<ast::Expr>]) -> io::Result<()> {
         let base_args = &args[1..];
         try!(self.print_expr(&*args[0]));
         try!(word(&mut self.s, "."));
         try!(self.print_ident(ident.node));
-        if !tys.is_empty() {
+        if !lifetimes.is_empty() || !tys.is_empty() {
             try!(word(&mut self.s, "::<"));
+            try!(self.commasep(Inconsistent, lifetimes,
+                               |s, lifetime| s.print_lifetime(lifetime)));
+            if !lifetimes.is_empty() && !tys.is_empty() {
+                try!(self.word_space(","));
+            }
             try!(self.commasep(Inconsistent, tys,
                                |s, ty| s.print_type(&**ty)));
             try!(word(&mut self.s, ">"));
@@ -1709,8 +1715,8 @@ impl<'a> State<'a> {
             ast::ExprCall(ref func, ref args) => {
                 try!(self.print_expr_call(&**func, &args[..]));
             }
-            ast::ExprMethodCall(ident, ref tys, ref args) => {
-                try!(self.print_expr_method_call(ident, &tys[..], &args[..]));
+            ast::ExprMethodCall(ident, ref lifetimes, ref tys, ref args) => {
+                try!(self.print_expr_method_call(ident, &lifetimes[..], &tys[..], &args[..]));
             }
             ast::ExprBinary(op, ref lhs, ref rhs) => {
                 try!(self.print_expr_binary(op, &**lhs, &**rhs));
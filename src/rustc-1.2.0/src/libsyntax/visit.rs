@@ -762,8 +762,11 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
             }
             visitor.visit_expr(&**callee_expression)
         }
-        ExprMethodCall(_, ref types, ref arguments) => {
+        ExprMethodCall(_, ref lifetimes, ref types, ref arguments) => {
             walk_exprs(visitor, arguments);
+            for lifetime in lifetimes {
+                visitor.visit_lifetime_ref(lifetime)
+            }
             for typ in types {
                 visitor.visit_ty(&**typ)
             }
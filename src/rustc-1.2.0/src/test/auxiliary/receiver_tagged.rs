@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(core)]
+
+use std::ops::{Deref, Receiver};
+
+pub struct Tagged<T, U> {
+    pub value: T,
+    pub tag: U,
+}
+
+impl<T, U> Deref for Tagged<T, U> {
+    type Target = T;
+    fn deref(&self) -> &T { &self.value }
+}
+
+impl<T, U> Receiver for Tagged<T, U> {}
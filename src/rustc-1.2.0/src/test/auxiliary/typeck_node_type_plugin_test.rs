@@ -0,0 +1,54 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(plugin_registrar)]
+#![feature(box_syntax, rustc_private)]
+
+extern crate syntax;
+extern crate rustc;
+
+use syntax::ast;
+use syntax::codemap::Span;
+use rustc::middle::ty::{self, Ty};
+use rustc::plugin::Registry;
+use rustc::plugin::typeck::{BodyContext, TypeckPass, TypeckPassObject};
+
+// Reports every node whose type is an array of more than four elements, as
+// writeback resolves it.
+struct Pass;
+
+impl TypeckPass for Pass {
+    fn check_body<'a, 'tcx>(&mut self,
+                            _: &BodyContext<'a, 'tcx>,
+                            _: &ast::FnDecl,
+                            _: &ast::Block,
+                            _: Span,
+                            _: ast::NodeId) {
+    }
+
+    fn check_node_type<'a, 'tcx>(&mut self,
+                                 cx: &BodyContext<'a, 'tcx>,
+                                 _: ast::NodeId,
+                                 span: Span,
+                                 ty: Ty<'tcx>) {
+        if let ty::TyArray(_, len) = ty.sty {
+            if len > 4 {
+                cx.sess().span_err(span, &format!("resolved to an array with {} elements", len));
+            }
+        }
+    }
+}
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_typeck_pass(box Pass as TypeckPassObject);
+}
@@ -0,0 +1,92 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(plugin_registrar)]
+#![feature(box_syntax, rustc_private)]
+
+extern crate syntax;
+extern crate rustc;
+
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::visit::{self, Visitor};
+use rustc::middle::ty;
+use rustc::plugin::Registry;
+use rustc::plugin::typeck::{BodyContext, TypeckPass, TypeckPassObject};
+
+// Reports method calls whose receiver was auto-dereferenced, those that
+// went through a `Deref` impl to get there, and those resolved to a method
+// of a trait impl, which can only be known once method confirmation has
+// been written back.
+struct Pass;
+
+struct Calls<'a, 'b: 'a, 'tcx: 'b> {
+    cx: &'a BodyContext<'b, 'tcx>,
+}
+
+impl<'a, 'b, 'tcx, 'v> Visitor<'v> for Calls<'a, 'b, 'tcx> {
+    fn visit_expr(&mut self, e: &'v ast::Expr) {
+        if let ast::ExprMethodCall(_, _, _, ref args) = e.node {
+            let chain = self.cx.autoderef_chain(args[0].id).unwrap_or(Vec::new());
+            if let Some(resolution) = self.cx.method_resolution(e.id) {
+                if autoderefs(&resolution.adjustment, &chain) > 0 {
+                    self.cx.sess().span_err(e.span, "receiver auto-dereferenced");
+                }
+                let tcx = self.cx.tcx;
+                if let ty::ImplContainer(impl_id) =
+                        ty::impl_or_trait_item(tcx, resolution.def_id).container() {
+                    if ty::impl_trait_ref(tcx, impl_id).is_some() {
+                        self.cx.sess().span_err(e.span, "method resolved to a trait impl");
+                    }
+                }
+            }
+            if chain.iter().any(|step| step.deref_impl.is_some()) {
+                self.cx.sess().span_err(e.span, "receiver dereferenced through a `Deref` impl");
+            }
+        }
+        visit::walk_expr(self, e);
+    }
+
+    // Closures get their own `check_body` call.
+    fn visit_fn(&mut self, _: visit::FnKind<'v>, _: &'v ast::FnDecl,
+                _: &'v ast::Block, _: Span, _: ast::NodeId) { }
+}
+
+// The autoderefs applied to a receiver, not counting the last one when it
+// only serves to reborrow a reference, as in `(&s).get()`.
+fn autoderefs(adjustment: &ty::AutoDerefRef, chain: &[ty::AutoderefStep]) -> usize {
+    let n = adjustment.autoderefs;
+    let reborrow = n > 0 && adjustment.autoref.is_some() && match chain.get(n - 1) {
+        Some(step) => match step.ty.sty {
+            ty::TyRef(..) => true,
+            _ => false,
+        },
+        None => false,
+    };
+    if reborrow { n - 1 } else { n }
+}
+
+impl TypeckPass for Pass {
+    fn check_body<'a, 'tcx>(&mut self,
+                            cx: &BodyContext<'a, 'tcx>,
+                            _: &ast::FnDecl,
+                            body: &ast::Block,
+                            _: Span,
+                            _: ast::NodeId) {
+        visit::walk_block(&mut Calls { cx: cx }, body);
+    }
+}
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_typeck_pass(box Pass as TypeckPassObject);
+}
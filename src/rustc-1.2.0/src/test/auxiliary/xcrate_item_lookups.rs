@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub trait Get<T> {
+    fn get(&self) -> T;
+    fn get_twice(&self) -> (T, T) {
+        (self.get(), self.get())
+    }
+}
+
+pub struct Wrapper<'a, T: 'a> {
+    pub value: &'a T,
+}
+
+impl<'a, T: Clone> Get<T> for Wrapper<'a, T> {
+    fn get(&self) -> T {
+        self.value.clone()
+    }
+}
+
+impl<'a, T: Clone> Wrapper<'a, T> {
+    pub fn new(value: &'a T) -> Wrapper<'a, T> {
+        Wrapper { value: value }
+    }
+
+    pub fn first(&self) -> T {
+        self.get()
+    }
+
+    pub fn second(&self) -> T {
+        self.get_twice().1
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:typeck_node_type_plugin_test.rs
+// ignore-stage1
+
+#![feature(plugin)]
+#![plugin(typeck_node_type_plugin_test)]
+
+pub fn main() {
+    [0u8; 4];
+    [0u8; 7]; //~ ERROR resolved to an array with 7 elements
+}
@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:typeck_pass_plugin_test.rs
+// ignore-stage1
+
+#![feature(plugin)]
+#![plugin(typeck_pass_plugin_test)]
+
+use std::rc::Rc;
+
+struct S;
+
+impl S {
+    fn get(&self) -> u32 { 0 }
+}
+
+trait T {
+    fn get_t(&self) -> u32;
+    fn get_default(&self) -> u32 { 1 }
+}
+
+impl T for S {
+    fn get_t(&self) -> u32 { 2 }
+}
+
+pub fn main() {
+    let s = S;
+    let r = &&s;
+    s.get();
+    (&s).get();
+    r.get(); //~ ERROR receiver auto-dereferenced
+    let f = || r.get(); //~ ERROR receiver auto-dereferenced
+    f();
+    let rc = Rc::new(S);
+    rc.get();
+    //~^ ERROR receiver auto-dereferenced
+    //~| ERROR receiver dereferenced through a `Deref` impl
+    s.get_t(); //~ ERROR method resolved to a trait impl
+    s.get_default();
+}
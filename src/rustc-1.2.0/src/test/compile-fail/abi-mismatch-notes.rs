@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that ABI mismatches spell out both ABIs, point at the found
+// function and suggest the required `extern` annotation.
+
+fn rust_fn() {}
+//~^ NOTE `rust_fn` is declared with the "Rust" ABI; declaring it as `extern "C" fn`
+
+fn main() {
+    let _: extern "C" fn() = rust_fn;
+    //~^ ERROR expected `extern "C"` fn, found `extern "Rust"` fn
+    //~| HELP only functions declared with `extern "C"` can be used here
+}
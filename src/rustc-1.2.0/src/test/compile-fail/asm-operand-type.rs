@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the types of register operands of inline assembly are
+// checked once inference is done.
+
+#![feature(asm, i128_type, simd)]
+
+#[simd]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct f32x4(f32, f32, f32, f32);
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+pub fn main() {
+    let pair = (1u8, 2u8);
+    let wide: u128 = 1;
+    let x = 5;
+    unsafe {
+        asm!("" :: "r"(pair));
+        //~^ ERROR cannot pass a value of type `(u8, u8)` to inline assembly
+        asm!("" :: "r"(wide));
+        //~^ ERROR cannot pass a value of type `u128` to inline assembly
+        //~| NOTE the value is 128 bits wide
+        asm!("" :: "r"(x));
+
+        // Only the general purpose register class is checked here.
+        let v = f32x4(1.0, 2.0, 3.0, 4.0);
+        asm!("" :: "x"(v));
+        asm!("" :: "{rdx}"(pair));
+        asm!("" :: "rm"(pair));
+    }
+}
+
+#[cfg(not(any(target_arch = "x86",
+              target_arch = "x86_64")))]
+pub fn main() {}
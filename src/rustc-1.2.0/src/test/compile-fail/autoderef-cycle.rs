@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// error-pattern: cyclic `Deref` implementations while auto-dereferencing `A`
+// error-pattern: `A` dereferences to `B`
+// error-pattern: `B` dereferences to `A`
+
+// Test that auto-dereferencing through `Deref` impls that form a cycle is
+// reported as soon as the cycle closes, naming each step of it.
+
+use std::ops::Deref;
+
+struct A;
+struct B;
+
+impl Deref for A {
+    type Target = B;
+    fn deref(&self) -> &B { panic!() }
+}
+
+impl Deref for B {
+    type Target = A;
+    fn deref(&self) -> &A { panic!() }
+}
+
+fn main() {
+    let a = A;
+    a.field;
+}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z autoderef-limit=2
+// error-pattern: reached the recursion limit while auto-dereferencing
+// error-pattern: gave up after 2 dereferences
+
+// Test that `-Z autoderef-limit` bounds auto-dereferencing independently of
+// the recursion limit.
+
+struct Foo {
+    x: i32,
+}
+
+fn main() {
+    let foo = &&&Foo { x: 0 };
+    let _ = foo.x;
+}
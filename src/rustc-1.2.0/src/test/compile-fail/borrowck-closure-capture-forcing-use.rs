@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that borrowck points at the use that made a closure capture a
+// variable mutably or by value.
+
+fn borrow() {
+    let mut v = vec![1];
+    let mut push = || {
+        let n = 2;
+        v.push(n);
+        //~^ NOTE `v` is captured by mutable reference because of this use in the closure
+    };
+    let r = &v; //~ ERROR cannot borrow `v` as immutable
+    push();
+}
+
+fn take(_: Vec<i32>) {}
+
+fn moved() {
+    let v = vec![1];
+    let f = || {
+        take(v);
+        //~^ NOTE `v` is captured by value because of this use in the closure
+    };
+    f();
+    let w = v; //~ ERROR use of moved value: `v`
+}
+
+fn main() {}
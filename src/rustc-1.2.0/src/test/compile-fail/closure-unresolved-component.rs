@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that E0196 says which part of a closure's type is unknown.
+
+fn param() {
+    let _f = |_x| {};
+    //~^ ERROR cannot determine a type for this closure
+    //~| NOTE the type of parameter #1 (`_x`) could not be determined
+}
+
+fn second_param() {
+    let _f = |x: u8, y| { x };
+    //~^ ERROR cannot determine a type for this closure
+    //~| NOTE the type of parameter #2 (`y`) could not be determined
+}
+
+fn ret() {
+    let _f = || None;
+    //~^ ERROR cannot determine a type for this closure
+    //~| NOTE the return type of this closure could not be determined
+}
+
+fn main() {}
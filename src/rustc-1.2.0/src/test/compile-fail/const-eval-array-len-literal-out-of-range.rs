@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that out-of-range literals in array lengths are reported by the
+// `overflowing_literals` lint rather than by constant evaluation, so that
+// the lint level applies and the negated minimum of a type is accepted.
+
+#![deny(overflowing_literals)]
+
+fn main() {
+    let _: [u8; 128i8 as usize]; //~ ERROR literal out of range for i8
+    let _: [u8; 256u8 as usize]; //~ ERROR literal out of range for u8
+    let _: [u8; (-128i8 / -2) as usize];
+}
+
+#[allow(overflowing_literals)]
+fn allowed() {
+    let _: [u8; 256u8 as usize];
+}
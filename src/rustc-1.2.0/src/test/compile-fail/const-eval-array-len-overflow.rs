@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Constant evaluation of array lengths happens before type checking, but
+// it still reports overflow against the types the expressions will have,
+// along with the values involved. Out-of-range literals are left to the
+// `overflowing_literals` lint.
+
+#![allow(overflowing_literals, exceeding_bitshifts)]
+
+use std::i8;
+
+fn main() {
+    let _: [u8; 300u8 as usize * 2];
+    let _: [u8; (i8::MAX + 1i8) as usize];
+    //~^ ERROR attempted to add with overflow: `127 + 1` does not fit in `i8`
+    let _: [u8; (200u8 * 2) as usize];
+    //~^ ERROR attempted to mul with overflow: `200 * 2` does not fit in `u8`
+    let _: [u8; (1u32 << 40) as usize];
+    //~^ ERROR shift amount `40` is not less than the bit width 32
+    let _: [u8; (-128i8 / -1) as usize];
+    //~^ ERROR attempted to divide with overflow: `-128 / -1` does not fit in `i8`
+}
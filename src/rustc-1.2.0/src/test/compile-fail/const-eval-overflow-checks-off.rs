@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C overflow-checks=off
+
+// Overflow in a constant expression is an error even when overflow
+// checks are disabled for the code generated at runtime.
+
+#[repr(u8)]
+#[derive(Copy, Clone)]
+enum A {
+    X = 255u8 + 2u8,
+    //~^ ERROR attempted to add with overflow: `255 + 2` does not fit in `u8`
+    Y = 0u8 - 1u8,
+    //~^ ERROR attempted to sub with overflow: `0 - 1` does not fit in `u8`
+}
+
+fn main() {
+    let _ = (A::X, A::Y);
+}
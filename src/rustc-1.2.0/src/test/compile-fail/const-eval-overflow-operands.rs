@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Overflow in a constant expression is an error that reports the
+// operands involved.
+
+#[repr(u8)]
+enum A {
+    X = 200u8 + 100u8,
+    //~^ ERROR constant evaluation error: attempted to add with overflow: `200 + 100`
+}
+
+#[repr(i8)]
+enum B {
+    X = -100i8 - 100i8,
+    //~^ ERROR constant evaluation error: attempted to sub with overflow: `-100 - 100`
+}
+
+const C: u16 = 300 * 300;
+//~^ ERROR attempted to mul with overflow: `300 * 300`
+
+fn main() {
+    let _ = (A::X, B::X, C);
+}
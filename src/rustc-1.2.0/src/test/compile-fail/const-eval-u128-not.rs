@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that constant evaluation, which works in 64 bits, signals an error
+// instead of truncating a `u128` whose high bits would be set.
+
+#![feature(i128_type)]
+
+fn main() {
+    let _: [u8; (3u128 * 4) as usize];
+    let _: [u8; (!0u8) as usize];
+    let _: [u8; (!0u128 >> 120) as usize];
+    //~^ ERROR 128-bit constant exceeds the 64-bit range supported by constant evaluation
+}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that mismatches between diverging and converging fn types name
+// the return type involved and explain why they are not interchangeable.
+
+fn returns_unit() {}
+
+fn never_returns() -> ! { panic!() }
+
+fn main() {
+    let _: fn() -> ! = returns_unit;
+    //~^ ERROR expected a function that never returns (`!`), found one that returns `()`
+    //~| NOTE although a call to a diverging function can be used wherever a value is expected
+    //~| HELP consider wrapping the function in one with the expected signature
+
+    let _: fn() -> u32 = never_returns;
+    //~^ ERROR expected a function that returns `u32`, found one that never returns (`!`)
+    //~| NOTE although a call to a diverging function can be used wherever a value is expected
+    //~| HELP consider wrapping the function in one with the expected signature
+}
@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:lint_stability.rs
+
+// Test that calls to deprecated methods are reported when the method is
+// picked, at the level the `deprecated` lint has at the call, so they are
+// not lost when type checking fails elsewhere.
+
+#![deny(deprecated)]
+
+extern crate lint_stability;
+
+use lint_stability::{MethodTester, Trait};
+
+fn main() {
+    let foo = MethodTester;
+    foo.method_deprecated(); //~ ERROR use of deprecated item
+    foo.method_deprecated_text(); //~ ERROR use of deprecated item: text
+    foo.trait_deprecated(); //~ ERROR use of deprecated item
+    foo.method_stable();
+    allowed();
+
+    let _: () = 0u8; //~ ERROR mismatched types
+}
+
+#[allow(deprecated)]
+fn allowed() {
+    MethodTester.method_deprecated();
+    MethodTester.trait_deprecated_text();
+}
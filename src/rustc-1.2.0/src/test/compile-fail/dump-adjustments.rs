@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check the adjustments recorded for method receivers and coercion
+// sites once writeback is done.
+
+#![feature(rustc_attrs)]
+
+struct S;
+
+impl S {
+    fn by_ref(&self) {}
+    fn by_mut(&mut self) {}
+}
+
+fn takes_slice(_: &[i32]) {}
+
+fn id(x: u32) -> u32 { x }
+
+#[rustc_dump_adjustments]
+fn f(mut s: S, b: Box<S>, a: &[i32; 4]) {
+    s.by_ref(); //~ NOTE adjustments: autoderefs: 0, autoref: &, unsize: none
+    s.by_mut(); //~ NOTE adjustments: autoderefs: 0, autoref: &mut, unsize: none
+    b.by_ref(); //~ NOTE adjustments: autoderefs: 1, autoref: &, unsize: none
+    takes_slice(a); //~ NOTE adjustments: autoderefs: 1, autoref: &, unsize: &[i32]
+    let _: fn(u32) -> u32 = id; //~ NOTE adjustments: reify fn pointer
+}
+
+#[rustc_error]
+fn main() { //~ ERROR compilation successful
+}
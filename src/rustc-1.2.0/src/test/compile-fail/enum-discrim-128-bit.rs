@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Discriminants are computed in 64 bits, so a 128-bit enum representation
+// is rejected rather than checked against the wrong range.
+
+#![feature(i128_type)]
+
+#[repr(u128)] //~ ERROR `u128` enum discriminants are not supported
+enum A {
+    A0 = 0xFFFF_FFFF_FFFF_FFFF,
+    A1,
+}
+
+#[repr(i128)] //~ ERROR `i128` enum discriminants are not supported
+enum B {
+    B0 = 1,
+    B1,
+}
+
+fn main() {
+    let _ = (A::A0, A::A1, B::B0, B::B1);
+}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --error-limit=1
+
+// Test that errors past the `--error-limit` are not emitted. The
+// second error is still counted, but since compiletest would complain
+// about an unexpected error if it were printed, this also checks that
+// it is not.
+
+fn first() {
+    let _: u8 = "not a u8"; //~ ERROR mismatched types
+}
+
+fn second() {
+    let _: u16 = "not a u16";
+}
+
+fn main() {
+}
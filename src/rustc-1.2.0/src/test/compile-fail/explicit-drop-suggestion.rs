@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that explicit destructor calls, through method call syntax or any
+// UFCS form, suggest `std::mem::drop` when the dropped value is known.
+
+struct Foo;
+
+impl Drop for Foo {
+    fn drop(&mut self) {}
+}
+
+fn by_ref(r: &mut Foo) {
+    r.drop(); //~ ERROR explicit use of destructor method
+}
+
+fn main() {
+    let mut a = Foo;
+    a.drop(); //~ ERROR explicit use of destructor method
+    //~^ HELP use `std::mem::drop` to drop the value early
+    //~| SUGGESTION std::mem::drop(a)
+
+    let mut b = Foo;
+    Drop::drop(&mut b); //~ ERROR explicit use of destructor method
+    //~^ HELP use `std::mem::drop` to drop the value early
+    //~| SUGGESTION std::mem::drop(b)
+
+    let mut c = Foo;
+    <Foo as Drop>::drop(&mut c); //~ ERROR explicit use of destructor method
+    //~^ HELP use `std::mem::drop` to drop the value early
+    //~| SUGGESTION std::mem::drop(c)
+
+    let mut d = Foo;
+    Foo::drop(&mut d); //~ ERROR explicit use of destructor method
+    //~^ HELP use `std::mem::drop` to drop the value early
+    //~| SUGGESTION std::mem::drop(d)
+}
@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that smart pointer and raw pointer receivers are gated by the
+// `arbitrary_self_types` feature gate
+
+use std::rc::Rc;
+
+struct Foo;
+
+impl Foo {
+    fn consume(self: Rc<Self>) {}
+    //~^ ERROR smart pointer receivers other than `Box<Self>` are unstable
+
+    fn address(self: *const Self) {}
+    //~^ ERROR raw pointer receivers are unstable
+}
+
+fn main() {}
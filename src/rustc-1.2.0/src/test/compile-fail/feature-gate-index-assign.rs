@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(core)]
+
+use std::ops::IndexAssign;
+
+struct Sink;
+
+impl IndexAssign<usize, u8> for Sink {
+    fn index_assign(&mut self, _: usize, _: u8) {}
+}
+
+fn main() {
+    let mut s = Sink;
+    s[0] = 1; //~ ERROR overloaded index assignment is unstable
+}
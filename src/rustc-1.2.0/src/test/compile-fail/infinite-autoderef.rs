@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// error-pattern: reached the recursion limit while auto-dereferencing
+// error-pattern: cyclic `Deref` implementations while auto-dereferencing `Foo`
 
 #![feature(box_syntax)]
 
@@ -19,7 +19,8 @@ struct Debuger<T> {
 impl<T: fmt::Debug> ops::FnOnce<(),> for Debuger<T> {
     type Output = ();
     fn call_once(self, _args: ()) {
-//~^ ERROR `call_once` has an incompatible type for trait: expected "rust-call" fn, found "Rust" fn
+//~^ ERROR `call_once` has an incompatible type for trait
+//~| expected `extern "rust-call"` fn, found `extern "Rust"` fn
         println!("{:?}", self.x);
     }
 }
@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the object types whose default lifetime bound will change to
+// `'static` are listed, and only those.
+
+#![deny(object_lifetime_default_change)]
+#![allow(dead_code)]
+
+fn will_change(_: &Box<Fn()>) {}
+//~^ ERROR the default lifetime bound of this object type will change
+
+fn explicit(_: &Box<Fn() + 'static>) {}
+
+fn from_reference(_: &Fn()) {}
+
+fn main() {}
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that each candidate of an ambiguous method call is shown with the
+// signature it would be confirmed with, and that working this out (which
+// normalizes `Self::Out`) leaves no obligations behind to fail later.
+
+trait Assoc { type Out; }
+impl Assoc for usize { type Out = u32; }
+
+trait Foo: Assoc { fn method(&self) -> Self::Out; }
+trait Bar { fn method(&self, _: u8) {} }
+
+impl Foo for usize { fn method(&self) -> u32 { 0 } }
+impl Bar for usize {}
+
+fn main() {
+    let x = 1_usize;
+    x.method(); //~ ERROR E0034
+    //~| NOTE candidate #1 would be called with arguments `()` and return `u32`
+    //~| NOTE candidate #2 would be called with arguments `(u8)` and return `()`
+}
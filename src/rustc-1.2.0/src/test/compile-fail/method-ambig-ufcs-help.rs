@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that each candidate of an ambiguous method call is reported with
+// the UFCS form that selects it, including the receiver adjustments.
+
+trait Foo { fn method(&self) {} }
+trait Bar { fn method(&self, _: u8) {} }
+
+impl Foo for usize {}
+impl Bar for usize {}
+
+fn main() {
+    let x = Box::new(1_usize);
+    x.method(); //~ ERROR E0034
+    //~| HELP to use candidate #1, write `Foo::method(&*x)`
+    //~| HELP to use candidate #2, write `Bar::method(&*x, ...)`
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a method whose type does not match the trait's points at
+// the trait method the expected type comes from.
+
+trait Foo {
+    fn foo(&self, x: u8); //~ NOTE the expected type comes from here
+}
+
+impl Foo for u32 {
+    fn foo(&self, x: i8) {}
+    //~^ ERROR method `foo` has an incompatible type for trait
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a bound of a trait impl that only fails once the receiver type
+// is inferred points at the impl the method call was resolved to.
+
+trait Ext {
+    fn ext(&self);
+}
+
+impl<T: Clone> Ext for Vec<T> {
+    fn ext(&self) {}
+}
+
+struct Foo;
+
+fn main() {
+    let mut v = Vec::new();
+    v.ext();
+    //~^ ERROR is not implemented for the type `Foo`
+    //~| NOTE the method `ext` was resolved via the impl of `Ext` for
+    v.push(Foo);
+}
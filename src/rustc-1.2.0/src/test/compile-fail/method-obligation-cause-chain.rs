@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that an unsatisfied bound of a method names the method, where it
+// was declared and what it was instantiated with.
+
+struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    fn duplicate(&self) -> (T, T) where T: Copy {
+        (self.0, self.0)
+    }
+}
+
+struct NotCopy;
+
+fn main() {
+    let w = Wrapper(NotCopy);
+    w.duplicate();
+    //~^ ERROR the trait `core::marker::Copy` is not implemented for the type `NotCopy`
+    //~| NOTE required by the method `duplicate`, declared on the inherent impl for `Wrapper<T>`
+}
@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that explicit type arguments which make the receiver disagree with
+// the method's `self` type are reported as a type error instead of
+// aborting confirmation.
+
+// aux-build:receiver_tagged.rs
+
+#![feature(arbitrary_self_types)]
+
+extern crate receiver_tagged;
+
+use receiver_tagged::Tagged;
+
+struct S;
+
+impl S {
+    fn tag<U>(self: Tagged<Self, U>) -> U {
+        self.tag
+    }
+}
+
+fn main() {
+    let t = Tagged { value: S, tag: 1u8 };
+    t.tag::<i32>();
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a failed lookup points at receivers under which the item
+// does exist.
+
+struct Inner;
+
+impl Inner {
+    fn frob(&self) {}
+}
+
+struct Wrapper(Inner);
+
+impl AsRef<Inner> for Wrapper {
+    fn as_ref(&self) -> &Inner { &self.0 }
+}
+
+trait Bump {
+    fn bump(self);
+}
+
+impl<'a> Bump for &'a mut Inner {
+    fn bump(self) {}
+}
+
+fn main() {
+    let w = Wrapper(Inner);
+    w.frob();
+    //~^ ERROR no method named `frob` found for type `Wrapper` in the current scope
+    //~| HELP try `w.as_ref().frob(...)`
+
+    let i = Inner;
+    <&Inner>::bump(&i);
+    //~^ ERROR no associated item named `bump` found for type `&Inner` in the current scope
+    //~| HELP the associated item `bump` exists for `&mut Inner`
+}
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+struct Foo;
+
+impl Foo {
+    fn first<'a, 'b: 'a>(&self, x: &'a u8, _y: &'b u8) -> &'a u8 {
+        x
+    }
+
+    fn late<'a>(&self, x: &'a u8) -> &'a u8 {
+        x
+    }
+}
+
+fn main() {
+    let x = 1;
+    Foo.first::<'static>(&x, &x);
+    //~^ ERROR wrong number of lifetime parameters: expected 2, found 1
+    Foo.late::<'static>(&x);
+    //~^ ERROR wrong number of lifetime parameters: expected 0, found 1
+}
@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a wrong number of method type parameters names the missing or
+// extra ones, and that the parameters given in the right place are kept.
+
+struct Test;
+
+impl Test {
+    fn pair<A, B>(&self, a: A, b: B) -> (A, B) { (a, b) }
+    fn one<T>(&self, t: T) -> T { t }
+}
+
+fn main() {
+    let x = Test;
+
+    let (a, _) = x.pair::<u8>(1, 2);
+    //~^ ERROR expected 2, found 1
+    //~| NOTE missing type parameter: `B`
+    let _: () = a; //~ ERROR mismatched types
+                   //~| expected `()`
+                   //~| found `u8`
+
+    let t = x.one::<i32, bool, char>(3);
+    //~^ ERROR expected 1, found 3
+    //~| NOTE extra type parameters: `bool`, `char`
+    let _: () = t; //~ ERROR mismatched types
+                   //~| expected `()`
+                   //~| found `i32`
+}
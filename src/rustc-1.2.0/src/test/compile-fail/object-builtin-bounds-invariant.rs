@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Object types behind `&mut` must have exactly the same builtin bounds.
+
+trait Tr {}
+
+fn fewer<'a>(x: &'a mut Box<Tr + Send + Sync>) -> &'a mut Box<Tr + Send> {
+    x //~ ERROR mismatched types
+}
+
+fn more<'a>(x: &'a mut Box<Tr + Send>) -> &'a mut Box<Tr + Send + Sync> {
+    x //~ ERROR mismatched types
+}
+
+fn main() {}
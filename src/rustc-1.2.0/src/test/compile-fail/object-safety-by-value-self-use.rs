@@ -8,8 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// Check that while a trait with by-value self is object-safe, we
-// can't actually invoke it from an object (yet...?).
+// Check that a method with by-value self can only be invoked on an
+// object that is behind a `Box`.
 
 #![feature(rustc_attrs)]
 
@@ -21,8 +21,8 @@ trait Baz {
     fn baz(self: Self);
 }
 
-fn use_bar(t: Box<Bar>) {
-    t.bar() //~ ERROR cannot move a value of type Bar
+fn use_bar(t: &Bar) {
+    t.bar() //~ ERROR no method named `bar` found for type `&Bar`
 }
 
 fn main() { }
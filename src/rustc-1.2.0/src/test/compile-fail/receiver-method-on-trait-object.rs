@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a method taking a smart pointer receiver cannot be called
+// on a trait object behind that pointer.
+
+#![feature(arbitrary_self_types)]
+
+use std::rc::Rc;
+
+trait Shape {
+    fn area(self: Rc<Self>) -> u32;
+}
+
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(self: Rc<Self>) -> u32 { self.0 * self.0 }
+}
+
+fn main() {
+    let shape: Rc<Shape> = Rc::new(Square(2));
+    shape.area();
+    //~^ ERROR the method `area` cannot be called on a trait object
+}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that region errors arising from an invariant position explain
+// where the invariance came from.
+
+use std::cell::Cell;
+
+fn mut_ref<'a>(x: &'a mut &'a u8) -> &'a mut &'static u8 {
+    x //~ ERROR mismatched types
+    //~^ NOTE types inside `&mut` are invariant
+}
+
+fn cell<'a>(x: &'a Cell<&'a u8>) -> &'a Cell<&'static u8> {
+    x //~ ERROR mismatched types
+    //~^ NOTE the type parameter `T` of
+}
+
+fn nested<'a>(x: &'a mut Cell<&'a u8>) -> &'a mut Cell<&'static u8> {
+    x //~ ERROR mismatched types
+    //~^ NOTE types inside `&mut` are invariant, and the type parameter `T` of
+}
+
+fn main() {
+}
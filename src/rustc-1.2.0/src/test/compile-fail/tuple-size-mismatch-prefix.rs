@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a tuple size mismatch reports which of the shared elements
+// already agree, without inferring anything from them.
+
+fn main() {
+    let a = (0u8, 'a', true, 0i32);
+    let _: (u8, char, bool) = a;
+    //~^ ERROR mismatched types
+    //~| expected a tuple with 3 elements
+    //~| found one with 4 elements; the first 3 elements match
+
+    let b = (0u8, 'a', 0i32);
+    let _: (u8, bool) = b;
+    //~^ ERROR mismatched types
+    //~| expected a tuple with 2 elements
+    //~| found one with 3 elements; of the first 2 elements, those at 0 match
+
+    // Finding out which elements match does not infer anything.
+    let mut v = Vec::new();
+    let _: (Vec<u8>, bool) = (v.clone(), 'a', 0i32);
+    //~^ ERROR mismatched types
+    //~| expected a tuple with 2 elements
+    //~| found one with 3 elements; of the first 2 elements, those at 0 match
+    v.push('c');
+}
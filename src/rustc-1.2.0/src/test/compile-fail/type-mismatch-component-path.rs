@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a mismatch nested inside composite types reports the path
+// to the component that differs.
+
+fn g(_: u8) -> (i8, i8) { (0, 0) }
+
+fn main() {
+    let y = (0u8, Some(0u32));
+    let _x: (u8, Option<bool>) = y;
+    //~^ ERROR mismatched types
+    //~| expected `(u8, core::option::Option<bool>)`
+    //~| found `(u8, core::option::Option<u32>)`
+    //~| expected bool
+    //~| found u32 at tuple element 1, type argument 0
+    //~| NOTE the mismatched component is `u32` here, but `bool` was expected
+
+    let _f: fn(u8) -> (i8, char) = g;
+    //~^ ERROR mismatched types
+    //~| expected char
+    //~| found i8 at return type, tuple element 1
+    //~| NOTE the mismatched component is `i8` here, but `char` was expected
+}
@@ -0,0 +1,20 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an unconstrained type parameter is pointed out as the cause
+// when writeback cannot determine a type.
+
+fn unconstrained<T: ?Sized>() {}
+
+fn main() {
+    unconstrained();
+    //~^ ERROR cannot determine a type for this expression: unconstrained type
+    //~| NOTE the type parameter `T` here is ambiguous
+}
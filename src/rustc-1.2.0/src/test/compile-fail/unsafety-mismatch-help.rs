@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test the suggestions given for mismatches in fn unsafety.
+
+unsafe fn dangerous() {}
+fn harmless() {}
+
+fn main() {
+    let _: Option<fn()> = Some(dangerous as unsafe fn());
+    //~^ ERROR mismatched types
+    //~| HELP consider calling it from an `unsafe {}` block
+
+    let _: Option<unsafe fn()> = Some(harmless as fn());
+    //~^ ERROR mismatched types
+    //~| NOTE only converted automatically at coercion sites
+
+    let _ = if true { dangerous as unsafe fn() } else { harmless as fn() };
+    //~^ ERROR if and else have incompatible types
+    //~| HELP add a cast to convert the safe fn to the expected type
+}
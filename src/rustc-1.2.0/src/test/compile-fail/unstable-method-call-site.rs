@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:lint_stability.rs
+
+// Test that calls to unstable methods are reported when the method is
+// picked, so they are not lost when type checking fails elsewhere.
+
+extern crate lint_stability;
+
+use lint_stability::{MethodTester, Trait};
+
+fn main() {
+    let foo = MethodTester;
+    foo.method_unstable(); //~ ERROR use of unstable library feature 'test_feature'
+    foo.trait_unstable_text(); //~ ERROR use of unstable library feature 'test_feature': text
+    foo.method_stable();
+
+    let _: () = 0u8; //~ ERROR mismatched types
+}
@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that when writeback cannot determine a type, the part of it that
+// was inferred is still reported.
+
+fn empty<T: ?Sized>() -> Vec<&'static T> { Vec::new() }
+
+fn main() {
+    let v = empty();
+    //~^ ERROR cannot determine a type for this local variable: unconstrained type
+    //~| NOTE the type is only known to be `collections::vec::Vec<&'static _>`
+    let _w = &v;
+}
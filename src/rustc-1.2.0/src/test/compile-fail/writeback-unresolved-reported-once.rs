@@ -0,0 +1,20 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an unconstrained type variable is reported once, where it is
+// first needed, and not again for every expression whose type contains it.
+
+fn main() {
+    let x = [];
+    //~^ ERROR cannot determine a type for this local variable: unconstrained type
+    let y = &x;
+    //~^ NOTE the type here could not be determined for the same reason
+    let _z = (y, y);
+}
@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn same(v: Vec<u32>) -> usize {
+    let n = 2;
+    let f = |x: &u32| *x > n;
+    v.iter().filter(|x| f(x)).count()
+}
+
+#[cfg(not(changed))]
+fn changed() -> u32 { 1 + 2 }
+
+#[cfg(changed)]
+fn changed() -> u32 { (1u8 + 2) as u32 }
+
+fn main() {
+    same(vec![1, 2, 3]);
+    changed();
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that array lengths, repeat counts and enum discriminants built from
+// unsuffixed literals get the integer type the constant is expected to have.
+
+#[repr(u8)]
+enum E {
+    A = 1 + 1,
+    B = 3 << 1,
+}
+
+fn main() {
+    let a: [u8; 1 + 2] = [0; 2 * 2 - 1];
+    let b = [1u16; (1 << 2)];
+    let c = &a as &[u8; 3];
+
+    assert_eq!(a.len(), 3);
+    assert_eq!(b.len(), 4);
+    assert_eq!(c.len(), 3);
+    assert_eq!(E::A as u8, 2);
+    assert_eq!(E::B as u8, 6);
+}
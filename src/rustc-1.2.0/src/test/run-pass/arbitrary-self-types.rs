@@ -0,0 +1,61 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test methods whose receivers are smart pointers implementing `Receiver`.
+
+#![feature(arbitrary_self_types)]
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn get(self: Rc<Self>) -> u32 {
+        self.count
+    }
+
+    fn get_shared(self: Arc<Self>) -> u32 {
+        self.count + 1
+    }
+
+    fn get_by_ref(self: Rc<&Self>) -> u32 {
+        self.count + 2
+    }
+}
+
+trait Named {
+    fn name(self: Rc<Self>) -> &'static str;
+}
+
+impl Named for Counter {
+    fn name(self: Rc<Self>) -> &'static str {
+        "counter"
+    }
+}
+
+fn name_of<T: Named>(t: Rc<T>) -> &'static str {
+    t.name()
+}
+
+pub fn main() {
+    let counter = Rc::new(Counter { count: 1 });
+    assert_eq!(counter.clone().get(), 1);
+    assert_eq!(counter.clone().name(), "counter");
+    assert_eq!(name_of(counter.clone()), "counter");
+
+    let shared = Arc::new(Counter { count: 1 });
+    assert_eq!(shared.get_shared(), 2);
+
+    let plain = Counter { count: 1 };
+    assert_eq!(Rc::new(&plain).get_by_ref(), 3);
+}
@@ -0,0 +1,58 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Associated constants on the primitive integer types, used in array
+// lengths (evaluated before type checking) and in patterns.
+
+#![feature(num_assoc_consts)]
+
+struct Buf {
+    bytes: [u8; u32::BYTES],
+    bits: [bool; u16::BITS],
+}
+
+fn classify(x: u8) -> &'static str {
+    match x {
+        u8::MIN => "min",
+        u8::MAX => "max",
+        _ => "other",
+    }
+}
+
+fn classify_signed(x: i8) -> &'static str {
+    match x {
+        i8::MIN => "min",
+        i8::MAX => "max",
+        _ => "other",
+    }
+}
+
+fn main() {
+    let buf = Buf { bytes: [0; 4], bits: [false; 16] };
+    assert_eq!(buf.bytes.len(), 4);
+    assert_eq!(buf.bits.len(), 16);
+
+    let a: [u8; u8::MAX as usize] = [0; 255];
+    assert_eq!(a.len(), 255);
+    let b: [u8; i8::MAX as usize + 1] = [0; 128];
+    assert_eq!(b.len(), 128);
+
+    assert_eq!(classify(0), "min");
+    assert_eq!(classify(255), "max");
+    assert_eq!(classify(7), "other");
+    assert_eq!(classify_signed(-128), "min");
+    assert_eq!(classify_signed(127), "max");
+    assert_eq!(classify_signed(0), "other");
+
+    assert_eq!(isize::MIN, std::isize::MIN);
+    assert_eq!(usize::MAX, std::usize::MAX);
+    assert_eq!(i64::BITS, 64);
+    assert_eq!(u16::BYTES, 2);
+}
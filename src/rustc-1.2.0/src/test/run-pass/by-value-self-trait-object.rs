@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that methods taking `self` by value can be called on a boxed
+// trait object, and that the value is dropped exactly once.
+
+use std::cell::Cell;
+
+trait Consume {
+    fn consume(self, suffix: &str) -> String;
+}
+
+struct Small(u8);
+
+impl Consume for Small {
+    fn consume(self, suffix: &str) -> String {
+        format!("{}{}", self.0, suffix)
+    }
+}
+
+struct Large<'a> {
+    name: String,
+    padding: [u64; 4],
+    drops: &'a Cell<usize>,
+}
+
+impl<'a> Drop for Large<'a> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+impl<'a> Consume for Large<'a> {
+    fn consume(self, suffix: &str) -> String {
+        format!("{}{}{}", self.name, self.padding[3], suffix)
+    }
+}
+
+fn main() {
+    let small: Box<Consume> = Box::new(Small(7));
+    assert_eq!(small.consume("!"), "7!");
+
+    let drops = Cell::new(0);
+    {
+        let large: Box<Consume> = Box::new(Large {
+            name: "large".to_string(),
+            padding: [0, 0, 0, 4],
+            drops: &drops,
+        });
+        assert_eq!(large.consume("?"), "large4?");
+    }
+    assert_eq!(drops.get(), 1);
+}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Casts of every kind whose source type is only known once inference
+// has finished; trans relies on the kind recorded in writeback.
+
+#[derive(Copy, Clone)]
+enum E { A = 1, B = 2 }
+
+fn id<T>(x: T) -> T { x }
+
+fn f() -> u32 { 7 }
+
+pub fn main() {
+    let n = id(Default::default());
+    let _: u8 = n;
+    assert_eq!(n as char, '\0');                       // u8-char-cast
+    assert_eq!(n as f64, 0.0);                         // numeric-cast
+    assert_eq!(id(E::B) as i32 - E::A as i32, 1);      // enum-cast
+    assert_eq!(id(true) as u8 + id('a') as u8, 98);    // prim-int-cast
+
+    let x = 5u32;
+    let p = id(&x as *const _);
+    let q = p as *const u8;                            // ptr-ptr-cast
+    let addr = q as usize;                             // ptr-addr-cast
+    assert_eq!(addr as *const u32, p);                 // addr-ptr-cast
+    assert_eq!(unsafe { *(addr as *const u32) }, 5);
+
+    let g = id(f as fn() -> u32);
+    assert!(g as usize != 0);                          // fptr-addr-cast
+    assert_eq!(g as *const u8 as usize, g as usize);   // fptr-ptr-cast
+
+    let a = id(&[1u16, 2, 3]);
+    let r = a as *const u16;                           // array-ptr-cast
+    assert_eq!(unsafe { *r.offset(2) }, 3);
+}
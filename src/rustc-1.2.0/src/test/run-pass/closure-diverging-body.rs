@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that closures whose bodies diverge can still be called through the
+// `Fn` traits, as trait objects, and directly.
+
+use std::thread;
+
+fn maybe_call<F: Fn() -> R, R>(f: F, call: bool) -> Option<R> {
+    if call { Some(f()) } else { None }
+}
+
+fn main() {
+    let panics = || panic!("closure diverged");
+    let none: Option<()> = maybe_call(panics, false);
+    assert!(none.is_none());
+
+    let loops = || loop {};
+    assert!(maybe_call(loops, false).is_none());
+
+    let boxed: Box<Fn() + Send> = Box::new(|| { let _x = 1; panic!() });
+    assert!(thread::spawn(move || boxed()).join().is_err());
+
+    let returns = |x: u32| { if x > 0 { return x; } panic!() };
+    assert_eq!(returns(3), 3);
+
+    let direct = || -> () { panic!() };
+    assert!(thread::spawn(move || direct()).join().is_err());
+}
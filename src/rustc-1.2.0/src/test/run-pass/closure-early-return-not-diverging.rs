@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a closure whose body ends in a diverging expression is not
+// given a diverging signature when it can still `return` early.
+
+use std::cell::Cell;
+
+fn main() {
+    let calls = Cell::new(0);
+    let f = |c: bool| {
+        calls.set(calls.get() + 1);
+        if c { return; }
+        panic!()
+    };
+    f(true);
+    f(true);
+    assert_eq!(calls.get(), 2);
+
+    // A `return` inside a nested closure does not return from the outer
+    // one, which still diverges.
+    let g = || {
+        let h = || { return; };
+        h();
+        panic!()
+    };
+    let _ = g;
+}
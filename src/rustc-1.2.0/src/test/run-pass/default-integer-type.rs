@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Unconstrained integer literals fall back to the type named by
+// `#![default_integer_type]`, while float literals still use `f64`.
+
+#![feature(default_integer_type)]
+#![default_integer_type(u64)]
+
+use std::mem::size_of_val;
+
+fn main() {
+    let x = 1;
+    assert_eq!(size_of_val(&x), 8);
+
+    let y = !0;
+    assert!(y > 0);
+
+    let z = 1.0;
+    assert_eq!(size_of_val(&z), 8);
+
+    // Literals that are constrained are unaffected.
+    let w: i8 = 1;
+    assert_eq!(size_of_val(&w), 1);
+}
@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Exercise the 128-bit integer types from literals through to formatting.
+
+#![feature(i128, i128_type, num_overflowing)]
+
+use std::{i128, u128};
+
+fn main() {
+    let x: u128 = 0xffff_ffff_ffff_ffff;
+    let y = x + 1u128;
+    assert_eq!(y, 1 << 64);
+    assert_eq!(y * y / y, y);
+    assert_eq!(y - 1, x);
+    assert_eq!(y >> 32, 1 << 32);
+    assert_eq!(format!("{}", y), "18446744073709551616");
+    assert_eq!(format!("{:x}", y), "10000000000000000");
+    assert_eq!(format!("{:b}", u128::MAX).len(), 128);
+
+    let n: i128 = -(y as i128);
+    assert!(n < 0);
+    assert_eq!(n / -1, y as i128);
+    assert_eq!(format!("{}", n), "-18446744073709551616");
+    assert_eq!(-n, y as i128);
+
+    assert_eq!(u128::MAX.count_ones(), 128);
+    assert_eq!((1u128 << 100).trailing_zeros(), 100);
+    assert_eq!(u128::MAX.overflowing_add(1), (0, true));
+    assert_eq!(i128::MIN.overflowing_neg(), (i128::MIN, true));
+    assert_eq!(i128::MAX.checked_add(1), None);
+    assert_eq!("-18446744073709551616".parse::<i128>(), Ok(n));
+}
@@ -0,0 +1,72 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `container[index] = value` uses `IndexAssign` when it is
+// implemented, and falls back to `IndexMut` otherwise.
+
+#![feature(core, index_assign)]
+
+use std::collections::HashMap;
+use std::ops::{Index, IndexAssign};
+
+struct Map {
+    entries: HashMap<String, i32>,
+}
+
+impl<'a> Index<&'a str> for Map {
+    type Output = i32;
+
+    fn index(&self, key: &'a str) -> &i32 {
+        &self.entries[key]
+    }
+}
+
+impl<'a> IndexAssign<&'a str, i32> for Map {
+    fn index_assign(&mut self, key: &'a str, value: i32) {
+        self.entries.insert(key.to_string(), value);
+    }
+}
+
+struct Log {
+    writes: Vec<(usize, String)>,
+}
+
+impl IndexAssign<usize, String> for Log {
+    fn index_assign(&mut self, index: usize, value: String) {
+        self.writes.push((index, value));
+    }
+}
+
+pub fn main() {
+    let mut map = Map { entries: HashMap::new() };
+    map["one"] = 1;
+    map["two"] = 2;
+    map["one"] = 3;
+    assert_eq!(map["one"], 3);
+    assert_eq!(map["two"], 2);
+
+    // The value is moved into `index_assign`.
+    let mut log = Log { writes: Vec::new() };
+    log[3] = "three".to_string();
+    log[1] = format!("{}", 1);
+    assert_eq!(log.writes, [(3, "three".to_string()), (1, "1".to_string())]);
+
+    // Through a `&mut` reference.
+    {
+        let r = &mut map;
+        r["four"] = 4;
+    }
+    assert_eq!(map["four"], 4);
+
+    // Types without `IndexAssign` still assign through `IndexMut`.
+    let mut v = vec![1, 2, 3];
+    v[1] = 5;
+    assert_eq!(v, [1, 5, 3]);
+}
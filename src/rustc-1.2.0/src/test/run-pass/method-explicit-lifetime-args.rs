@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that early-bound lifetime parameters of a method can be given
+// explicitly at the call site, alone or together with type parameters.
+
+struct Foo;
+
+impl Foo {
+    fn first<'a, 'b: 'a>(&self, x: &'a u8, _y: &'b u8) -> &'a u8 {
+        x
+    }
+
+    fn get<'a, T: 'a>(&self, x: &'a T) -> &'a T {
+        x
+    }
+}
+
+static X: u8 = 1;
+static Y: u8 = 2;
+
+fn borrowed<'c>(x: &'c u8) -> &'c u8 {
+    Foo.first::<'c, 'static>(x, &Y)
+}
+
+fn main() {
+    let r: &'static u8 = Foo.first::<'static, 'static>(&X, &Y);
+    assert_eq!(*r, 1);
+    assert_eq!(*Foo.get::<'static, u8>(&Y), 2);
+    assert_eq!(*borrowed(&X), 1);
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An object type with more builtin bounds is a subtype of one with fewer,
+// wherever the object type appears covariantly.
+
+trait Tr {
+    fn get(&self) -> u8;
+}
+
+impl Tr for u8 {
+    fn get(&self) -> u8 { *self }
+}
+
+fn in_vec(x: Vec<Box<Tr + Send + Sync>>) -> Vec<Box<Tr + Send>> {
+    x
+}
+
+fn in_option<'a>(x: Option<&'a (Tr + Send + Sync)>) -> Option<&'a (Tr + Sync)> {
+    x
+}
+
+pub fn main() {
+    let v = in_vec(vec![Box::new(1u8) as Box<Tr + Send + Sync>]);
+    assert_eq!(v[0].get(), 1);
+
+    let x = 2u8;
+    assert_eq!(in_option(Some(&x as &(Tr + Send + Sync))).unwrap().get(), 2);
+
+    // The arms have a common supertype with the bounds both have.
+    let send_sync = vec![Box::new(3u8) as Box<Tr + Send + Sync>];
+    let send = vec![Box::new(4u8) as Box<Tr + Send>];
+    let either = if v.len() == 1 { send_sync } else { send };
+    assert_eq!(either[0].get(), 3);
+}
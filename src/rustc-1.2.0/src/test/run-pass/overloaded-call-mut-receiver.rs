@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that calling an `FnMut` reached through overloaded derefs or
+// indexing uses `DerefMut` and `IndexMut`, as a `&mut self` method would.
+
+use std::ops::{Deref, DerefMut};
+
+struct Wrapper<F>(F);
+
+impl<F> Deref for Wrapper<F> {
+    type Target = F;
+    fn deref(&self) -> &F { &self.0 }
+}
+
+impl<F> DerefMut for Wrapper<F> {
+    fn deref_mut(&mut self) -> &mut F { &mut self.0 }
+}
+
+struct Holder<F> {
+    f: Wrapper<F>,
+}
+
+pub fn main() {
+    let mut a = 0;
+    let mut b = 0;
+    let mut c = 0;
+    {
+        let mut w = Wrapper(|| a += 1);
+        w();
+        w();
+
+        let mut fs = vec![|x: i32| b += x];
+        fs[0](3);
+
+        let mut h = Holder { f: Wrapper(|x: i32| c = x) };
+        (h.f)(7);
+    }
+    assert_eq!(a, 2);
+    assert_eq!(b, 3);
+    assert_eq!(c, 7);
+}
@@ -0,0 +1,93 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that every overloaded deref and index along the receiver of an
+// `&mut self` method call uses the mutable variant, including nested
+// ones and those inserted by autoderef.
+
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+struct Counted<T> {
+    count_imm: Cell<usize>,
+    count_mut: usize,
+    value: T
+}
+
+impl<T> Counted<T> {
+    fn new(value: T) -> Counted<T> {
+        Counted {
+            count_imm: Cell::new(0),
+            count_mut: 0,
+            value: value
+        }
+    }
+
+    fn counts(&self) -> (usize, usize) {
+        (self.count_imm.get(), self.count_mut)
+    }
+}
+
+impl<T> Deref for Counted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.count_imm.set(self.count_imm.get() + 1);
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Counted<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.count_mut += 1;
+        &mut self.value
+    }
+}
+
+impl<T> Index<usize> for Counted<Vec<T>> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        self.count_imm.set(self.count_imm.get() + 1);
+        &self.value[i]
+    }
+}
+
+impl<T> IndexMut<usize> for Counted<Vec<T>> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        self.count_mut += 1;
+        &mut self.value[i]
+    }
+}
+
+pub fn main() {
+    // Autoderef of the receiver itself.
+    let mut v = Counted::new(vec![1]);
+    v.push(2);
+    assert_eq!(v.counts(), (0, 1));
+
+    // Overloaded index, then autoderef of the indexed element.
+    let mut rows = Counted::new(vec![Counted::new(vec![1])]);
+    rows[0].push(2);
+    assert_eq!(rows.counts(), (0, 1));
+    assert_eq!(rows.value[0].counts(), (0, 1));
+
+    // Explicit deref of an overloaded index.
+    (*rows[0]).push(3);
+    assert_eq!(rows.counts(), (0, 2));
+    assert_eq!(rows.value[0].counts(), (0, 2));
+
+    // Nested overloaded indices through a field.
+    let mut grid = (Counted::new(vec![Counted::new(vec![vec![1]])]),);
+    grid.0[0][0].push(2);
+    assert_eq!(grid.0.counts(), (0, 1));
+    assert_eq!(grid.0.value[0].counts(), (0, 1));
+    assert_eq!(grid.0.value[0].value[0], [1, 2]);
+}
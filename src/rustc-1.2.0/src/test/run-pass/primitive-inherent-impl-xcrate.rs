@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that methods of the inherent impls that libcore attaches to the
+// primitive types through lang items can be called, both as methods and
+// through paths, from a crate that only sees them in the metadata.
+
+fn main() {
+    assert_eq!(5u8.count_ones(), 2);
+    assert_eq!(u8::count_ones(5), 2);
+    assert_eq!(<u16>::swap_bytes(0x1234), 0x3412);
+    assert_eq!((-1i32).wrapping_add(1), 0);
+    assert_eq!(u64::max_value().checked_add(1), None);
+
+    let x = 3;
+    let p = &x as *const i32;
+    assert!(!p.is_null());
+
+    let v = [1, 2, 3];
+    assert_eq!(v.len(), 3);
+    assert_eq!('a'.len_utf8(), 1);
+    assert_eq!("abc".len(), 3);
+}
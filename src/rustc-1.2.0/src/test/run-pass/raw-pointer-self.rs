@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test inherent methods taking a raw pointer `self`, called both on
+// pointers and, through an unsafe autoref, on values.
+
+#![feature(arbitrary_self_types)]
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn get(self: *const Self) -> u32 {
+        unsafe { (*self).count }
+    }
+
+    fn bump(self: *mut Self) {
+        unsafe { (*self).count += 1; }
+    }
+}
+
+pub fn main() {
+    let mut counter = Counter { count: 1 };
+    counter.bump();
+    assert_eq!(counter.get(), 2);
+
+    let p = &mut counter as *mut Counter;
+    p.bump();
+    assert_eq!((p as *const Counter).get(), 3);
+
+    let mut boxed = Box::new(Counter { count: 5 });
+    boxed.bump();
+    assert_eq!(boxed.get(), 6);
+}
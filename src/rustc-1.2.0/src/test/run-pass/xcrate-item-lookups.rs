@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:xcrate_item_lookups.rs
+
+// Looks up the same upstream items many times: the methods of one impl,
+// the trait they implement and the variances of the type.
+
+extern crate xcrate_item_lookups;
+
+use xcrate_item_lookups::{Get, Wrapper};
+
+fn shorten<'a, T>(w: Wrapper<'static, T>) -> Wrapper<'a, T> {
+    w
+}
+
+static FIVE: u8 = 5;
+
+pub fn main() {
+    let w = Wrapper::new(&FIVE);
+    assert_eq!(w.get(), 5);
+    assert_eq!(w.first(), 5);
+    assert_eq!(w.second(), 5);
+    assert_eq!(w.get_twice(), (5, 5));
+
+    let w = shorten(w);
+    assert_eq!(w.get(), 5);
+    assert_eq!(w.first(), 5);
+    assert_eq!(w.second(), 5);
+    assert_eq!(w.get_twice(), (5, 5));
+
+    let v = vec![1u16, 2, 3];
+    let ws: Vec<_> = v.iter().map(Wrapper::new).collect();
+    assert_eq!(ws.iter().map(|w| w.first() + w.second()).collect::<Vec<_>>(), [2, 4, 6]);
+}